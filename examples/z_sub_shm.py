@@ -29,7 +29,7 @@ def main(conf: zenoh.Config, key: str):
                     f">> [Subscriber] Received {sample.kind} ('{sample.key_expr}': '{payload}')[{payload_type}] ",
                     end="",
                 )
-                if att := sample.attachment:
+                if att := sample.raw_attachment:
                     attachment_type, attachment = handle_bytes(att)
                     print(f" ({attachment_type}: {attachment})")
                 print()
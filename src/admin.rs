@@ -0,0 +1,399 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::BTreeSet,
+    str::FromStr,
+    sync::{Arc, Condvar, Mutex},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+    types::{PyDict, PyTuple},
+};
+use serde_json::Value;
+use zenoh::{
+    config::ZenohId as RustZenohId,
+    key_expr::{keyexpr, KeyExpr as RustKeyExpr},
+    query::QueryTarget,
+    Wait,
+};
+
+use crate::{
+    config::ZenohId,
+    error::{new_zerror_with_code, ErrorCode},
+    handlers::log_error,
+    key_expr::KeyExpr,
+    utils::IntoPyResult,
+};
+
+const NODE_TYPES: [&str; 3] = ["router", "peer", "client"];
+const SOURCE_TYPES: [&str; 3] = ["routers", "peers", "clients"];
+/// Bounds of the exponential backoff between the polls of `wait_for_matching`.
+const MATCHING_POLL_MIN: Duration = Duration::from_millis(10);
+const MATCHING_POLL_MAX: Duration = Duration::from_millis(500);
+
+type Entity = (String, RustZenohId);
+
+/// The admin space chunk of an entity kind, i.e. `subscribers`, `publishers` or `queryables`.
+pub(crate) fn admin_kind(kind: &str) -> PyResult<&'static str> {
+    match kind {
+        "subscribers" => Ok("subscriber"),
+        "publishers" => Ok("publisher"),
+        "queryables" => Ok("queryable"),
+        _ => Err(PyValueError::new_err(
+            "kind must be 'subscribers', 'publishers' or 'queryables'",
+        )),
+    }
+}
+
+/// Adds the entities described by an admin space sample, ignoring unexpected layouts.
+fn parse_entities(kind: &str, sample: &zenoh::sample::Sample, entities: &mut BTreeSet<Entity>) {
+    let chunks = sample
+        .key_expr()
+        .as_str()
+        .splitn(5, '/')
+        .collect::<Vec<_>>();
+    let [_, first, second, entity_kind, key_expr] = chunks[..] else {
+        return;
+    };
+    if entity_kind != kind {
+        return;
+    }
+    let node = if NODE_TYPES.contains(&first) {
+        second
+    } else {
+        first
+    };
+    let Ok(node) = RustZenohId::from_str(node) else {
+        return;
+    };
+    if zenoh::key_expr::keyexpr::new(key_expr).is_err() {
+        return;
+    }
+    let payload = sample.payload().to_bytes();
+    let sources = match serde_json::from_slice::<Value>(&payload) {
+        Ok(Value::Object(sources)) => SOURCE_TYPES
+            .iter()
+            .filter_map(|node_type| sources.get(*node_type)?.as_array())
+            .flatten()
+            .filter_map(|zid| RustZenohId::from_str(zid.as_str()?).ok())
+            .collect(),
+        _ => vec![node],
+    };
+    entities.extend(sources.into_iter().map(|zid| (key_expr.to_string(), zid)));
+}
+
+/// Queries the entities of the given kind reported by the admin space of the nodes enabling it.
+///
+/// Since zenoh 1.0, the minimum supported version, entities are reported under
+/// `@/<zid>/<whatami>/<kind>/<key_expr>`, with a JSON payload listing the zids of their sources
+/// by node type, e.g. `{"routers": [], "peers": ["<zid>"], "clients": []}`. Older nodes used
+/// `@/<whatami>/<zid>/<kind>/<key_expr>` without sources; their entities are attributed to the
+/// node itself.
+fn query_entities(
+    session: &zenoh::Session,
+    kind: &str,
+    timeout: Option<Duration>,
+) -> zenoh::Result<BTreeSet<Entity>> {
+    let mut builder = session.get(format!("@/*/*/{kind}/**"));
+    if let Some(timeout) = timeout {
+        builder = builder.timeout(timeout);
+    }
+    let replies = builder.wait()?;
+    let mut entities = BTreeSet::new();
+    while let Ok(reply) = replies.recv() {
+        if let Ok(sample) = reply.result() {
+            parse_entities(kind, sample, &mut entities);
+        }
+    }
+    Ok(entities)
+}
+
+#[pyclass(frozen)]
+pub(crate) struct EntityInfo {
+    #[pyo3(get)]
+    key_expr: KeyExpr,
+    #[pyo3(get)]
+    zid: ZenohId,
+}
+
+#[pymethods]
+impl EntityInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "EntityInfo(key_expr={}, zid={})",
+            self.key_expr.0, self.zid.0
+        )
+    }
+}
+
+#[pyclass(frozen)]
+pub(crate) struct EntityEvent {
+    #[pyo3(get)]
+    declared: bool,
+    #[pyo3(get)]
+    key_expr: KeyExpr,
+    #[pyo3(get)]
+    zid: ZenohId,
+}
+
+#[pymethods]
+impl EntityEvent {
+    fn __repr__(&self) -> String {
+        let event = if self.declared {
+            "declared"
+        } else {
+            "undeclared"
+        };
+        format!(
+            "EntityEvent({event}, key_expr={}, zid={})",
+            self.key_expr.0, self.zid.0
+        )
+    }
+}
+
+pub(crate) fn list_entities(
+    py: Python,
+    session: &zenoh::Session,
+    kind: &str,
+    timeout: Option<Duration>,
+) -> PyResult<Vec<EntityInfo>> {
+    let kind = admin_kind(kind)?;
+    let entities = py.allow_threads(|| query_entities(session, kind, timeout));
+    let entities = entities.into_pyres()?.into_iter().map(|(key_expr, zid)| {
+        Ok(EntityInfo {
+            key_expr: KeyExpr::new(key_expr)?,
+            zid: zid.into(),
+        })
+    });
+    entities.collect()
+}
+
+/// Entity whose matching status tells whether there is at least one matching subscriber or
+/// queryable, without requiring the admin space.
+enum Matcher {
+    Publisher(zenoh::pubsub::Publisher<'static>),
+    Querier(zenoh::query::Querier<'static>),
+}
+
+impl Matcher {
+    fn declare(
+        session: &zenoh::Session,
+        kind: &str,
+        key_expr: &RustKeyExpr<'static>,
+    ) -> zenoh::Result<Option<Self>> {
+        Ok(match kind {
+            "subscriber" => {
+                let publisher = session.declare_publisher(key_expr.clone());
+                Some(Self::Publisher(publisher.wait()?))
+            }
+            "queryable" => {
+                let querier = session
+                    .declare_querier(key_expr.clone())
+                    .target(QueryTarget::All);
+                Some(Self::Querier(querier.wait()?))
+            }
+            _ => None,
+        })
+    }
+
+    fn matching(&self) -> zenoh::Result<bool> {
+        Ok(match self {
+            Self::Publisher(publisher) => publisher.matching_status().wait()?.matching(),
+            Self::Querier(querier) => querier.matching_status().wait()?.matching(),
+        })
+    }
+}
+
+/// Counts the entities of the given kind whose key expression intersects `key_expr`.
+fn count_matching(
+    session: &zenoh::Session,
+    kind: &str,
+    key_expr: &keyexpr,
+    timeout: Duration,
+) -> zenoh::Result<usize> {
+    let entities = query_entities(session, kind, Some(timeout))?;
+    let matching = |(entity, _): &&Entity| {
+        keyexpr::new(entity.as_str()).is_ok_and(|entity| entity.intersects(key_expr))
+    };
+    Ok(entities.iter().filter(matching).count())
+}
+
+/// Waits until at least `count` entities of the given kind match `key_expr`, polling with an
+/// exponential backoff, and returns the observed count.
+///
+/// A single subscriber or queryable is waited for with the matching status of a temporary
+/// publisher or querier; larger counts and publishers are polled through the admin space.
+pub(crate) fn wait_for_matching(
+    py: Python,
+    session: &zenoh::Session,
+    kind: &str,
+    key_expr: RustKeyExpr<'static>,
+    count: usize,
+    timeout: Duration,
+) -> PyResult<usize> {
+    let admin_kind = admin_kind(kind)?;
+    if count == 0 {
+        return Err(PyValueError::new_err("count must be positive"));
+    }
+    let deadline = Instant::now() + timeout;
+    let matcher = match count {
+        1 => py
+            .allow_threads(|| Matcher::declare(session, admin_kind, &key_expr))
+            .into_pyres()?,
+        _ => None,
+    };
+    let mut poll = MATCHING_POLL_MIN;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let observed = py
+            .allow_threads(|| match &matcher {
+                Some(matcher) => matcher.matching().map(usize::from),
+                None => count_matching(session, admin_kind, &key_expr, remaining),
+            })
+            .into_pyres()?;
+        if observed >= count {
+            return Ok(observed);
+        }
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            let msg = format!(
+                "timed out after {timeout:?} waiting for {count} {kind} matching '{key_expr}', \
+                {observed} observed"
+            );
+            return Err(new_zerror_with_code(msg, ErrorCode::Timeout));
+        }
+        py.allow_threads(|| std::thread::sleep(remaining.min(poll)));
+        poll = (poll * 2).min(MATCHING_POLL_MAX);
+        py.check_signals()?;
+    }
+}
+
+type StopFlag = Arc<(Mutex<bool>, Condvar)>;
+
+/// Calls `callback` with the undeclared entities of `known`, then with the declared ones.
+fn notify(py: Python, callback: &PyObject, known: &BTreeSet<Entity>, entities: &BTreeSet<Entity>) {
+    let undeclared = known.difference(entities).map(|entity| (false, entity));
+    let declared = entities.difference(known).map(|entity| (true, entity));
+    for (declared, (key_expr, zid)) in undeclared.chain(declared) {
+        let event = KeyExpr::new(key_expr.clone()).map(|key_expr| EntityEvent {
+            declared,
+            key_expr,
+            zid: (*zid).into(),
+        });
+        log_error(py, event.and_then(|event| callback.call1(py, (event,))));
+    }
+}
+
+/// Polls the admin space every `interval`, calling `callback` with the differences.
+fn watch(
+    session: zenoh::session::WeakSession,
+    kind: &'static str,
+    callback: PyObject,
+    interval: Duration,
+    stopped: StopFlag,
+) {
+    let mut known = BTreeSet::new();
+    loop {
+        match query_entities(&session, kind, None) {
+            Ok(entities) if entities != known => {
+                Python::with_gil(|py| notify(py, &callback, &known, &entities));
+                known = entities;
+            }
+            Ok(_) => {}
+            Err(_) if session.is_closed() => return,
+            // transient failures are retried at the next poll
+            Err(_) => {}
+        }
+        let (lock, condvar) = &*stopped;
+        let stop = lock.lock().unwrap();
+        let (stop, _) = condvar
+            .wait_timeout_while(stop, interval, |stop| !*stop)
+            .unwrap();
+        if *stop {
+            return;
+        }
+    }
+}
+
+#[pyclass]
+pub(crate) struct EntityWatcher {
+    stopped: StopFlag,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl EntityWatcher {
+    pub(crate) fn start(
+        session: &zenoh::Session,
+        kind: &str,
+        callback: PyObject,
+        interval: Duration,
+    ) -> PyResult<Self> {
+        let kind = admin_kind(kind)?;
+        // the watcher doesn't keep the session open
+        let session = session.downgrade();
+        let stopped = StopFlag::default();
+        let stopped_clone = stopped.clone();
+        let thread =
+            std::thread::spawn(move || watch(session, kind, callback, interval, stopped_clone));
+        Ok(Self {
+            stopped,
+            thread: Some(thread),
+        })
+    }
+
+    fn set_stopped(&self) {
+        let (lock, condvar) = &*self.stopped;
+        *lock.lock().unwrap() = true;
+        condvar.notify_all();
+    }
+}
+
+#[pymethods]
+impl EntityWatcher {
+    fn __enter__<'a, 'py>(this: &'a Bound<'py, Self>) -> &'a Bound<'py, Self> {
+        this
+    }
+
+    #[pyo3(signature = (*_args, **_kwargs))]
+    fn __exit__(
+        &mut self,
+        py: Python,
+        _args: &Bound<PyTuple>,
+        _kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<()> {
+        self.stop(py)
+    }
+
+    fn stop(&mut self, py: Python) -> PyResult<()> {
+        self.set_stopped();
+        let Some(thread) = self.thread.take() else {
+            return Ok(());
+        };
+        // the watcher can be stopped from its callback, which runs in the thread
+        if thread.thread().id() != std::thread::current().id() {
+            py.allow_threads(|| thread.join()).ok();
+        }
+        Ok(())
+    }
+}
+
+impl Drop for EntityWatcher {
+    fn drop(&mut self) {
+        self.set_stopped();
+    }
+}
@@ -11,52 +11,122 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{borrow::Cow, io::Read};
+use std::{borrow::Cow, ffi::c_int, io::Read};
 
 use pyo3::{
     exceptions::{PyTypeError, PyValueError},
+    ffi,
     prelude::*,
-    types::{PyByteArray, PyBytes, PyString},
+    sync::with_critical_section,
+    types::{PyBool, PyByteArray, PyBytes, PyDateTime, PyDict, PySlice, PyString, PyType},
 };
 
 use crate::{
-    macros::{downcast_or_new, wrapper},
+    json::{is_json_object, json_to_object, object_to_json},
+    macros::{downcast_or_new, import, wrapper},
+    time::{datetime_from_rfc3339, datetime_to_rfc3339, DATETIME_SCHEMA},
     utils::{IntoPyResult, MapInto},
 };
 
 wrapper!(zenoh::bytes::ZBytes: Clone, Default);
 downcast_or_new!(ZBytes);
 
-#[pymethods]
+/// A memoryview of an object supporting the buffer protocol other than bytes, bytearray and str,
+/// e.g. a numpy array; the buffer API is not part of the limited API before Python 3.11.
+fn as_memoryview<'py>(obj: &Bound<'py, PyAny>) -> Option<Bound<'py, PyAny>> {
+    if obj.is_instance_of::<PyBytes>()
+        || obj.is_instance_of::<PyByteArray>()
+        || obj.is_instance_of::<PyString>()
+    {
+        return None;
+    }
+    import!(obj.py(), builtins.memoryview).call1((obj,)).ok()
+}
+
+/// `PyBUF_WRITE` flag of `PyMemoryView_FromMemory`.
+const PYBUF_WRITE: c_int = 0x200;
+
+/// Copies a memoryview in C order, directly into the returned vector if it is C-contiguous;
+/// without the buffer API, non-contiguous ones are first copied by `tobytes`.
+fn memoryview_to_vec(view: &Bound<PyAny>) -> PyResult<Vec<u8>> {
+    let py = view.py();
+    if !view.getattr("c_contiguous")?.is_truthy()? {
+        let bytes = view.call_method0("tobytes")?;
+        return Ok(bytes.downcast::<PyBytes>()?.as_bytes().to_vec());
+    }
+    let mut vec = vec![0; view.getattr("nbytes")?.extract()?];
+    let len = vec.len() as ffi::Py_ssize_t;
+    // SAFETY: the memoryview is only used to fill the vector, and released before returning it
+    let target = unsafe {
+        let ptr = ffi::PyMemoryView_FromMemory(vec.as_mut_ptr().cast(), len, PYBUF_WRITE);
+        Bound::from_owned_ptr_or_err(py, ptr)?
+    };
+    let copied = target.set_item(PySlice::full(py), view.call_method1("cast", ("B",))?);
+    target.call_method0("release")?;
+    copied?;
+    Ok(vec)
+}
+
 impl ZBytes {
-    #[new]
-    fn new(obj: Option<&Bound<PyAny>>) -> PyResult<Self> {
-        let Some(obj) = obj else {
-            return Ok(Self::default());
-        };
-        if let Ok(bytes) = obj.downcast::<PyByteArray>() {
-            Ok(Self(bytes.to_vec().into()))
+    /// Converts a payload, returning whether it is a buffer protocol object, see `as_memoryview`.
+    pub(crate) fn from_payload(obj: &Bound<PyAny>) -> PyResult<(Self, bool)> {
+        if let Ok(boolean) = obj.downcast::<PyBool>() {
+            // JSON literal, see `to_bool`
+            let text = if boolean.is_true() { "true" } else { "false" };
+            Ok((Self(text.into()), false))
+        } else if let Ok(bytes) = obj.downcast::<PyByteArray>() {
+            Ok((
+                Self(with_critical_section(bytes, || bytes.to_vec()).into()),
+                false,
+            ))
         } else if let Ok(bytes) = obj.downcast::<PyBytes>() {
-            Ok(Self(bytes.as_bytes().into()))
+            Ok((Self(bytes.as_bytes().into()), false))
         } else if let Ok(string) = obj.downcast::<PyString>() {
-            Ok(Self(string.to_string().into()))
+            Ok((Self(string.to_string().into()), false))
+        } else if let Ok(datetime) = obj.downcast::<PyDateTime>() {
+            Ok((Self(datetime_to_rfc3339(datetime)?.into()), false))
+        } else if is_json_object(obj) {
+            Ok((Self(object_to_json(obj)?.into()), false))
         } else {
             #[cfg(feature = "shared-memory")]
             if let Ok(buf) = obj.downcast_exact::<crate::shm::ZShmMut>() {
-                return Ok(Self(buf.borrow_mut().take()?.into()));
+                return Ok((Self(buf.borrow_mut().take()?.into()), false));
             }
             #[cfg(feature = "shared-memory")]
             if let Ok(buf) = obj.downcast_exact::<crate::shm::ZShm>() {
-                return Ok(Self(buf.borrow().0.clone().into()));
+                return Ok((Self(buf.borrow().0.clone().into()), false));
+            }
+            if let Some(view) = as_memoryview(obj) {
+                return Ok((Self(memoryview_to_vec(&view)?.into()), true));
             }
             Err(PyTypeError::new_err(format!(
-                "expected bytes/str type, found '{}'",
-                obj.get_type().name().unwrap()
+                "expected bytes/str/bool/datetime/dict/list/dataclass/buffer type, found '{}'",
+                obj.get_type().name()?
             )))
         }
     }
 
-    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+    /// Converts an `attachment` argument like [`ZBytes::from_py_opt`], except for dicts, which
+    /// are serialized by [`ZBytes::from_dict`] instead of as JSON.
+    pub(crate) fn attachment_from_py_opt(obj: &Bound<PyAny>) -> PyResult<Option<Self>> {
+        match obj.downcast::<PyDict>() {
+            Ok(dict) => Self::from_dict(dict).map(Some),
+            Err(_) => Self::from_py_opt(obj),
+        }
+    }
+}
+
+#[pymethods]
+impl ZBytes {
+    #[new]
+    fn new(obj: Option<&Bound<PyAny>>) -> PyResult<Self> {
+        match obj {
+            Some(obj) => Ok(Self::from_payload(obj)?.0),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub(crate) fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
         // Not using `ZBytes::to_bytes`
         PyBytes::new_with(py, self.0.len(), |bytes| {
             self.0.reader().read_exact(bytes).into_pyres()
@@ -69,6 +139,79 @@ impl ZBytes {
             .map_err(|_| PyValueError::new_err("not an UTF8 error"))
     }
 
+    fn to_datetime<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        datetime_from_rfc3339(py, &self.to_string()?)
+    }
+
+    fn to_bool(&self) -> PyResult<bool> {
+        match &*self.0.to_bytes() {
+            b"true" => Ok(true),
+            b"false" => Ok(false),
+            _ => Err(PyValueError::new_err("not a 'true' or 'false' payload")),
+        }
+    }
+
+    fn to_json_value(&self, py: Python) -> PyResult<PyObject> {
+        json_to_object(py, &self.0.to_bytes())
+    }
+
+    /// Serializes a dict of bytes/str like a `dict[bytes, bytes]` by `zenoh.ext.z_serialize`,
+    /// i.e. the entry count followed by the length-prefixed keys and values, so that other
+    /// bindings can deserialize it as a map, e.g. for attachments.
+    #[staticmethod]
+    fn from_dict(dict: &Bound<PyDict>) -> PyResult<Self> {
+        let mut bytes = Vec::new();
+        write_varint(&mut bytes, dict.len() as u64);
+        let mut write_bytes = |obj: &Bound<PyAny>| {
+            let obj = if let Ok(string) = obj.downcast::<PyString>() {
+                Cow::Owned(string.to_cow()?.into_owned().into_bytes())
+            } else if let Ok(obj) = obj.downcast::<PyBytes>() {
+                Cow::Borrowed(obj.as_bytes())
+            } else {
+                return Err(PyTypeError::new_err(format!(
+                    "dict keys and values must be bytes or str, found '{}'",
+                    obj.get_type().name()?
+                )));
+            };
+            write_varint(&mut bytes, obj.len() as u64);
+            bytes.extend_from_slice(&obj);
+            Ok(())
+        };
+        for (key, value) in dict.iter() {
+            write_bytes(&key)?;
+            write_bytes(&value)?;
+        }
+        Ok(Self(bytes.into()))
+    }
+
+    /// Deserializes a dict serialized by [`ZBytes::from_dict`].
+    pub(crate) fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let invalid = || PyValueError::new_err("not a serialized dict");
+        let bytes = self.0.to_bytes();
+        let mut input = &*bytes;
+        let mut read_bytes = || {
+            let len = usize::try_from(read_varint(&mut input).ok_or_else(invalid)?)
+                .map_err(|_| invalid())?;
+            let (bytes, rest) = input.split_at_checked(len).ok_or_else(invalid)?;
+            input = rest;
+            // text is given back as str, and the rest as bytes
+            match std::str::from_utf8(bytes) {
+                Ok(text) => Ok(PyString::new(py, text).into_any()),
+                Err(_) => Ok(PyBytes::new(py, bytes).into_any()),
+            }
+        };
+        let dict = PyDict::new(py);
+        let len = read_varint(&mut input).ok_or_else(invalid)?;
+        for _ in 0..len {
+            let key = read_bytes()?;
+            dict.set_item(key, read_bytes()?)?;
+        }
+        if !input.is_empty() {
+            return Err(invalid());
+        }
+        Ok(dict)
+    }
+
     #[cfg(feature = "shared-memory")]
     fn as_shm(&self) -> Option<crate::shm::ZShm> {
         self.0.as_shm().map(ToOwned::to_owned).map_into()
@@ -103,6 +246,29 @@ impl ZBytes {
     }
 }
 
+/// Writes `value` as LEB128, the varint format of zenoh-ext serialization.
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+/// Reads a LEB128 varint, see [`write_varint`].
+fn read_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input.split_first()?;
+        *input = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
 wrapper!(zenoh::bytes::Encoding: Clone, Default);
 downcast_or_new!(Encoding => Option<String>);
 
@@ -113,6 +279,22 @@ impl Encoding {
         s.map_into().map(Self).unwrap_or_default()
     }
 
+    /// Builds an encoding from its raw prefix id, e.g. one unknown to this binding.
+    #[classmethod]
+    #[pyo3(signature = (prefix_id, schema = None))]
+    fn from_prefix_id(_cls: &Bound<PyType>, prefix_id: u16, schema: Option<String>) -> Self {
+        let encoding = zenoh::bytes::Encoding::new(prefix_id, None);
+        match schema {
+            Some(schema) => Self(encoding.with_schema(schema)),
+            None => Self(encoding),
+        }
+    }
+
+    #[getter]
+    fn prefix_id(&self) -> u16 {
+        self.0.id()
+    }
+
     fn with_schema(&self, schema: String) -> Self {
         Self(self.0.clone().with_schema(schema))
     }
@@ -140,6 +322,11 @@ impl Encoding {
     #[classattr]
     const ZENOH_SERIALIZED: Self = Self(zenoh::bytes::Encoding::ZENOH_SERIALIZED);
     #[classattr]
+    #[pyo3(name = "ZENOH_DATETIME")]
+    fn zenoh_datetime() -> Self {
+        Self(zenoh::bytes::Encoding::ZENOH_STRING.with_schema(DATETIME_SCHEMA))
+    }
+    #[classattr]
     const APPLICATION_OCTET_STREAM: Self = Self(zenoh::bytes::Encoding::APPLICATION_OCTET_STREAM);
     #[classattr]
     const TEXT_PLAIN: Self = Self(zenoh::bytes::Encoding::TEXT_PLAIN);
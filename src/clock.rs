@@ -0,0 +1,94 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+#[cfg(feature = "mock-clock")]
+use std::sync::Mutex;
+use std::{
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "mock-clock")]
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+#[cfg(not(feature = "mock-clock"))]
+use crate::error::{new_zerror_with_code, ErrorCode};
+#[cfg(feature = "mock-clock")]
+use crate::handlers::log_error;
+
+/// Controller of the mock clock, see `set_mock_clock`; without the `mock-clock` feature, the
+/// system clock is always used, at no cost.
+#[cfg(feature = "mock-clock")]
+static MOCK_CLOCK: Mutex<Option<PyObject>> = Mutex::new(None);
+
+/// An instant of the monotonic clock of the time-based features, i.e. the time elapsed since the
+/// clock origin, which can be mocked in tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct ClockInstant(Duration);
+
+impl ClockInstant {
+    pub(crate) fn elapsed(&self) -> Duration {
+        now().0.saturating_sub(self.0)
+    }
+}
+
+fn system_now() -> Duration {
+    static ORIGIN: OnceLock<Instant> = OnceLock::new();
+    ORIGIN.get_or_init(Instant::now).elapsed()
+}
+
+/// Calls `now()` on the mock clock controller, if any; errors are logged, and the system clock
+/// used instead.
+#[cfg(feature = "mock-clock")]
+fn mock_now() -> Option<Duration> {
+    Python::with_gil(|py| {
+        let controller = MOCK_CLOCK.lock().unwrap().as_ref()?.clone_ref(py);
+        let now = controller.call_method0(py, "now").and_then(|now| {
+            let now = now.extract::<f64>(py)?;
+            Duration::try_from_secs_f64(now).map_err(|err| PyValueError::new_err(err.to_string()))
+        });
+        match now {
+            Ok(now) => Some(now),
+            Err(err) => {
+                log_error(py, Err(err));
+                None
+            }
+        }
+    })
+}
+
+pub(crate) fn now() -> ClockInstant {
+    #[cfg(feature = "mock-clock")]
+    if let Some(now) = mock_now() {
+        return ClockInstant(now);
+    }
+    ClockInstant(system_now())
+}
+
+/// Replaces the clock of the time-based features by `controller.now()`, in seconds, or restores
+/// the system clock if `controller` is None.
+#[pyfunction]
+pub(crate) fn set_mock_clock(controller: Option<PyObject>) -> PyResult<()> {
+    #[cfg(feature = "mock-clock")]
+    {
+        *MOCK_CLOCK.lock().unwrap() = controller;
+        Ok(())
+    }
+    #[cfg(not(feature = "mock-clock"))]
+    {
+        let _ = controller;
+        let msg = "the mock clock requires the 'mock-clock' feature".to_string();
+        Err(new_zerror_with_code(msg, ErrorCode::FeatureUnavailable))
+    }
+}
@@ -0,0 +1,92 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+/// The declaration options outside of the zenoh-pico subset, as
+/// `(declaration, option, reason)`.
+const PICO_UNSUPPORTED: &[(&str, &str, &str)] = &[
+    (
+        "declare_subscriber",
+        "allowed_origin",
+        "locality requires the local subscriber feature, disabled in default zenoh-pico builds",
+    ),
+    (
+        "declare_subscriber",
+        "verify_integrity",
+        "integrity attachments are a convention of these bindings",
+    ),
+    (
+        "declare_subscriber",
+        "auto_decode",
+        "registered types are a convention of these bindings",
+    ),
+    (
+        "declare_queryable",
+        "allowed_origin",
+        "locality requires the local queryable feature, disabled in default zenoh-pico builds",
+    ),
+    (
+        "declare_publisher",
+        "allowed_destination",
+        "locality requires the local subscriber feature, disabled in default zenoh-pico builds",
+    ),
+    (
+        "declare_publisher",
+        "reliability",
+        "publisher reliability is unstable in zenoh-pico",
+    ),
+    (
+        "declare_publisher",
+        "retain",
+        "retained values are a convention of these bindings",
+    ),
+];
+
+/// The `compatibility` of a declaration, restricting its options to the ones supported by
+/// another Zenoh implementation.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Compatibility {
+    Pico,
+}
+
+impl Compatibility {
+    pub(crate) fn from_py_opt(obj: &Bound<PyAny>) -> PyResult<Option<Self>> {
+        if obj.is_none() {
+            return Ok(None);
+        }
+        match obj.extract::<&str>() {
+            Ok("pico") => Ok(Some(Self::Pico)),
+            _ => Err(PyValueError::new_err("compatibility must be 'pico'")),
+        }
+    }
+
+    /// Raises an error for the first option set on `declaration` which is not supported,
+    /// `options` being the `(option, is_set)` pairs of the declaration.
+    pub(crate) fn check(self, declaration: &str, options: &[(&str, bool)]) -> PyResult<()> {
+        let unsupported = match self {
+            Self::Pico => PICO_UNSUPPORTED,
+        };
+        for (option, _) in options.iter().filter(|(_, is_set)| *is_set) {
+            let unsupported = unsupported
+                .iter()
+                .find(|(decl, opt, _)| *decl == declaration && opt == option);
+            if let Some((_, _, reason)) = unsupported {
+                return Err(PyValueError::new_err(format!(
+                    "{option} is not supported with compatibility='pico': {reason}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    bytes::{Encoding, ZBytes},
+    error::{new_zerror_with_code, ErrorCode},
+    macros::zerror,
+};
+
+/// Well-known compression suffixes which are not supported, to fail instead of returning
+/// a compressed payload.
+const UNSUPPORTED: &[&str] = &["gzip", "deflate", "zlib", "br", "brotli", "snappy", "xz"];
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Compression {
+    Zstd,
+    Lz4,
+}
+
+impl Compression {
+    fn suffix(&self) -> &'static str {
+        match self {
+            Self::Zstd => "zstd",
+            Self::Lz4 => "lz4",
+        }
+    }
+
+    fn from_suffix(suffix: &str) -> Option<Self> {
+        match suffix {
+            "zstd" => Some(Self::Zstd),
+            "lz4" => Some(Self::Lz4),
+            _ => None,
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for Compression {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let compression = ob.extract::<String>()?;
+        Self::from_suffix(&compression).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "invalid compression '{compression}', expected 'zstd' or 'lz4'"
+            ))
+        })
+    }
+}
+
+/// Compresses the payload if requested, appending the compression suffix to its encoding.
+pub(crate) fn compress(
+    py: Python,
+    compression: Option<Compression>,
+    payload: ZBytes,
+    encoding: Option<Encoding>,
+) -> PyResult<(ZBytes, Option<Encoding>)> {
+    let Some(compression) = compression else {
+        return Ok((payload, encoding));
+    };
+    let bytes = payload.0.to_bytes();
+    let compressed = py.allow_threads(|| match compression {
+        Compression::Zstd => zstd::bulk::compress(&bytes, 0),
+        // the uncompressed size is prepended, as lz4 block format doesn't include it
+        Compression::Lz4 => Ok(lz4_flex::compress_prepend_size(&bytes)),
+    });
+    let compressed = compressed.map_err(|err| zerror!("failed to compress payload: {err}"))?;
+    let encoding = encoding.map(|e| e.0).unwrap_or_default();
+    let suffix = compression.suffix();
+    let encoding = zenoh::bytes::Encoding::from(format!("{encoding};{suffix}"));
+    Ok((ZBytes(compressed.into()), Some(Encoding(encoding))))
+}
+
+/// Decompresses the payload if its encoding has a compression suffix.
+pub(crate) fn decompress(
+    py: Python,
+    payload: &zenoh::bytes::ZBytes,
+    encoding: &zenoh::bytes::Encoding,
+) -> PyResult<ZBytes> {
+    let encoding = encoding.to_string();
+    let Some((_, suffix)) = encoding.rsplit_once(';') else {
+        return Ok(ZBytes(payload.clone()));
+    };
+    let Some(compression) = Compression::from_suffix(suffix) else {
+        if UNSUPPORTED.contains(&suffix) {
+            let msg = format!("unsupported compression '{suffix}' in encoding '{encoding}'");
+            return Err(new_zerror_with_code(msg, ErrorCode::FeatureUnavailable));
+        }
+        return Ok(ZBytes(payload.clone()));
+    };
+    let bytes = payload.to_bytes();
+    let decompressed = py.allow_threads(|| match compression {
+        Compression::Zstd => zstd::stream::decode_all(&*bytes).map_err(|err| err.to_string()),
+        Compression::Lz4 => {
+            lz4_flex::decompress_size_prepended(&bytes).map_err(|err| err.to_string())
+        }
+    });
+    match decompressed {
+        Ok(bytes) => Ok(ZBytes(bytes.into())),
+        Err(err) => Err(zerror!("failed to decompress {suffix} payload: {err}")),
+    }
+}
@@ -11,21 +11,147 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::path::PathBuf;
+use std::{
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, OnceLock,
+    },
+};
 
 use pyo3::{
+    exceptions::PyValueError,
     prelude::*,
-    types::{PyBytes, PyType},
+    types::{PyBytes, PyCFunction, PyDict, PyType},
 };
+use serde_json::{Map, Value};
 
 use crate::{
-    macros::{downcast_or_new, enum_mapper, wrapper},
-    time::TimestampId,
+    files::io_error,
+    handlers::{log_error, CHECK_SIGNALS_INTERVAL},
+    macros::{downcast_or_new, enum_mapper, import, wrapper},
+    report::{is_secret, redact, REDACTED},
+    time::{binary_format_payload, TimestampId, BINARY_FORMAT_VERSION},
     utils::{IntoPyResult, IntoRust},
 };
 
 wrapper!(zenoh::Config: Default, Clone);
 
+impl Config {
+    /// Extracts a `Config`, or a dict converted to JSON.
+    pub(crate) fn from_py(obj: &Bound<PyAny>) -> PyResult<Self> {
+        if obj.is_instance_of::<PyDict>() {
+            let json = import!(obj.py(), json.dumps).call1((obj,))?;
+            let config = zenoh::Config::from_json5(&json.extract::<String>()?).into_pyres()?;
+            return Ok(Self(config));
+        }
+        obj.extract()
+    }
+
+    pub(crate) fn from_py_opt(obj: &Bound<PyAny>) -> PyResult<Option<Self>> {
+        if obj.is_none() {
+            return Ok(None);
+        }
+        Self::from_py(obj).map(Some)
+    }
+
+    /// Rebuilds a configuration from the values of its top-level keys, e.g. the ones of an
+    /// opened session.
+    fn from_values(get: impl Fn(&str) -> Option<Value>) -> PyResult<Self> {
+        let mut map = Map::new();
+        for key in config_keys().iter().filter(|key| !key.contains('/')) {
+            if let Some(value) = get(key) {
+                map.insert(key.clone(), value);
+            }
+        }
+        let json = Value::Object(map).to_string();
+        Ok(Self(zenoh::Config::from_json5(&json).into_pyres()?))
+    }
+
+    fn insert_value(&mut self, key: &str, value: impl Into<Value>) -> PyResult<()> {
+        self.0
+            .insert_json5(key, &value.into().to_string())
+            .into_pyres()
+    }
+
+    /// The configuration as JSON, with secrets redacted, see `report::redact`.
+    fn redacted_json(&self) -> Value {
+        let mut map = Map::new();
+        for key in config_keys().iter().filter(|key| !key.contains('/')) {
+            let value = self.0.get_json(key).ok();
+            let Some(mut value) = value.and_then(|v| serde_json::from_str::<Value>(&v).ok()) else {
+                continue;
+            };
+            if is_secret(key) && !value.is_null() {
+                value = REDACTED.into();
+            }
+            redact(&mut value);
+            map.insert(key.clone(), value);
+        }
+        Value::Object(map)
+    }
+}
+
+const USER_KEY: &str = "transport/auth/usrpwd/user";
+const PASSWORD_KEY: &str = "transport/auth/usrpwd/password";
+const TLS_ROOT_CA_KEY: &str = "transport/link/tls/root_ca_certificate";
+/// The certificate and private key are used both to listen and to connect, with mutual TLS.
+const TLS_CERT_KEYS: [&str; 2] = [
+    "transport/link/tls/listen_certificate",
+    "transport/link/tls/connect_certificate",
+];
+const TLS_KEY_KEYS: [&str; 2] = [
+    "transport/link/tls/listen_private_key",
+    "transport/link/tls/connect_private_key",
+];
+const TLS_VERIFY_NAME_KEY: &str = "transport/link/tls/verify_name_on_connect";
+
+/// Checks the file can be read now, rather than when opening the session, and returns its path.
+fn readable_path(path: &Path) -> PyResult<String> {
+    // opening a directory succeeds, reading it doesn't
+    let read = std::fs::File::open(path).and_then(|mut file| file.read(&mut [0; 1]));
+    read.map_err(|err| io_error("read", path, err))?;
+    match path.to_str() {
+        Some(path) => Ok(path.to_string()),
+        None => Err(PyValueError::new_err(format!(
+            "non UTF-8 path '{}'",
+            path.display()
+        ))),
+    }
+}
+
+/// Prefix of plugin keys, whose schema is defined by the plugins themselves.
+const PLUGINS_PREFIX: &str = "plugins/";
+/// Maximum edit distance of the keys suggested for an unknown key.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+const MAX_SUGGESTIONS: usize = 3;
+
+/// All the keys of the configuration schema, plugin ones excepted.
+pub(crate) fn config_keys() -> &'static [String] {
+    static KEYS: OnceLock<Vec<String>> = OnceLock::new();
+    KEYS.get_or_init(|| {
+        let mut keys = zenoh::Config::default().keys().collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys
+    })
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.as_bytes();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.bytes().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
 #[pymethods]
 impl Config {
     #[new]
@@ -51,6 +177,39 @@ impl Config {
         Ok(Self(zenoh::config::Config::from_json5(json).into_pyres()?))
     }
 
+    /// Normalizes a dotted or slashed key, and checks it is part of the configuration schema.
+    #[staticmethod]
+    fn validate_key(key: &str) -> PyResult<String> {
+        let key = key.replace('.', "/").trim_matches('/').to_string();
+        let keys = config_keys();
+        if key.starts_with(PLUGINS_PREFIX) || keys.binary_search(&key).is_ok() {
+            return Ok(key);
+        }
+        let mut suggestions = keys
+            .iter()
+            .map(|k| (edit_distance(&key, k), k))
+            .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+            .collect::<Vec<_>>();
+        suggestions.sort();
+        let suggestions = suggestions
+            .iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, k)| format!("'{k}'"));
+        let suggestions = suggestions.collect::<Vec<_>>();
+        if suggestions.is_empty() {
+            return Err(PyValueError::new_err(format!("unknown config key '{key}'")));
+        }
+        Err(PyValueError::new_err(format!(
+            "unknown config key '{key}', did you mean {}?",
+            suggestions.join(", ")
+        )))
+    }
+
+    #[staticmethod]
+    fn keys() -> Vec<&'static str> {
+        config_keys().iter().map(String::as_str).collect()
+    }
+
     fn get_json(&self, key: &str) -> PyResult<String> {
         self.0.get_json(key).into_pyres()
     }
@@ -59,12 +218,146 @@ impl Config {
         self.0.insert_json5(key, value).into_pyres()
     }
 
+    fn set_credentials(&mut self, user: &str, password: &str) -> PyResult<()> {
+        if user.is_empty() {
+            return Err(PyValueError::new_err("the user must not be empty"));
+        }
+        self.insert_value(USER_KEY, user)?;
+        self.insert_value(PASSWORD_KEY, password)
+    }
+
+    #[pyo3(signature = (*, root_ca = None, cert = None, key = None, verify_name = true))]
+    fn set_tls(
+        &mut self,
+        root_ca: Option<PathBuf>,
+        cert: Option<PathBuf>,
+        key: Option<PathBuf>,
+        verify_name: bool,
+    ) -> PyResult<()> {
+        if cert.is_some() != key.is_some() {
+            return Err(PyValueError::new_err("cert and key must be given together"));
+        }
+        // all the files are checked before the configuration is modified
+        let readable = |path: Option<PathBuf>| path.as_deref().map(readable_path).transpose();
+        let (root_ca, cert, key) = (readable(root_ca)?, readable(cert)?, readable(key)?);
+        if let Some(root_ca) = root_ca {
+            self.insert_value(TLS_ROOT_CA_KEY, root_ca)?;
+        }
+        if let (Some(cert), Some(key)) = (cert, key) {
+            for cert_key in TLS_CERT_KEYS {
+                self.insert_value(cert_key, cert.as_str())?;
+            }
+            for key_key in TLS_KEY_KEYS {
+                self.insert_value(key_key, key.as_str())?;
+            }
+        }
+        self.insert_value(TLS_VERIFY_NAME_KEY, verify_name)
+    }
+
+    fn to_json(&self) -> String {
+        self.redacted_json().to_string()
+    }
+
     fn __repr__(&self) -> String {
-        format!("{:?}", self.0)
+        // not the zenoh debug representation, to be sure secrets are not echoed
+        format!("Config({})", self.to_json())
     }
 
     fn __str__(&self) -> String {
-        format!("{}", self.0)
+        self.to_json()
+    }
+}
+
+/// Configuration of an opened session, whose changes are notified, see `Session.config`.
+#[pyclass(frozen)]
+pub(crate) struct ConfigNotifier(pub(crate) zenoh::Session);
+
+/// Returns true if a change of `key` may change the entries under `prefix`, i.e. one of them
+/// is a parent of the other.
+fn is_related_key(key: &str, prefix: &str) -> bool {
+    let is_parent = |parent: &str, child: &str| {
+        parent.is_empty()
+            || child
+                .strip_prefix(parent)
+                .is_some_and(|rest| rest.starts_with('/'))
+    };
+    key == prefix || is_parent(prefix, key) || is_parent(key, prefix)
+}
+
+#[pymethods]
+impl ConfigNotifier {
+    fn get_json(&self, key: &str) -> PyResult<String> {
+        let value = self.0.config().get_typed::<Value>(key).into_pyres()?;
+        Ok(value.to_string())
+    }
+
+    fn insert_json5(&self, py: Python, key: &str, value: &str) -> PyResult<()> {
+        py.allow_threads(|| self.0.config().insert_json5(key, value))
+            .into_pyres()
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        let config = self.0.config();
+        Ok(Config::from_values(|key| config.get_typed::<Value>(key).ok())?.to_json())
+    }
+
+    /// Calls `callback` with the key and its new JSON value, in a dedicated thread, whenever an
+    /// entry under `prefix` is changed.
+    fn on_change(&self, py: Python, prefix: &str, callback: PyObject) -> PyResult<ConfigWatcher> {
+        let prefix = prefix.replace('.', "/").trim_matches('/').to_string();
+        let receiver = self.0.config().subscribe();
+        let session = self.0.clone();
+        let unsubscribed = Arc::new(AtomicBool::new(false));
+        let stopped = unsubscribed.clone();
+        let target = PyCFunction::new_closure(py, None, None, move |args, _| {
+            let py = args.py();
+            // No need to call `Python::check_signals` because it's not the main thread.
+            let recv = || receiver.recv_timeout(CHECK_SIGNALS_INTERVAL);
+            while !stopped.load(Ordering::Relaxed) {
+                let key = match py.allow_threads(recv) {
+                    Ok(key) => key,
+                    // the notifier is dropped with the session
+                    Err(_) if receiver.is_disconnected() => break,
+                    Err(_) => continue,
+                };
+                if stopped.load(Ordering::Relaxed) || !is_related_key(&key, &prefix) {
+                    continue;
+                }
+                let value = session
+                    .config()
+                    .get_typed::<Value>(&key)
+                    .unwrap_or_default();
+                let value = import!(py, json.loads).call1((value.to_string(),));
+                log_error(py, value.and_then(|v| callback.call1(py, (&*key, v))));
+            }
+        })?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("target", target)?;
+        kwargs.set_item("daemon", true)?;
+        let thread = import!(py, threading.Thread).call((), Some(&kwargs))?;
+        thread.call_method0("start")?;
+        Ok(ConfigWatcher(unsubscribed))
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        Ok(format!("ConfigNotifier({})", self.to_json()?))
+    }
+}
+
+/// Registration of a `ConfigNotifier.on_change` callback.
+#[pyclass(frozen)]
+pub(crate) struct ConfigWatcher(Arc<AtomicBool>);
+
+#[pymethods]
+impl ConfigWatcher {
+    /// The callback is no longer called once this returns, except if it is running.
+    fn unsubscribe(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[getter]
+    fn unsubscribed(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
     }
 }
 
@@ -165,6 +458,22 @@ impl ZenohId {
         Ok(TimestampId(self.0.to_le_bytes().try_into().into_pyres()?).__bytes__(py))
     }
 
+    // Python methods can't take `self` by value
+    #[allow(clippy::wrong_self_convention)]
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let mut bytes = vec![BINARY_FORMAT_VERSION];
+        bytes.extend_from_slice(self.__bytes__(py)?.as_bytes());
+        Ok(PyBytes::new(py, &bytes))
+    }
+
+    #[classmethod]
+    fn from_bytes(_cls: &Bound<PyType>, bytes: &[u8]) -> PyResult<Self> {
+        let payload = binary_format_payload(bytes, "zenoh id")?;
+        zenoh::config::ZenohId::try_from(payload)
+            .map(Self)
+            .map_err(|_| PyValueError::new_err(format!("invalid zenoh id bytes {bytes:?}")))
+    }
+
     fn __eq__(&self, other: ZenohId) -> PyResult<bool> {
         Ok(self.0 == other.0)
     }
@@ -0,0 +1,405 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    cell::OnceCell,
+    collections::HashSet,
+    fmt::Write,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Condvar, Mutex, Weak,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyString};
+
+use crate::{
+    clock::{self, ClockInstant},
+    group::is_undeclared,
+    handlers::log_error,
+    macros::import,
+    session::Session,
+};
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+static PENDING_TRACKING: AtomicBool = AtomicBool::new(false);
+static EXIT_REPORT_REGISTERED: AtomicBool = AtomicBool::new(false);
+/// Handles recorded while tracking is enabled, in creation order.
+static HANDLES: Mutex<Vec<TrackedHandle>> = Mutex::new(Vec::new());
+
+struct TrackedHandle {
+    /// A weak reference, so tracking doesn't keep entities alive, except for background
+    /// entities, which are not undeclared when dropped.
+    entity: PyObject,
+    background: bool,
+    /// The session of the entity, as closing it undeclares the entity.
+    session: Option<zenoh::session::WeakSession>,
+    location: String,
+    traceback: String,
+}
+
+impl TrackedHandle {
+    fn resolve<'py>(&self, py: Python<'py>) -> Option<Bound<'py, PyAny>> {
+        let entity = self.entity.bind(py);
+        if self.background {
+            return Some(entity.clone());
+        }
+        entity.call0().ok().filter(|entity| !entity.is_none())
+    }
+
+    fn is_open(&self, entity: &Bound<PyAny>) -> bool {
+        if self
+            .session
+            .as_ref()
+            .is_some_and(|session| session.is_closed())
+        {
+            return false;
+        }
+        match entity.downcast::<Session>() {
+            Ok(session) => session.try_borrow().is_ok_and(|s| !s.0.is_closed()),
+            Err(_) => !is_undeclared(entity),
+        }
+    }
+}
+
+#[pyclass(frozen)]
+pub(crate) struct OpenHandle {
+    #[pyo3(get)]
+    entity: PyObject,
+    #[pyo3(get)]
+    location: String,
+    #[pyo3(get)]
+    traceback: String,
+}
+
+#[pymethods]
+impl OpenHandle {
+    fn __repr__(&self, py: Python) -> PyResult<String> {
+        let kind = self.entity.bind(py).get_type().name()?;
+        Ok(format!("OpenHandle({kind} created at {})", self.location))
+    }
+}
+
+/// Records the creation location of a session or entity, if tracking is enabled.
+pub(crate) fn track(
+    entity: &Bound<PyAny>,
+    session: Option<&zenoh::Session>,
+    background: bool,
+) -> PyResult<()> {
+    if !TRACKING.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    let py = entity.py();
+    let stack = import!(py, traceback.extract_stack).call0()?;
+    let location = match stack.len()? {
+        0 => "<unknown>".to_string(),
+        n => {
+            let frame = stack.get_item(n - 1)?;
+            let (filename, lineno) = (frame.getattr("filename")?, frame.getattr("lineno")?);
+            format!("{filename}:{lineno}")
+        }
+    };
+    let lines = import!(py, traceback.format_list).call1((stack,))?;
+    let traceback = PyString::new(py, "").call_method1("join", (lines,))?;
+    let entity = if background {
+        entity.clone()
+    } else {
+        import!(py, weakref.ref).call1((entity,))?
+    };
+    let handle = TrackedHandle {
+        entity: entity.unbind(),
+        background,
+        session: session.map(zenoh::Session::downgrade),
+        location,
+        traceback: traceback.extract()?,
+    };
+    HANDLES.lock().unwrap().push(handle);
+    Ok(())
+}
+
+#[pyfunction]
+pub(crate) fn track_handles(py: Python, enabled: bool) -> PyResult<()> {
+    TRACKING.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        // handles are dropped after releasing the lock
+        let _handles = std::mem::take(&mut *HANDLES.lock().unwrap());
+        return Ok(());
+    }
+    if !EXIT_REPORT_REGISTERED.swap(true, Ordering::Relaxed) {
+        let report = wrap_pyfunction!(report_open_handles, py)?;
+        import!(py, atexit.register).call1((report,))?;
+    }
+    Ok(())
+}
+
+#[pyfunction]
+pub(crate) fn open_handles(py: Python) -> Vec<OpenHandle> {
+    let mut open = Vec::new();
+    // closed handles never reopen, so they are forgotten; checking them doesn't run arbitrary
+    // code, which could deadlock while holding the lock
+    HANDLES.lock().unwrap().retain(|handle| {
+        let Some(entity) = handle.resolve(py).filter(|e| handle.is_open(e)) else {
+            return false;
+        };
+        open.push(OpenHandle {
+            entity: entity.unbind(),
+            location: handle.location.clone(),
+            traceback: handle.traceback.clone(),
+        });
+        true
+    });
+    open
+}
+
+/// Prints the handles still open at interpreter exit to stderr.
+#[pyfunction]
+fn report_open_handles(py: Python) -> PyResult<()> {
+    if !TRACKING.load(Ordering::Relaxed) {
+        return Ok(());
+    }
+    let handles = open_handles(py);
+    if handles.is_empty() {
+        return Ok(());
+    }
+    let mut report = format!("zenoh: {} handles still open at exit\n", handles.len());
+    for handle in &handles {
+        let kind = handle.entity.bind(py).get_type().name()?;
+        writeln!(report, "{kind} created at {}:", handle.location).unwrap();
+        for line in handle.traceback.lines() {
+            writeln!(report, "  {line}").unwrap();
+        }
+    }
+    let stderr = py.import("sys")?.getattr("stderr")?;
+    stderr.call_method1("write", (report,))?;
+    Ok(())
+}
+
+/// The key expression or selector of a pending operation, only converted to a string when listed.
+pub(crate) enum PendingKey {
+    None,
+    Python(PyObject),
+    Rust(zenoh::key_expr::KeyExpr<'static>),
+}
+
+struct PendingEntry {
+    id: u64,
+    operation: &'static str,
+    key_expr: PendingKey,
+    start: ClockInstant,
+}
+
+/// The blocking calls in progress in a thread, nested calls after the outer ones.
+///
+/// Only the thread itself and the listing lock the calls, so registering a call costs a few
+/// uncontended atomic operations.
+struct ThreadCalls {
+    thread_id: u64,
+    calls: Mutex<Vec<PendingEntry>>,
+}
+
+static THREADS: Mutex<Vec<Weak<ThreadCalls>>> = Mutex::new(Vec::new());
+static NEXT_PENDING_ID: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static THREAD_CALLS: OnceCell<Arc<ThreadCalls>> = const { OnceCell::new() };
+}
+
+fn thread_calls(py: Python) -> Arc<ThreadCalls> {
+    THREAD_CALLS.with(|calls| {
+        calls
+            .get_or_init(|| {
+                let ident = import!(py, threading.get_ident).call0();
+                let calls = Arc::new(ThreadCalls {
+                    thread_id: ident.and_then(|ident| ident.extract()).unwrap_or_default(),
+                    calls: Mutex::default(),
+                });
+                let mut threads = THREADS.lock().unwrap();
+                threads.retain(|thread| thread.strong_count() > 0);
+                threads.push(Arc::downgrade(&calls));
+                calls
+            })
+            .clone()
+    })
+}
+
+#[pyfunction]
+pub(crate) fn track_pending(enabled: bool) {
+    PENDING_TRACKING.store(enabled, Ordering::Relaxed);
+}
+
+/// Unregisters a pending operation when dropped.
+pub(crate) struct PendingGuard(Arc<ThreadCalls>);
+
+impl Drop for PendingGuard {
+    fn drop(&mut self) {
+        // the key is dropped after releasing the lock
+        let _call = self.0.calls.lock().unwrap().pop();
+    }
+}
+
+/// Registers a blocking call until the returned guard is dropped, if tracking is enabled, see
+/// `pending_operations`.
+pub(crate) fn pending(
+    py: Python,
+    operation: &'static str,
+    key_expr: impl FnOnce() -> PendingKey,
+) -> Option<PendingGuard> {
+    if !PENDING_TRACKING.load(Ordering::Relaxed) {
+        return None;
+    }
+    let thread = thread_calls(py);
+    let start = clock::now();
+    thread.calls.lock().unwrap().push(PendingEntry {
+        id: NEXT_PENDING_ID.fetch_add(1, Ordering::Relaxed),
+        operation,
+        key_expr: key_expr(),
+        start,
+    });
+    Some(PendingGuard(thread))
+}
+
+#[pyclass(frozen)]
+pub(crate) struct PendingOperation {
+    #[pyo3(get)]
+    operation: &'static str,
+    #[pyo3(get)]
+    key_expr: Option<String>,
+    #[pyo3(get)]
+    thread_id: u64,
+    #[pyo3(get)]
+    age: f64,
+}
+
+#[pymethods]
+impl PendingOperation {
+    fn __repr__(&self) -> String {
+        let key_expr = self
+            .key_expr
+            .as_deref()
+            .map_or(String::new(), |k| format!(" on '{k}'"));
+        let (operation, thread_id, age) = (self.operation, self.thread_id, self.age);
+        format!("PendingOperation({operation}{key_expr} in thread {thread_id} for {age:.3}s)")
+    }
+}
+
+/// The outermost pending call of each thread, with its id.
+fn outermost_calls(py: Python) -> Vec<(u64, PendingOperation)> {
+    let threads = THREADS
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect::<Vec<_>>();
+    let mut operations = Vec::new();
+    for thread in threads {
+        // keys are converted after releasing the lock, as it runs arbitrary code
+        let (id, operation, key_expr, start) = {
+            let calls = thread.calls.lock().unwrap();
+            let Some(call) = calls.first() else {
+                continue;
+            };
+            let key_expr = match &call.key_expr {
+                PendingKey::None => PendingKey::None,
+                PendingKey::Python(obj) => PendingKey::Python(obj.clone_ref(py)),
+                PendingKey::Rust(key_expr) => PendingKey::Rust(key_expr.clone()),
+            };
+            (call.id, call.operation, key_expr, call.start)
+        };
+        let key_expr = match key_expr {
+            PendingKey::None => None,
+            PendingKey::Python(obj) => obj.bind(py).str().ok().map(|s| s.to_string()),
+            PendingKey::Rust(key_expr) => Some(key_expr.to_string()),
+        };
+        let operation = PendingOperation {
+            operation,
+            key_expr,
+            thread_id: thread.thread_id,
+            age: start.elapsed().as_secs_f64(),
+        };
+        operations.push((id, operation));
+    }
+    operations.sort_by(|(_, op1), (_, op2)| op2.age.total_cmp(&op1.age));
+    operations
+}
+
+#[pyfunction]
+pub(crate) fn pending_operations(py: Python) -> Vec<PendingOperation> {
+    outermost_calls(py)
+        .into_iter()
+        .map(|(_, operation)| operation)
+        .collect()
+}
+
+type StopFlag = Arc<(Mutex<bool>, Condvar)>;
+
+struct Watchdog {
+    stopped: StopFlag,
+    thread: JoinHandle<()>,
+}
+
+static WATCHDOG: Mutex<Option<Watchdog>> = Mutex::new(None);
+
+/// Calls `callback` once with each pending call exceeding `threshold`.
+fn watch_pending(callback: PyObject, threshold: Duration, stopped: StopFlag) {
+    let interval = (threshold / 10).clamp(Duration::from_millis(10), Duration::from_secs(1));
+    let mut reported = HashSet::new();
+    loop {
+        Python::with_gil(|py| {
+            let calls = outermost_calls(py);
+            reported.retain(|id| calls.iter().any(|(call_id, _)| call_id == id));
+            for (id, operation) in calls {
+                if operation.age >= threshold.as_secs_f64() && reported.insert(id) {
+                    log_error(py, callback.call1(py, (operation,)));
+                }
+            }
+        });
+        let (lock, condvar) = &*stopped;
+        let stop = lock.lock().unwrap();
+        let (stop, _) = condvar
+            .wait_timeout_while(stop, interval, |stop| !*stop)
+            .unwrap();
+        if *stop {
+            return;
+        }
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (threshold = 10.0, *, callback))]
+pub(crate) fn enable_watchdog(py: Python, threshold: f64, callback: PyObject) -> PyResult<()> {
+    let threshold = Duration::try_from_secs_f64(threshold)
+        .map_err(|_| PyValueError::new_err("negative threshold"))?;
+    disable_watchdog(py);
+    track_pending(true);
+    let stopped = StopFlag::default();
+    let stopped_clone = stopped.clone();
+    let thread = std::thread::spawn(move || watch_pending(callback, threshold, stopped_clone));
+    *WATCHDOG.lock().unwrap() = Some(Watchdog { stopped, thread });
+    Ok(())
+}
+
+#[pyfunction]
+pub(crate) fn disable_watchdog(py: Python) {
+    let Some(Watchdog { stopped, thread }) = WATCHDOG.lock().unwrap().take() else {
+        return;
+    };
+    let (lock, condvar) = &*stopped;
+    *lock.lock().unwrap() = true;
+    condvar.notify_all();
+    // the watchdog can be disabled from its callback, which runs in the thread
+    if thread.thread().id() != std::thread::current().id() {
+        py.allow_threads(|| thread.join()).ok();
+    }
+}
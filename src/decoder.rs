@@ -0,0 +1,121 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    RwLock,
+};
+
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+};
+
+use crate::{bytes::ZBytes, handlers::map_callback, key_expr::KeyExpr, sample::Sample};
+
+/// Registered decoders, in registration order.
+static DECODERS: RwLock<Vec<(u64, zenoh::key_expr::KeyExpr<'static>, PyObject)>> =
+    RwLock::new(Vec::new());
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+#[pyclass(frozen)]
+pub(crate) struct TypeRegistration {
+    id: u64,
+    key_expr: zenoh::key_expr::KeyExpr<'static>,
+}
+
+#[pymethods]
+impl TypeRegistration {
+    #[getter]
+    fn key_expr(&self) -> KeyExpr {
+        self.key_expr.clone().into()
+    }
+
+    /// Removes the decoder, returning false if it was already removed.
+    fn unregister(&self) -> bool {
+        let mut decoders = DECODERS.write().unwrap();
+        let Some(index) = decoders.iter().position(|(id, ..)| *id == self.id) else {
+            return false;
+        };
+        // the decoder is dropped after releasing the lock
+        let _decoder = decoders.remove(index);
+        drop(decoders);
+        true
+    }
+
+    fn __repr__(&self) -> String {
+        format!("TypeRegistration(key_expr={})", self.key_expr)
+    }
+}
+
+#[pyfunction]
+pub(crate) fn register_type(
+    #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
+    decoder: &Bound<PyAny>,
+) -> PyResult<TypeRegistration> {
+    if !decoder.is_callable() {
+        return Err(PyTypeError::new_err("decoder must be callable"));
+    }
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let entry = (id, key_expr.0.clone(), decoder.clone().unbind());
+    DECODERS.write().unwrap().push(entry);
+    Ok(TypeRegistration {
+        id,
+        key_expr: key_expr.0,
+    })
+}
+
+/// Decodes the payload with the first registered decoder whose key expression intersects
+/// `key_expr`, if any.
+pub(crate) fn decode_registered(
+    py: Python,
+    key_expr: &zenoh::key_expr::KeyExpr,
+    payload: &ZBytes,
+) -> PyResult<Option<PyObject>> {
+    let decoders = DECODERS.read().unwrap();
+    let Some((.., decoder)) = decoders.iter().find(|(_, k, _)| k.intersects(key_expr)) else {
+        return Ok(None);
+    };
+    // the lock is not held while calling Python code, which may register decoders
+    let decoder = decoder.clone_ref(py);
+    drop(decoders);
+    Ok(Some(decoder.call1(py, (payload.to_bytes(py)?,))?))
+}
+
+/// Callback wrapper passing the sample decoded with the registered decoders, or its payload
+/// if there is none, as second argument.
+#[pyclass(frozen)]
+struct AutoDecode(PyObject);
+
+#[pymethods]
+impl AutoDecode {
+    fn __call__(&self, py: Python, sample: Bound<Sample>) -> PyResult<PyObject> {
+        let decoded = sample.borrow().decode(py, true)?;
+        self.0.call1(py, (sample, decoded))
+    }
+}
+
+/// Wraps a subscriber callback handler with [`AutoDecode`].
+pub(crate) fn auto_decode_handler<'py>(
+    handler: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let Some(handler) = handler.filter(|h| h.is_callable()) else {
+        return Err(PyValueError::new_err(
+            "auto_decode requires a callback handler",
+        ));
+    };
+    let py = handler.py();
+    map_callback(handler, |callback| {
+        Ok(Py::new(py, AutoDecode(callback))?.into_any())
+    })
+}
@@ -0,0 +1,98 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{any::Any, io};
+
+use pyo3::{prelude::*, types::PyType};
+
+use crate::{macros::import, ZError};
+
+#[pyclass(eq, module = "zenoh")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ErrorCode {
+    #[pyo3(name = "SESSION_CLOSED")]
+    SessionClosed,
+    #[pyo3(name = "INVALID_KEYEXPR")]
+    InvalidKeyexpr,
+    #[pyo3(name = "TIMEOUT")]
+    Timeout,
+    #[pyo3(name = "IO")]
+    Io,
+    #[pyo3(name = "CONGESTION")]
+    Congestion,
+    #[pyo3(name = "ACCESS_DENIED")]
+    AccessDenied,
+    #[pyo3(name = "FEATURE_UNAVAILABLE")]
+    FeatureUnavailable,
+    #[pyo3(name = "OTHER")]
+    Other,
+}
+
+impl ErrorCode {
+    /// The code of the typed zenoh errors, i.e. closed sessions and I/O errors, `OTHER` for the
+    /// other ones, whose code is set by their call site.
+    pub(crate) fn of(err: &dyn Any) -> Self {
+        let Some(err) = err.downcast_ref::<zenoh::Error>() else {
+            return Self::Other;
+        };
+        if err.is::<zenoh::session::SessionClosedError>() {
+            return Self::SessionClosed;
+        }
+        match err.downcast_ref::<io::Error>().map(io::Error::kind) {
+            Some(io::ErrorKind::TimedOut) => Self::Timeout,
+            Some(io::ErrorKind::PermissionDenied) => Self::AccessDenied,
+            Some(_) => Self::Io,
+            None => Self::Other,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::SessionClosed => "SESSION_CLOSED",
+            Self::InvalidKeyexpr => "INVALID_KEYEXPR",
+            Self::Timeout => "TIMEOUT",
+            Self::Io => "IO",
+            Self::Congestion => "CONGESTION",
+            Self::AccessDenied => "ACCESS_DENIED",
+            Self::FeatureUnavailable => "FEATURE_UNAVAILABLE",
+            Self::Other => "OTHER",
+        }
+    }
+}
+
+#[pymethods]
+impl ErrorCode {
+    // enum variants can't be constructed, so they are unpickled by name
+    fn __reduce__<'py>(
+        &self,
+        py: Python<'py>,
+    ) -> (Bound<'py, PyAny>, (Bound<'py, PyType>, &'static str)) {
+        (
+            import!(py, builtins.getattr).clone(),
+            (py.get_type::<Self>(), self.name()),
+        )
+    }
+}
+
+/// Creates a [`ZError`] with the `OTHER` code, see [`ErrorCode::of`].
+pub(crate) fn new_zerror(msg: String) -> PyErr {
+    ZError::new_err(msg)
+}
+
+/// Creates a [`ZError`] with an explicit `code` attribute.
+pub(crate) fn new_zerror_with_code(msg: String, code: ErrorCode) -> PyErr {
+    let err = ZError::new_err(msg);
+    // the code is left to the `OTHER` class attribute if it can't be set
+    Python::with_gil(|py| err.value(py).setattr("code", code).ok());
+    err
+}
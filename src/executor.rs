@@ -0,0 +1,247 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+    types::{PyCFunction, PyDict, PyTuple},
+};
+
+use crate::{macros::import, utils::duration};
+
+pub(crate) type Task = Box<dyn FnOnce(Python) + Send>;
+
+#[derive(Default)]
+struct Queue {
+    tasks: VecDeque<Task>,
+    // tasks pinned to a worker, one queue per worker
+    lanes: Vec<VecDeque<Task>>,
+    shutdown: bool,
+}
+
+impl Queue {
+    fn len(&self) -> usize {
+        self.tasks.len() + self.lanes.iter().map(VecDeque::len).sum::<usize>()
+    }
+}
+
+/// Task queue shared between an [`Executor`] and its workers.
+struct ExecutorQueue {
+    queue: Mutex<Queue>,
+    condvar: Condvar,
+    max_queue: Option<usize>,
+    busy: AtomicUsize,
+    dropped: AtomicUsize,
+    next_lane: AtomicUsize,
+}
+
+impl ExecutorQueue {
+    /// Enqueues the task, pinned to the worker of `lane` if any, which is dropped if the
+    /// executor is shut down or its queue is full.
+    fn submit(&self, lane: Option<usize>, task: Task) {
+        let mut queue = self.queue.lock().unwrap();
+        if queue.shutdown || self.max_queue.is_some_and(|max| queue.len() >= max) {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            // the task may hold the last reference to a Python callback
+            drop(queue);
+            return;
+        }
+        match lane {
+            Some(lane) => {
+                queue.lanes[lane].push_back(task);
+                // the lane worker may not be the one woken up
+                self.condvar.notify_all();
+            }
+            None => {
+                queue.tasks.push_back(task);
+                self.condvar.notify_one();
+            }
+        }
+    }
+
+    /// Waits for the next task of the worker of `lane`, its pinned tasks first, returning `None`
+    /// once shut down and drained.
+    fn next(&self, lane: usize) -> Option<Task> {
+        let queue = self.queue.lock().unwrap();
+        let mut queue = self
+            .condvar
+            .wait_while(queue, |q| {
+                q.tasks.is_empty() && q.lanes[lane].is_empty() && !q.shutdown
+            })
+            .unwrap();
+        queue.lanes[lane]
+            .pop_front()
+            .or_else(|| queue.tasks.pop_front())
+    }
+
+    fn shutdown(&self) {
+        self.queue.lock().unwrap().shutdown = true;
+        self.condvar.notify_all();
+    }
+}
+
+#[pyclass(frozen)]
+pub(crate) struct Executor {
+    queue: Arc<ExecutorQueue>,
+    workers: Vec<PyObject>,
+}
+
+impl Executor {
+    pub(crate) fn submit(&self, task: Task) {
+        self.queue.submit(None, task);
+    }
+
+    /// Returns a lane, the lanes being assigned to the workers in turn.
+    pub(crate) fn lane(&self) -> usize {
+        self.queue.next_lane.fetch_add(1, Ordering::Relaxed) % self.workers.len()
+    }
+
+    /// Same as [`Self::submit`], but the task is run by the worker of `lane`, after the tasks
+    /// previously submitted to it.
+    pub(crate) fn submit_to_lane(&self, lane: usize, task: Task) {
+        self.queue.submit(Some(lane), task);
+    }
+}
+
+#[pymethods]
+impl Executor {
+    #[new]
+    #[pyo3(signature = (threads = 1, *, max_queue = None))]
+    fn new(py: Python, threads: usize, max_queue: Option<usize>) -> PyResult<Self> {
+        if threads == 0 || max_queue == Some(0) {
+            return Err(PyValueError::new_err(
+                "invalid executor threads or max queue",
+            ));
+        }
+        let queue = Queue {
+            lanes: (0..threads).map(|_| VecDeque::new()).collect(),
+            ..Queue::default()
+        };
+        let queue = Arc::new(ExecutorQueue {
+            queue: Mutex::new(queue),
+            condvar: Condvar::new(),
+            max_queue,
+            busy: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+            next_lane: AtomicUsize::new(0),
+        });
+        let workers = (0..threads)
+            .map(|i| {
+                let queue = queue.clone();
+                let target = PyCFunction::new_closure(py, None, None, move |args, _| {
+                    let py = args.py();
+                    while let Some(task) = py.allow_threads(|| queue.next(i)) {
+                        queue.busy.fetch_add(1, Ordering::SeqCst);
+                        task(py);
+                        queue.busy.fetch_sub(1, Ordering::SeqCst);
+                    }
+                })?;
+                let kwargs = PyDict::new(py);
+                kwargs.set_item("target", target)?;
+                kwargs.set_item("name", format!("zenoh-executor-{i}"))?;
+                kwargs.set_item("daemon", true)?;
+                let thread = import!(py, threading.Thread).call((), Some(&kwargs))?;
+                thread.call_method0("start")?;
+                Ok(thread.unbind())
+            })
+            .collect::<PyResult<_>>()?;
+        Ok(Self { queue, workers })
+    }
+
+    #[getter]
+    fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    #[getter]
+    fn queue_depth(&self) -> usize {
+        self.queue.queue.lock().unwrap().len()
+    }
+
+    #[getter]
+    fn busy_workers(&self) -> usize {
+        self.queue.busy.load(Ordering::SeqCst)
+    }
+
+    #[getter]
+    fn dropped(&self) -> usize {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn is_shutdown(&self) -> bool {
+        self.queue.queue.lock().unwrap().shutdown
+    }
+
+    /// Returns whether all the workers have terminated.
+    #[pyo3(signature = (*, wait = true, timeout = None))]
+    fn shutdown(
+        &self,
+        py: Python,
+        wait: bool,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<bool> {
+        self.queue.shutdown();
+        let deadline = timeout.map(|t| Instant::now() + t);
+        let mut terminated = true;
+        for worker in self.workers.iter().map(|w| w.bind(py)) {
+            if wait {
+                let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+                worker.call_method1("join", (remaining.map(|r| r.as_secs_f64()),))?;
+            }
+            terminated &= !worker.call_method0("is_alive")?.extract::<bool>()?;
+        }
+        Ok(terminated)
+    }
+
+    fn __enter__<'a, 'py>(this: &'a Bound<'py, Self>) -> &'a Bound<'py, Self> {
+        this
+    }
+
+    #[pyo3(signature = (*_args, **_kwargs))]
+    fn __exit__(
+        &self,
+        py: Python,
+        _args: &Bound<PyTuple>,
+        _kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<PyObject> {
+        self.shutdown(py, true, None)?;
+        Ok(py.None())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Executor(threads={}, queue_depth={}, busy_workers={}, dropped={})",
+            self.threads(),
+            self.queue_depth(),
+            self.busy_workers(),
+            self.dropped()
+        )
+    }
+}
+
+impl Drop for Executor {
+    fn drop(&mut self) {
+        // workers drain the queue before terminating
+        self.queue.shutdown();
+    }
+}
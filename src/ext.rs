@@ -1,8 +1,9 @@
 use std::time::Duration;
 
 use pyo3::{
-    exceptions::{PyTypeError, PyValueError},
+    exceptions::{PyOverflowError, PyTypeError, PyValueError},
     prelude::*,
+    sync::with_critical_section,
     types::{
         PyBool, PyByteArray, PyBytes, PyDict, PyFloat, PyFrozenSet, PyInt, PyIterator, PyList,
         PySet, PyString, PyTuple, PyType,
@@ -19,7 +20,7 @@ use crate::{
     handlers::{into_handler, HandlerImpl},
     key_expr::KeyExpr,
     macros::{build, import, option_wrapper, py_static, try_import, wrapper},
-    pubsub::Subscriber,
+    pubsub::{subscriber_handler, Subscriber},
     qos::{CongestionControl, Priority, Reliability},
     sample::{Locality, Sample},
     session::{EntityGlobalId, Session},
@@ -172,15 +173,28 @@ fn serialize_impl(
     };
     match tp {
         SupportedType::ZBytes => serializer.serialize(obj.extract::<ZBytes>()?.0),
-        // SAFETY: bytes are immediately copied
+        // SAFETY: bytes are immediately copied, and the bytearray cannot be resized
+        // concurrently in the critical section
         SupportedType::ByteArray => {
-            serializer.serialize(unsafe { obj.downcast::<PyByteArray>()?.as_bytes() })
+            let bytes = obj.downcast::<PyByteArray>()?;
+            with_critical_section(bytes, || serializer.serialize(unsafe { bytes.as_bytes() }))
         }
         SupportedType::Bytes => serializer.serialize(obj.downcast::<PyBytes>()?.as_bytes()),
         SupportedType::Str => serializer.serialize(&obj.downcast::<PyString>()?.to_cow()?),
         SupportedType::Int8 => serializer.serialize(obj.extract::<i8>()?),
         SupportedType::Int16 => serializer.serialize(obj.extract::<i16>()?),
-        SupportedType::Int | SupportedType::Int32 => serializer.serialize(obj.extract::<i32>()?),
+        SupportedType::Int => match obj.extract::<i32>() {
+            Ok(int) => serializer.serialize(int),
+            Err(_) => {
+                return Err(PyOverflowError::new_err(format!(
+                    "int {obj} is out of int32 range [{}, {}], \
+                    use Int64, Int128, UInt64 or UInt128 wrappers to serialize bigger integers",
+                    i32::MIN,
+                    i32::MAX
+                )))
+            }
+        },
+        SupportedType::Int32 => serializer.serialize(obj.extract::<i32>()?),
         SupportedType::Int64 => serializer.serialize(obj.extract::<i64>()?),
         SupportedType::Int128 => serializer.serialize(obj.extract::<i128>()?),
         SupportedType::UInt8 => serializer.serialize(obj.extract::<u8>()?),
@@ -499,7 +513,7 @@ impl AdvancedPublisher {
         py: Python,
         #[pyo3(from_py_with = ZBytes::from_py)] payload: ZBytes,
         #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         timestamp: Option<Timestamp>,
         timestamp_instrumentation: Option<TimestampInstrumentation>,
     ) -> PyResult<()> {
@@ -520,7 +534,7 @@ impl AdvancedPublisher {
     fn delete(
         &self,
         py: Python,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         timestamp: Option<Timestamp>,
         timestamp_instrumentation: Option<TimestampInstrumentation>,
     ) -> PyResult<()> {
@@ -604,7 +618,7 @@ impl AdvancedSubscriber {
         handler: Option<&Bound<PyAny>>,
         history: Option<bool>,
     ) -> PyResult<Subscriber> {
-        let (handler, background) = into_handler(py, handler, None)?;
+        let (handler, background) = subscriber_handler(py, handler, None)?;
         let builder = build!(self.get_ref()?.detect_publishers(), history);
         let mut subscriber = wait(py, builder.with(handler))?;
         if background {
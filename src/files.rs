@@ -0,0 +1,58 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::path::Path;
+
+use pyo3::prelude::*;
+use zenoh::bytes::{Encoding, ZBytes};
+
+use crate::error::{new_zerror_with_code, ErrorCode};
+
+/// Encodings inferred from file extensions, matched case-insensitively.
+const EXTENSION_ENCODINGS: [(&str, Encoding); 7] = [
+    ("json", Encoding::APPLICATION_JSON),
+    ("yaml", Encoding::APPLICATION_YAML),
+    ("yml", Encoding::APPLICATION_YAML),
+    ("png", Encoding::IMAGE_PNG),
+    ("jpeg", Encoding::IMAGE_JPEG),
+    ("jpg", Encoding::IMAGE_JPEG),
+    ("txt", Encoding::TEXT_PLAIN),
+];
+
+/// The encoding inferred from the file extension, `application/octet-stream` if unknown.
+pub(crate) fn path_encoding(path: &Path) -> Encoding {
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    let known = |(ext, _): &&(&str, Encoding)| {
+        extension.is_some_and(|extension| extension.eq_ignore_ascii_case(ext))
+    };
+    match EXTENSION_ENCODINGS.iter().find(known) {
+        Some((_, encoding)) => encoding.clone(),
+        None => Encoding::APPLICATION_OCTET_STREAM,
+    }
+}
+
+/// File errors always have the IO code, whatever their message.
+pub(crate) fn io_error(operation: &str, path: &Path, err: std::io::Error) -> PyErr {
+    let msg = format!("failed to {operation} '{}': {err}", path.display());
+    new_zerror_with_code(msg, ErrorCode::Io)
+}
+
+pub(crate) fn read_file(py: Python, path: &Path) -> PyResult<Vec<u8>> {
+    py.allow_threads(|| std::fs::read(path))
+        .map_err(|err| io_error("read", path, err))
+}
+
+pub(crate) fn write_file(py: Python, path: &Path, payload: &ZBytes) -> PyResult<()> {
+    py.allow_threads(|| std::fs::write(path, payload.to_bytes()))
+        .map_err(|err| io_error("write", path, err))
+}
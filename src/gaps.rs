@@ -0,0 +1,111 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{collections::HashMap, sync::Mutex};
+
+use pyo3::{prelude::*, types::PyDict};
+use zenoh::{sample::SourceSn, session::EntityGlobalId, time::Timestamp};
+
+use crate::config::ZenohId;
+
+/// Reception diagnostics of the samples of a source, see `Subscriber.gap_report`.
+#[pyclass(frozen)]
+#[derive(Clone, Debug, Default)]
+pub(crate) struct GapStats {
+    /// Number of sequence number jumps.
+    #[pyo3(get)]
+    gaps: u64,
+    /// Largest number of sequence numbers skipped by a jump.
+    #[pyo3(get)]
+    largest_gap: u64,
+    /// Number of samples whose timestamp is older than the previous one.
+    #[pyo3(get)]
+    out_of_order: u64,
+    #[pyo3(get)]
+    last_sn: Option<SourceSn>,
+}
+
+#[pymethods]
+impl GapStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "GapStats(gaps={}, largest_gap={}, out_of_order={}, last_sn={:?})",
+            self.gaps, self.largest_gap, self.out_of_order, self.last_sn
+        )
+    }
+}
+
+#[derive(Default)]
+struct SourceState {
+    stats: GapStats,
+    last_timestamp: Option<Timestamp>,
+}
+
+/// Tracks the gaps of each source of the samples received by a subscriber; samples without
+/// source info are ignored.
+#[derive(Default)]
+pub(crate) struct GapTracker(Mutex<HashMap<EntityGlobalId, SourceState>>);
+
+impl GapTracker {
+    pub(crate) fn on_sample(&self, sample: &zenoh::sample::Sample) {
+        let Some(source_info) = sample.source_info() else {
+            return;
+        };
+        let sn = source_info.source_sn();
+        let mut sources = self.0.lock().unwrap();
+        let source = sources.entry(*source_info.source_id()).or_default();
+        let stats = &mut source.stats;
+        // late samples don't fill the gaps they were counted in
+        let skipped = match stats.last_sn {
+            Some(last) if sn <= last => None,
+            Some(last) => Some(u64::from(sn) - u64::from(last) - 1),
+            None => Some(0),
+        };
+        if let Some(skipped) = skipped {
+            if skipped > 0 {
+                stats.gaps += 1;
+                stats.largest_gap = stats.largest_gap.max(skipped);
+            }
+            stats.last_sn = Some(sn);
+        }
+        if let Some(timestamp) = sample.timestamp() {
+            if source.last_timestamp.is_some_and(|last| *timestamp < last) {
+                source.stats.out_of_order += 1;
+            } else {
+                source.last_timestamp = Some(*timestamp);
+            }
+        }
+    }
+
+    /// Returns the stats aggregated by source zid, the last sequence number being the highest
+    /// among the entities of the zid.
+    pub(crate) fn report<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let mut by_zid = HashMap::<_, GapStats>::new();
+        for (id, source) in self.0.lock().unwrap().iter() {
+            let stats = by_zid.entry(id.zid()).or_default();
+            stats.gaps += source.stats.gaps;
+            stats.largest_gap = stats.largest_gap.max(source.stats.largest_gap);
+            stats.out_of_order += source.stats.out_of_order;
+            stats.last_sn = stats.last_sn.max(source.stats.last_sn);
+        }
+        let report = PyDict::new(py);
+        for (zid, stats) in by_zid {
+            report.set_item(ZenohId::from(zid), stats)?;
+        }
+        Ok(report)
+    }
+
+    pub(crate) fn reset(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
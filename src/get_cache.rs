@@ -0,0 +1,153 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{collections::HashMap, sync::Mutex, time::Duration};
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+
+use crate::{
+    clock::{self, ClockInstant},
+    utils::duration,
+};
+
+const DEFAULT_MAX_ENTRIES: usize = 1024;
+const DEFAULT_TTL: Duration = Duration::from_secs(30);
+
+static GET_CACHE: Mutex<Option<GetCache>> = Mutex::new(None);
+
+/// How `Session.get_cached` uses the cache.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheMode {
+    Use,
+    /// Bypasses the cache, but stores the replies, `refresh=True`.
+    Refresh,
+}
+
+struct Entry {
+    replies: Vec<zenoh::query::Reply>,
+    inserted: ClockInstant,
+    // tick of the last use, the least recently used entry being evicted first
+    used: u64,
+}
+
+struct GetCache {
+    max_entries: usize,
+    ttl: Duration,
+    entries: HashMap<String, Entry>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+/// The cache key of a selector, with its parameters sorted, as their order is not significant.
+pub(crate) fn cache_key(selector: &zenoh::query::Selector) -> String {
+    let mut parameters = selector.parameters().iter().collect::<Vec<_>>();
+    if parameters.is_empty() {
+        return selector.key_expr().to_string();
+    }
+    parameters.sort_unstable();
+    let parameters = parameters.iter().map(|(k, v)| format!("{k}={v}"));
+    format!(
+        "{}?{}",
+        selector.key_expr(),
+        parameters.collect::<Vec<_>>().join(";")
+    )
+}
+
+/// Returns the cached replies of `key` if they are fresh.
+///
+/// Raises an error if the cache is not enabled, see `enable_get_cache`.
+pub(crate) fn lookup(key: &str, mode: CacheMode) -> PyResult<Option<Vec<zenoh::query::Reply>>> {
+    let mut cache = GET_CACHE.lock().unwrap();
+    let Some(cache) = cache.as_mut() else {
+        return Err(PyValueError::new_err(
+            "the get cache is not enabled, see enable_get_cache",
+        ));
+    };
+    if mode == CacheMode::Refresh {
+        return Ok(None);
+    }
+    cache.tick += 1;
+    let (tick, ttl) = (cache.tick, cache.ttl);
+    match cache.entries.get_mut(key) {
+        Some(entry) if entry.inserted.elapsed() < ttl => {
+            entry.used = tick;
+            cache.hits += 1;
+            Ok(Some(entry.replies.clone()))
+        }
+        expired => {
+            if expired.is_some() {
+                cache.entries.remove(key);
+            }
+            cache.misses += 1;
+            Ok(None)
+        }
+    }
+}
+
+/// Caches the replies of `key`, unless one of them is an error.
+pub(crate) fn store(key: String, replies: &[zenoh::query::Reply]) {
+    if replies.iter().any(|reply| reply.result().is_err()) {
+        return;
+    }
+    let mut cache = GET_CACHE.lock().unwrap();
+    let Some(cache) = cache.as_mut().filter(|cache| cache.max_entries > 0) else {
+        return;
+    };
+    if !cache.entries.contains_key(&key) && cache.entries.len() >= cache.max_entries {
+        let lru = cache.entries.iter().min_by_key(|(_, entry)| entry.used);
+        if let Some(lru) = lru.map(|(key, _)| key.clone()) {
+            cache.entries.remove(&lru);
+            cache.evictions += 1;
+        }
+    }
+    cache.tick += 1;
+    let entry = Entry {
+        replies: replies.to_vec(),
+        inserted: clock::now(),
+        used: cache.tick,
+    };
+    cache.entries.insert(key, entry);
+}
+
+/// Enables the cache, or resets it if it is already enabled.
+#[pyfunction]
+#[pyo3(signature = (max_entries = DEFAULT_MAX_ENTRIES, ttl = None))]
+pub(crate) fn enable_get_cache(
+    max_entries: usize,
+    #[pyo3(from_py_with = duration)] ttl: Option<Duration>,
+) {
+    *GET_CACHE.lock().unwrap() = Some(GetCache {
+        max_entries,
+        ttl: ttl.unwrap_or(DEFAULT_TTL),
+        entries: HashMap::new(),
+        tick: 0,
+        hits: 0,
+        misses: 0,
+        evictions: 0,
+    });
+}
+
+#[pyfunction]
+pub(crate) fn get_cache_stats<'py>(py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+    let cache = GET_CACHE.lock().unwrap();
+    let stats = PyDict::new(py);
+    let cache = cache.as_ref();
+    stats.set_item("enabled", cache.is_some())?;
+    stats.set_item("entries", cache.map_or(0, |cache| cache.entries.len()))?;
+    stats.set_item("hits", cache.map_or(0, |cache| cache.hits))?;
+    stats.set_item("misses", cache.map_or(0, |cache| cache.misses))?;
+    stats.set_item("evictions", cache.map_or(0, |cache| cache.evictions))?;
+    Ok(stats)
+}
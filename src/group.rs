@@ -0,0 +1,369 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    sync::{mpsc, Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use pyo3::{
+    exceptions::{PyKeyError, PyValueError},
+    prelude::*,
+    types::{PyDict, PyTuple},
+};
+
+use crate::{
+    liveliness::LivelinessToken,
+    macros::zerror,
+    matching::MatchingListener,
+    pubsub::{Publisher, Subscriber},
+    query::{Querier, Queryable},
+    session::Session,
+};
+
+type Entities = Mutex<Vec<PyObject>>;
+
+/// Entity groups of a session, used to prevent closing it while they hold open entities.
+#[derive(Default)]
+pub(crate) struct EntityGroups(Mutex<Vec<Weak<Entities>>>);
+
+impl EntityGroups {
+    fn register(&self, entities: &Arc<Entities>) {
+        let mut groups = self.0.lock().unwrap();
+        groups.retain(|group| group.strong_count() > 0);
+        groups.push(Arc::downgrade(entities));
+    }
+
+    pub(crate) fn open_entities(&self, py: Python) -> usize {
+        let groups = self.0.lock().unwrap();
+        let count = |entities: Arc<Entities>| {
+            let entities = entities.lock().unwrap();
+            entities
+                .iter()
+                .filter(|e| !is_undeclared(e.bind(py)))
+                .count()
+        };
+        groups.iter().filter_map(Weak::upgrade).map(count).sum()
+    }
+
+    /// The type and key of the open entities of all the groups, in creation order.
+    pub(crate) fn describe_open_entities(&self, py: Python) -> Vec<(String, String)> {
+        let groups = self.0.lock().unwrap();
+        let mut descriptions = Vec::new();
+        for entities in groups.iter().filter_map(Weak::upgrade) {
+            let entities = entities.lock().unwrap();
+            for entity in entities.iter().map(|e| e.bind(py)) {
+                if !is_undeclared(entity) {
+                    let kind = entity
+                        .get_type()
+                        .name()
+                        .map_or(String::new(), |n| n.to_string());
+                    descriptions.push((kind, entity_key(entity)));
+                }
+            }
+        }
+        descriptions
+    }
+
+    /// Removes the open entities from all the groups, returning them in reverse creation order.
+    pub(crate) fn take_open_entities(&self, py: Python) -> Vec<PyObject> {
+        let groups = self.0.lock().unwrap();
+        let take = |entities: Arc<Entities>| std::mem::take(&mut *entities.lock().unwrap());
+        let mut entities: Vec<_> = groups
+            .iter()
+            .filter_map(Weak::upgrade)
+            .flat_map(take)
+            .filter(|e| !is_undeclared(e.bind(py)))
+            .collect();
+        entities.reverse();
+        entities
+    }
+}
+
+/// The key expression of an entity if it has one, or else its string representation.
+fn entity_key(entity: &Bound<PyAny>) -> String {
+    match entity
+        .getattr("key_expr")
+        .and_then(|key_expr| key_expr.str())
+    {
+        Ok(key_expr) => key_expr.to_string(),
+        Err(_) => entity.to_string(),
+    }
+}
+
+/// Undeclares `entities` on background threads, at most `parallelism` at a time, calling
+/// `on_progress(done, total, key)` each time one completes or times out.
+///
+/// Returns the `(key, error)` pairs of the entities which failed to be undeclared, or were not
+/// within `timeout`; the latter are left running in the background.
+pub(crate) fn undeclare_concurrently(
+    py: Python,
+    entities: Vec<PyObject>,
+    parallelism: usize,
+    timeout: Duration,
+    on_progress: Option<&Bound<PyAny>>,
+) -> PyResult<Vec<(String, String)>> {
+    let total = entities.len();
+    let (tx, rx) = mpsc::channel();
+    let mut queue = entities.into_iter().enumerate();
+    let mut keys = Vec::with_capacity(total);
+    let mut running: Vec<(usize, Instant)> = Vec::new();
+    let mut failures = Vec::new();
+    for done in 1..=total {
+        while running.len() < parallelism.max(1) {
+            let Some((index, entity)) = queue.next() else {
+                break;
+            };
+            keys.push(entity_key(entity.bind(py)));
+            let tx = tx.clone();
+            std::thread::spawn(move || {
+                let result = Python::with_gil(|py| {
+                    let result = entity.bind(py).call_method0("undeclare");
+                    result.map(drop).map_err(|err| err.to_string())
+                });
+                tx.send((index, result)).ok();
+            });
+            running.push((index, Instant::now() + timeout));
+        }
+        let (index, result) = loop {
+            let (pos, &(index, deadline)) = running
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, (_, deadline))| *deadline)
+                .expect("an undeclaration is running");
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match py.allow_threads(|| rx.recv_timeout(remaining)) {
+                // late completions of timed out undeclarations are ignored
+                Ok((index, result)) => match running.iter().position(|(i, _)| *i == index) {
+                    Some(pos) => break (running.swap_remove(pos).0, result),
+                    None => continue,
+                },
+                Err(_) => {
+                    running.swap_remove(pos);
+                    break (index, Err(format!("timed out after {timeout:?}")));
+                }
+            }
+        };
+        if let Err(err) = result {
+            failures.push((keys[index].clone(), err));
+        }
+        if let Some(on_progress) = on_progress {
+            on_progress.call1((done, total, keys[index].as_str()))?;
+        }
+    }
+    Ok(failures)
+}
+
+/// Entities of unknown types are always considered open.
+pub(crate) fn is_undeclared(entity: &Bound<PyAny>) -> bool {
+    macro_rules! check {
+        ($($ty:ty),*) => {$(
+            if let Ok(entity) = entity.downcast::<$ty>() {
+                return entity.try_borrow().is_ok_and(|entity| entity.0.is_none());
+            }
+        )*};
+    }
+    check!(
+        Subscriber,
+        Publisher,
+        Queryable,
+        Querier,
+        LivelinessToken,
+        MatchingListener
+    );
+    #[cfg(feature = "zenoh-ext")]
+    check!(
+        crate::ext::AdvancedPublisher,
+        crate::ext::AdvancedSubscriber
+    );
+    false
+}
+
+#[pyclass]
+pub(crate) struct EntityGroup {
+    session: Py<Session>,
+    entities: Arc<Entities>,
+    names: Mutex<Vec<(String, PyObject)>>,
+}
+
+impl EntityGroup {
+    pub(crate) fn declare(
+        &self,
+        target: &Bound<PyAny>,
+        method: &str,
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<PyObject> {
+        let entity = target.call_method(method, args, kwargs)?;
+        self.entities.lock().unwrap().push(entity.clone().unbind());
+        Ok(entity.unbind())
+    }
+
+    pub(crate) fn set_name(&self, name: String, entity: &Bound<PyAny>) -> PyResult<()> {
+        let mut names = self.names.lock().unwrap();
+        if names.iter().any(|(n, _)| *n == name) {
+            return Err(PyValueError::new_err(format!(
+                "an entity named '{name}' is already in the group"
+            )));
+        }
+        names.push((name, entity.clone().unbind()));
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl EntityGroup {
+    #[new]
+    pub(crate) fn new(session: Bound<Session>) -> Self {
+        let entities = Arc::new(Entities::default());
+        session.borrow().1.register(&entities);
+        Self {
+            session: session.unbind(),
+            entities,
+            names: Mutex::default(),
+        }
+    }
+
+    #[getter]
+    fn session(&self, py: Python) -> Py<Session> {
+        self.session.clone_ref(py)
+    }
+
+    #[getter]
+    fn names(&self) -> Vec<String> {
+        let names = self.names.lock().unwrap();
+        names.iter().map(|(name, _)| name.clone()).collect()
+    }
+
+    #[pyo3(signature = (entity, *, name = None))]
+    fn add(&self, entity: &Bound<PyAny>, name: Option<String>) -> PyResult<()> {
+        if let Some(name) = name {
+            self.set_name(name, entity)?;
+        }
+        self.entities.lock().unwrap().push(entity.clone().unbind());
+        Ok(())
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn declare_subscriber(
+        &self,
+        py: Python,
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<PyObject> {
+        self.declare(self.session.bind(py), "declare_subscriber", args, kwargs)
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn declare_queryable(
+        &self,
+        py: Python,
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<PyObject> {
+        self.declare(self.session.bind(py), "declare_queryable", args, kwargs)
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn declare_publisher(
+        &self,
+        py: Python,
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<PyObject> {
+        self.declare(self.session.bind(py), "declare_publisher", args, kwargs)
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn declare_querier(
+        &self,
+        py: Python,
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<PyObject> {
+        self.declare(self.session.bind(py), "declare_querier", args, kwargs)
+    }
+
+    #[pyo3(signature = (*args, **kwargs))]
+    fn declare_liveliness_token(
+        &self,
+        py: Python,
+        args: &Bound<PyTuple>,
+        kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<PyObject> {
+        let liveliness = self.session.bind(py).call_method0("liveliness")?;
+        self.declare(&liveliness, "declare_token", args, kwargs)
+    }
+
+    pub(crate) fn close_all(&self, py: Python) -> PyResult<()> {
+        let _names = std::mem::take(&mut *self.names.lock().unwrap());
+        let entities = std::mem::take(&mut *self.entities.lock().unwrap());
+        let mut errors = Vec::new();
+        for entity in entities.iter().rev().map(|e| e.bind(py)) {
+            if is_undeclared(entity) {
+                continue;
+            }
+            if let Err(err) = entity.call_method0("undeclare") {
+                errors.push(format!("{entity}: {err}"));
+            }
+        }
+        if !errors.is_empty() {
+            return Err(zerror!(
+                "failed to close {} entities: {}",
+                errors.len(),
+                errors.join(", ")
+            ));
+        }
+        Ok(())
+    }
+
+    fn __enter__<'a, 'py>(this: &'a Bound<'py, Self>) -> &'a Bound<'py, Self> {
+        this
+    }
+
+    #[pyo3(signature = (*_args, **_kwargs))]
+    fn __exit__(
+        &self,
+        py: Python,
+        _args: &Bound<PyTuple>,
+        _kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<PyObject> {
+        self.close_all(py)?;
+        Ok(py.None())
+    }
+
+    fn __getitem__(&self, py: Python, name: &str) -> PyResult<PyObject> {
+        let names = self.names.lock().unwrap();
+        match names.iter().find(|(n, _)| n == name) {
+            Some((_, entity)) => Ok(entity.clone_ref(py)),
+            None => Err(PyKeyError::new_err(name.to_string())),
+        }
+    }
+
+    fn __contains__(&self, name: &str) -> bool {
+        let names = self.names.lock().unwrap();
+        names.iter().any(|(n, _)| n == name)
+    }
+
+    fn __len__(&self, py: Python) -> usize {
+        let entities = self.entities.lock().unwrap();
+        entities
+            .iter()
+            .filter(|e| !is_undeclared(e.bind(py)))
+            .count()
+    }
+
+    fn __repr__(&self, py: Python) -> String {
+        format!("EntityGroup(open_entities={})", self.__len__(py))
+    }
+}
@@ -11,20 +11,33 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::{fmt, marker::PhantomData, sync::Arc, time::Duration};
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    fmt,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
 use pyo3::{
-    exceptions::PyValueError,
+    exceptions::{PyTypeError, PyValueError},
     prelude::*,
-    types::{PyCFunction, PyDict, PyType},
+    types::{PyCFunction, PyDict, PyNone, PyType},
     BoundObject,
 };
 use zenoh::handlers::{CallbackParameter, IntoHandler};
 
 use crate::{
     cancellation::CancellationToken,
-    macros::{import, py_static},
-    utils::{generic, short_type_name, IntoPyResult, IntoPython, IntoRust},
+    debug::{self, PendingKey},
+    executor::{Executor, Task},
+    macros::{import, py_static, zerror},
+    sample::Sample,
+    utils::{duration, generic, short_type_name, IntoPyResult, IntoPython, IntoRust},
     ZError,
 };
 
@@ -35,7 +48,7 @@ type RustCallback<T> = zenoh::handlers::Callback<T>;
 /// Signals received by Python interpreter while executing Rust code in `allow_threads`
 /// are not handled and kept as pending. It's Rust code responsibility to regularly check
 /// them. Blocking calls like channel `recv` must then be done in a loop with small timeouts.
-const CHECK_SIGNALS_INTERVAL: Duration = Duration::from_millis(100);
+pub(crate) const CHECK_SIGNALS_INTERVAL: Duration = Duration::from_millis(100);
 const DROP_CALLBACK_WARNING: &str = "Passing drop-callback using a tuple \
 `(callback, drop-callback)` no longer works in 1.0;\n\
 `zenoh.handlers.Callback(callback, drop_callback)` must be used instead.\n\
@@ -44,7 +57,7 @@ If you are already passing a handler and this warning is still incorrectly displ
 you can silence it with:\n\
 warnings.filterwarnings(\"ignore\", message=\"Passing drop-callback\")";
 
-fn log_error(py: Python, result: PyResult<PyObject>) {
+pub(crate) fn log_error(py: Python, result: PyResult<PyObject>) {
     if let Err(err) = result {
         let kwargs = PyDict::new(py);
         kwargs.set_item("exc_info", err.into_value(py)).unwrap();
@@ -118,10 +131,175 @@ impl RingChannel {
     }
 }
 
+/// Number of zenoh priorities, from `RealTime` to `Background`.
+const PRIORITY_LEVELS: usize =
+    1 + zenoh::qos::Priority::MIN as usize - zenoh::qos::Priority::MAX as usize;
+
+/// Bounded channel of samples, with one queue per priority, the higher priorities being
+/// received first.
+#[pyclass]
+#[derive(Clone)]
+pub(crate) struct PriorityChannel {
+    capacity: usize,
+    starvation_ratio: Option<usize>,
+}
+
+impl PriorityChannel {
+    /// Builds the subscriber callback, queuing the samples by priority, and its handler.
+    pub(crate) fn into_handler(
+        self,
+        py: Python,
+    ) -> (RustCallback<zenoh::sample::Sample>, HandlerImpl<Sample>) {
+        let queue = Arc::new(PriorityQueue {
+            state: Mutex::new(PriorityState::default()),
+            condvar: Condvar::new(),
+            capacity: self.capacity,
+            starvation_ratio: self.starvation_ratio,
+        });
+        let sender = PrioritySender(queue.clone());
+        let callback = RustCallback::new(Arc::new(move |sample| sender.0.push(sample)));
+        let handler = Py::new(py, Handler(Box::new(PriorityReceiver(queue)))).unwrap();
+        (callback, HandlerImpl::Rust(handler, PhantomData))
+    }
+}
+
+#[pymethods]
+impl PriorityChannel {
+    #[new]
+    #[pyo3(signature = (capacity, *, starvation_ratio = 8))]
+    fn new(capacity: usize, starvation_ratio: Option<usize>) -> PyResult<Self> {
+        if capacity == 0 {
+            return Err(PyValueError::new_err("capacity must be positive"));
+        }
+        if starvation_ratio == Some(0) {
+            return Err(PyValueError::new_err("starvation_ratio must be positive"));
+        }
+        Ok(Self {
+            capacity,
+            starvation_ratio,
+        })
+    }
+}
+
+#[derive(Default)]
+struct PriorityState {
+    levels: [VecDeque<zenoh::sample::Sample>; PRIORITY_LEVELS],
+    enqueued: [u64; PRIORITY_LEVELS],
+    delivered: [u64; PRIORITY_LEVELS],
+    // samples received in a row while a lower priority one was pending
+    streak: usize,
+    len: usize,
+    sender_dropped: bool,
+    receiver_dropped: bool,
+}
+
+impl PriorityState {
+    /// Pops the oldest sample of the highest pending priority, or of the next pending one once
+    /// `starvation_ratio` samples have been received ahead of it.
+    fn pop(&mut self, starvation_ratio: Option<usize>) -> Option<zenoh::sample::Sample> {
+        let mut pending = (0..PRIORITY_LEVELS).filter(|&level| !self.levels[level].is_empty());
+        let highest = pending.next()?;
+        let lower = pending.next();
+        let level = match lower {
+            Some(lower) if starvation_ratio.is_some_and(|ratio| self.streak >= ratio) => {
+                self.streak = 0;
+                lower
+            }
+            Some(_) => {
+                self.streak += 1;
+                highest
+            }
+            None => {
+                self.streak = 0;
+                highest
+            }
+        };
+        self.len -= 1;
+        self.delivered[level] += 1;
+        self.levels[level].pop_front()
+    }
+}
+
+/// Queues shared between the callback and the handler of a [`PriorityChannel`].
+struct PriorityQueue {
+    state: Mutex<PriorityState>,
+    // notified on push, pop, and when either side is dropped
+    condvar: Condvar,
+    capacity: usize,
+    starvation_ratio: Option<usize>,
+}
+
+impl PriorityQueue {
+    /// Enqueues the sample, blocking while the channel is full, like [`FifoChannel`]; the
+    /// sample is dropped if the handler is.
+    fn push(&self, sample: zenoh::sample::Sample) {
+        // `Control` is above `RealTime`, so it shares the highest priority queue
+        let level = (sample.priority() as usize)
+            .saturating_sub(zenoh::qos::Priority::MAX as usize)
+            .min(PRIORITY_LEVELS - 1);
+        let state = self.state.lock().unwrap();
+        let mut state = self
+            .condvar
+            .wait_while(state, |s| s.len >= self.capacity && !s.receiver_dropped)
+            .unwrap();
+        if state.receiver_dropped {
+            return;
+        }
+        state.levels[level].push_back(sample);
+        state.enqueued[level] += 1;
+        state.len += 1;
+        self.condvar.notify_all();
+    }
+
+    /// Returns `Ok(None)` if no sample is received before `timeout`, and an error once the
+    /// channel is empty and closed.
+    fn pop_timeout(&self, timeout: Duration) -> PyResult<Option<zenoh::sample::Sample>> {
+        let state = self.state.lock().unwrap();
+        let (mut state, _) = self
+            .condvar
+            .wait_timeout_while(state, timeout, |s| s.len == 0 && !s.sender_dropped)
+            .unwrap();
+        match state.pop(self.starvation_ratio) {
+            Some(sample) => {
+                self.condvar.notify_all();
+                Ok(Some(sample))
+            }
+            None if state.sender_dropped => Err(zerror!("receiving on a closed channel")),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Closes the channel when the subscriber callback is dropped.
+struct PrioritySender(Arc<PriorityQueue>);
+
+impl Drop for PrioritySender {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().sender_dropped = true;
+        self.0.condvar.notify_all();
+    }
+}
+
+/// Unblocks the subscriber callback when the handler is dropped.
+struct PriorityReceiver(Arc<PriorityQueue>);
+
+impl Drop for PriorityReceiver {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().receiver_dropped = true;
+        self.0.condvar.notify_all();
+    }
+}
+
 pub(crate) trait Receiver {
     fn type_name(&self) -> &'static str;
     fn try_recv(&self, py: Python) -> PyResult<PyObject>;
     fn recv(&self, py: Python) -> PyResult<PyObject>;
+    /// Returns `None` if no item is received before `timeout`.
+    fn recv_timeout(&self, py: Python, timeout: Duration) -> PyResult<Option<PyObject>>;
+    /// The counters of each priority, for the receivers queuing by priority.
+    fn priority_counters<'py>(&self, _py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        Ok(None)
+    }
 }
 
 #[pyclass]
@@ -138,8 +316,19 @@ impl Handler {
         self.0.try_recv(py)
     }
 
-    fn recv(&self, py: Python) -> PyResult<PyObject> {
-        self.0.recv(py)
+    #[pyo3(signature = (timeout = None))]
+    fn recv(
+        &self,
+        py: Python,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<PyObject> {
+        match timeout {
+            Some(timeout) => Ok(self
+                .0
+                .recv_timeout(py, timeout)?
+                .unwrap_or_else(|| py.None())),
+            None => self.0.recv(py),
+        }
     }
 
     fn __iter__(this: Py<Self>) -> Py<Self> {
@@ -154,6 +343,11 @@ impl Handler {
         }
     }
 
+    #[getter]
+    fn priority_counters<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.0.priority_counters(py)
+    }
+
     fn __repr__(&self) -> String {
         format!("Handler[{}]", self.0.type_name())
     }
@@ -191,37 +385,111 @@ impl Callback {
     }
 }
 
+/// Wraps the callable of a callback handler, keeping the drop callback and the indirect flag of
+/// [`Callback`] objects.
+pub(crate) fn map_callback<'py>(
+    obj: &Bound<'py, PyAny>,
+    f: impl FnOnce(PyObject) -> PyResult<PyObject>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let py = obj.py();
+    let Ok(cb) = obj.downcast::<Callback>().map(Bound::borrow) else {
+        return Ok(f(obj.clone().unbind())?.into_bound(py));
+    };
+    let callback = Callback {
+        callback: f(cb.callback.clone_ref(py))?,
+        drop: cb.drop.as_ref().map(|d| d.clone_ref(py)),
+        indirect: cb.indirect,
+    };
+    Ok(Bound::new(py, callback)?.into_any())
+}
+
+thread_local! {
+    static IN_PYTHON_CALLBACK: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Returns true if the current thread is executing a Python callback passed as handler.
+pub(crate) fn in_python_callback() -> bool {
+    IN_PYTHON_CALLBACK.get()
+}
+
+/// Python callable of a callback handler, which can be replaced while the handler is in use.
+#[derive(Clone)]
+pub(crate) struct CallbackSlot(Arc<Mutex<PyObject>>);
+
+impl CallbackSlot {
+    /// Replaces the callable; invocations in progress complete with the previous one.
+    fn set(&self, callback: &Bound<PyAny>) -> PyResult<()> {
+        let callback = match callback.downcast::<Callback>() {
+            Ok(cb) => cb.borrow().callback.clone_ref(callback.py()),
+            Err(_) if callback.is_none() => {
+                return Err(PyValueError::new_err("callback cannot be None"));
+            }
+            Err(_) if !callback.is_callable() => {
+                return Err(PyTypeError::new_err("callback must be callable"));
+            }
+            Err(_) => callback.clone().unbind(),
+        };
+        // the previous callable is dropped after releasing the lock
+        let _previous = std::mem::replace(&mut *self.0.lock().unwrap(), callback);
+        Ok(())
+    }
+
+    fn get(&self, py: Python) -> PyObject {
+        self.0.lock().unwrap().clone_ref(py)
+    }
+}
+
 pub(crate) struct PythonCallback {
-    callback: Callback,
+    callback: CallbackSlot,
+    drop: Option<PyObject>,
+    indirect: bool,
     _notifier: Option<zenoh::cancellation::SyncGroupNotifier>,
+    cancelled: Option<Arc<AtomicBool>>,
 }
 
 impl PythonCallback {
-    fn new(obj: &Bound<PyAny>, notifier: Option<zenoh::cancellation::SyncGroupNotifier>) -> Self {
-        if let Ok(cb) = obj.downcast::<Callback>().map(Bound::borrow) {
-            return Self {
-                callback: Callback::new(
-                    cb.callback.clone_ref(obj.py()),
-                    cb.drop.as_ref().map(|d| d.clone_ref(obj.py())),
-                    cb.indirect,
-                ),
-                _notifier: notifier,
-            };
-        }
+    fn new(
+        obj: &Bound<PyAny>,
+        notifier: Option<zenoh::cancellation::SyncGroupNotifier>,
+        cancelled: Option<Arc<AtomicBool>>,
+    ) -> Self {
+        let py = obj.py();
+        let (callback, drop, indirect) = match obj.downcast::<Callback>().map(Bound::borrow) {
+            Ok(cb) => (
+                cb.callback.clone_ref(py),
+                cb.drop.as_ref().map(|d| d.clone_ref(py)),
+                cb.indirect,
+            ),
+            Err(_) => (obj.clone().unbind(), None, true),
+        };
         Self {
-            callback: Callback::new(obj.clone().unbind(), None, true),
+            callback: CallbackSlot(Arc::new(Mutex::new(callback))),
+            drop,
+            indirect,
             _notifier: notifier,
+            cancelled,
         }
     }
 
     fn call<T: IntoPython>(&self, py: Python, t: T) {
-        log_error(py, self.callback.callback.call1(py, (t.into_pyobject(py),)));
+        // checked before each invocation, as indirect callbacks may have queued values
+        if self
+            .cancelled
+            .as_ref()
+            .is_some_and(|c| c.load(Ordering::SeqCst))
+        {
+            return;
+        }
+        let in_callback = IN_PYTHON_CALLBACK.replace(true);
+        let callback = self.callback.get(py);
+        log_error(py, callback.call1(py, (t.into_pyobject(py),)));
+        IN_PYTHON_CALLBACK.set(in_callback);
     }
 }
 
 impl Drop for PythonCallback {
     fn drop(&mut self) {
-        if let Some(drop) = &self.callback.drop {
+        if let Some(drop) = &self.drop {
             Python::with_gil(|gil| log_error(gil, drop.call0(gil)));
         }
     }
@@ -232,6 +500,8 @@ impl Drop for PythonCallback {
 pub(crate) enum HandlerImpl<T> {
     Rust(Py<Handler>, PhantomData<T>),
     Python(PyObject),
+    // exposed as `None`
+    Callback(CallbackSlot),
 }
 
 impl<T> fmt::Debug for HandlerImpl<T> {
@@ -239,6 +509,7 @@ impl<T> fmt::Debug for HandlerImpl<T> {
         match self {
             Self::Rust(..) => write!(f, "Handler[{}]", short_type_name::<T>()),
             Self::Python(obj) => write!(f, "{obj:?}"),
+            Self::Callback(_) => write!(f, "None"),
         }
     }
 }
@@ -252,12 +523,13 @@ impl<'py, T> IntoPyObject<'py> for HandlerImpl<T> {
         Ok(match self {
             HandlerImpl::Rust(obj, _) => obj.into_any(),
             HandlerImpl::Python(obj) => obj,
+            HandlerImpl::Callback(_) => py.None(),
         }
         .into_bound(py))
     }
 }
 
-impl<'a, 'py, T> IntoPyObject<'py> for &'a HandlerImpl<T> {
+impl<'a, 'py: 'a, T> IntoPyObject<'py> for &'a HandlerImpl<T> {
     type Target = PyAny;
     type Output = Borrowed<'a, 'py, Self::Target>;
     type Error = PyErr;
@@ -266,6 +538,7 @@ impl<'a, 'py, T> IntoPyObject<'py> for &'a HandlerImpl<T> {
         Ok(match self {
             HandlerImpl::Rust(obj, _) => obj.bind_borrowed(py).into_any(),
             HandlerImpl::Python(obj) => obj.bind_borrowed(py),
+            HandlerImpl::Callback(_) => PyNone::get(py).into_any(),
         })
     }
 }
@@ -275,6 +548,7 @@ impl<T> HandlerImpl<T> {
         match self {
             Self::Rust(handler, _) => handler.borrow(py).try_recv(py),
             Self::Python(handler) => handler.call_method0(py, "try_recv"),
+            Self::Callback(_) => py.None().call_method0(py, "try_recv"),
         }
     }
 
@@ -282,6 +556,15 @@ impl<T> HandlerImpl<T> {
         match self {
             Self::Rust(handler, _) => handler.borrow(py).recv(py),
             Self::Python(handler) => handler.call_method0(py, "recv"),
+            Self::Callback(_) => py.None().call_method0(py, "recv"),
+        }
+    }
+
+    /// Replaces the Python callable of a callback handler.
+    pub(crate) fn set_callback(&self, callback: &Bound<PyAny>) -> PyResult<()> {
+        match self {
+            Self::Callback(slot) => slot.set(callback),
+            _ => Err(PyValueError::new_err("the handler is not a callback")),
         }
     }
 }
@@ -306,6 +589,7 @@ macro_rules! impl_receiver {
             }
 
             fn recv(&self, py: Python) -> PyResult<PyObject> {
+                let _pending = debug::pending(py, "recv", || PendingKey::None);
                 // See `CHECK_SIGNALS_INTERVAL` doc
                 let recv_timeout = || self.handler.recv_timeout(CHECK_SIGNALS_INTERVAL);
                 loop {
@@ -316,11 +600,77 @@ macro_rules! impl_receiver {
                     }
                 }
             }
+
+            fn recv_timeout(&self, py: Python, timeout: Duration) -> PyResult<Option<PyObject>> {
+                let _pending = debug::pending(py, "recv", || PendingKey::None);
+                let deadline = Instant::now() + timeout;
+                loop {
+                    let remaining = deadline.saturating_duration_since(Instant::now());
+                    // See `CHECK_SIGNALS_INTERVAL` doc
+                    let interval = remaining.min(CHECK_SIGNALS_INTERVAL);
+                    match py.allow_threads(|| self.handler.recv_timeout(interval)).into_pyres()? {
+                        Some(obj) => return Ok(Some(obj.into_pyobject(py))),
+                        None if remaining.is_zero() => return Ok(None),
+                        None => py.check_signals()?,
+                    }
+                }
+            }
         }
     )*};
 }
 impl_receiver!(DefaultHandler, FifoChannel, RingChannel);
 
+impl Receiver for PriorityReceiver {
+    fn type_name(&self) -> &'static str {
+        short_type_name::<zenoh::sample::Sample>()
+    }
+
+    fn try_recv(&self, py: Python) -> PyResult<PyObject> {
+        Ok(self.0.pop_timeout(Duration::ZERO)?.into_pyobject(py))
+    }
+
+    fn recv(&self, py: Python) -> PyResult<PyObject> {
+        let _pending = debug::pending(py, "recv", || PendingKey::None);
+        loop {
+            // See `CHECK_SIGNALS_INTERVAL` doc
+            match py.allow_threads(|| self.0.pop_timeout(CHECK_SIGNALS_INTERVAL))? {
+                Some(sample) => return Ok(sample.into_pyobject(py)),
+                None => py.check_signals()?,
+            }
+        }
+    }
+
+    fn recv_timeout(&self, py: Python, timeout: Duration) -> PyResult<Option<PyObject>> {
+        let _pending = debug::pending(py, "recv", || PendingKey::None);
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            // See `CHECK_SIGNALS_INTERVAL` doc
+            let interval = remaining.min(CHECK_SIGNALS_INTERVAL);
+            match py.allow_threads(|| self.0.pop_timeout(interval))? {
+                Some(sample) => return Ok(Some(sample.into_pyobject(py))),
+                None if remaining.is_zero() => return Ok(None),
+                None => py.check_signals()?,
+            }
+        }
+    }
+
+    fn priority_counters<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let state = self.0.state.lock().unwrap();
+        let counters = PyDict::new(py);
+        for level in 0..PRIORITY_LEVELS {
+            let priority = level as u8 + zenoh::qos::Priority::MAX as u8;
+            let priority = zenoh::qos::Priority::try_from(priority).into_pyres()?;
+            let level_counters = PyDict::new(py);
+            level_counters.set_item("enqueued", state.enqueued[level])?;
+            level_counters.set_item("delivered", state.delivered[level])?;
+            level_counters.set_item("pending", state.levels[level].len())?;
+            counters.set_item(priority.into_python(), level_counters)?;
+        }
+        Ok(Some(counters))
+    }
+}
+
 fn rust_handler<H: IntoRust, T: IntoPython + CallbackParameter>(
     py: Python,
     into_handler: H,
@@ -342,12 +692,14 @@ where
 fn python_callback<T: IntoPython + CallbackParameter>(
     callback: &Bound<PyAny>,
     cancellation_token: Option<&CancellationToken>,
-) -> PyResult<RustCallback<T>> {
+    cancelled: Option<Arc<AtomicBool>>,
+) -> PyResult<(RustCallback<T>, HandlerImpl<T::Into>)> {
     let py = callback.py();
     let notifier = cancellation_token.and_then(|ct| ct.0.notifier());
     let is_cancelled = cancellation_token.is_some() && notifier.is_none();
-    let callback = PythonCallback::new(callback, notifier);
-    Ok(if callback.callback.indirect && !is_cancelled {
+    let callback = PythonCallback::new(callback, notifier, cancelled);
+    let handler = HandlerImpl::Callback(callback.callback.clone());
+    let rust_callback = if callback.indirect && !is_cancelled {
         let (rust_callback, receiver) = DefaultHandler.into_rust().into_handler();
         let kwargs = PyDict::new(py);
         let target = PyCFunction::new_closure(py, None, None, move |args, _| {
@@ -365,13 +717,77 @@ fn python_callback<T: IntoPython + CallbackParameter>(
         RustCallback::new(Arc::new(move |t| {
             Python::with_gil(|gil| callback.call(gil, t))
         }))
-    })
+    };
+    Ok((rust_callback, handler))
+}
+
+/// Same as [`into_handler`], but if `executor` is provided, the handler must be a Python
+/// callback, whose invocations are submitted to the executor instead of being run in zenoh
+/// threads.
+pub(crate) fn into_executor_handler<T: IntoPython + CallbackParameter + Send + 'static>(
+    py: Python,
+    obj: Option<&Bound<PyAny>>,
+    executor: Option<&Bound<Executor>>,
+) -> PyResult<(impl IntoHandler<T, Handler = HandlerImpl<T::Into>>, bool)> {
+    let Some(executor) = executor else {
+        let (handler, background) = into_handler(py, obj, None)?;
+        return Ok((handler.into_handler(), background));
+    };
+    Ok((executor_callback(obj, executor, None, None)?, true))
+}
+
+/// Same as [`into_executor_handler`] with an executor, but all the invocations are run in order
+/// by the same worker, and no longer once `cancelled` is set; the drop callback, if any, is
+/// called after the last invocation returns.
+pub(crate) fn into_lane_handler<T: IntoPython + CallbackParameter + Send + 'static>(
+    obj: Option<&Bound<PyAny>>,
+    executor: &Bound<Executor>,
+    cancelled: Option<Arc<AtomicBool>>,
+) -> PyResult<impl IntoHandler<T, Handler = HandlerImpl<T::Into>>> {
+    let lane = executor.get().lane();
+    executor_callback(obj, executor, Some(lane), cancelled)
+}
+
+fn executor_callback<T: IntoPython + CallbackParameter + Send + 'static>(
+    obj: Option<&Bound<PyAny>>,
+    executor: &Bound<Executor>,
+    lane: Option<usize>,
+    cancelled: Option<Arc<AtomicBool>>,
+) -> PyResult<(RustCallback<T>, HandlerImpl<T::Into>)> {
+    let Some(obj) = obj.filter(|obj| obj.is_callable()) else {
+        return Err(PyValueError::new_err(
+            "an executor requires a callback handler",
+        ));
+    };
+    // the last reference is dropped by the last task, calling the drop callback
+    let callback = Arc::new(PythonCallback::new(obj, None, cancelled));
+    let handler = HandlerImpl::Callback(callback.callback.clone());
+    let executor = executor.clone().unbind();
+    let rust_callback = RustCallback::new(Arc::new(move |t| {
+        let callback = callback.clone();
+        let task: Task = Box::new(move |py| callback.call(py, t));
+        match lane {
+            Some(lane) => executor.get().submit_to_lane(lane, task),
+            None => executor.get().submit(task),
+        }
+    }));
+    Ok((rust_callback, handler))
 }
 
 pub(crate) fn into_handler<T: IntoPython + CallbackParameter>(
     py: Python,
     obj: Option<&Bound<PyAny>>,
     cancellation_token: Option<&CancellationToken>,
+) -> PyResult<(impl IntoHandler<T, Handler = HandlerImpl<T::Into>>, bool)> {
+    into_cancellable_handler(py, obj, cancellation_token, None)
+}
+
+/// Same as [`into_handler`], but Python callbacks are no longer invoked once `cancelled` is set.
+pub(crate) fn into_cancellable_handler<T: IntoPython + CallbackParameter>(
+    py: Python,
+    obj: Option<&Bound<PyAny>>,
+    cancellation_token: Option<&CancellationToken>,
+    cancelled: Option<Arc<AtomicBool>>,
 ) -> PyResult<(impl IntoHandler<T, Handler = HandlerImpl<T::Into>>, bool)> {
     let mut background = false;
     let Some(obj) = obj else {
@@ -383,12 +799,13 @@ pub(crate) fn into_handler<T: IntoPython + CallbackParameter>(
         rust_handler(py, handler)
     } else if let Ok(handler) = obj.extract::<RingChannel>() {
         rust_handler(py, handler)
+    } else if obj.is_instance_of::<PriorityChannel>() {
+        return Err(PyValueError::new_err(
+            "PriorityChannel is only supported by subscribers",
+        ));
     } else if obj.is_callable() {
         background = true;
-        (
-            python_callback(obj, cancellation_token)?,
-            HandlerImpl::Python(py.None()),
-        )
+        python_callback(obj, cancellation_token, cancelled)?
     } else if let Some((cb, handler)) = obj
         .extract::<(Bound<PyAny>, PyObject)>()
         .ok()
@@ -397,10 +814,8 @@ pub(crate) fn into_handler<T: IntoPython + CallbackParameter>(
         if handler.bind(py).is_callable() {
             import!(py, warnings.warn).call1((DROP_CALLBACK_WARNING,))?;
         }
-        (
-            python_callback(&cb, cancellation_token)?,
-            HandlerImpl::Python(handler),
-        )
+        let (callback, _) = python_callback(&cb, cancellation_token, cancelled)?;
+        (callback, HandlerImpl::Python(handler))
     } else {
         return Err(PyValueError::new_err(format!(
             "Invalid handler type {}",
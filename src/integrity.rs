@@ -0,0 +1,149 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{bytes::ZBytes, handlers::log_error, sample::Sample};
+
+/// Reserved key ending the attachment of samples carrying a payload digest. It is preceded by
+/// the digest (u64, little-endian), the algorithm id (u8), and whether the original attachment,
+/// which comes first, was present (u8).
+const RESERVED_KEY: &[u8] = b"zenoh:integrity";
+const TRAILER_SIZE: usize = 8 + 1 + 1 + RESERVED_KEY.len();
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Integrity {
+    Crc32c,
+    Xxh3,
+}
+
+impl Integrity {
+    fn id(&self) -> u8 {
+        match self {
+            Self::Crc32c => 1,
+            Self::Xxh3 => 2,
+        }
+    }
+
+    fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::Crc32c),
+            2 => Some(Self::Xxh3),
+            _ => None,
+        }
+    }
+
+    fn digest(&self, payload: &zenoh::bytes::ZBytes) -> u64 {
+        match self {
+            Self::Crc32c => payload.slices().fold(0, crc32c::crc32c_append).into(),
+            Self::Xxh3 => {
+                let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+                payload.slices().for_each(|slice| hasher.update(slice));
+                hasher.digest()
+            }
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for Integrity {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        match &*ob.extract::<String>()? {
+            "crc32c" => Ok(Self::Crc32c),
+            "xxh3" => Ok(Self::Xxh3),
+            integrity => Err(PyValueError::new_err(format!(
+                "invalid integrity '{integrity}', expected 'crc32c' or 'xxh3'"
+            ))),
+        }
+    }
+}
+
+/// Appends the payload digest to the attachment if requested.
+pub(crate) fn attach(
+    py: Python,
+    integrity: Option<Integrity>,
+    payload: &ZBytes,
+    attachment: Option<ZBytes>,
+) -> Option<ZBytes> {
+    let Some(integrity) = integrity else {
+        return attachment;
+    };
+    let digest = py.allow_threads(|| integrity.digest(&payload.0));
+    let mut bytes = attachment
+        .as_ref()
+        .map_or_else(Vec::new, |a| a.0.to_bytes().into_owned());
+    bytes.extend_from_slice(&digest.to_le_bytes());
+    bytes.push(integrity.id());
+    bytes.push(attachment.is_some().into());
+    bytes.extend_from_slice(RESERVED_KEY);
+    Some(ZBytes(bytes.into()))
+}
+
+/// Splits an attachment into the original attachment, the algorithm, and the digest.
+fn split(attachment: &zenoh::bytes::ZBytes) -> Option<(Option<Vec<u8>>, Integrity, u64)> {
+    let bytes = attachment.to_bytes();
+    if bytes.len() < TRAILER_SIZE || !bytes.ends_with(RESERVED_KEY) {
+        return None;
+    }
+    let (original, trailer) = bytes.split_at(bytes.len() - TRAILER_SIZE);
+    let digest = u64::from_le_bytes(trailer[0..8].try_into().unwrap());
+    let integrity = Integrity::from_id(trailer[8])?;
+    let original = (trailer[9] != 0).then(|| original.to_vec());
+    Some((original, integrity, digest))
+}
+
+/// Integrity verification of a subscriber.
+pub(crate) struct IntegrityCheck {
+    on_corrupt: Option<PyObject>,
+    corrupt: AtomicUsize,
+    unverified: AtomicUsize,
+}
+
+impl IntegrityCheck {
+    pub(crate) fn new(on_corrupt: Option<PyObject>) -> Self {
+        Self {
+            on_corrupt,
+            corrupt: AtomicUsize::new(0),
+            unverified: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn corrupt_count(&self) -> usize {
+        self.corrupt.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn unverified_count(&self) -> usize {
+        self.unverified.load(Ordering::Relaxed)
+    }
+
+    /// Returns the sample with its original attachment, or `None` if it is corrupted, in which
+    /// case it is passed to `on_corrupt`.
+    pub(crate) fn verify(&self, sample: zenoh::sample::Sample) -> Option<zenoh::sample::Sample> {
+        let Some((attachment, integrity, digest)) = sample.attachment().and_then(split) else {
+            self.unverified.fetch_add(1, Ordering::Relaxed);
+            return Some(sample);
+        };
+        if integrity.digest(sample.payload()) != digest {
+            self.corrupt.fetch_add(1, Ordering::Relaxed);
+            if let Some(on_corrupt) = &self.on_corrupt {
+                Python::with_gil(|py| {
+                    log_error(py, on_corrupt.call1(py, (Sample::from(sample),)));
+                });
+            }
+            return None;
+        }
+        let builder = zenoh::sample::SampleBuilder::from(sample);
+        Some(builder.attachment(attachment).into())
+    }
+}
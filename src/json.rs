@@ -0,0 +1,218 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    intern,
+    prelude::*,
+    types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple, PyType},
+};
+use serde_json::{Map, Number, Value};
+
+use crate::{
+    bytes::{Encoding, ZBytes},
+    macros::import,
+};
+
+/// Metadata key of dataclass fields excluded from serialization.
+const EXCLUDE_KEY: &str = "zenoh_exclude";
+
+type Fields = Arc<[Py<PyString>]>;
+
+/// Serialized fields of each dataclass type, indexed by type address, with a weak reference
+/// to the type, so that types can be collected and entries of reused addresses detected.
+static DATACLASS_FIELDS: Mutex<BTreeMap<usize, (PyObject, Fields)>> = Mutex::new(BTreeMap::new());
+
+/// Whether the object is a dataclass instance, as opposed to a dataclass type.
+pub(crate) fn is_dataclass(obj: &Bound<PyAny>) -> bool {
+    let py = obj.py();
+    !obj.is_instance_of::<PyType>()
+        && obj
+            .get_type()
+            .hasattr(intern!(py, "__dataclass_fields__"))
+            .unwrap_or(false)
+}
+
+fn dataclass_fields(tp: &Bound<PyType>) -> PyResult<Fields> {
+    let py = tp.py();
+    let key = tp.as_ptr() as usize;
+    if let Some((type_ref, fields)) = DATACLASS_FIELDS.lock().unwrap().get(&key) {
+        if type_ref.bind(py).call0()?.is(tp) {
+            return Ok(fields.clone());
+        }
+    }
+    let field_marker = import!(py, dataclasses._FIELD);
+    let mut fields = Vec::new();
+    let all_fields = tp.getattr(intern!(py, "__dataclass_fields__"))?;
+    // pseudo-fields, i.e. `ClassVar` and `InitVar`, are skipped like `dataclasses.fields` does
+    for field in all_fields.downcast::<PyDict>()?.values() {
+        if !field.getattr(intern!(py, "_field_type"))?.is(field_marker) {
+            continue;
+        }
+        let metadata = field.getattr(intern!(py, "metadata"))?;
+        if metadata
+            .call_method1(intern!(py, "get"), (EXCLUDE_KEY,))?
+            .is_truthy()?
+        {
+            continue;
+        }
+        fields.push(
+            field
+                .getattr(intern!(py, "name"))?
+                .downcast_into::<PyString>()?
+                .unbind(),
+        );
+    }
+    let fields: Fields = fields.into();
+    let entry = (
+        import!(py, weakref.ref).call1((tp,))?.unbind(),
+        fields.clone(),
+    );
+    let mut cache = DATACLASS_FIELDS.lock().unwrap();
+    // entries of collected types are dropped
+    cache.retain(|_, (type_ref, _)| type_ref.bind(py).call0().is_ok_and(|tp| !tp.is_none()));
+    cache.insert(key, entry);
+    Ok(fields)
+}
+
+fn field_path(path: &str, field: &str) -> String {
+    if path.is_empty() {
+        field.to_string()
+    } else {
+        format!("{path}.{field}")
+    }
+}
+
+/// Converts an object to a JSON value following `dataclasses.asdict` semantics, `path` being
+/// the field path used in error messages.
+fn to_json(obj: &Bound<PyAny>, path: &str) -> PyResult<Value> {
+    let py = obj.py();
+    let invalid = |msg: &str| PyValueError::new_err(format!("{msg} for field '{path}'"));
+    if obj.is_none() {
+        Ok(Value::Null)
+    } else if let Ok(b) = obj.downcast::<PyBool>() {
+        Ok(Value::Bool(b.is_true()))
+    } else if let Ok(int) = obj.downcast::<PyInt>() {
+        if let Ok(int) = int.extract::<i64>() {
+            Ok(int.into())
+        } else if let Ok(int) = int.extract::<u64>() {
+            Ok(int.into())
+        } else {
+            Err(invalid("integer out of range"))
+        }
+    } else if let Ok(float) = obj.downcast::<PyFloat>() {
+        let float = Number::from_f64(float.value()).ok_or_else(|| invalid("non-finite float"))?;
+        Ok(Value::Number(float))
+    } else if let Ok(string) = obj.downcast::<PyString>() {
+        Ok(Value::String(string.to_cow()?.into_owned()))
+    } else if is_dataclass(obj) {
+        let mut map = Map::new();
+        for name in dataclass_fields(&obj.get_type())?
+            .iter()
+            .map(|n| n.bind(py))
+        {
+            let value = obj.getattr(name)?;
+            let name = name.to_cow()?;
+            map.insert(name.to_string(), to_json(&value, &field_path(path, &name))?);
+        }
+        Ok(Value::Object(map))
+    } else if let Ok(dict) = obj.downcast::<PyDict>() {
+        let mut map = Map::new();
+        for (key, value) in dict {
+            let Ok(key) = key.downcast::<PyString>() else {
+                return Err(invalid("non-string dict key"));
+            };
+            let key = key.to_cow()?;
+            map.insert(key.to_string(), to_json(&value, &field_path(path, &key))?);
+        }
+        Ok(Value::Object(map))
+    } else if obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>() {
+        let items = obj.try_iter()?.enumerate();
+        let items = items.map(|(i, item)| to_json(&item?, &format!("{path}[{i}]")));
+        Ok(Value::Array(items.collect::<PyResult<_>>()?))
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "unsupported type '{}' for field '{path}'",
+            obj.get_type().name()?
+        )))
+    }
+}
+
+/// Whether the object is serialized as JSON, i.e. a dataclass instance, a dict or a list.
+pub(crate) fn is_json_object(obj: &Bound<PyAny>) -> bool {
+    obj.is_instance_of::<PyDict>() || obj.is_instance_of::<PyList>() || is_dataclass(obj)
+}
+
+/// Serializes a dataclass instance, a dict or a list to JSON.
+pub(crate) fn object_to_json(obj: &Bound<PyAny>) -> PyResult<String> {
+    Ok(to_json(obj, "")?.to_string())
+}
+
+fn from_json(py: Python, value: Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => PyBool::new(py, b).to_owned().into_any().unbind(),
+        Value::Number(n) => match (n.as_i64(), n.as_u64()) {
+            (Some(int), _) => int.into_pyobject(py)?.into_any().unbind(),
+            (_, Some(int)) => int.into_pyobject(py)?.into_any().unbind(),
+            _ => PyFloat::new(py, n.as_f64().unwrap_or_default())
+                .into_any()
+                .unbind(),
+        },
+        Value::String(s) => s.into_pyobject(py)?.into_any().unbind(),
+        Value::Array(items) => {
+            let items = items.into_iter().map(|item| from_json(py, item));
+            PyList::new(py, items.collect::<PyResult<Vec<_>>>()?)?
+                .into_any()
+                .unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, value) in map {
+                dict.set_item(key, from_json(py, value)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+/// Deserializes JSON to the corresponding Python objects, the inverse of [`object_to_json`]
+/// for dicts and lists.
+pub(crate) fn json_to_object(py: Python, json: &[u8]) -> PyResult<PyObject> {
+    let value = serde_json::from_slice(json)
+        .map_err(|err| PyValueError::new_err(format!("invalid JSON: {err}")))?;
+    from_json(py, value)
+}
+
+/// Converts a payload, with the encoding inferred from its type, i.e. JSON for dataclasses,
+/// dicts, lists and bools, and octet stream for buffer protocol objects other than bytes,
+/// bytearray and str.
+pub(crate) fn encoded_payload(payload: &Bound<PyAny>) -> PyResult<(ZBytes, Option<Encoding>)> {
+    if let Ok(bytes) = payload.extract::<ZBytes>() {
+        return Ok((bytes, None));
+    }
+    let (bytes, is_buffer) = ZBytes::from_payload(payload)?;
+    let encoding = if payload.is_instance_of::<PyBool>() || is_json_object(payload) {
+        Some(zenoh::bytes::Encoding::APPLICATION_JSON)
+    } else if is_buffer {
+        Some(zenoh::bytes::Encoding::APPLICATION_OCTET_STREAM)
+    } else {
+        None
+    };
+    Ok((bytes, encoding.map(Encoding)))
+}
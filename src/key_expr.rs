@@ -18,8 +18,9 @@ use pyo3::{
 };
 
 use crate::{
+    error::{new_zerror_with_code, ErrorCode},
     macros::{downcast_or_new, enum_mapper, wrapper},
-    utils::{IntoPyResult, MapInto},
+    utils::MapInto,
 };
 
 enum_mapper!(zenoh::key_expr::SetIntersectionLevel: u8 {
@@ -32,17 +33,22 @@ enum_mapper!(zenoh::key_expr::SetIntersectionLevel: u8 {
 wrapper!(zenoh::key_expr::KeyExpr<'static>: Clone);
 downcast_or_new!(KeyExpr => String);
 
+/// Converts a key expression parsing error, raised with the `INVALID_KEYEXPR` code.
+pub(crate) fn invalid_key_expr(err: impl ToString) -> PyErr {
+    new_zerror_with_code(err.to_string(), ErrorCode::InvalidKeyexpr)
+}
+
 #[pymethods]
 impl KeyExpr {
     #[new]
     pub(crate) fn new(s: String) -> PyResult<Self> {
-        Ok(Self(s.parse().into_pyres()?))
+        Ok(Self(s.parse().map_err(invalid_key_expr)?))
     }
 
     #[classmethod]
     fn autocanonize(_cls: &Bound<PyType>, key_expr: String) -> PyResult<Self> {
         zenoh::key_expr::KeyExpr::autocanonize(key_expr)
-            .into_pyres()
+            .map_err(invalid_key_expr)
             .map_into()
     }
 
@@ -62,11 +68,11 @@ impl KeyExpr {
     }
 
     fn join(&self, other: String) -> PyResult<Self> {
-        self.0.join(&other).into_pyres().map_into()
+        self.0.join(&other).map_err(invalid_key_expr).map_into()
     }
 
     fn concat(&self, other: String) -> PyResult<Self> {
-        self.0.concat(&other).into_pyres().map_into()
+        self.0.concat(&other).map_err(invalid_key_expr).map_into()
     }
 
     fn __eq__(&self, #[pyo3(from_py_with = Self::from_py)] other: Self) -> bool {
@@ -85,7 +91,9 @@ impl KeyExpr {
         PyString::new(py, self.0.as_str()).hash()
     }
 
-    fn __truediv__(&self, #[pyo3(from_py_with = Self::from_py)] other: Self) -> Self {
-        Self(&self.0 / &other.0)
+    // Not using `from_py_with`, as binary operators turn extraction errors into
+    // `NotImplemented`, hiding the validation error.
+    fn __truediv__(&self, other: &Bound<PyAny>) -> PyResult<Self> {
+        Ok(Self(&self.0 / &Self::from_py(other)?.0))
     }
 }
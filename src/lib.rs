@@ -13,27 +13,52 @@
 //
 // TODO https://github.com/eclipse-zenoh/zenoh-python/pull/235#discussion_r1644498390
 // mod logging;
+mod admin;
 mod bytes;
 mod cancellation;
+mod clock;
+mod compat;
+mod compression;
 mod config;
+mod debug;
+mod decoder;
+mod error;
+mod executor;
 #[cfg(feature = "zenoh-ext")]
 mod ext;
+mod files;
+mod gaps;
+mod get_cache;
+mod group;
 mod handlers;
+mod integrity;
+mod json;
 mod key_expr;
 mod liveliness;
 mod macros;
+mod manifest;
 mod matching;
+mod metrics;
+mod policy;
+mod projection;
 mod pubsub;
 mod qos;
 mod query;
+mod report;
+mod ring;
+mod runtime;
 mod sample;
 mod scouting;
 mod session;
+mod shard;
 #[cfg(feature = "shared-memory")]
 mod shm;
+mod spool;
 mod time;
 mod timestamp_stack;
+mod transcode;
 mod utils;
+mod validation;
 
 use pyo3::prelude::*;
 
@@ -42,7 +67,8 @@ pyo3::create_exception!(zenoh, ZError, pyo3::exceptions::PyException);
 #[cfg(feature = "zenoh-ext")]
 pyo3::create_exception!(zenoh, ZDeserializeError, pyo3::exceptions::PyException);
 
-#[pymodule]
+// the GIL is kept on free-threaded builds until the module state has been audited for it
+#[pymodule(gil_used = true)]
 pub(crate) mod zenoh {
     use pyo3::prelude::*;
 
@@ -58,37 +84,69 @@ pub(crate) mod zenoh {
 
     #[pymodule_export]
     use crate::{
+        admin::{EntityEvent, EntityInfo, EntityWatcher},
         bytes::{Encoding, ZBytes},
         cancellation::CancellationToken,
-        config::{Config, WhatAmI, WhatAmIMatcher, ZenohId},
+        config::{Config, ConfigNotifier, ConfigWatcher, WhatAmI, WhatAmIMatcher, ZenohId},
+        decoder::{register_type, TypeRegistration},
+        error::ErrorCode,
+        executor::Executor,
+        gaps::GapStats,
+        get_cache::{enable_get_cache, get_cache_stats},
+        group::EntityGroup,
         handlers::Handler,
         key_expr::{KeyExpr, SetIntersectionLevel},
         liveliness::{Liveliness, LivelinessToken},
+        manifest::apply_manifest,
         matching::{MatchingListener, MatchingStatus},
+        metrics::metrics_snapshot,
+        policy::{set_subscriber_policy, SubscriberPolicy},
         pubsub::{Publisher, Subscriber},
         qos::{CongestionControl, Priority, Reliability},
         query::{
-            ConsolidationMode, Parameters, Querier, Query, QueryConsolidation, QueryTarget,
-            Queryable, Reply, ReplyError, ReplyKeyExpr, Selector,
+            replies_to_columns, ConsolidationMode, GetHandle, OnceQueryable, PagedGet, Parameters,
+            Querier, Query, QueryConsolidation, QueryTarget, Queryable, Replies, Reply, ReplyError,
+            ReplyKeyExpr, Selector,
         },
+        report::bug_report,
+        ring::PayloadRing,
+        runtime::runtime_info,
         sample::{Locality, Sample, SampleKind, SourceInfo},
-        scouting::{scout, Hello, Scout},
+        scouting::{open_auto, scout, AutoOpenReport, Hello, Scout},
         session::{
-            open, EntityGlobalId, Link, LinkEvent, LinkEventsListener, Session, SessionInfo,
-            Transport, TransportEvent, TransportEventsListener,
+            open, CanonicalInfo, EntityGlobalId, Link, LinkEvent, LinkEventsListener, Session,
+            SessionInfo, SubscriberBuilder, Transport, TransportEvent, TransportEventsListener,
+            TransportInfo,
         },
-        time::{Timestamp, TimestampId, NTP64},
+        shard::{shard_key_expr, shard_matches},
+        spool::SpooledReplies,
+        time::{set_naive_datetime_policy, Timestamp, TimestampId, NTP64},
         timestamp_stack::{
             InterceptionPoint, TimestampContext, TimestampInstrumentation,
             TimestampInstrumentationBuilder, TimestampStack, TimestampStackRecord,
         },
+        validation::set_publish_validation,
         ZError,
     };
 
+    #[pymodule]
+    mod debug {
+        #[pymodule_export]
+        use crate::{
+            clock::set_mock_clock,
+            debug::{
+                disable_watchdog, enable_watchdog, open_handles, pending_operations, track_handles,
+                track_pending, OpenHandle, PendingOperation,
+            },
+        };
+    }
+
     #[pymodule]
     mod handlers {
         #[pymodule_export]
-        use crate::handlers::{Callback, DefaultHandler, FifoChannel, Handler, RingChannel};
+        use crate::handlers::{
+            Callback, DefaultHandler, FifoChannel, Handler, PriorityChannel, RingChannel,
+        };
     }
 
     #[cfg(feature = "zenoh-ext")]
@@ -117,12 +175,19 @@ pub(crate) mod zenoh {
 
     #[pymodule_init]
     fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+        crate::runtime::init_runtime_env();
         let sys_modules = m.py().import("sys")?.getattr("modules")?;
+        sys_modules.set_item("zenoh.debug", m.getattr("debug")?)?;
         sys_modules.set_item("zenoh.handlers", m.getattr("handlers")?)?;
         #[cfg(feature = "zenoh-ext")]
         sys_modules.set_item("zenoh._ext", m.getattr("_ext")?)?;
         #[cfg(feature = "shared-memory")]
         sys_modules.set_item("zenoh.shm", m.getattr("shm")?)?;
+        // context attributes set on raised errors, see `utils::with_context`
+        let zerror = m.getattr("ZError")?;
+        zerror.setattr("operation", m.py().None())?;
+        zerror.setattr("key_expr", m.py().None())?;
+        zerror.setattr("code", crate::error::ErrorCode::Other)?;
         // TODO
         // crate::logging::init_logger(m.py())?;
         Ok(())
@@ -10,7 +10,7 @@ use crate::{
     handlers::{into_handler, HandlerImpl},
     key_expr::KeyExpr,
     macros::{build, option_wrapper},
-    pubsub::Subscriber,
+    pubsub::{subscriber_handler, Subscriber},
     query::Reply,
     utils::{duration, wait, MapInto},
 };
@@ -38,7 +38,7 @@ impl Liveliness {
         handler: Option<&Bound<PyAny>>,
         history: Option<bool>,
     ) -> PyResult<Subscriber> {
-        let (handler, background) = into_handler(py, handler, None)?;
+        let (handler, background) = subscriber_handler(py, handler, None)?;
         let liveliness = self.0.liveliness();
         let builder = build!(liveliness.declare_subscriber(key_expr), history);
         let mut subscriber = wait(py, builder.with(handler))?;
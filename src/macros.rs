@@ -57,7 +57,7 @@ macro_rules! into_rust {
 pub(crate) use into_rust;
 
 macro_rules! zerror {
-    ($($tt:tt)*) => { $crate::ZError::new_err(format!($($tt)*)) };
+    ($($tt:tt)*) => { $crate::error::new_zerror(format!($($tt)*)) };
 }
 pub(crate) use zerror;
 
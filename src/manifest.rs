@@ -0,0 +1,267 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{collections::HashSet, fmt};
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+    types::{PyBool, PyDict, PyList, PyString, PyTuple},
+    PyTypeInfo,
+};
+
+use crate::{
+    group::EntityGroup,
+    handlers::{FifoChannel, RingChannel},
+    qos::{CongestionControl, Priority, Reliability},
+    session::Session,
+};
+
+/// Manifest entity kinds, with the session method declaring them and their allowed options.
+const ENTITY_KINDS: [(&str, &str, &[&str]); 3] = [
+    (
+        "publishers",
+        "declare_publisher",
+        &[
+            "priority",
+            "congestion_control",
+            "reliability",
+            "express",
+            "encoding",
+        ],
+    ),
+    (
+        "subscribers",
+        "declare_subscriber",
+        &["handler", "capacity"],
+    ),
+    (
+        "queryables",
+        "declare_queryable",
+        &["handler", "capacity", "complete"],
+    ),
+];
+
+struct Declaration {
+    name: String,
+    method: &'static str,
+    key_expr: String,
+    kwargs: Py<PyDict>,
+}
+
+struct Validator<'py> {
+    namespace: Option<Bound<'py, PyDict>>,
+    names: HashSet<String>,
+    errors: Vec<String>,
+}
+
+impl<'py> Validator<'py> {
+    fn error(&mut self, path: &str, msg: impl fmt::Display) {
+        self.errors.push(format!("{path}: {msg}"));
+    }
+
+    /// Sets the enum variant named (in lowercase) by `value` as the `field` argument.
+    fn enum_option<T: PyTypeInfo>(
+        &mut self,
+        kwargs: &Bound<'py, PyDict>,
+        path: &str,
+        field: &str,
+        value: &Bound<'py, PyAny>,
+    ) -> PyResult<()> {
+        let py = value.py();
+        let variant = match value.downcast::<PyString>() {
+            Ok(name) => py
+                .get_type::<T>()
+                .getattr(name.to_cow()?.to_uppercase())
+                .ok(),
+            Err(_) => None,
+        };
+        match variant.filter(|variant| variant.is_instance_of::<T>()) {
+            Some(variant) => kwargs.set_item(field, variant)?,
+            None => self.error(path, format_args!("invalid {field} {}", value.repr()?)),
+        }
+        Ok(())
+    }
+
+    fn handler(
+        &mut self,
+        kwargs: &Bound<'py, PyDict>,
+        path: &str,
+        handler: Option<Bound<'py, PyAny>>,
+        capacity: Option<Bound<'py, PyAny>>,
+    ) -> PyResult<()> {
+        let py = kwargs.py();
+        let handler_path = format!("{path}/handler");
+        let capacity_path = format!("{path}/capacity");
+        let Some(handler) = handler else {
+            if capacity.is_some() {
+                self.error(&capacity_path, "requires a 'fifo' or 'ring' handler");
+            }
+            return Ok(());
+        };
+        let Ok(handler) = handler.extract::<String>() else {
+            self.error(&handler_path, "expected a string");
+            return Ok(());
+        };
+        let channel = match handler.as_str() {
+            "fifo" => Some(FifoChannel::type_object(py)),
+            "ring" => Some(RingChannel::type_object(py)),
+            _ => None,
+        };
+        match (channel, capacity) {
+            (Some(channel), Some(capacity)) => match capacity.extract::<usize>() {
+                Ok(capacity) if capacity > 0 => {
+                    kwargs.set_item("handler", channel.call1((capacity,))?)?
+                }
+                _ => self.error(&capacity_path, "expected a positive integer"),
+            },
+            (Some(_), None) => self.error(
+                &capacity_path,
+                format_args!("required by a '{handler}' handler"),
+            ),
+            (None, Some(_)) => self.error(&capacity_path, "requires a 'fifo' or 'ring' handler"),
+            (None, None) => match self.namespace.as_ref().map(|ns| ns.get_item(&handler)) {
+                Some(Ok(Some(obj))) => kwargs.set_item("handler", obj)?,
+                Some(Err(err)) => return Err(err),
+                _ => self.error(
+                    &handler_path,
+                    format_args!("'{handler}' is not in the namespace"),
+                ),
+            },
+        }
+        Ok(())
+    }
+
+    fn entry(
+        &mut self,
+        path: &str,
+        method: &'static str,
+        options: &[&str],
+        entry: &Bound<'py, PyAny>,
+    ) -> PyResult<Option<Declaration>> {
+        let Ok(entry) = entry.downcast::<PyDict>() else {
+            self.error(path, "expected an object");
+            return Ok(None);
+        };
+        let kwargs = PyDict::new(entry.py());
+        let (mut key_expr, mut name, mut handler, mut capacity) = (None, None, None, None);
+        for (field, value) in entry {
+            let field = field.str()?.to_cow()?.into_owned();
+            let path = format!("{path}/{field}");
+            if !["key", "name"].contains(&field.as_str()) && !options.contains(&field.as_str()) {
+                self.error(&path, "unknown field");
+                continue;
+            }
+            match field.as_str() {
+                "key" => match value.extract::<String>() {
+                    Ok(key) => match zenoh::key_expr::KeyExpr::try_from(key.as_str()) {
+                        Ok(_) => key_expr = Some(key),
+                        Err(err) => self.error(&path, err),
+                    },
+                    Err(_) => self.error(&path, "expected a string"),
+                },
+                "name" => match value.extract::<String>() {
+                    Ok(n) => name = Some(n),
+                    Err(_) => self.error(&path, "expected a string"),
+                },
+                "handler" => handler = Some(value),
+                "capacity" => capacity = Some(value),
+                "priority" => self.enum_option::<Priority>(&kwargs, &path, &field, &value)?,
+                "congestion_control" => {
+                    self.enum_option::<CongestionControl>(&kwargs, &path, &field, &value)?
+                }
+                "reliability" => self.enum_option::<Reliability>(&kwargs, &path, &field, &value)?,
+                "express" | "complete" if value.is_instance_of::<PyBool>() => {
+                    kwargs.set_item(field, value)?
+                }
+                "encoding" if value.is_instance_of::<PyString>() => {
+                    kwargs.set_item(field, value)?
+                }
+                "express" | "complete" => self.error(&path, "expected a boolean"),
+                _ => self.error(&path, "expected a string"),
+            }
+        }
+        self.handler(&kwargs, path, handler, capacity)?;
+        let Some(key_expr) = key_expr else {
+            if !entry.contains("key")? {
+                self.error(path, "missing field 'key'");
+            }
+            return Ok(None);
+        };
+        let name = name.unwrap_or_else(|| key_expr.clone());
+        if !self.names.insert(name.clone()) {
+            self.error(path, format_args!("duplicate name '{name}'"));
+            return Ok(None);
+        }
+        Ok(Some(Declaration {
+            name,
+            method,
+            key_expr,
+            kwargs: kwargs.unbind(),
+        }))
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (session, manifest, namespace = None))]
+pub(crate) fn apply_manifest(
+    py: Python,
+    session: &Bound<Session>,
+    manifest: &Bound<PyDict>,
+    namespace: Option<Bound<PyDict>>,
+) -> PyResult<EntityGroup> {
+    let mut validator = Validator {
+        namespace,
+        names: HashSet::new(),
+        errors: Vec::new(),
+    };
+    let mut declarations = Vec::new();
+    for (kind, entries) in manifest {
+        let path = format!("/{}", kind.str()?.to_cow()?);
+        let Some((_, method, options)) = ENTITY_KINDS.iter().find(|(k, ..)| path[1..] == **k)
+        else {
+            validator.error(&path, "unknown entity kind");
+            continue;
+        };
+        let Ok(entries) = entries.downcast::<PyList>() else {
+            validator.error(&path, "expected a list");
+            continue;
+        };
+        for (i, entry) in entries.iter().enumerate() {
+            let path = format!("{path}/{i}");
+            declarations.extend(validator.entry(&path, method, options, &entry)?);
+        }
+    }
+    if !validator.errors.is_empty() {
+        return Err(PyValueError::new_err(format!(
+            "invalid manifest, {} errors: {}",
+            validator.errors.len(),
+            validator.errors.join("; ")
+        )));
+    }
+    let group = EntityGroup::new(session.clone());
+    let declare = |decl: &Declaration| {
+        let args = PyTuple::new(py, [&decl.key_expr])?;
+        let kwargs = decl.kwargs.bind(py);
+        let entity = group.declare(session.as_any(), decl.method, &args, Some(kwargs))?;
+        group.set_name(decl.name.clone(), entity.bind(py))
+    };
+    if let Err(err) = declarations.iter().try_for_each(declare) {
+        // roll back the entities declared before the failure
+        if let Err(rollback_err) = group.close_all(py) {
+            err.set_cause(py, Some(rollback_err));
+        }
+        return Err(err);
+    }
+    Ok(group)
+}
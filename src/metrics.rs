@@ -0,0 +1,162 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    sync::{Arc, Mutex, Weak},
+    time::{Duration, Instant},
+};
+
+use pyo3::{
+    prelude::*,
+    types::{PyDict, PyList, PyTuple},
+};
+
+use crate::handlers::map_callback;
+
+/// Number of durations kept to estimate the percentiles.
+const RESERVOIR_SIZE: usize = 1024;
+
+/// Callback stats of the live subscribers, with their key expression.
+static SUBSCRIBER_STATS: Mutex<Vec<(String, Weak<CallbackStats>)>> = Mutex::new(Vec::new());
+
+/// Durations of the invocations of a callback, the percentiles being estimated from a uniform
+/// sample of them.
+#[derive(Default)]
+pub(crate) struct CallbackStats(Mutex<StatsInner>);
+
+#[derive(Default)]
+struct StatsInner {
+    count: u64,
+    total: Duration,
+    min: Duration,
+    max: Duration,
+    reservoir: Vec<Duration>,
+    // xorshift state of the reservoir sampling
+    rng: u64,
+}
+
+impl CallbackStats {
+    fn record(&self, elapsed: Duration) {
+        let mut inner = self.0.lock().unwrap();
+        inner.count += 1;
+        inner.total += elapsed;
+        inner.min = if inner.count == 1 {
+            elapsed
+        } else {
+            inner.min.min(elapsed)
+        };
+        inner.max = inner.max.max(elapsed);
+        if inner.reservoir.len() < RESERVOIR_SIZE {
+            inner.reservoir.push(elapsed);
+            return;
+        }
+        // each duration is kept with a probability of RESERVOIR_SIZE / count
+        let mut rng = if inner.rng == 0 {
+            inner.count
+        } else {
+            inner.rng
+        };
+        rng ^= rng << 13;
+        rng ^= rng >> 7;
+        rng ^= rng << 17;
+        inner.rng = rng;
+        let index = (rng % inner.count) as usize;
+        if index < RESERVOIR_SIZE {
+            inner.reservoir[index] = elapsed;
+        }
+    }
+
+    /// The stats in seconds, with `prefix` prepended to their names.
+    fn fill_dict(&self, dict: &Bound<PyDict>, prefix: &str) -> PyResult<()> {
+        let inner = self.0.lock().unwrap();
+        let mut reservoir = inner.reservoir.clone();
+        reservoir.sort_unstable();
+        let p95 = reservoir
+            .get((reservoir.len() * 95).div_ceil(100).saturating_sub(1))
+            .copied()
+            .unwrap_or_default();
+        let mean = match inner.count {
+            0 => Duration::ZERO,
+            count => inner.total.div_f64(count as f64),
+        };
+        dict.set_item(format!("{prefix}count"), inner.count)?;
+        dict.set_item(format!("{prefix}min"), inner.min.as_secs_f64())?;
+        dict.set_item(format!("{prefix}mean"), mean.as_secs_f64())?;
+        dict.set_item(format!("{prefix}max"), inner.max.as_secs_f64())?;
+        dict.set_item(format!("{prefix}p95"), p95.as_secs_f64())?;
+        Ok(())
+    }
+
+    pub(crate) fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new(py);
+        self.fill_dict(&dict, "")?;
+        Ok(dict)
+    }
+}
+
+/// Callable timing the invocations of the wrapped one.
+#[pyclass(frozen)]
+struct TimedCallback {
+    callback: PyObject,
+    stats: Arc<CallbackStats>,
+}
+
+#[pymethods]
+impl TimedCallback {
+    #[pyo3(signature = (*args))]
+    fn __call__(&self, args: &Bound<PyTuple>) -> PyResult<PyObject> {
+        let start = Instant::now();
+        let result = self.callback.call1(args.py(), args);
+        self.stats.record(start.elapsed());
+        result
+    }
+}
+
+/// Wraps a subscriber callback handler with [`TimedCallback`], registering its stats for
+/// `metrics_snapshot`.
+pub(crate) fn timed_handler<'py>(
+    handler: &Bound<'py, PyAny>,
+    key_expr: &str,
+) -> PyResult<(Bound<'py, PyAny>, Arc<CallbackStats>)> {
+    let py = handler.py();
+    let stats = Arc::new(CallbackStats::default());
+    let handler = map_callback(handler, |callback| {
+        let stats = stats.clone();
+        Ok(Py::new(py, TimedCallback { callback, stats })?.into_any())
+    })?;
+    let mut registry = SUBSCRIBER_STATS.lock().unwrap();
+    registry.retain(|(_, stats)| stats.strong_count() > 0);
+    registry.push((key_expr.to_string(), Arc::downgrade(&stats)));
+    Ok((handler, stats))
+}
+
+/// Returns the metrics of the live entities, i.e. the callback stats of the subscribers, as
+/// dicts with their `key_expr` and `callback_count`, `callback_min`, `callback_mean`,
+/// `callback_max` and `callback_p95`, in seconds.
+#[pyfunction]
+pub(crate) fn metrics_snapshot(py: Python) -> PyResult<Bound<PyDict>> {
+    let registry = SUBSCRIBER_STATS.lock().unwrap();
+    let subscribers = PyList::empty(py);
+    for (key_expr, stats) in registry.iter() {
+        let Some(stats) = stats.upgrade() else {
+            continue;
+        };
+        let entry = PyDict::new(py);
+        entry.set_item("key_expr", key_expr)?;
+        stats.fill_dict(&entry, "callback_")?;
+        subscribers.append(entry)?;
+    }
+    let snapshot = PyDict::new(py);
+    snapshot.set_item("subscribers", subscribers)?;
+    Ok(snapshot)
+}
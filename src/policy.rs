@@ -0,0 +1,68 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::RwLock;
+
+use pyo3::prelude::*;
+
+use crate::{key_expr::KeyExpr, sample::Locality, utils::MapInto};
+
+static SUBSCRIBER_POLICY: RwLock<Vec<SubscriberPolicy>> = RwLock::new(Vec::new());
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub(crate) struct SubscriberPolicy {
+    key_expr: zenoh::key_expr::KeyExpr<'static>,
+    allowed_origin: Option<zenoh::sample::Locality>,
+}
+
+#[pymethods]
+impl SubscriberPolicy {
+    #[new]
+    #[pyo3(signature = (key_expr, *, allowed_origin = None))]
+    fn new(
+        #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
+        allowed_origin: Option<Locality>,
+    ) -> Self {
+        Self {
+            key_expr: key_expr.0,
+            allowed_origin: allowed_origin.map(Into::into),
+        }
+    }
+
+    #[getter]
+    fn key_expr(&self) -> KeyExpr {
+        self.key_expr.clone().into()
+    }
+
+    #[getter]
+    fn allowed_origin(&self) -> Option<Locality> {
+        self.allowed_origin.map_into()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[pyfunction]
+pub(crate) fn set_subscriber_policy(rules: Vec<SubscriberPolicy>) {
+    *SUBSCRIBER_POLICY.write().unwrap() = rules;
+}
+
+/// Returns the `allowed_origin` of the first policy rule including `key_expr`.
+pub(crate) fn subscriber_allowed_origin(key_expr: &KeyExpr) -> Option<Locality> {
+    let rules = SUBSCRIBER_POLICY.read().unwrap();
+    let rule = rules.iter().find(|r| r.key_expr.includes(&key_expr.0))?;
+    rule.allowed_origin.map_into()
+}
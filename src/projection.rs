@@ -0,0 +1,157 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+    types::PyString,
+};
+use serde_json::{Map, Value};
+use zenoh::{
+    bytes::Encoding,
+    query::{Parameters, Selector},
+    sample::{Sample, SampleBuilder, SampleBuilderPut},
+};
+
+/// Selector parameter requesting the projection to queryables, see `Query::projection`.
+const QUERY_PROJECT: &str = "_project";
+/// Separator of the field paths in the projection parameter.
+const PATH_SEPARATOR: &str = "|";
+
+/// Field paths extracted from JSON payloads, e.g. `pose.x`, before the samples reach Python.
+pub(crate) struct Projection {
+    // sorted by length, so that a field is projected before its sub-fields
+    paths: Vec<Vec<String>>,
+    unprojected: AtomicUsize,
+}
+
+impl Projection {
+    pub(crate) fn from_py_opt(obj: &Bound<PyAny>) -> PyResult<Option<Self>> {
+        if obj.is_none() {
+            return Ok(None);
+        }
+        if obj.is_instance_of::<PyString>() {
+            return Err(PyTypeError::new_err(
+                "project must be a list of field paths",
+            ));
+        }
+        let mut paths = Vec::new();
+        for path in obj.try_iter()? {
+            let path = path?.extract::<String>()?;
+            let fields = path.split('.').map(String::from).collect::<Vec<_>>();
+            // the separators of the paths and of the selector parameters are reserved
+            let invalid = |f: &String| f.is_empty() || f.contains(['|', ';', '=']);
+            if fields.iter().any(invalid) {
+                return Err(PyValueError::new_err(format!(
+                    "invalid field path '{path}'"
+                )));
+            }
+            paths.push(fields);
+        }
+        if paths.is_empty() {
+            return Err(PyValueError::new_err(
+                "project must contain at least one field path",
+            ));
+        }
+        paths.sort_by_key(Vec::len);
+        Ok(Some(Self {
+            paths,
+            unprojected: AtomicUsize::new(0),
+        }))
+    }
+
+    /// The number of samples passed through unmodified, as their payload is not JSON.
+    pub(crate) fn unprojected_count(&self) -> usize {
+        self.unprojected.load(Ordering::Relaxed)
+    }
+
+    /// Adds the projection parameter to the selector, for queryables to project at the source.
+    pub(crate) fn with_parameter(&self, selector: Selector<'static>) -> Selector<'static> {
+        let (key_expr, mut parameters) = selector.split();
+        let paths = self.paths.iter().map(|path| path.join("."));
+        let value = paths.collect::<Vec<_>>().join(PATH_SEPARATOR);
+        parameters.insert(QUERY_PROJECT, value);
+        (key_expr, parameters).into()
+    }
+
+    fn project_value(&self, value: &Value) -> Value {
+        let mut projected = Map::new();
+        for path in &self.paths {
+            let field = lookup(value, path).cloned().unwrap_or(Value::Null);
+            insert(&mut projected, path, field);
+        }
+        Value::Object(projected)
+    }
+
+    /// Returns the sample with its JSON payload replaced by the projected object, or as is,
+    /// and counted, if its payload is not JSON.
+    pub(crate) fn project(&self, sample: Sample) -> Sample {
+        let id = sample.encoding().id();
+        let value = [Encoding::TEXT_JSON.id(), Encoding::APPLICATION_JSON.id()]
+            .contains(&id)
+            .then(|| serde_json::from_slice::<Value>(&sample.payload().to_bytes()).ok())
+            .flatten();
+        let Some(value) = value else {
+            self.unprojected.fetch_add(1, Ordering::Relaxed);
+            return sample;
+        };
+        // cloning a sample shares its payload buffers, it doesn't copy them
+        match SampleBuilder::<SampleBuilderPut>::try_from(sample.clone()) {
+            Ok(builder) => builder
+                .payload(self.project_value(&value).to_string())
+                .into(),
+            Err(_) => {
+                self.unprojected.fetch_add(1, Ordering::Relaxed);
+                sample
+            }
+        }
+    }
+}
+
+/// Returns the field at `path`, array items being indexed by their position.
+fn lookup<'a>(mut value: &'a Value, path: &[String]) -> Option<&'a Value> {
+    for field in path {
+        value = match value {
+            Value::Object(map) => map.get(field)?,
+            Value::Array(items) => items.get(field.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// Inserts the field at `path` in the projected object, unless a field of a shorter path,
+/// projected before, already contains it; projecting a projected object is then a no-op.
+fn insert(mut object: &mut Map<String, Value>, path: &[String], value: Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    for field in parents {
+        let entry = object
+            .entry(field.as_str())
+            .or_insert_with(|| Map::new().into());
+        let Value::Object(map) = entry else {
+            return;
+        };
+        object = map;
+    }
+    object.entry(last.as_str()).or_insert(value);
+}
+
+/// The field paths requested by the querier, if any.
+pub(crate) fn requested_paths(parameters: &Parameters) -> Option<Vec<String>> {
+    let value = parameters.get(QUERY_PROJECT)?;
+    Some(value.split(PATH_SEPARATOR).map(String::from).collect())
+}
@@ -11,27 +11,117 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex, RwLock, Weak,
+    },
+    time::Duration,
+};
+
 use pyo3::{
+    exceptions::PyValueError,
     prelude::*,
     types::{PyDict, PyIterator, PyTuple, PyType},
     IntoPyObjectExt,
 };
+use zenoh::{
+    handlers::{Callback as RustCallback, IntoHandler},
+    sample::SampleBuilder,
+    Wait,
+};
 
 use crate::{
     bytes::{Encoding, ZBytes},
-    handlers::{into_handler, HandlerImpl},
+    compression::{compress, Compression},
+    debug::{self, PendingKey},
+    gaps::GapTracker,
+    handlers::{into_handler, log_error, HandlerImpl},
+    integrity::{attach, Integrity, IntegrityCheck},
+    json::encoded_payload,
     key_expr::KeyExpr,
-    macros::{build, option_wrapper},
+    macros::{build, import, zerror},
     matching::{MatchingListener, MatchingStatus},
+    metrics::CallbackStats,
+    projection::Projection,
     qos::{CongestionControl, Priority, Reliability},
-    sample::{Sample, SourceInfo},
+    sample::{Locality, Sample, SampleKind, SourceInfo},
     session::EntityGlobalId,
+    shard::Shard,
     time::Timestamp,
     timestamp_stack::TimestampInstrumentation,
     utils::{generic, wait},
+    validation::validate_payload,
 };
 
-option_wrapper!(zenoh::pubsub::Publisher<'static>, "Undeclared publisher");
+/// Last sample of a publisher declared with `retain=True`, answered by its queryable.
+pub(crate) struct Retained {
+    sample: Arc<Mutex<Option<zenoh::sample::Sample>>>,
+    queryable: zenoh::query::Queryable<()>,
+}
+
+impl Retained {
+    pub(crate) fn declare(
+        py: Python,
+        session: &zenoh::Session,
+        key_expr: &zenoh::key_expr::KeyExpr<'static>,
+    ) -> PyResult<Self> {
+        let sample = Arc::<Mutex<Option<zenoh::sample::Sample>>>::default();
+        let retained = sample.clone();
+        let callback = move |query: zenoh::query::Query| {
+            let sample = retained.lock().unwrap().clone();
+            if let Some(sample) = sample {
+                // replies can only fail if the session is closed
+                query.reply_sample(sample).wait().ok();
+            }
+        };
+        let queryable = wait(py, session.declare_queryable(key_expr).callback(callback))?;
+        Ok(Self { sample, queryable })
+    }
+
+    fn set(&self, sample: Option<zenoh::sample::Sample>) {
+        *self.sample.lock().unwrap() = sample;
+    }
+}
+
+// Not using `option_wrapper!`, as the publisher may also hold its retained samples.
+#[pyclass(weakref)]
+pub(crate) struct Publisher(
+    pub(crate) Option<zenoh::pubsub::Publisher<'static>>,
+    pub(crate) Option<Retained>,
+);
+
+impl Publisher {
+    fn check<'a, 'py>(this: &'a Bound<'py, Self>) -> PyResult<&'a Bound<'py, Self>> {
+        this.borrow().get_ref()?;
+        Ok(this)
+    }
+
+    fn get_ref(&self) -> PyResult<&zenoh::pubsub::Publisher<'static>> {
+        self.0
+            .as_ref()
+            .ok_or_else(|| zerror!("Undeclared publisher"))
+    }
+}
+
+impl From<zenoh::pubsub::Publisher<'static>> for Publisher {
+    fn from(value: zenoh::pubsub::Publisher<'static>) -> Self {
+        Self(Some(value), None)
+    }
+}
+
+impl Drop for Publisher {
+    fn drop(&mut self) {
+        Python::with_gil(|gil| {
+            gil.allow_threads(|| {
+                drop(self.0.take());
+                drop(self.1.take());
+            })
+        });
+    }
+}
 
 #[pymethods]
 impl Publisher {
@@ -46,7 +136,9 @@ impl Publisher {
         _args: &Bound<PyTuple>,
         _kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<PyObject> {
-        self.undeclare(py)?;
+        if self.0.is_some() {
+            self.undeclare(py)?;
+        }
         Ok(py.None())
     }
 
@@ -86,18 +178,48 @@ impl Publisher {
     }
 
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (payload, *, encoding = None, attachment = None, timestamp = None, timestamp_instrumentation = None, source_info = None))]
+    #[pyo3(signature = (payload, *, encoding = None, attachment = None, timestamp = None, timestamp_instrumentation = None, source_info = None, compression = None, integrity = None, validate = None))]
     fn put(
         &self,
         py: Python,
-        #[pyo3(from_py_with = ZBytes::from_py)] payload: ZBytes,
+        payload: &Bound<PyAny>,
         #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         timestamp: Option<Timestamp>,
         timestamp_instrumentation: Option<TimestampInstrumentation>,
         source_info: Option<SourceInfo>,
+        compression: Option<Compression>,
+        integrity: Option<Integrity>,
+        validate: Option<bool>,
     ) -> PyResult<()> {
         let this = self.get_ref()?;
+        let (payload, payload_encoding) = encoded_payload(payload)?;
+        // the inferred encoding doesn't override the publisher one
+        let encoding = encoding.or_else(|| match this.encoding() {
+            e if *e == zenoh::bytes::Encoding::default() => payload_encoding,
+            _ => None,
+        });
+        let effective_encoding = encoding.as_ref().map_or(this.encoding(), |e| &e.0);
+        validate_payload(validate, &payload, effective_encoding)?;
+        // the suffix is appended to the publisher encoding if not overridden
+        let encoding = encoding.or_else(|| compression.map(|_| this.encoding().clone().into()));
+        let (payload, encoding) = compress(py, compression, payload, encoding)?;
+        let attachment = attach(py, integrity, &payload, attachment);
+        let retained_sample = self.1.as_ref().map(|_| {
+            let encoding = encoding
+                .clone()
+                .unwrap_or_else(|| this.encoding().clone().into());
+            let builder = SampleBuilder::put(this.key_expr().clone(), payload.0.clone());
+            let (attachment, timestamp) = (attachment.clone(), timestamp.clone());
+            let source_info = source_info.clone();
+            let builder = build!(
+                builder.encoding(encoding.0),
+                attachment,
+                timestamp,
+                source_info
+            );
+            zenoh::sample::Sample::from(builder)
+        });
         let builder = build!(
             this.put(payload),
             encoding,
@@ -106,26 +228,82 @@ impl Publisher {
             timestamp_instrumentation,
             source_info
         );
-        wait(py, builder)
+        let key = || PendingKey::Rust(this.key_expr().clone());
+        let _pending = debug::pending(py, "put", key);
+        wait(py, builder)?;
+        // only retained once published
+        if let Some(retained) = &self.1 {
+            retained.set(retained_sample);
+        }
+        Ok(())
     }
 
     #[pyo3(signature = (*, attachment = None, timestamp = None, timestamp_instrumentation = None, source_info = None))]
     fn delete(
         &self,
         py: Python,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         timestamp: Option<Timestamp>,
         timestamp_instrumentation: Option<TimestampInstrumentation>,
         source_info: Option<SourceInfo>,
     ) -> PyResult<()> {
+        let this = self.get_ref()?;
         let builder = build!(
-            self.get_ref()?.delete(),
+            this.delete(),
             attachment,
             timestamp,
             timestamp_instrumentation,
             source_info
         );
-        wait(py, builder)
+        let key = || PendingKey::Rust(this.key_expr().clone());
+        let _pending = debug::pending(py, "delete", key);
+        wait(py, builder)?;
+        if let Some(retained) = &self.1 {
+            retained.set(None);
+        }
+        Ok(())
+    }
+
+    /// Publishes a put or a delete depending on `kind`, e.g. to forward received samples.
+    #[pyo3(signature = (kind, payload = None, *, encoding = None, attachment = None, timestamp = None))]
+    fn write(
+        &self,
+        py: Python,
+        kind: SampleKind,
+        payload: Option<&Bound<PyAny>>,
+        #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
+        timestamp: Option<Timestamp>,
+    ) -> PyResult<()> {
+        match (kind, payload) {
+            (SampleKind::Put, Some(payload)) => self.put(
+                py, payload, encoding, attachment, timestamp, None, None, None, None, None,
+            ),
+            (SampleKind::Put, None) => Err(PyValueError::new_err("put requires a payload")),
+            (SampleKind::Delete, None) if encoding.is_none() => {
+                self.delete(py, attachment, timestamp, None, None)
+            }
+            (SampleKind::Delete, _) => Err(PyValueError::new_err(
+                "delete doesn't take a payload nor an encoding",
+            )),
+        }
+    }
+
+    fn retained(&self) -> PyResult<Vec<Sample>> {
+        self.get_ref()?;
+        let Some(retained) = &self.1 else {
+            return Ok(Vec::new());
+        };
+        let sample = retained.sample.lock().unwrap();
+        Ok(sample.iter().cloned().map(Sample::from).collect())
+    }
+
+    fn clear_retained(&self) -> PyResult<()> {
+        self.get_ref()?;
+        if let Some(retained) = &self.1 {
+            retained.set(None);
+        }
+        Ok(())
     }
 
     #[pyo3(signature = (handler = None))]
@@ -143,7 +321,17 @@ impl Publisher {
     }
 
     fn undeclare(&mut self, py: Python) -> PyResult<()> {
-        wait(py, self.take()?.undeclare())
+        let publisher = self
+            .0
+            .take()
+            .ok_or_else(|| zerror!("Undeclared publisher"))?;
+        // the publisher is undeclared even if the retained queryable fails to be, the first
+        // error is returned
+        let result = match self.1.take() {
+            Some(retained) => wait(py, retained.queryable.undeclare()),
+            None => Ok(()),
+        };
+        wait(py, publisher.undeclare()).and(result)
     }
 
     fn __repr__(&self) -> PyResult<String> {
@@ -151,11 +339,446 @@ impl Publisher {
     }
 }
 
-option_wrapper!(
-    zenoh::pubsub::Subscriber<HandlerImpl<Sample>>,
-    "Undeclared subscriber"
+/// State shared between a [`Subscriber`] and its sample callback.
+pub(crate) struct SubscriberState {
+    // removed when the subscriber limits are reached
+    callback: RwLock<Option<RustCallback<zenoh::sample::Sample>>>,
+    paused: AtomicBool,
+    pause_buffer: Mutex<PauseBuffer>,
+    dropped_while_paused: AtomicUsize,
+    limits: Option<SubscriberLimits>,
+    integrity: Option<IntegrityCheck>,
+    shard: Option<Shard>,
+    gaps: GapTracker,
+    // set for callback handlers timed with `SubscriberBuilder::time_callbacks`
+    callback_stats: Option<Arc<CallbackStats>>,
+    self_filter: Option<SelfFilter>,
+    projection: Option<Projection>,
+}
+
+/// Drops the samples published by the subscriber's own session, see
+/// `SubscriberBuilder::ignore_self`.
+pub(crate) struct SelfFilter {
+    zid: zenoh::session::ZenohId,
+    ignored: AtomicUsize,
+    unknown_origin: AtomicUsize,
+}
+
+impl SelfFilter {
+    pub(crate) fn new(zid: zenoh::session::ZenohId) -> Self {
+        Self {
+            zid,
+            ignored: AtomicUsize::new(0),
+            unknown_origin: AtomicUsize::new(0),
+        }
+    }
+
+    /// Whether the sample comes from another session; the samples without source info are
+    /// delivered, as their origin is unknown, but counted apart.
+    fn accepts(&self, sample: &zenoh::sample::Sample) -> bool {
+        let Some(source_info) = sample.source_info() else {
+            self.unknown_origin.fetch_add(1, Ordering::Relaxed);
+            return true;
+        };
+        if source_info.source_id().zid() != self.zid {
+            return true;
+        }
+        self.ignored.fetch_add(1, Ordering::Relaxed);
+        false
+    }
+}
+
+#[derive(Default)]
+struct PauseBuffer {
+    capacity: usize,
+    samples: VecDeque<zenoh::sample::Sample>,
+    // reset by `pause`, to stop a concurrent `resume`
+    resuming: bool,
+}
+
+/// Limits after which a subscriber is automatically undeclared.
+pub(crate) struct SubscriberLimits {
+    max_duration: Option<Duration>,
+    max_samples: Option<usize>,
+    on_complete: Option<PyObject>,
+    received: AtomicUsize,
+    completed: AtomicBool,
+    // the Python subscriber, or a weak reference to it, undeclared on completion
+    owner: Mutex<Option<PyObject>>,
+    // wakes up the duration timer when completed
+    timer: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl SubscriberLimits {
+    pub(crate) fn new(
+        max_duration: Option<Duration>,
+        max_samples: Option<usize>,
+        on_complete: Option<PyObject>,
+    ) -> Option<Self> {
+        if max_duration.is_none() && max_samples.is_none() {
+            return None;
+        }
+        Some(Self {
+            max_duration,
+            max_samples,
+            on_complete,
+            received: AtomicUsize::new(0),
+            completed: AtomicBool::new(false),
+            owner: Mutex::new(None),
+            timer: Arc::default(),
+        })
+    }
+
+    fn count(&self) -> usize {
+        let received = self.received.load(Ordering::SeqCst);
+        self.max_samples.map_or(received, |max| received.min(max))
+    }
+
+    fn stop_timer(&self) {
+        let (stopped, condvar) = &*self.timer;
+        *stopped.lock().unwrap() = true;
+        condvar.notify_all();
+    }
+}
+
+impl Drop for SubscriberLimits {
+    fn drop(&mut self) {
+        self.stop_timer();
+    }
+}
+
+/// Undeclares the Python subscriber, if still alive and not in use.
+fn undeclare_owner(py: Python, owner: PyObject) {
+    let owner = owner.into_bound(py);
+    // weak references are used when the subscriber is not in background
+    let owner = match owner.downcast::<Subscriber>() {
+        Ok(subscriber) => subscriber.clone(),
+        Err(_) => match owner.call0().map(|s| s.downcast_into::<Subscriber>()) {
+            Ok(Ok(subscriber)) => subscriber,
+            _ => return,
+        },
+    };
+    // if the subscriber is in use, e.g. blocked in `recv`, it will be undeclared by the user,
+    // as the channel is closed when the callback is dropped
+    let Ok(mut owner) = owner.try_borrow_mut() else {
+        return;
+    };
+    owner.1 = true;
+    if let Some(subscriber) = owner.0.take() {
+        wait(py, subscriber.undeclare()).ok();
+    }
+}
+
+impl SubscriberState {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        callback: RustCallback<zenoh::sample::Sample>,
+        limits: Option<SubscriberLimits>,
+        integrity: Option<IntegrityCheck>,
+        shard: Option<Shard>,
+        callback_stats: Option<Arc<CallbackStats>>,
+        self_filter: Option<SelfFilter>,
+        projection: Option<Projection>,
+    ) -> Self {
+        Self {
+            callback: RwLock::new(Some(callback)),
+            paused: AtomicBool::new(false),
+            pause_buffer: Mutex::default(),
+            dropped_while_paused: AtomicUsize::new(0),
+            limits,
+            integrity,
+            shard,
+            gaps: GapTracker::default(),
+            callback_stats,
+            self_filter,
+            projection,
+        }
+    }
+
+    fn on_sample(self: &Arc<Self>, sample: zenoh::sample::Sample) {
+        // samples of other shards are ignored altogether
+        if let Some(shard) = &self.shard {
+            if !shard.matches(sample.key_expr().as_str()) {
+                return;
+            }
+        }
+        // so are the samples of the subscriber's own session
+        if let Some(self_filter) = &self.self_filter {
+            if !self_filter.accepts(&sample) {
+                return;
+            }
+        }
+        self.gaps.on_sample(&sample);
+        // corrupted samples are never delivered, nor buffered
+        let sample = match &self.integrity {
+            Some(integrity) => match integrity.verify(sample) {
+                Some(sample) => sample,
+                None => return,
+            },
+            None => sample,
+        };
+        // projected after the integrity check, which needs the original payload
+        let sample = match &self.projection {
+            Some(projection) => projection.project(sample),
+            None => sample,
+        };
+        // the flag is checked before taking any lock, and the GIL is only taken by the
+        // wrapped callback
+        if self.paused.load(Ordering::SeqCst) {
+            let mut buffer = self.pause_buffer.lock().unwrap();
+            // `resume` may have flushed the buffer in the meantime
+            if self.paused.load(Ordering::SeqCst) {
+                if buffer.samples.len() < buffer.capacity {
+                    buffer.samples.push_back(sample);
+                } else {
+                    self.dropped_while_paused.fetch_add(1, Ordering::Relaxed);
+                }
+                return;
+            }
+        }
+        self.deliver(sample);
+    }
+
+    fn deliver(self: &Arc<Self>, sample: zenoh::sample::Sample) {
+        let mut last = false;
+        if let Some(limits) = &self.limits {
+            if limits.completed.load(Ordering::SeqCst) {
+                return;
+            }
+            let received = limits.received.fetch_add(1, Ordering::SeqCst) + 1;
+            match limits.max_samples {
+                Some(max) if received > max => return,
+                Some(max) => last = received == max,
+                None => {}
+            }
+        }
+        if let Some(callback) = &*self.callback.read().unwrap() {
+            callback.call(sample);
+        }
+        if last {
+            // not completed in the callback thread, as it undeclares the subscriber
+            let state = self.clone();
+            std::thread::spawn(move || state.complete("count"));
+        }
+    }
+
+    /// Starts the duration timer, if any.
+    fn start_timer(self: &Arc<Self>) {
+        let Some(limits) = &self.limits else { return };
+        let Some(max_duration) = limits.max_duration else {
+            return;
+        };
+        let state = Arc::downgrade(self);
+        let timer = limits.timer.clone();
+        std::thread::spawn(move || {
+            let (stopped, condvar) = &*timer;
+            let stopped = stopped.lock().unwrap();
+            let (stopped, _) = condvar
+                .wait_timeout_while(stopped, max_duration, |stopped| !*stopped)
+                .unwrap();
+            if !*stopped {
+                drop(stopped);
+                if let Some(state) = Weak::upgrade(&state) {
+                    state.complete("duration");
+                }
+            }
+        });
+    }
+
+    /// Stops the delivery, calls `on_complete`, and undeclares the subscriber, exactly once.
+    fn complete(&self, reason: &str) {
+        let Some(limits) = &self.limits else { return };
+        if limits.completed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        limits.stop_timer();
+        // waits for the callbacks in progress
+        let callback = self.callback.write().unwrap().take();
+        Python::with_gil(|py| {
+            if let Some(on_complete) = &limits.on_complete {
+                log_error(py, on_complete.call1(py, (limits.count(), reason)));
+            }
+            // closes the channel, or calls the drop callback
+            py.allow_threads(|| drop(callback));
+            if let Some(owner) = limits.owner.lock().unwrap().take() {
+                undeclare_owner(py, owner);
+            }
+        });
+    }
+
+    /// Registers the Python subscriber to undeclare on completion.
+    fn set_owner(&self, py: Python, owner: PyObject) {
+        let Some(limits) = &self.limits else { return };
+        let mut current = limits.owner.lock().unwrap();
+        // `completed` is set before taking the lock in `complete`
+        if limits.completed.load(Ordering::SeqCst) {
+            drop(current);
+            undeclare_owner(py, owner);
+        } else {
+            *current = Some(owner);
+        }
+    }
+
+    fn pause(&self, buffer: usize) {
+        let mut pause_buffer = self.pause_buffer.lock().unwrap();
+        pause_buffer.capacity = buffer;
+        pause_buffer.resuming = false;
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    fn resume(self: &Arc<Self>) {
+        self.pause_buffer.lock().unwrap().resuming = true;
+        // samples are delivered without holding the lock, as callbacks may pause the subscriber
+        // again, which takes effect after the current batch; the samples received meanwhile are
+        // buffered and delivered in the next batch, preserving order
+        loop {
+            let mut buffer = self.pause_buffer.lock().unwrap();
+            if !buffer.resuming {
+                return;
+            }
+            let samples = std::mem::take(&mut buffer.samples);
+            if samples.is_empty() {
+                self.paused.store(false, Ordering::SeqCst);
+                return;
+            }
+            drop(buffer);
+            for sample in samples {
+                self.deliver(sample);
+            }
+        }
+    }
+}
+
+/// Handler of a [`Subscriber`], carrying along the subscriber settings which cannot be
+/// retrieved from zenoh afterwards.
+pub(crate) struct SubscriberHandler {
+    pub(crate) handler: HandlerImpl<Sample>,
+    pub(crate) allowed_origin: zenoh::sample::Locality,
+    pub(crate) state: Arc<SubscriberState>,
+}
+
+impl fmt::Debug for SubscriberHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.handler.fmt(f)
+    }
+}
+
+pub(crate) fn subscriber_handler(
+    py: Python,
+    obj: Option<&Bound<PyAny>>,
+    allowed_origin: Option<Locality>,
+) -> PyResult<(
+    impl IntoHandler<zenoh::sample::Sample, Handler = SubscriberHandler>,
+    bool,
+)> {
+    let (handler, background) = into_handler(py, obj, None)?;
+    let (callback, handler) = handler.into_handler();
+    let handler = rust_subscriber_handler(
+        callback,
+        handler,
+        allowed_origin,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
+    Ok((handler, background))
+}
+
+/// Same as [`subscriber_handler`], but with a Rust callback; `handler` is only exposed
+/// as [`Subscriber::handler`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn rust_subscriber_handler(
+    callback: RustCallback<zenoh::sample::Sample>,
+    handler: HandlerImpl<Sample>,
+    allowed_origin: Option<Locality>,
+    limits: Option<SubscriberLimits>,
+    integrity: Option<IntegrityCheck>,
+    shard: Option<Shard>,
+    callback_stats: Option<Arc<CallbackStats>>,
+    self_filter: Option<SelfFilter>,
+    projection: Option<Projection>,
+) -> impl IntoHandler<zenoh::sample::Sample, Handler = SubscriberHandler> {
+    let state = SubscriberState::new(
+        callback,
+        limits,
+        integrity,
+        shard,
+        callback_stats,
+        self_filter,
+        projection,
+    );
+    let state = Arc::new(state);
+    state.start_timer();
+    let handler = SubscriberHandler {
+        handler,
+        allowed_origin: allowed_origin.map_or_else(Default::default, Into::into),
+        state: state.clone(),
+    };
+    let guard = CallbackGuard(state);
+    let callback = RustCallback::new(Arc::new(move |sample| guard.0.on_sample(sample)));
+    (callback, handler)
+}
+
+/// Drops the subscriber callback along with the zenoh one, e.g. when the session is closed,
+/// as the state is kept alive by the handler; it closes the channel of indirect callbacks.
+struct CallbackGuard(Arc<SubscriberState>);
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        // dropped after releasing the lock
+        let callback = self.0.callback.write().unwrap().take();
+        drop(callback);
+    }
+}
+
+// Not using `option_wrapper!`, as the subscriber also records whether it has been undeclared
+// because its limits were reached.
+#[pyclass(weakref)]
+pub(crate) struct Subscriber(
+    pub(crate) Option<zenoh::pubsub::Subscriber<SubscriberHandler>>,
+    pub(crate) bool,
 );
 
+impl Subscriber {
+    fn get_ref(&self) -> PyResult<&zenoh::pubsub::Subscriber<SubscriberHandler>> {
+        self.0
+            .as_ref()
+            .ok_or_else(|| zerror!("Undeclared subscriber"))
+    }
+
+    /// Registers the subscriber to be undeclared when its limits are reached, if any.
+    pub(crate) fn register_limits(this: &Bound<Self>, background: bool) -> PyResult<()> {
+        let py = this.py();
+        let state = this.borrow().get_ref()?.handler().state.clone();
+        if state.limits.is_none() {
+            return Ok(());
+        }
+        // background subscribers are kept alive until completion
+        let owner = if background {
+            this.clone().into_any()
+        } else {
+            import!(py, weakref.ref).call1((this,))?
+        };
+        state.set_owner(py, owner.unbind());
+        Ok(())
+    }
+}
+
+impl From<zenoh::pubsub::Subscriber<SubscriberHandler>> for Subscriber {
+    fn from(value: zenoh::pubsub::Subscriber<SubscriberHandler>) -> Self {
+        Self(Some(value), false)
+    }
+}
+
+impl Drop for Subscriber {
+    fn drop(&mut self) {
+        Python::with_gil(|gil| gil.allow_threads(|| drop(self.0.take())));
+    }
+}
+
 #[pymethods]
 impl Subscriber {
     #[classmethod]
@@ -174,7 +797,9 @@ impl Subscriber {
         _args: &Bound<PyTuple>,
         _kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<PyObject> {
-        self.undeclare(py)?;
+        if self.0.is_some() {
+            self.undeclare(py)?;
+        }
         Ok(py.None())
     }
 
@@ -190,19 +815,141 @@ impl Subscriber {
 
     #[getter]
     fn handler(&self, py: Python) -> PyResult<PyObject> {
-        self.get_ref()?.handler().into_py_any(py)
+        (&self.get_ref()?.handler().handler).into_py_any(py)
+    }
+
+    #[getter]
+    fn allowed_origin(&self) -> PyResult<Locality> {
+        Ok(self.get_ref()?.handler().allowed_origin.into())
+    }
+
+    #[getter]
+    fn paused(&self) -> PyResult<bool> {
+        Ok(self
+            .get_ref()?
+            .handler()
+            .state
+            .paused
+            .load(Ordering::SeqCst))
+    }
+
+    #[getter]
+    fn dropped_while_paused(&self) -> PyResult<usize> {
+        let state = &self.get_ref()?.handler().state;
+        Ok(state.dropped_while_paused.load(Ordering::Relaxed))
+    }
+
+    #[getter]
+    fn corrupt_count(&self) -> PyResult<usize> {
+        let integrity = &self.get_ref()?.handler().state.integrity;
+        Ok(integrity.as_ref().map_or(0, IntegrityCheck::corrupt_count))
+    }
+
+    #[getter]
+    fn unverified_count(&self) -> PyResult<usize> {
+        let integrity = &self.get_ref()?.handler().state.integrity;
+        Ok(integrity
+            .as_ref()
+            .map_or(0, IntegrityCheck::unverified_count))
+    }
+
+    #[getter]
+    fn ignored_self_count(&self) -> PyResult<usize> {
+        let self_filter = &self.get_ref()?.handler().state.self_filter;
+        Ok(self_filter
+            .as_ref()
+            .map_or(0, |f| f.ignored.load(Ordering::Relaxed)))
+    }
+
+    #[getter]
+    fn unknown_origin_count(&self) -> PyResult<usize> {
+        let self_filter = &self.get_ref()?.handler().state.self_filter;
+        Ok(self_filter
+            .as_ref()
+            .map_or(0, |f| f.unknown_origin.load(Ordering::Relaxed)))
+    }
+
+    #[getter]
+    fn unprojected_count(&self) -> PyResult<usize> {
+        let projection = &self.get_ref()?.handler().state.projection;
+        Ok(projection.as_ref().map_or(0, Projection::unprojected_count))
+    }
+
+    fn callback_stats<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let stats = &self.get_ref()?.handler().state.callback_stats;
+        stats.as_ref().map(|stats| stats.to_dict(py)).transpose()
+    }
+
+    fn gap_report<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.get_ref()?.handler().state.gaps.report(py)
+    }
+
+    fn reset_gap_report(&self) -> PyResult<()> {
+        self.get_ref()?.handler().state.gaps.reset();
+        Ok(())
+    }
+
+    #[pyo3(signature = (*, pause_buffer = 0))]
+    fn pause(&self, pause_buffer: usize) -> PyResult<()> {
+        self.get_ref()?.handler().state.pause(pause_buffer);
+        Ok(())
+    }
+
+    fn resume(&self, py: Python) -> PyResult<()> {
+        let state = &self.get_ref()?.handler().state;
+        // flushing may block on a full channel, or on callbacks taking the GIL
+        py.allow_threads(|| state.resume());
+        Ok(())
     }
 
     fn try_recv(&self, py: Python) -> PyResult<PyObject> {
-        self.get_ref()?.handler().try_recv(py)
+        self.get_ref()?.handler().handler.try_recv(py)
+    }
+
+    #[pyo3(signature = (timeout = None))]
+    fn recv(this: &Bound<Self>, timeout: Option<&Bound<PyAny>>) -> PyResult<PyObject> {
+        // the subscriber is not borrowed while blocking, so it can be undeclared meanwhile,
+        // which unblocks the call
+        let handler = this.borrow().handler(this.py())?;
+        let handler = handler.bind(this.py());
+        let sample = match timeout {
+            Some(timeout) if !timeout.is_none() => handler.call_method1("recv", (timeout,))?,
+            _ => handler.call_method0("recv")?,
+        };
+        Ok(sample.unbind())
     }
 
-    fn recv(&self, py: Python) -> PyResult<PyObject> {
-        self.get_ref()?.handler().recv(py)
+    fn set_callback(&self, callback: &Bound<PyAny>) -> PyResult<()> {
+        self.get_ref()?.handler().handler.set_callback(callback)
+    }
+
+    #[getter]
+    fn closed(&self) -> bool {
+        let Some(subscriber) = &self.0 else {
+            return true;
+        };
+        let limits = &subscriber.handler().state.limits;
+        limits
+            .as_ref()
+            .is_some_and(|l| l.completed.load(Ordering::SeqCst))
     }
 
     fn undeclare(&mut self, py: Python) -> PyResult<()> {
-        wait(py, self.take()?.undeclare())
+        // undeclaring is a no-op once the subscriber limits are reached
+        if self.1 && self.0.is_none() {
+            return Ok(());
+        }
+        let subscriber = self
+            .0
+            .take()
+            .ok_or_else(|| zerror!("Undeclared subscriber"))?;
+        if let Some(limits) = &subscriber.handler().state.limits {
+            // `on_complete` is not called when undeclared before the limits are reached
+            limits.completed.store(true, Ordering::SeqCst);
+            limits.stop_timer();
+            limits.owner.lock().unwrap().take();
+        }
+        wait(py, subscriber.undeclare())
     }
 
     fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
@@ -11,27 +11,55 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    time::{Duration, Instant},
+};
 
+use base64::{
+    engine::general_purpose::{STANDARD as BASE64, URL_SAFE_NO_PAD as BASE64_URL},
+    Engine,
+};
 use pyo3::{
+    exceptions::{PyIndexError, PyTypeError, PyValueError},
     prelude::*,
-    types::{PyDict, PyIterator, PyList, PyTuple, PyType},
+    sync::GILOnceCell,
+    types::{PyBytes, PyCFunction, PyDateTime, PyDict, PyIterator, PyList, PyTuple, PyType},
     IntoPyObjectExt,
 };
+use zenoh::{
+    handlers::{Callback as RustCallback, CallbackParameter, IntoHandler},
+    Wait,
+};
 
 use crate::{
     bytes::{Encoding, ZBytes},
     cancellation::CancellationToken,
-    handlers::{into_handler, HandlerImpl},
-    key_expr::KeyExpr,
-    macros::{build, downcast_or_new, enum_mapper, import, option_wrapper, wrapper},
+    compression,
+    config::ZenohId,
+    debug::{self, PendingKey},
+    error::{new_zerror_with_code, ErrorCode},
+    handlers::{
+        in_python_callback, into_handler, log_error, map_callback, HandlerImpl,
+        CHECK_SIGNALS_INTERVAL,
+    },
+    key_expr::{invalid_key_expr, KeyExpr},
+    macros::{build, downcast_or_new, enum_mapper, import, option_wrapper, wrapper, zerror},
     matching::{MatchingListener, MatchingStatus},
+    projection::{requested_paths, Projection},
     qos::{CongestionControl, Priority},
-    sample::SourceInfo,
-    session::EntityGlobalId,
-    time::Timestamp,
+    sample::{Sample, SourceInfo},
+    session::{EntityGlobalId, Session},
+    time::{datetime_to_rfc3339, Timestamp},
     timestamp_stack::{TimestampInstrumentation, TimestampStack},
-    utils::{generic, wait, IntoPyResult, IntoPython, IntoRust, MapInto},
+    transcode::transcode,
+    utils::{duration, generic, wait, IntoPyResult, IntoPython, IntoRust, MapInto},
+    validation::validate_payload,
+    ZError,
 };
 
 enum_mapper!(zenoh::query::QueryTarget: u8 {
@@ -93,7 +121,157 @@ impl QueryConsolidation {
     }
 }
 
-option_wrapper!(zenoh::query::Query, "Dropped query");
+/// Defaults of the replies of a queryable, set with `Session.declare_queryable`, unless
+/// overridden by the reply call.
+#[derive(Default)]
+pub(crate) struct ReplyDefaults {
+    pub(crate) encoding: Option<Encoding>,
+    pub(crate) express: Option<bool>,
+}
+
+/// A query passed to the handler of a queryable, along with its reply defaults.
+pub(crate) struct DefaultedQuery(zenoh::query::Query, Arc<ReplyDefaults>);
+
+impl CallbackParameter for DefaultedQuery {
+    type Message<'a> = Self;
+
+    fn from_message(msg: Self::Message<'_>) -> Self {
+        msg
+    }
+}
+
+impl IntoPython for DefaultedQuery {
+    type Into = Query;
+
+    fn into_python(self) -> Self::Into {
+        Query(
+            Some(self.0),
+            self.1,
+            AtomicUsize::default(),
+            GILOnceCell::new(),
+        )
+    }
+}
+
+/// Wraps the callback of a queryable handler, so that its queries carry the reply defaults.
+pub(crate) fn defaulted_callback(
+    callback: RustCallback<DefaultedQuery>,
+    defaults: Arc<ReplyDefaults>,
+) -> RustCallback<zenoh::query::Query> {
+    RustCallback::new(Arc::new(move |query| {
+        callback.call(DefaultedQuery(query, defaults.clone()))
+    }))
+}
+
+// Not using `option_wrapper!`, as the query also holds the reply defaults of its queryable,
+// the count of replies sent, and its decoded properties.
+#[pyclass]
+pub(crate) struct Query(
+    pub(crate) Option<zenoh::query::Query>,
+    pub(crate) Arc<ReplyDefaults>,
+    AtomicUsize,
+    GILOnceCell<Py<PyDict>>,
+);
+
+impl Query {
+    fn get_ref(&self) -> PyResult<&zenoh::query::Query> {
+        self.0.as_ref().ok_or_else(|| zerror!("Dropped query"))
+    }
+
+    /// Counts the replies sent successfully.
+    fn replied(&self, result: PyResult<()>, count: usize) -> PyResult<()> {
+        if result.is_ok() {
+            self.2.fetch_add(count, Ordering::Relaxed);
+        }
+        result
+    }
+
+    /// Sends a reply, then finishes the query if the reply is `final` and has been sent.
+    ///
+    /// The query is only borrowed mutably to finish it, so replies can be sent concurrently; a
+    /// final reply fails to finish the query while another thread is replying to it.
+    fn reply_then(
+        this: &Bound<Self>,
+        r#final: bool,
+        reply: impl FnOnce(&Self) -> PyResult<()>,
+    ) -> PyResult<()> {
+        let query = this
+            .try_borrow()
+            .map_err(|_| zerror!("Query is being finished"))?;
+        reply(&query)?;
+        drop(query);
+        if r#final {
+            this.try_borrow_mut()
+                .map_err(|_| zerror!("Cannot finish query while another reply is being sent"))?
+                .drop();
+        }
+        Ok(())
+    }
+}
+
+impl Query {
+    /// Replies with a value returned by an `auto_reply` callback, see [`AUTO_REPLY_PROTOCOL`].
+    fn auto_reply(this: &Bound<Self>, reply: &Bound<PyAny>) -> PyResult<()> {
+        if let Ok(sample) = reply.downcast::<Sample>() {
+            return Self::reply_sample(this, &sample.borrow(), false);
+        }
+        let protocol_error = || PyTypeError::new_err(AUTO_REPLY_PROTOCOL);
+        let (key_expr, payload) = match reply.downcast::<PyTuple>() {
+            Ok(tuple) if tuple.len() == 2 => {
+                let key_expr =
+                    KeyExpr::from_py(&tuple.get_item(0)?).map_err(|_| protocol_error())?;
+                (key_expr, tuple.get_item(1)?)
+            }
+            Ok(_) => return Err(protocol_error()),
+            Err(_) => (
+                this.borrow()
+                    .get_ref()?
+                    .key_expr()
+                    .clone()
+                    .into_owned()
+                    .into(),
+                reply.clone(),
+            ),
+        };
+        let payload = ZBytes::from_py(&payload).map_err(|_| protocol_error())?;
+        Self::reply(
+            this, key_expr, payload, None, None, None, None, None, None, None, false,
+        )
+    }
+}
+
+impl From<zenoh::query::Query> for Query {
+    fn from(value: zenoh::query::Query) -> Self {
+        Self(
+            Some(value),
+            Arc::default(),
+            AtomicUsize::default(),
+            GILOnceCell::new(),
+        )
+    }
+}
+
+impl IntoPython for zenoh::query::Query {
+    type Into = Query;
+
+    fn into_python(self) -> Self::Into {
+        self.into()
+    }
+}
+
+impl IntoPython for Query {
+    type Into = Query;
+
+    fn into_python(self) -> Self::Into {
+        self
+    }
+}
+
+impl Drop for Query {
+    fn drop(&mut self) {
+        Python::with_gil(|gil| gil.allow_threads(|| drop(self.0.take())));
+    }
+}
 
 #[pymethods]
 impl Query {
@@ -127,6 +305,34 @@ impl Query {
         Ok(self.get_ref()?.parameters().clone().into_owned().into())
     }
 
+    #[pyo3(signature = (key, default = None, *, as_bytes = false))]
+    fn parameter(
+        &self,
+        py: Python,
+        key: &str,
+        default: Option<PyObject>,
+        as_bytes: bool,
+    ) -> PyResult<Option<PyObject>> {
+        match self.get_ref()?.parameters().get(key) {
+            Some(value) => decode_parameter(py, value, as_bytes).map(Some),
+            None => Ok(default),
+        }
+    }
+
+    /// The parameters, decoded like [`Query::parameter`] once per query.
+    #[getter]
+    fn properties<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let properties = self.3.get_or_try_init(py, || {
+            let properties = PyDict::new(py);
+            for (key, value) in self.get_ref()?.parameters().iter() {
+                properties.set_item(key, decode_parameter(py, value, false)?)?;
+            }
+            PyResult::Ok(properties.unbind())
+        })?;
+        // copied, as the dict is mutable
+        properties.bind(py).copy()
+    }
+
     #[getter]
     fn payload(&self) -> PyResult<Option<ZBytes>> {
         Ok(self.get_ref()?.payload().cloned().map_into())
@@ -138,7 +344,12 @@ impl Query {
     }
 
     #[getter]
-    fn attachment(&self) -> PyResult<Option<ZBytes>> {
+    fn attachment<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.raw_attachment()?.map(|a| a.to_dict(py)).transpose()
+    }
+
+    #[getter]
+    fn raw_attachment(&self) -> PyResult<Option<ZBytes>> {
         Ok(self.get_ref()?.attachment().cloned().map_into())
     }
 
@@ -146,84 +357,206 @@ impl Query {
         Ok(self.get_ref()?.accepts_replies().into())
     }
 
+    fn paging(&self) -> PyResult<Option<(usize, usize)>> {
+        paging(self.get_ref()?.parameters())
+    }
+
+    // the target and the consolidation are not part of the queries received by queryables
+    #[getter]
+    fn target(&self) -> PyResult<QueryTarget> {
+        self.get_ref()?;
+        Ok(QueryTarget::DEFAULT)
+    }
+
+    #[getter]
+    fn consolidation(&self) -> PyResult<ConsolidationMode> {
+        self.get_ref()?;
+        Ok(ConsolidationMode::DEFAULT)
+    }
+
+    /// The field paths projected by the querier, see `Session::get_handle`.
+    #[getter]
+    fn projection(&self) -> PyResult<Option<Vec<String>>> {
+        Ok(requested_paths(self.get_ref()?.parameters()))
+    }
+
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (key_expr, payload, *, encoding = None, congestion_control = None, priority = None, express = None, attachment = None, timestamp = None))]
+    #[pyo3(signature = (key_expr, payload, *, encoding = None, congestion_control = None, priority = None, express = None, attachment = None, timestamp = None, validate = None, r#final = false))]
     fn reply(
-        &self,
-        py: Python,
+        this: &Bound<Self>,
         #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
         #[pyo3(from_py_with = ZBytes::from_py)] payload: ZBytes,
         #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
         congestion_control: Option<CongestionControl>,
         priority: Option<Priority>,
         express: Option<bool>,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         timestamp: Option<Timestamp>,
+        validate: Option<bool>,
+        r#final: bool,
     ) -> PyResult<()> {
-        if congestion_control.is_some() {
-            import!(py, warnings.warn).call1((
-                "congestion_control in Query.reply is deprecated, it will be ignored",
-                py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
-            ))?;
-        }
-        if priority.is_some() {
-            import!(py, warnings.warn).call1((
-                "priority in Query.reply is deprecated, it will be ignored",
-                py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
-            ))?;
-        }
-        let build = build!(
-            self.get_ref()?.reply(key_expr, payload),
-            encoding,
-            express,
-            attachment,
-            timestamp,
-        );
-        wait(py, build)
+        let py = this.py();
+        Self::reply_then(this, r#final, |query| {
+            if congestion_control.is_some() {
+                import!(py, warnings.warn).call1((
+                    "congestion_control in Query.reply is deprecated, it will be ignored",
+                    py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
+                ))?;
+            }
+            if priority.is_some() {
+                import!(py, warnings.warn).call1((
+                    "priority in Query.reply is deprecated, it will be ignored",
+                    py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
+                ))?;
+            }
+            let encoding = encoding.or_else(|| query.1.encoding.clone());
+            let express = express.or(query.1.express);
+            if let Some(encoding) = &encoding {
+                validate_payload(validate, &payload, &encoding.0)?;
+            }
+            let build = build!(
+                query.get_ref()?.reply(key_expr, payload),
+                encoding,
+                express,
+                attachment,
+                timestamp,
+            );
+            query.replied(wait(py, build), 1)
+        })
+    }
+
+    #[pyo3(signature = (sample, *, r#final = false))]
+    fn reply_sample(this: &Bound<Self>, sample: &Sample, r#final: bool) -> PyResult<()> {
+        Self::reply_then(this, r#final, |query| {
+            let build = query.get_ref()?.reply_sample(sample.0.clone());
+            query.replied(wait(this.py(), build), 1)
+        })
+    }
+
+    #[pyo3(signature = (sample, *, strict = false))]
+    fn reply_negotiated(&self, py: Python, sample: &Sample, strict: bool) -> PyResult<()> {
+        let query = self.get_ref()?;
+        let accepted = query.parameters().values(QUERY_ACCEPT);
+        let accepted = accepted
+            .map(zenoh::bytes::Encoding::from)
+            .collect::<Vec<_>>();
+        let encoding = sample.0.encoding();
+        if accepted.is_empty() || accepted.contains(encoding) {
+            return self.replied(wait(py, query.reply_sample(sample.0.clone())), 1);
+        }
+        let payload = sample.0.payload().to_bytes();
+        let mut errors = Vec::new();
+        for target in &accepted {
+            match transcode(&payload, encoding, target) {
+                Some(Ok(payload)) => {
+                    let builder = query.reply(sample.0.key_expr().clone(), payload);
+                    let builder = builder
+                        .encoding(target.clone())
+                        .timestamp(sample.0.timestamp().cloned())
+                        .attachment(sample.0.attachment().cloned());
+                    return self.replied(wait(py, builder), 1);
+                }
+                Some(Err(err)) => errors.push(format!("to '{target}': {err}")),
+                None => {}
+            }
+        }
+        if strict {
+            let errors = errors
+                .iter()
+                .map(|err| format!(", {err}"))
+                .collect::<String>();
+            // the prefix id tells apart the encodings unknown to this binding
+            let id = encoding.id();
+            return Err(PyValueError::new_err(format!(
+                "cannot transcode '{encoding}' (prefix id {id}) to an accepted encoding{errors}"
+            )));
+        }
+        self.replied(wait(py, query.reply_sample(sample.0.clone())), 1)
     }
 
-    #[pyo3(signature = (payload, *, encoding = None))]
+    #[pyo3(signature = (payload, *, encoding = None, r#final = false))]
     fn reply_err(
-        &self,
-        py: Python,
+        this: &Bound<Self>,
         #[pyo3(from_py_with = ZBytes::from_py)] payload: ZBytes,
         #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
+        r#final: bool,
     ) -> PyResult<()> {
-        let build = build!(self.get_ref()?.reply_err(payload), encoding);
-        wait(py, build)
+        Self::reply_then(this, r#final, |query| {
+            let build = build!(query.get_ref()?.reply_err(payload), encoding);
+            query.replied(wait(this.py(), build), 1)
+        })
     }
 
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (key_expr, *, congestion_control = None, priority = None, express = None, attachment = None, timestamp = None))]
+    #[pyo3(signature = (key_expr, *, congestion_control = None, priority = None, express = None, attachment = None, timestamp = None, r#final = false))]
     fn reply_del(
-        &self,
-        py: Python,
+        this: &Bound<Self>,
         #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
         congestion_control: Option<CongestionControl>,
         priority: Option<Priority>,
         express: Option<bool>,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         timestamp: Option<Timestamp>,
+        r#final: bool,
     ) -> PyResult<()> {
-        if congestion_control.is_some() {
-            import!(py, warnings.warn).call1((
-                "congestion_control in Query.reply_del is deprecated, it will be ignored",
-                py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
-            ))?;
-        }
-        if priority.is_some() {
-            import!(py, warnings.warn).call1((
-                "priority in Query.reply_del is deprecated, it will be ignored",
-                py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
-            ))?;
-        }
-        let build = build!(
-            self.get_ref()?.reply_del(key_expr),
-            express,
-            attachment,
-            timestamp,
-        );
-        wait(py, build)
+        let py = this.py();
+        Self::reply_then(this, r#final, |query| {
+            if congestion_control.is_some() {
+                import!(py, warnings.warn).call1((
+                    "congestion_control in Query.reply_del is deprecated, it will be ignored",
+                    py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
+                ))?;
+            }
+            if priority.is_some() {
+                import!(py, warnings.warn).call1((
+                    "priority in Query.reply_del is deprecated, it will be ignored",
+                    py.get_type::<pyo3::exceptions::PyDeprecationWarning>(),
+                ))?;
+            }
+            let express = express.or(query.1.express);
+            let build = build!(
+                query.get_ref()?.reply_del(key_expr),
+                express,
+                attachment,
+                timestamp,
+            );
+            query.replied(wait(py, build), 1)
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (session, selector, *, key_remap = None, target = None, consolidation = None, timeout = None))]
+    fn forward(
+        &self,
+        py: Python,
+        session: &Session,
+        #[pyo3(from_py_with = Selector::from_py)] selector: Selector,
+        key_remap: Option<(String, String)>,
+        target: Option<QueryTarget>,
+        #[pyo3(from_py_with = QueryConsolidation::from_py_opt)] consolidation: Option<
+            QueryConsolidation,
+        >,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<usize> {
+        let query = self.get_ref()?;
+        let builder = build!(session.0.get(selector), target, consolidation, timeout);
+        py.allow_threads(|| {
+            let replies = builder.wait().into_pyres()?;
+            let mut relayed = 0;
+            while let Ok(reply) = replies.recv() {
+                match reply.into_result() {
+                    Ok(sample) => relay_sample(query, sample, key_remap.as_ref())?,
+                    Err(err) => query
+                        .reply_err(err.payload().clone())
+                        .encoding(err.encoding().clone())
+                        .wait()
+                        .into_pyres()?,
+                }
+                relayed += 1;
+                self.2.fetch_add(1, Ordering::Relaxed);
+            }
+            Ok(relayed)
+        })
     }
 
     #[getter]
@@ -236,10 +569,38 @@ impl Query {
         Ok(self.get_ref()?.timestamp_stack().cloned().map_into())
     }
 
+    /// The querier zid, as declared in the query source info, which zenoh doesn't authenticate.
+    #[getter]
+    fn origin_zid(&self) -> PyResult<Option<ZenohId>> {
+        let source_info = self.get_ref()?.source_info();
+        Ok(source_info.map(|info| info.source_id().zid().into()))
+    }
+
+    #[getter]
+    fn raw_selector(&self) -> PyResult<String> {
+        Ok(self.get_ref()?.selector().to_string())
+    }
+
+    #[getter]
+    fn reply_count(&self) -> usize {
+        self.2.load(Ordering::Relaxed)
+    }
+
     fn drop(&mut self) {
         Python::with_gil(|gil| gil.allow_threads(|| drop(self.0.take())));
     }
 
+    /// Ends the reply stream and returns the number of replies sent.
+    fn finish(&mut self) -> usize {
+        self.drop();
+        self.2.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn is_finished(&self) -> bool {
+        self.0.is_none()
+    }
+
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!("{:?}", self.get_ref()?))
     }
@@ -249,6 +610,58 @@ impl Query {
     }
 }
 
+/// Replies to `query` with a sample received by `Query::forward`.
+fn relay_sample(
+    query: &zenoh::query::Query,
+    sample: zenoh::sample::Sample,
+    key_remap: Option<&(String, String)>,
+) -> PyResult<()> {
+    let key_expr = match key_remap {
+        Some((prefix, replacement)) => remap_key_expr(sample.key_expr(), prefix, replacement)?,
+        None => sample.key_expr().clone(),
+    };
+    match sample.kind() {
+        zenoh::sample::SampleKind::Put => {
+            let mut builder = query
+                .reply(key_expr, sample.payload().clone())
+                .encoding(sample.encoding().clone());
+            if let Some(attachment) = sample.attachment() {
+                builder = builder.attachment(attachment.clone());
+            }
+            if let Some(timestamp) = sample.timestamp() {
+                builder = builder.timestamp(*timestamp);
+            }
+            builder.wait().into_pyres()
+        }
+        zenoh::sample::SampleKind::Delete => {
+            let mut builder = query.reply_del(key_expr);
+            if let Some(attachment) = sample.attachment() {
+                builder = builder.attachment(attachment.clone());
+            }
+            if let Some(timestamp) = sample.timestamp() {
+                builder = builder.timestamp(*timestamp);
+            }
+            builder.wait().into_pyres()
+        }
+    }
+}
+
+/// Replaces the leading chunks `prefix` of `key_expr` by `replacement`.
+///
+/// Key expressions not starting with `prefix` are returned unchanged.
+fn remap_key_expr(
+    key_expr: &zenoh::key_expr::KeyExpr<'static>,
+    prefix: &str,
+    replacement: &str,
+) -> PyResult<zenoh::key_expr::KeyExpr<'static>> {
+    let remapped = match key_expr.as_str().strip_prefix(prefix) {
+        Some("") => replacement.to_string(),
+        Some(suffix) if suffix.starts_with('/') => format!("{replacement}{suffix}"),
+        _ => return Ok(key_expr.clone()),
+    };
+    zenoh::key_expr::KeyExpr::try_from(remapped).map_err(invalid_key_expr)
+}
+
 wrapper!(zenoh::query::Reply);
 
 #[pymethods]
@@ -277,6 +690,11 @@ impl Reply {
         }
     }
 
+    #[getter]
+    fn is_ok(&self) -> bool {
+        self.0.result().is_ok()
+    }
+
     #[getter]
     fn replier_id(&self) -> Option<EntityGlobalId> {
         self.0.replier_id().map_into()
@@ -287,6 +705,249 @@ impl Reply {
     }
 }
 
+/// The results of the replies of an iterable of [`Reply`], successful if `ok`, else the errors,
+/// consuming it if it is an iterator.
+pub(crate) fn reply_results(replies: &Bound<PyAny>, ok: bool) -> PyResult<Vec<PyObject>> {
+    let py = replies.py();
+    let mut results = Vec::new();
+    for reply in replies.try_iter()? {
+        let reply = reply?;
+        let reply = reply.downcast::<Reply>()?.borrow();
+        let result = if ok { reply.ok(py) } else { reply.err(py) };
+        if !result.is_none(py) {
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+/// The first successful sample of an iterable of [`Reply`], consuming it up to that sample if
+/// it is an iterator.
+pub(crate) fn first_ok(replies: &Bound<PyAny>) -> PyResult<Option<PyObject>> {
+    let py = replies.py();
+    for reply in replies.try_iter()? {
+        let sample = reply?.downcast::<Reply>()?.borrow().ok(py);
+        if !sample.is_none(py) {
+            return Ok(Some(sample));
+        }
+    }
+    Ok(None)
+}
+
+/// Replies of a get fully received, e.g. from the cache, with list semantics.
+#[pyclass(sequence)]
+pub(crate) struct Replies(Vec<Py<Reply>>);
+
+impl Replies {
+    pub(crate) fn new(
+        py: Python,
+        replies: impl IntoIterator<Item = zenoh::query::Reply>,
+    ) -> PyResult<Self> {
+        let replies = replies
+            .into_iter()
+            .map(|reply| Py::new(py, Reply::from(reply)));
+        Ok(Self(replies.collect::<PyResult<_>>()?))
+    }
+}
+
+#[pymethods]
+impl Replies {
+    fn ok(this: &Bound<Self>) -> PyResult<Vec<PyObject>> {
+        reply_results(this.as_any(), true)
+    }
+
+    fn errors(this: &Bound<Self>) -> PyResult<Vec<PyObject>> {
+        reply_results(this.as_any(), false)
+    }
+
+    fn first(this: &Bound<Self>) -> PyResult<Option<PyObject>> {
+        first_ok(this.as_any())
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    fn __getitem__(&self, py: Python, index: isize) -> PyResult<Py<Reply>> {
+        let len = self.0.len() as isize;
+        let index = if index < 0 { index + len } else { index };
+        if !(0..len).contains(&index) {
+            return Err(PyIndexError::new_err("reply index out of range"));
+        }
+        Ok(self.0[index as usize].clone_ref(py))
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyList::new(py, &self.0)?.try_iter()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Replies(len={})", self.0.len())
+    }
+}
+
+/// Converts a decompressed reply payload according to `replies_to_columns` `payload_as`.
+fn convert_payload(py: Python, payload: ZBytes, payload_as: &str) -> PyResult<PyObject> {
+    if payload_as == "bytes" {
+        return Ok(payload.to_bytes(py)?.into_any().unbind());
+    }
+    let invalid = |err: &dyn std::fmt::Display| PyValueError::new_err(err.to_string());
+    let bytes = payload.0.to_bytes();
+    let text = std::str::from_utf8(&bytes).map_err(|err| invalid(&err))?;
+    match payload_as {
+        "float" => text
+            .trim()
+            .parse::<f64>()
+            .map_err(|err| invalid(&err))?
+            .into_py_any(py),
+        "int" => text
+            .trim()
+            .parse::<i64>()
+            .map_err(|err| invalid(&err))?
+            .into_py_any(py),
+        _ => Ok(import!(py, json.loads).call1((text,))?.unbind()),
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (replies, payload_as = "bytes"))]
+pub(crate) fn replies_to_columns<'py>(
+    py: Python<'py>,
+    replies: &Bound<'py, PyAny>,
+    payload_as: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    if !["bytes", "float", "int", "json"].contains(&payload_as) {
+        return Err(PyValueError::new_err(
+            "payload_as must be 'float', 'int', 'bytes' or 'json'",
+        ));
+    }
+    let keys = PyList::empty(py);
+    let times = PyList::empty(py);
+    let payloads = PyList::empty(py);
+    let errors = PyList::empty(py);
+    for (index, reply) in replies.try_iter()?.enumerate() {
+        let reply = reply?;
+        let reply = reply.downcast::<Reply>()?.borrow();
+        let sample = match reply.0.result() {
+            Ok(sample) => sample,
+            Err(err) => {
+                errors.append(err.clone().into_pyobject(py))?;
+                continue;
+            }
+        };
+        let payload = compression::decompress(py, sample.payload(), sample.encoding())?;
+        let payload = convert_payload(py, payload, payload_as).map_err(|err| {
+            PyValueError::new_err(format!(
+                "reply {index}: invalid {payload_as} payload: {err}"
+            ))
+        })?;
+        keys.append(sample.key_expr().as_str())?;
+        times.append(sample.timestamp().map(|ts| ts.get_time().to_system_time()))?;
+        payloads.append(payload)?;
+    }
+    let columns = PyDict::new(py);
+    columns.set_item("key", keys)?;
+    columns.set_item("time", times)?;
+    columns.set_item("payload", payloads)?;
+    columns.set_item("errors", errors)?;
+    Ok(columns)
+}
+
+/// Serialization of the replies streamed by `Session.get_to`.
+#[derive(Clone, Copy)]
+pub(crate) enum ReplyFormat {
+    /// One JSON object per line, the error replies having an `error` field.
+    Ndjson,
+    /// The payloads of the ok replies, each prefixed by its length as a little-endian u32.
+    Raw,
+}
+
+impl ReplyFormat {
+    pub(crate) fn new(format: &str) -> PyResult<Self> {
+        match format {
+            "ndjson" => Ok(Self::Ndjson),
+            "raw" => Ok(Self::Raw),
+            _ => Err(PyValueError::new_err("format must be 'ndjson' or 'raw'")),
+        }
+    }
+
+    /// Appends the serialized reply to `out`, returning whether it has been written.
+    fn serialize(self, reply: &zenoh::query::Reply, out: &mut Vec<u8>) -> PyResult<bool> {
+        let json_payload = |payload: &zenoh::bytes::ZBytes| match payload.try_to_string() {
+            Ok(text) => serde_json::Value::from(text.as_ref()),
+            Err(_) => serde_json::json!({ "base64": BASE64.encode(payload.to_bytes()) }),
+        };
+        let record = match (self, reply.result()) {
+            (Self::Raw, Err(_)) => return Ok(false),
+            (Self::Raw, Ok(sample)) => {
+                let payload = sample.payload().to_bytes();
+                let len = u32::try_from(payload.len())
+                    .map_err(|_| PyValueError::new_err("payload too large for the raw format"))?;
+                out.extend_from_slice(&len.to_le_bytes());
+                out.extend_from_slice(&payload);
+                return Ok(true);
+            }
+            (Self::Ndjson, Err(err)) => serde_json::json!({
+                "error": json_payload(err.payload()),
+                "encoding": err.encoding().to_string(),
+            }),
+            (Self::Ndjson, Ok(sample)) => serde_json::json!({
+                "key_expr": sample.key_expr().as_str(),
+                "payload": json_payload(sample.payload()),
+                "encoding": sample.encoding().to_string(),
+                "kind": match sample.kind() {
+                    zenoh::sample::SampleKind::Put => "PUT",
+                    zenoh::sample::SampleKind::Delete => "DELETE",
+                },
+                "timestamp": sample.timestamp().map(ToString::to_string),
+                "attachment": sample.attachment().map(json_payload),
+            }),
+        };
+        serde_json::to_writer(&mut *out, &record).expect("serializing to a Vec cannot fail");
+        out.push(b'\n');
+        Ok(true)
+    }
+}
+
+/// Writes the replies to `writer` in chunks of at least `chunk_size` bytes, the last one
+/// excepted, returning the count of replies written.
+///
+/// The replies are received and serialized without the GIL, which is only acquired to call
+/// `writer.write` once per chunk.
+pub(crate) fn write_replies(
+    py: Python,
+    replies: &zenoh::handlers::FifoChannelHandler<zenoh::query::Reply>,
+    writer: &Bound<PyAny>,
+    format: ReplyFormat,
+    chunk_size: usize,
+) -> PyResult<usize> {
+    let mut chunk = Vec::with_capacity(chunk_size);
+    let mut count = 0;
+    loop {
+        let fill_chunk = || {
+            while chunk.len() < chunk_size {
+                // See `CHECK_SIGNALS_INTERVAL` doc
+                match replies.recv_timeout(CHECK_SIGNALS_INTERVAL) {
+                    Ok(Some(reply)) => count += usize::from(format.serialize(&reply, &mut chunk)?),
+                    Ok(None) => return Ok(false),
+                    // the channel is closed once the query is finalized
+                    Err(_) => return Ok(true),
+                }
+            }
+            PyResult::Ok(false)
+        };
+        let closed = py.allow_threads(fill_chunk)?;
+        if chunk.len() >= chunk_size || (closed && !chunk.is_empty()) {
+            writer.call_method1("write", (PyBytes::new(py, &chunk),))?;
+            chunk.clear();
+        }
+        if closed {
+            return Ok(count);
+        }
+        py.check_signals()?;
+    }
+}
+
 wrapper!(zenoh::query::ReplyError: Clone);
 
 #[pymethods]
@@ -306,16 +967,634 @@ impl ReplyError {
         self.0.timestamp_stack().cloned().map_into()
     }
 
+    #[pyo3(signature = (*, decompress = true))]
+    fn decode(&self, py: Python, decompress: bool) -> PyResult<ZBytes> {
+        if !decompress {
+            return Ok(self.payload());
+        }
+        compression::decompress(py, self.0.payload(), self.0.encoding())
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
 }
 
-option_wrapper!(
-    zenoh::query::Queryable<HandlerImpl<Query>>,
-    "Undeclared queryable"
+#[derive(Default)]
+pub(crate) struct GetState {
+    cancelled: Arc<AtomicBool>,
+    done: AtomicBool,
+    // notified when done, see `GetHandle::wait`
+    done_signal: (Mutex<()>, Condvar),
+    replies_received: AtomicUsize,
+    // set with `raise_on_timeout`, see `GetHandle::check_timed_out`
+    deadline: Option<Instant>,
+    timed_out: AtomicBool,
+    projection: Option<Projection>,
+}
+
+impl GetState {
+    pub(crate) fn new(deadline: Option<Instant>, projection: Option<Projection>) -> Self {
+        Self {
+            deadline,
+            projection,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn cancelled(&self) -> Arc<AtomicBool> {
+        self.cancelled.clone()
+    }
+
+    fn is_done(&self) -> bool {
+        self.done.load(Ordering::SeqCst) || self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Waits until the get is done, or `timeout` has elapsed.
+    fn wait_done(&self, timeout: Duration) {
+        let (lock, condvar) = &self.done_signal;
+        let guard = lock.lock().unwrap();
+        drop(
+            condvar
+                .wait_timeout_while(guard, timeout, |_| !self.is_done())
+                .unwrap(),
+        );
+    }
+
+    /// Wrap the reply callback to count and project the replies, and to mark the get as done
+    /// when zenoh drops the callback.
+    pub(crate) fn wrap_callback(
+        self: &Arc<Self>,
+        callback: zenoh::handlers::Callback<zenoh::query::Reply>,
+    ) -> zenoh::handlers::Callback<zenoh::query::Reply> {
+        struct DoneGuard(Arc<GetState>);
+        impl Drop for DoneGuard {
+            fn drop(&mut self) {
+                // zenoh finalizes the get at the latest once its timeout elapses
+                if self
+                    .0
+                    .deadline
+                    .is_some_and(|deadline| Instant::now() >= deadline)
+                {
+                    self.0.timed_out.store(true, Ordering::SeqCst);
+                }
+                let _lock = self.0.done_signal.0.lock().unwrap();
+                self.0.done.store(true, Ordering::SeqCst);
+                self.0.done_signal.1.notify_all();
+            }
+        }
+        let guard = DoneGuard(self.clone());
+        zenoh::handlers::Callback::new(Arc::new(move |mut reply: zenoh::query::Reply| {
+            let state = &guard.0;
+            if !state.cancelled.load(Ordering::SeqCst) {
+                state.replies_received.fetch_add(1, Ordering::Relaxed);
+                if let (Some(projection), Ok(sample)) = (&state.projection, reply.result_mut()) {
+                    *sample = projection.project(sample.clone());
+                }
+                callback.call(reply);
+            }
+        }))
+    }
+}
+
+#[pyclass]
+pub(crate) struct GetHandle {
+    handler: HandlerImpl<Reply>,
+    state: Arc<GetState>,
+    cancellation_token: zenoh::cancellation::CancellationToken,
+    selector: PyObject,
+    replies_consumed: AtomicUsize,
+}
+
+impl GetHandle {
+    pub(crate) fn new(
+        handler: HandlerImpl<Reply>,
+        state: Arc<GetState>,
+        cancellation_token: zenoh::cancellation::CancellationToken,
+        selector: PyObject,
+    ) -> Self {
+        Self {
+            handler,
+            state,
+            cancellation_token,
+            selector,
+            replies_consumed: AtomicUsize::default(),
+        }
+    }
+
+    fn check_cancelled(&self) -> PyResult<()> {
+        if self.state.cancelled.load(Ordering::SeqCst) {
+            return Err(zerror!("Cancelled get"));
+        }
+        Ok(())
+    }
+
+    /// Raises a timeout error if the get was finalized by its timeout, rather than by the
+    /// completion of the queryables, with `raise_on_timeout`.
+    fn check_timed_out(&self) -> PyResult<()> {
+        if !self.state.timed_out.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+        let received = self.state.replies_received.load(Ordering::Relaxed);
+        let msg = format!("get timed out, {received} replies received");
+        Err(new_zerror_with_code(msg, ErrorCode::Timeout))
+    }
+}
+
+#[pymethods]
+impl GetHandle {
+    #[classmethod]
+    fn __class_getitem__(cls: &Bound<PyType>, args: &Bound<PyAny>) -> PyObject {
+        generic(cls, args)
+    }
+
+    #[getter]
+    fn handler(&self, py: Python) -> PyResult<PyObject> {
+        (&self.handler).into_py_any(py)
+    }
+
+    #[getter]
+    fn replies_received(&self) -> usize {
+        self.state.replies_received.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn unprojected_count(&self) -> usize {
+        let projection = &self.state.projection;
+        projection.as_ref().map_or(0, Projection::unprojected_count)
+    }
+
+    fn is_done(&self) -> bool {
+        self.state.is_done()
+    }
+
+    #[pyo3(signature = (timeout = None))]
+    fn wait(
+        &self,
+        py: Python,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<bool> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        while !self.state.is_done() {
+            let remaining = deadline.map_or(CHECK_SIGNALS_INTERVAL, |deadline| {
+                deadline.saturating_duration_since(Instant::now())
+            });
+            if remaining.is_zero() {
+                return Ok(false);
+            }
+            // See `CHECK_SIGNALS_INTERVAL` doc
+            let timeout = remaining.min(CHECK_SIGNALS_INTERVAL);
+            py.allow_threads(|| self.state.wait_done(timeout));
+            py.check_signals()?;
+        }
+        Ok(true)
+    }
+
+    fn cancel(&self, py: Python) -> PyResult<()> {
+        self.state.cancelled.store(true, Ordering::SeqCst);
+        if in_python_callback() {
+            // cancelling waits for the running callbacks to return, so it cannot be
+            // done synchronously from a callback
+            let cancellation_token = self.cancellation_token.clone();
+            std::thread::spawn(move || cancellation_token.cancel().wait());
+            return Ok(());
+        }
+        wait(py, self.cancellation_token.cancel())
+    }
+
+    fn try_recv(&self, py: Python) -> PyResult<PyObject> {
+        self.check_cancelled()?;
+        let reply = self.handler.try_recv(py)?;
+        if !reply.is_none(py) {
+            self.replies_consumed.fetch_add(1, Ordering::Relaxed);
+        }
+        Ok(reply)
+    }
+
+    fn recv(&self, py: Python) -> PyResult<PyObject> {
+        self.check_cancelled()?;
+        let key = || PendingKey::Python(self.selector.clone_ref(py));
+        let _pending = debug::pending(py, "get", key);
+        let reply = self.handler.recv(py)?;
+        self.replies_consumed.fetch_add(1, Ordering::Relaxed);
+        Ok(reply)
+    }
+
+    fn ok(this: &Bound<Self>) -> PyResult<Vec<PyObject>> {
+        reply_results(this.as_any(), true)
+    }
+
+    fn errors(this: &Bound<Self>) -> PyResult<Vec<PyObject>> {
+        reply_results(this.as_any(), false)
+    }
+
+    fn first(this: &Bound<Self>) -> PyResult<Option<PyObject>> {
+        first_ok(this.as_any())
+    }
+
+    /// The replies received but not consumed yet, the get being possibly not done.
+    fn __length_hint__(&self) -> usize {
+        let consumed = self.replies_consumed.load(Ordering::Relaxed);
+        self.replies_received().saturating_sub(consumed)
+    }
+
+    fn __iter__(this: Py<Self>) -> Py<Self> {
+        this
+    }
+
+    fn __next__(&self, py: Python) -> PyResult<Option<PyObject>> {
+        match self.recv(py) {
+            Ok(obj) => Ok(Some(obj)),
+            // the channel is closed once the get is finalized
+            Err(err) if err.is_instance_of::<ZError>(py) => self.check_timed_out().map(|_| None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GetHandle(handler={:?}, replies_received={}, done={})",
+            self.handler,
+            self.replies_received(),
+            self.is_done()
+        )
+    }
+}
+
+/// Parameter listing the encodings accepted by the querier, separated by `|`, see
+/// `Query::reply_negotiated`.
+const QUERY_ACCEPT: &str = "_accept";
+
+/// Performs the gets of `selectors` concurrently, at most `max_concurrency` at once, without
+/// the GIL, returning the replies of each selector in order.
+pub(crate) fn get_concurrently(
+    session: &zenoh::Session,
+    selectors: Vec<zenoh::query::Selector<'static>>,
+    max_concurrency: usize,
+    target: Option<QueryTarget>,
+    consolidation: Option<QueryConsolidation>,
+    timeout: Option<Duration>,
+) -> Vec<zenoh::Result<Vec<zenoh::query::Reply>>> {
+    let workers = max_concurrency.min(selectors.len());
+    let queue = Mutex::new(selectors.into_iter().enumerate());
+    let results = Mutex::new(Vec::new());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let Some((index, selector)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let consolidation = consolidation.clone();
+                let builder = build!(session.get(selector), target, consolidation, timeout);
+                let replies = builder.wait().map(|replies| replies.iter().collect());
+                results.lock().unwrap().push((index, replies));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_unstable_by_key(|(index, _)| *index);
+    results.into_iter().map(|(_, replies)| replies).collect()
+}
+
+/// Parameters of the paging convention, set by [`PagedGet`] and parsed by `Query::paging`.
+const PAGING_OFFSET: &str = "_offset";
+const PAGING_LIMIT: &str = "_limit";
+
+/// Parses the paging parameters, if any.
+fn paging(parameters: &zenoh::query::Parameters) -> PyResult<Option<(usize, usize)>> {
+    let parse =
+        |key| {
+            let value = parameters.get(key)?;
+            Some(value.parse::<usize>().map_err(|_| {
+                PyValueError::new_err(format!("invalid paging parameter {key}={value}"))
+            }))
+        };
+    match (parse(PAGING_OFFSET), parse(PAGING_LIMIT)) {
+        (None, None) => Ok(None),
+        (Some(offset), Some(limit)) => Ok(Some((offset?, limit?))),
+        _ => Err(PyValueError::new_err(format!(
+            "paging requires both {PAGING_OFFSET} and {PAGING_LIMIT} parameters"
+        ))),
+    }
+}
+
+/// Iterator over the replies of successive paged gets, stopping after a short page.
+#[pyclass]
+pub(crate) struct PagedGet {
+    session: zenoh::Session,
+    selector: zenoh::query::Selector<'static>,
+    page_size: usize,
+    target: Option<QueryTarget>,
+    consolidation: Option<QueryConsolidation>,
+    timeout: Option<Duration>,
+    offset: usize,
+    pages: usize,
+    page: VecDeque<zenoh::query::Reply>,
+    done: bool,
+}
+
+impl PagedGet {
+    pub(crate) fn new(
+        session: zenoh::Session,
+        selector: zenoh::query::Selector<'static>,
+        page_size: usize,
+        target: Option<QueryTarget>,
+        consolidation: Option<QueryConsolidation>,
+        timeout: Option<Duration>,
+    ) -> PyResult<Self> {
+        if page_size == 0 {
+            return Err(PyValueError::new_err("page_size must be positive"));
+        }
+        Ok(Self {
+            session,
+            selector,
+            page_size,
+            target,
+            consolidation,
+            timeout,
+            offset: 0,
+            pages: 0,
+            page: VecDeque::new(),
+            done: false,
+        })
+    }
+
+    fn next_page(&mut self, py: Python) -> PyResult<()> {
+        let (key_expr, mut parameters) = self.selector.clone().split();
+        parameters.insert(PAGING_OFFSET, self.offset.to_string());
+        parameters.insert(PAGING_LIMIT, self.page_size.to_string());
+        let selector = zenoh::query::Selector::from((key_expr, parameters));
+        let (target, consolidation, timeout) =
+            (self.target, self.consolidation.clone(), self.timeout);
+        let builder = build!(self.session.get(selector), target, consolidation, timeout);
+        let page = py.allow_threads(|| {
+            let replies = builder.wait().into_pyres()?;
+            PyResult::Ok(replies.iter().collect::<VecDeque<_>>())
+        })?;
+        self.done = page.len() < self.page_size;
+        self.offset += page.len();
+        self.pages += 1;
+        self.page = page;
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl PagedGet {
+    #[getter]
+    fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    #[getter]
+    fn pages(&self) -> usize {
+        self.pages
+    }
+
+    fn __iter__(this: Py<Self>) -> Py<Self> {
+        this
+    }
+
+    fn __next__(&mut self, py: Python) -> PyResult<Option<Reply>> {
+        if self.page.is_empty() && !self.done {
+            self.next_page(py)?;
+        }
+        Ok(self.page.pop_front().map_into())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PagedGet(selector={}, page_size={}, pages={}, done={})",
+            self.selector,
+            self.page_size,
+            self.pages,
+            self.done && self.page.is_empty()
+        )
+    }
+}
+
+/// Breadth limit of the queries accepted by a queryable, either a maximum number of wildcard
+/// chunks in the key expression, or a Python predicate taking the selector.
+pub(crate) enum MaxBreadth {
+    WildcardChunks(usize),
+    Predicate(PyObject),
+}
+
+impl MaxBreadth {
+    pub(crate) fn from_py_opt(obj: &Bound<PyAny>) -> PyResult<Option<Self>> {
+        if obj.is_none() {
+            Ok(None)
+        } else if obj.is_callable() {
+            Ok(Some(Self::Predicate(obj.clone().unbind())))
+        } else if let Ok(max) = obj.extract::<usize>() {
+            Ok(Some(Self::WildcardChunks(max)))
+        } else {
+            Err(PyValueError::new_err(
+                "max_breadth must be a non-negative int or a callable",
+            ))
+        }
+    }
+
+    fn accepts(&self, query: &zenoh::query::Query) -> bool {
+        match self {
+            Self::WildcardChunks(max) => {
+                let chunks = query.key_expr().as_str().split('/');
+                chunks.filter(|chunk| chunk.contains('*')).count() <= *max
+            }
+            Self::Predicate(predicate) => Python::with_gil(|py| {
+                let selector = Selector::from(query.selector().into_owned());
+                let result = predicate.bind(py).call1((selector,));
+                match result.and_then(|accepted| accepted.is_truthy()) {
+                    Ok(accepted) => accepted,
+                    Err(err) => {
+                        log_error(py, Err(err));
+                        false
+                    }
+                }
+            }),
+        }
+    }
+
+    /// Wraps the queryable callback to answer queries too broad with an error reply, without
+    /// calling it.
+    pub(crate) fn wrap_callback(
+        self,
+        callback: RustCallback<zenoh::query::Query>,
+        rejected: Arc<AtomicUsize>,
+    ) -> RustCallback<zenoh::query::Query> {
+        RustCallback::new(Arc::new(move |query| {
+            if self.accepts(&query) {
+                return callback.call(query);
+            }
+            rejected.fetch_add(1, Ordering::Relaxed);
+            // the error reply can only fail if the session is closed
+            query.reply_err("query too broad").wait().ok();
+        }))
+    }
+}
+
+/// Parameters required by a queryable, queries without them being dropped without calling its
+/// callback, so that they are finalized without reply.
+pub(crate) struct QueryFilter(HashMap<String, String>);
+
+impl QueryFilter {
+    pub(crate) fn from_py_opt(obj: &Bound<PyAny>) -> PyResult<Option<Self>> {
+        if obj.is_none() {
+            return Ok(None);
+        }
+        let required = obj
+            .downcast::<PyDict>()?
+            .iter()
+            .map(|(k, v)| Ok((k.extract()?, parameter_value(&v)?)))
+            .collect::<PyResult<_>>()?;
+        Ok(Some(Self(required)))
+    }
+
+    fn accepts(&self, query: &zenoh::query::Query) -> bool {
+        // the parameters are decoded once for all the required ones
+        let parameters = query.parameters();
+        let parameters = parameters.iter().collect::<HashMap<_, _>>();
+        let matches = |(key, value): (&String, &String)| {
+            parameters.get(key.as_str()) == Some(&value.as_str())
+        };
+        self.0.iter().all(matches)
+    }
+
+    /// Wraps the queryable callback to drop the queries not matching the filter, without
+    /// taking the GIL.
+    pub(crate) fn wrap_callback(
+        self,
+        callback: RustCallback<zenoh::query::Query>,
+    ) -> RustCallback<zenoh::query::Query> {
+        RustCallback::new(Arc::new(move |query| {
+            if self.accepts(&query) {
+                callback.call(query);
+            }
+        }))
+    }
+}
+
+/// Callback wrapper calling the audit callable with the query, its reply count and the callback
+/// duration in seconds, once the callback completes.
+#[pyclass(frozen)]
+struct AuditedCallback {
+    callback: PyObject,
+    audit: PyObject,
+}
+
+#[pymethods]
+impl AuditedCallback {
+    fn __call__(&self, py: Python, query: Bound<Query>) -> PyResult<PyObject> {
+        let start = Instant::now();
+        let result = self.callback.call1(py, (query.clone(),));
+        let elapsed = start.elapsed().as_secs_f64();
+        let reply_count = query.borrow().reply_count();
+        // the callback error, if any, is the one raised
+        log_error(py, self.audit.call1(py, (query, reply_count, elapsed)));
+        result
+    }
+}
+
+/// Wraps a queryable callback handler with [`AuditedCallback`].
+pub(crate) fn audited_handler<'py>(
+    handler: Option<&Bound<'py, PyAny>>,
+    audit: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let Some(handler) = handler.filter(|h| h.is_callable()) else {
+        return Err(PyValueError::new_err("audit requires a callback handler"));
+    };
+    let py = handler.py();
+    map_callback(handler, |callback| {
+        let audit = audit.clone().unbind();
+        Ok(Py::new(py, AuditedCallback { callback, audit })?.into_any())
+    })
+}
+
+/// Return values accepted from the callbacks of queryables declared with `auto_reply=True`.
+const AUTO_REPLY_PROTOCOL: &str = "auto_reply callbacks must return None if they replied \
+    themselves, or a Sample, a payload, a (key_expr, payload) tuple, or a list of these, to \
+    reply with";
+
+/// Callback wrapper replying with the value returned by the wrapped callback, then finalizing
+/// the query, see [`AUTO_REPLY_PROTOCOL`].
+#[pyclass(frozen)]
+struct AutoReplyCallback(PyObject);
+
+#[pymethods]
+impl AutoReplyCallback {
+    fn __call__(&self, py: Python, query: Bound<Query>) -> PyResult<()> {
+        let returned = self.0.bind(py).call1((query.clone(),))?;
+        if returned.is_none() {
+            return Ok(());
+        }
+        let replies = match returned.downcast::<PyList>() {
+            Ok(list) => list.iter().collect(),
+            Err(_) => vec![returned],
+        };
+        for reply in &replies {
+            Query::auto_reply(&query, reply)?;
+        }
+        query.borrow_mut().drop();
+        Ok(())
+    }
+}
+
+/// Wraps a queryable callback handler with [`AutoReplyCallback`].
+pub(crate) fn auto_reply_handler<'py>(
+    handler: Option<&Bound<'py, PyAny>>,
+) -> PyResult<Bound<'py, PyAny>> {
+    let Some(handler) = handler.filter(|h| h.is_callable()) else {
+        return Err(PyValueError::new_err(
+            "auto_reply requires a callback handler",
+        ));
+    };
+    let py = handler.py();
+    map_callback(handler, |callback| {
+        Ok(Py::new(py, AutoReplyCallback(callback))?.into_any())
+    })
+}
+
+// Not using `option_wrapper!`, as a queryable declared on several key expressions holds one
+// additional queryable per extra key expression, sharing the callback of the first one.
+#[pyclass(weakref)]
+pub(crate) struct Queryable(
+    pub(crate) Option<zenoh::query::Queryable<HandlerImpl<Query>>>,
+    pub(crate) Vec<zenoh::query::Queryable<()>>,
+    /// Number of queries rejected because of `max_breadth`.
+    pub(crate) Arc<AtomicUsize>,
+    pub(crate) Arc<ReplyDefaults>,
 );
 
+impl Queryable {
+    fn check<'a, 'py>(this: &'a Bound<'py, Self>) -> PyResult<&'a Bound<'py, Self>> {
+        this.borrow().get_ref()?;
+        Ok(this)
+    }
+
+    fn get_ref(&self) -> PyResult<&zenoh::query::Queryable<HandlerImpl<Query>>> {
+        self.0
+            .as_ref()
+            .ok_or_else(|| zerror!("Undeclared queryable"))
+    }
+}
+
+impl From<zenoh::query::Queryable<HandlerImpl<Query>>> for Queryable {
+    fn from(value: zenoh::query::Queryable<HandlerImpl<Query>>) -> Self {
+        Self(Some(value), Vec::new(), Arc::default(), Arc::default())
+    }
+}
+
+impl Drop for Queryable {
+    fn drop(&mut self) {
+        Python::with_gil(|gil| {
+            gil.allow_threads(|| {
+                drop(self.0.take());
+                self.1.clear();
+            })
+        });
+    }
+}
+
 #[pymethods]
 impl Queryable {
     #[classmethod]
@@ -334,7 +1613,9 @@ impl Queryable {
         _args: &Bound<PyTuple>,
         _kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<PyObject> {
-        self.undeclare(py)?;
+        if self.0.is_some() {
+            self.undeclare(py)?;
+        }
         Ok(py.None())
     }
 
@@ -348,6 +1629,27 @@ impl Queryable {
         Ok(self.get_ref()?.key_expr().clone().into())
     }
 
+    #[getter]
+    fn key_exprs(&self) -> PyResult<Vec<KeyExpr>> {
+        let others = self.1.iter().map(|q| q.key_expr().clone().into());
+        Ok([self.key_expr()?].into_iter().chain(others).collect())
+    }
+
+    #[getter]
+    fn rejected_count(&self) -> usize {
+        self.2.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn reply_encoding(&self) -> Option<Encoding> {
+        self.3.encoding.clone()
+    }
+
+    #[getter]
+    fn reply_express(&self) -> Option<bool> {
+        self.3.express
+    }
+
     #[getter]
     fn handler(&self, py: Python) -> PyResult<PyObject> {
         self.get_ref()?.handler().into_py_any(py)
@@ -361,8 +1663,23 @@ impl Queryable {
         self.get_ref()?.handler().recv(py)
     }
 
+    fn set_callback(&self, callback: &Bound<PyAny>) -> PyResult<()> {
+        // the callback is shared with the queryables of the other key expressions
+        self.get_ref()?.handler().set_callback(callback)
+    }
+
     fn undeclare(&mut self, py: Python) -> PyResult<()> {
-        wait(py, self.take()?.undeclare())
+        let queryable = self
+            .0
+            .take()
+            .ok_or_else(|| zerror!("Undeclared queryable"))?;
+        let others = std::mem::take(&mut self.1);
+        // all queryables are undeclared even if one fails, the first error is returned
+        let mut result = Ok(());
+        for other in others {
+            result = result.and(wait(py, other.undeclare()));
+        }
+        wait(py, queryable.undeclare()).and(result)
     }
 
     fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
@@ -374,6 +1691,126 @@ impl Queryable {
     }
 }
 
+/// Queryable receiving a single query, undeclared once it is received, see
+/// `Session.queryable_once`.
+#[pyclass(frozen)]
+pub(crate) struct OnceQueryable {
+    queryable: Mutex<Option<zenoh::query::Queryable<()>>>,
+    receiver: zenoh::handlers::FifoChannelHandler<zenoh::query::Query>,
+    timeout: Option<Duration>,
+    has_callback: bool,
+}
+
+impl OnceQueryable {
+    pub(crate) fn declare(
+        py: Python,
+        session: &zenoh::Session,
+        key_expr: KeyExpr,
+        timeout: Option<Duration>,
+        has_callback: bool,
+    ) -> PyResult<Self> {
+        let (callback, receiver) = zenoh::handlers::FifoChannel::new(1).into_handler();
+        let received = AtomicBool::new(false);
+        // the following queries are dropped, i.e. finalized without reply, even if they are
+        // received before the queryable is undeclared
+        let callback = RustCallback::new(Arc::new(move |query| {
+            if !received.swap(true, Ordering::SeqCst) {
+                callback.call(query);
+            }
+        }));
+        let queryable = wait(py, session.declare_queryable(key_expr.0).with(callback))?;
+        Ok(Self {
+            queryable: Mutex::new(Some(queryable)),
+            receiver,
+            timeout,
+            has_callback,
+        })
+    }
+
+    /// Calls `callback` with the query in a dedicated thread; the queryable is closed silently
+    /// if the timeout elapses first.
+    pub(crate) fn spawn_callback(this: &Bound<Self>, callback: PyObject) -> PyResult<()> {
+        let py = this.py();
+        let this = this.clone().unbind();
+        let target = PyCFunction::new_closure(py, None, None, move |args, _| {
+            let py = args.py();
+            if let Ok(query) = this.get().receive(py, this.get().timeout) {
+                log_error(py, callback.call1(py, (query,)));
+            }
+        })?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("target", target)?;
+        let thread = import!(py, threading.Thread).call((), Some(&kwargs))?;
+        thread.call_method0("start")?;
+        Ok(())
+    }
+
+    /// Waits for the query, the queryable being closed once it is received, or on timeout.
+    fn receive(&self, py: Python, timeout: Option<Duration>) -> PyResult<Py<Query>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let result = loop {
+            let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+            let poll = remaining.map_or(CHECK_SIGNALS_INTERVAL, |r| r.min(CHECK_SIGNALS_INTERVAL));
+            match py.allow_threads(|| self.receiver.recv_timeout(poll)) {
+                Ok(Some(query)) => break Ok(query),
+                Ok(None) if remaining.is_some_and(|r| r.is_zero()) => {
+                    let timeout = timeout.unwrap();
+                    let msg = format!("timed out after {timeout:?} waiting for a query");
+                    break Err(new_zerror_with_code(msg, ErrorCode::Timeout));
+                }
+                Ok(None) => {
+                    if let Err(err) = py.check_signals() {
+                        break Err(err);
+                    }
+                }
+                Err(_) => break Err(zerror!("Undeclared queryable")),
+            }
+        };
+        self.close(py)?;
+        Py::new(py, Query::from(result?))
+    }
+}
+
+impl Drop for OnceQueryable {
+    fn drop(&mut self) {
+        let queryable = self.queryable.get_mut().unwrap().take();
+        Python::with_gil(|gil| gil.allow_threads(|| drop(queryable)));
+    }
+}
+
+#[pymethods]
+impl OnceQueryable {
+    #[pyo3(signature = (timeout = None))]
+    fn wait(
+        &self,
+        py: Python,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<Py<Query>> {
+        if self.has_callback {
+            return Err(PyValueError::new_err("the query is passed to the callback"));
+        }
+        self.receive(py, timeout.or(self.timeout))
+    }
+
+    fn close(&self, py: Python) -> PyResult<()> {
+        let queryable = self.queryable.lock().unwrap().take();
+        match queryable {
+            Some(queryable) => wait(py, queryable.undeclare()),
+            None => Ok(()),
+        }
+    }
+
+    #[getter]
+    fn closed(&self) -> bool {
+        self.queryable.lock().unwrap().is_none()
+    }
+
+    fn __repr__(&self) -> String {
+        let state = if self.closed() { "closed" } else { "open" };
+        format!("OnceQueryable({state})")
+    }
+}
+
 option_wrapper!(zenoh::query::Querier<'static>, "Undeclared querier");
 
 #[pymethods]
@@ -394,7 +1831,9 @@ impl Querier {
         _args: &Bound<PyTuple>,
         _kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<PyObject> {
-        self.undeclare(py)?;
+        if self.0.is_some() {
+            self.undeclare(py)?;
+        }
         Ok(py.None())
     }
 
@@ -427,7 +1866,7 @@ impl Querier {
         #[pyo3(from_py_with = Parameters::from_py_opt)] parameters: Option<Parameters>,
         #[pyo3(from_py_with = ZBytes::from_py_opt)] payload: Option<ZBytes>,
         #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         source_info: Option<SourceInfo>,
         cancellation_token: Option<CancellationToken>,
         timestamp_instrumentation: Option<TimestampInstrumentation>,
@@ -484,7 +1923,7 @@ impl Selector {
         Ok(Self(if let Some(params) = parameters {
             (KeyExpr::from_py(arg)?.0, params.0).into()
         } else if let Ok(s) = arg.extract::<String>() {
-            s.parse().into_pyres()?
+            s.parse().map_err(invalid_key_expr)?
         } else if let Ok(k) = arg.extract::<KeyExpr>() {
             k.0.into()
         } else {
@@ -497,11 +1936,34 @@ impl Selector {
         self.0.key_expr().clone().into_owned().into()
     }
 
+    #[setter]
+    fn set_key_expr(&mut self, #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr) {
+        *self = self.with_key_expr(key_expr);
+    }
+
     #[getter]
     fn get_parameters(&self) -> Parameters {
         self.0.parameters().clone().into_owned().into()
     }
 
+    #[setter]
+    fn set_parameters(
+        &mut self,
+        #[pyo3(from_py_with = Parameters::from_py)] parameters: Parameters,
+    ) {
+        let key_expr = self.0.key_expr().clone().into_owned();
+        self.0 = (key_expr, parameters.0).into();
+    }
+
+    fn with_key_expr(&self, #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr) -> Self {
+        let parameters = self.0.parameters().clone().into_owned();
+        Self((key_expr.0, parameters).into())
+    }
+
+    fn without_parameters(&self) -> Self {
+        Self(self.0.key_expr().clone().into_owned().into())
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -511,6 +1973,39 @@ impl Selector {
     }
 }
 
+/// Prefix of the bytes parameter values, encoded in URL-safe base64 without padding, as `=`
+/// separates parameter keys and values.
+const BYTES_PREFIX: &str = "b64:";
+
+/// Datetime parameter values are converted to RFC3339, and bytes ones to base64 with
+/// [`BYTES_PREFIX`]; string values are kept as is.
+fn parameter_value(obj: &Bound<PyAny>) -> PyResult<String> {
+    if let Ok(bytes) = obj.downcast::<PyBytes>() {
+        let encoded = BASE64_URL.encode(bytes.as_bytes());
+        return Ok(format!("{BYTES_PREFIX}{encoded}"));
+    }
+    match obj.downcast::<PyDateTime>() {
+        Ok(datetime) => datetime_to_rfc3339(datetime),
+        Err(_) => obj.extract(),
+    }
+}
+
+/// Reverses [`parameter_value`]: values with [`BYTES_PREFIX`] are decoded if `as_bytes`, other
+/// values being then encoded in UTF-8.
+fn decode_parameter(py: Python, value: &str, as_bytes: bool) -> PyResult<PyObject> {
+    if !as_bytes {
+        return value.into_py_any(py);
+    }
+    let bytes = match value.strip_prefix(BYTES_PREFIX) {
+        Some(encoded) => {
+            let decoded = BASE64_URL.decode(encoded);
+            decoded.map_err(|err| PyValueError::new_err(format!("invalid base64 value: {err}")))?
+        }
+        None => value.as_bytes().to_vec(),
+    };
+    PyBytes::new(py, &bytes).into_py_any(py)
+}
+
 wrapper!(zenoh::query::Parameters<'static>: Clone);
 downcast_or_new!(Parameters);
 
@@ -521,7 +2016,11 @@ impl Parameters {
         let Some(obj) = obj else {
             return Ok(Self(zenoh::query::Parameters::empty()));
         };
-        if let Ok(map) = obj.extract::<HashMap<String, String>>() {
+        if let Ok(dict) = obj.downcast::<PyDict>() {
+            let map = dict
+                .iter()
+                .map(|(k, v)| Ok((k.extract()?, parameter_value(&v)?)))
+                .collect::<PyResult<HashMap<String, String>>>()?;
             return Ok(Self(map.into()));
         }
         Ok(Self(obj.extract::<String>()?.into()))
@@ -531,17 +2030,26 @@ impl Parameters {
         self.0.is_empty()
     }
 
-    #[pyo3(signature = (key, default = None))]
-    fn get(&self, key: &str, default: Option<String>) -> Option<String> {
-        self.0.get(key).map_into().or(default)
+    #[pyo3(signature = (key, default = None, *, as_bytes = false))]
+    fn get(
+        &self,
+        py: Python,
+        key: &str,
+        default: Option<PyObject>,
+        as_bytes: bool,
+    ) -> PyResult<Option<PyObject>> {
+        match self.0.get(key) {
+            Some(value) => decode_parameter(py, value, as_bytes).map(Some),
+            None => Ok(default),
+        }
     }
 
     fn values(&self, key: &str) -> Vec<&str> {
         self.0.values(key).collect()
     }
 
-    fn insert(&mut self, key: &str, value: &str) -> Option<String> {
-        self.0.insert(key, value)
+    fn insert(&mut self, key: &str, value: &Bound<PyAny>) -> PyResult<Option<String>> {
+        Ok(self.0.insert(key, parameter_value(value)?))
     }
 
     fn remove(&mut self, key: &str) -> Option<String> {
@@ -564,8 +2072,8 @@ impl Parameters {
         self.0.contains_key(key)
     }
 
-    fn __getitem__(&self, key: &str) -> Option<String> {
-        self.get(key, None)
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<Option<PyObject>> {
+        self.get(py, key, None, false)
     }
 
     fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
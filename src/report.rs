@@ -0,0 +1,174 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{fmt::Write, path::PathBuf};
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+use serde_json::{json, Map, Value};
+use zenoh::Wait;
+
+use crate::{config::config_keys, debug, session::Session};
+
+/// Version of the state dump layout, to be bumped on incompatible changes.
+const STATE_DUMP_VERSION: u32 = 1;
+/// Configuration keys whose values are redacted if they contain one of these words.
+const SECRET_KEY_PATTERNS: [&str; 3] = ["password", "secret", "private_key"];
+pub(crate) const REDACTED: &str = "<redacted>";
+
+pub(crate) fn is_secret(key: &str) -> bool {
+    SECRET_KEY_PATTERNS
+        .iter()
+        .any(|pattern| key.contains(pattern))
+}
+
+/// Redacts the values of secret keys nested in `value`.
+pub(crate) fn redact(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret(key) && !value.is_null() {
+                    *value = REDACTED.into();
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+/// The session configuration, flattened by key, with secrets redacted.
+///
+/// Intermediate keys are skipped, as their values are spread over their children.
+fn redacted_config(session: &zenoh::Session) -> Value {
+    let config = session.config();
+    let keys = config_keys();
+    let mut map = Map::new();
+    for key in keys {
+        let prefix = format!("{key}/");
+        if keys.iter().any(|k| k.starts_with(&prefix)) {
+            continue;
+        }
+        let Ok(mut value) = config.get_typed::<Value>(key) else {
+            continue;
+        };
+        if is_secret(key) && !value.is_null() {
+            value = REDACTED.into();
+        }
+        redact(&mut value);
+        map.insert(key.clone(), value);
+    }
+    Value::Object(map)
+}
+
+fn transports(session: &zenoh::Session) -> Value {
+    let info = session.info();
+    let links = info.links().wait().collect::<Vec<_>>();
+    let transports = info.transports().wait().map(|transport| {
+        let links = links.iter().filter(|link| link.zid() == transport.zid());
+        let links = links.map(|link| {
+            let (src, dst) = (link.src().to_string(), link.dst().to_string());
+            json!({"src": src, "dst": dst, "mtu": link.mtu()})
+        });
+        json!({
+            "zid": transport.zid().to_string(),
+            "whatami": transport.whatami().to_str(),
+            "multicast": transport.is_multicast(),
+            "qos": transport.is_qos(),
+            "links": links.collect::<Vec<_>>(),
+        })
+    });
+    Value::Array(transports.collect())
+}
+
+/// The state of `session` as a JSON document, see `Session.dump_state`.
+fn collect_state(py: Python, session: &Session) -> Value {
+    let mode = session
+        .0
+        .config()
+        .get_typed::<Option<zenoh::config::WhatAmI>>("mode")
+        .ok()
+        // peer is zenoh default mode
+        .map(|mode| mode.unwrap_or(zenoh::config::WhatAmI::Peer).to_str());
+    let transports = py.allow_threads(|| transports(&session.0));
+    let entities = session.1.describe_open_entities(py).into_iter();
+    let entities = entities.map(|(kind, key_expr)| json!({"type": kind, "key_expr": key_expr}));
+    json!({
+        "format_version": STATE_DUMP_VERSION,
+        "versions": {
+            "zenoh-python": env!("CARGO_PKG_VERSION"),
+            "zenoh": zenoh::GIT_VERSION,
+            "features": zenoh::FEATURES.trim(),
+        },
+        "session": {
+            "zid": session.0.zid().to_string(),
+            "mode": mode,
+            "closed": session.0.is_closed(),
+        },
+        "config": redacted_config(&session.0),
+        "transports": transports,
+        "entities": entities.collect::<Vec<_>>(),
+        "counters": {
+            "group_entities": session.1.open_entities(py),
+            "pending_operations": debug::pending_operations(py).len(),
+            "tracked_open_handles": debug::open_handles(py).len(),
+        },
+    })
+}
+
+/// Formats the state as `[section]` headers followed by `key = value` lines, or `- value` lines
+/// for list sections, with values as compact JSON, strings excepted.
+fn to_text(state: &Value) -> String {
+    let scalar = |value: &Value| match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    };
+    let mut text = format!("zenoh-python state dump, format version {STATE_DUMP_VERSION}\n");
+    for (section, value) in state.as_object().into_iter().flatten() {
+        match value {
+            Value::Object(map) => {
+                writeln!(text, "\n[{section}]").unwrap();
+                for (key, value) in map {
+                    writeln!(text, "{key} = {}", scalar(value)).unwrap();
+                }
+            }
+            Value::Array(values) => {
+                writeln!(text, "\n[{section}]").unwrap();
+                for value in values {
+                    writeln!(text, "- {}", scalar(value)).unwrap();
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+pub(crate) fn dump_state(py: Python, session: &Session, format: &str) -> PyResult<String> {
+    let state = collect_state(py, session);
+    match format {
+        "text" => Ok(to_text(&state)),
+        "json" => Ok(serde_json::to_string_pretty(&state).unwrap()),
+        _ => Err(PyValueError::new_err(format!(
+            "invalid state dump format '{format}', expected 'text' or 'json'"
+        ))),
+    }
+}
+
+#[pyfunction]
+pub(crate) fn bug_report(session: &Bound<Session>, path: PathBuf) -> PyResult<()> {
+    let dump = dump_state(session.py(), &session.borrow(), "text")?;
+    std::fs::write(path, dump)?;
+    Ok(())
+}
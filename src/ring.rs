@@ -0,0 +1,248 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use pyo3::{
+    exceptions::{PyIndexError, PyValueError},
+    prelude::*,
+    types::{PyByteArray, PyList, PySlice},
+};
+
+use crate::macros::import;
+
+/// Slot header: sequence number (u64), timestamp (u64), payload length (u32), key id (u32),
+/// all little-endian.
+const HEADER_SIZE: usize = 24;
+
+#[derive(Default)]
+struct Producer {
+    next_seq: u64,
+    key_ids: HashMap<String, u32>,
+    key_exprs: Vec<String>,
+}
+
+impl Producer {
+    fn key_id(&mut self, key_expr: &str) -> u32 {
+        if let Some(id) = self.key_ids.get(key_expr) {
+            return *id;
+        }
+        let id = self.key_exprs.len() as u32;
+        self.key_ids.insert(key_expr.to_string(), id);
+        self.key_exprs.push(key_expr.to_string());
+        id
+    }
+}
+
+struct RingBuffer {
+    ptr: *mut u8,
+    // keeps the memory alive, it can't be resized as memoryviews are exported
+    _bytearray: Py<PyByteArray>,
+}
+
+// SAFETY: slots are accessed either by the producer or by the consumer, never by both,
+// see `RingState::on_sample`
+unsafe impl Send for RingBuffer {}
+unsafe impl Sync for RingBuffer {}
+
+/// Ring state shared between a [`PayloadRing`] and the subscriber callbacks writing into it.
+pub(crate) struct RingState {
+    buffer: RingBuffer,
+    capacity: usize,
+    slot_size: usize,
+    skip_oversized: bool,
+    // subscriber callbacks may be called concurrently
+    producer: Mutex<Producer>,
+    // number of published slots
+    head: AtomicUsize,
+    // number of slots released by the consumer
+    tail: AtomicUsize,
+    truncated: AtomicUsize,
+    skipped: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+impl RingState {
+    fn slot_stride(&self) -> usize {
+        HEADER_SIZE + self.slot_size
+    }
+
+    pub(crate) fn on_sample(&self, sample: zenoh::sample::Sample) {
+        let mut producer = self.producer.lock().unwrap();
+        let seq = producer.next_seq;
+        producer.next_seq += 1;
+        let head = self.head.load(Ordering::Relaxed);
+        if head - self.tail.load(Ordering::Acquire) == self.capacity {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        let mut len = sample.payload().len();
+        if len > self.slot_size {
+            if self.skip_oversized {
+                self.skipped.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+            self.truncated.fetch_add(1, Ordering::Relaxed);
+            len = self.slot_size;
+        }
+        let key_id = producer.key_id(sample.key_expr().as_str());
+        let timestamp = sample.timestamp().map_or(0, |ts| ts.get_time().as_u64());
+        // SAFETY: slots between `tail` and `tail + capacity` not yet published are neither
+        // exposed to the consumer nor written by other producers, as the lock is held
+        let slot = unsafe {
+            let offset = (head % self.capacity) * self.slot_stride();
+            std::slice::from_raw_parts_mut(self.buffer.ptr.add(offset), self.slot_stride())
+        };
+        slot[0..8].copy_from_slice(&seq.to_le_bytes());
+        slot[8..16].copy_from_slice(&timestamp.to_le_bytes());
+        slot[16..20].copy_from_slice(&(len as u32).to_le_bytes());
+        slot[20..24].copy_from_slice(&key_id.to_le_bytes());
+        let mut payload = &mut slot[HEADER_SIZE..HEADER_SIZE + len];
+        for chunk in sample.payload().slices() {
+            let n = chunk.len().min(payload.len());
+            payload[..n].copy_from_slice(&chunk[..n]);
+            payload = &mut payload[n..];
+            if payload.is_empty() {
+                break;
+            }
+        }
+        self.head.store(head + 1, Ordering::Release);
+    }
+}
+
+#[pyclass(frozen)]
+pub(crate) struct PayloadRing {
+    state: Arc<RingState>,
+    // read-only memoryviews of each slot, created once
+    slots: Vec<PyObject>,
+    // end of the batch returned by the last `read_available`, locked as consumers are not
+    // serialized by the GIL on free-threaded builds
+    pending: Mutex<usize>,
+}
+
+impl PayloadRing {
+    pub(crate) fn state(&self) -> Arc<RingState> {
+        self.state.clone()
+    }
+}
+
+#[pymethods]
+impl PayloadRing {
+    #[classattr]
+    const HEADER_SIZE: usize = HEADER_SIZE;
+
+    #[new]
+    #[pyo3(signature = (capacity, slot_size, *, skip_oversized = false))]
+    fn new(py: Python, capacity: usize, slot_size: usize, skip_oversized: bool) -> PyResult<Self> {
+        if capacity == 0 || slot_size == 0 || slot_size > u32::MAX as usize {
+            return Err(PyValueError::new_err("invalid ring capacity or slot size"));
+        }
+        let stride = HEADER_SIZE + slot_size;
+        let bytearray = PyByteArray::new_with(py, capacity * stride, |_| Ok(()))?;
+        let view = import!(py, builtins.memoryview)
+            .call1((&bytearray,))?
+            .call_method0("toreadonly")?;
+        let slots = (0..capacity)
+            .map(|i| {
+                let slice = PySlice::new(py, (i * stride) as isize, ((i + 1) * stride) as isize, 1);
+                Ok(view.get_item(slice)?.unbind())
+            })
+            .collect::<PyResult<_>>()?;
+        let state = RingState {
+            buffer: RingBuffer {
+                ptr: bytearray.data(),
+                _bytearray: bytearray.unbind(),
+            },
+            capacity,
+            slot_size,
+            skip_oversized,
+            producer: Mutex::default(),
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+            truncated: AtomicUsize::new(0),
+            skipped: AtomicUsize::new(0),
+            dropped: AtomicUsize::new(0),
+        };
+        Ok(Self {
+            state: Arc::new(state),
+            slots,
+            pending: Mutex::new(0),
+        })
+    }
+
+    #[getter]
+    fn capacity(&self) -> usize {
+        self.state.capacity
+    }
+
+    #[getter]
+    fn slot_size(&self) -> usize {
+        self.state.slot_size
+    }
+
+    #[getter]
+    fn received(&self) -> u64 {
+        self.state.producer.lock().unwrap().next_seq
+    }
+
+    #[getter]
+    fn truncated(&self) -> usize {
+        self.state.truncated.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn skipped(&self) -> usize {
+        self.state.skipped.load(Ordering::Relaxed)
+    }
+
+    #[getter]
+    fn dropped(&self) -> usize {
+        self.state.dropped.load(Ordering::Relaxed)
+    }
+
+    fn key_expr(&self, key_id: u32) -> PyResult<String> {
+        let producer = self.state.producer.lock().unwrap();
+        match producer.key_exprs.get(key_id as usize) {
+            Some(key_expr) => Ok(key_expr.clone()),
+            None => Err(PyIndexError::new_err(format!("unknown key id {key_id}"))),
+        }
+    }
+
+    fn read_available<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let mut pending = self.pending.lock().unwrap();
+        self.state.tail.store(*pending, Ordering::Release);
+        let head = self.state.head.load(Ordering::Acquire);
+        let batch = std::mem::replace(&mut *pending, head)..head;
+        drop(pending);
+        let capacity = self.state.capacity;
+        PyList::new(py, batch.map(|i| self.slots[i % capacity].bind(py)))
+    }
+
+    fn release(&self) {
+        let pending = self.pending.lock().unwrap();
+        self.state.tail.store(*pending, Ordering::Release);
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PayloadRing(capacity={}, slot_size={})",
+            self.state.capacity, self.state.slot_size
+        )
+    }
+}
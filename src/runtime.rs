@@ -0,0 +1,172 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::OnceLock;
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+
+use crate::{
+    error::{new_zerror_with_code, ErrorCode},
+    macros::zerror,
+};
+
+/// Environment variable read by zenoh when its runtime is first used.
+const RUNTIME_ENV: &str = "ZENOH_RUNTIME";
+/// Environment variable read by Rust for the stack size of the threads it spawns.
+const STACK_SIZE_ENV: &str = "RUST_MIN_STACK";
+/// A single worker thread, the other zenoh runtimes being handed over to the application one.
+#[cfg(feature = "minimal-runtime")]
+const MINIMAL_RUNTIME: &str = "(app: (worker_threads: 1, max_blocking_threads: 1), \
+    acc: (handover: app), tx: (handover: app), rx: (handover: app), net: (handover: app))";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    Default,
+    #[cfg(feature = "minimal-runtime")]
+    Minimal,
+    /// Configured by the user with another `ZENOH_RUNTIME` value.
+    Custom,
+}
+
+impl Flavor {
+    const AVAILABLE: &[Self] = &[
+        Self::Default,
+        #[cfg(feature = "minimal-runtime")]
+        Self::Minimal,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            #[cfg(feature = "minimal-runtime")]
+            Self::Minimal => "minimal",
+            Self::Custom => "custom",
+        }
+    }
+
+    /// The `ZENOH_RUNTIME` value selecting the flavor.
+    fn env(self) -> Option<&'static str> {
+        match self {
+            #[cfg(feature = "minimal-runtime")]
+            Self::Minimal => Some(MINIMAL_RUNTIME),
+            _ => None,
+        }
+    }
+
+    fn from_name(name: &str) -> PyResult<Self> {
+        if let Some(flavor) = Self::AVAILABLE.iter().find(|f| f.name() == name) {
+            return Ok(*flavor);
+        }
+        if name == "minimal" {
+            let msg = "the minimal runtime is not available, zenoh is built without the \
+                'minimal-runtime' feature";
+            return Err(new_zerror_with_code(
+                msg.into(),
+                ErrorCode::FeatureUnavailable,
+            ));
+        }
+        Err(PyValueError::new_err(
+            "runtime must be 'default' or 'minimal'",
+        ))
+    }
+
+    fn from_env(env: Option<&str>) -> Self {
+        let Some(env) = env else {
+            return Self::Default;
+        };
+        let normalize = |s: &str| s.split_whitespace().collect::<String>();
+        Self::AVAILABLE
+            .iter()
+            .copied()
+            .find(|flavor| flavor.env().is_some_and(|e| normalize(e) == normalize(env)))
+            .unwrap_or(Self::Custom)
+    }
+}
+
+/// The runtime flavor and thread stack size in KiB, read from the environment when the module
+/// is imported; zenoh and Rust read it when their first thread is spawned, so changing it at
+/// runtime would be unsound, with other threads reading it, and possibly without effect.
+struct RuntimeEnv {
+    flavor: Flavor,
+    stack_kb: Option<usize>,
+}
+
+static RUNTIME: OnceLock<RuntimeEnv> = OnceLock::new();
+
+fn runtime_env() -> &'static RuntimeEnv {
+    RUNTIME.get_or_init(|| {
+        let flavor = Flavor::from_env(std::env::var(RUNTIME_ENV).ok().as_deref());
+        let stack_size = std::env::var(STACK_SIZE_ENV).ok();
+        let stack_kb = stack_size
+            .and_then(|size| size.parse::<usize>().ok())
+            .map(|b| b / 1024);
+        RuntimeEnv { flavor, stack_kb }
+    })
+}
+
+/// Reads the runtime configuration before zenoh spawns any thread, see `RuntimeEnv`.
+pub(crate) fn init_runtime_env() {
+    runtime_env();
+}
+
+/// Checks the runtime requested by a session is the one configured by the environment.
+pub(crate) fn check_runtime(runtime: Option<&str>, stack_kb: Option<usize>) -> PyResult<()> {
+    let requested = runtime.map(Flavor::from_name).transpose()?;
+    if stack_kb == Some(0) {
+        return Err(PyValueError::new_err("runtime_stack_kb must be positive"));
+    }
+    let env = runtime_env();
+    if let Some(requested) = requested.filter(|requested| *requested != env.flavor) {
+        let name = env.flavor.name();
+        return Err(match requested.env() {
+            Some(value) => zerror!(
+                "the runtime has the {name} flavor, {RUNTIME_ENV}='{value}' must be set before \
+                importing zenoh"
+            ),
+            None => zerror!(
+                "the runtime has the {name} flavor, {RUNTIME_ENV} must be unset before importing \
+                zenoh"
+            ),
+        });
+    }
+    if stack_kb.is_some() && stack_kb != env.stack_kb {
+        let kb = stack_kb.unwrap_or_default();
+        return Err(zerror!(
+            "the runtime has another stack size, {STACK_SIZE_ENV}={} must be set before \
+            importing zenoh",
+            kb * 1024
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the runtime `flavor`, the flavors `available` in this build, the `ZENOH_RUNTIME`
+/// value selecting each of them in `flavor_env`, and the thread `stack_kb`, if set.
+#[pyfunction]
+pub(crate) fn runtime_info(py: Python) -> PyResult<Bound<PyDict>> {
+    let env = runtime_env();
+    let available = Flavor::AVAILABLE
+        .iter()
+        .map(|f| f.name())
+        .collect::<Vec<_>>();
+    let flavor_env = PyDict::new(py);
+    for flavor in Flavor::AVAILABLE {
+        flavor_env.set_item(flavor.name(), flavor.env())?;
+    }
+    let info = PyDict::new(py);
+    info.set_item("flavor", env.flavor.name())?;
+    info.set_item("available", available)?;
+    info.set_item("flavor_env", flavor_env)?;
+    info.set_item("stack_kb", env.stack_kb)?;
+    Ok(info)
+}
@@ -11,18 +11,20 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use pyo3::prelude::*;
-use zenoh::sample::SourceSn;
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyDict};
+use zenoh::sample::{SampleBuilder, SampleBuilderPut, SourceSn};
 
 use crate::{
     bytes::{Encoding, ZBytes},
+    compression,
+    decoder::decode_registered,
     key_expr::KeyExpr,
-    macros::{enum_mapper, wrapper},
+    macros::{build, enum_mapper, wrapper},
     qos::{CongestionControl, Priority},
     session::EntityGlobalId,
     time::Timestamp,
     timestamp_stack::TimestampStack,
-    utils::MapInto,
+    utils::{IntoPyResult, MapInto},
 };
 
 enum_mapper!(zenoh::sample::SampleKind: u8 {
@@ -46,6 +48,41 @@ wrapper!(zenoh::sample::Sample);
 
 #[pymethods]
 impl Sample {
+    #[new]
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (key_expr, payload = None, *, kind = SampleKind::Put, encoding = None, timestamp = None, attachment = None, source_info = None, congestion_control = None, priority = None, express = None))]
+    fn new(
+        #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
+        #[pyo3(from_py_with = ZBytes::from_py_opt)] payload: Option<ZBytes>,
+        kind: SampleKind,
+        #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
+        timestamp: Option<Timestamp>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
+        source_info: Option<SourceInfo>,
+        congestion_control: Option<CongestionControl>,
+        priority: Option<Priority>,
+        express: Option<bool>,
+    ) -> PyResult<Self> {
+        let sample = match kind {
+            SampleKind::Put => {
+                let builder = SampleBuilder::put(key_expr, payload.unwrap_or_default());
+                let builder = build!(builder, congestion_control, priority, express);
+                build!(builder, encoding, timestamp, attachment, source_info).into()
+            }
+            SampleKind::Delete if payload.is_some() || encoding.is_some() => {
+                return Err(PyValueError::new_err(
+                    "a delete sample has neither payload nor encoding",
+                ));
+            }
+            SampleKind::Delete => {
+                let builder = SampleBuilder::delete(key_expr);
+                let builder = build!(builder, congestion_control, priority, express);
+                build!(builder, timestamp, attachment, source_info).into()
+            }
+        };
+        Ok(Self(sample))
+    }
+
     #[getter]
     fn key_expr(&self) -> KeyExpr {
         self.0.key_expr().clone().into()
@@ -87,7 +124,12 @@ impl Sample {
     }
 
     #[getter]
-    fn attachment(&self) -> Option<ZBytes> {
+    fn attachment<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.raw_attachment().map(|a| a.to_dict(py)).transpose()
+    }
+
+    #[getter]
+    fn raw_attachment(&self) -> Option<ZBytes> {
         self.0.attachment().cloned().map_into()
     }
 
@@ -101,6 +143,56 @@ impl Sample {
         self.0.timestamp_stack().cloned().map_into()
     }
 
+    fn with_key_expr(&self, #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr) -> Self {
+        // cloning a sample shares its payload buffers, it doesn't copy them
+        Self(
+            SampleBuilder::from(self.0.clone())
+                .keyexpr(key_expr.0)
+                .into(),
+        )
+    }
+
+    fn with_payload(
+        &self,
+        #[pyo3(from_py_with = ZBytes::from_py)] payload: ZBytes,
+    ) -> PyResult<Self> {
+        let builder = SampleBuilder::<SampleBuilderPut>::try_from(self.0.clone()).into_pyres()?;
+        Ok(Self(builder.payload(payload).into()))
+    }
+
+    fn with_encoding(
+        &self,
+        #[pyo3(from_py_with = Encoding::from_py)] encoding: Encoding,
+    ) -> PyResult<Self> {
+        let builder = SampleBuilder::<SampleBuilderPut>::try_from(self.0.clone()).into_pyres()?;
+        Ok(Self(builder.encoding(encoding).into()))
+    }
+
+    fn with_attachment(
+        &self,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
+    ) -> Self {
+        let attachment = attachment.map(|attachment| attachment.0);
+        Self(
+            SampleBuilder::from(self.0.clone())
+                .attachment(attachment)
+                .into(),
+        )
+    }
+
+    #[pyo3(signature = (*, decompress = true))]
+    pub(crate) fn decode(&self, py: Python, decompress: bool) -> PyResult<PyObject> {
+        let payload = if decompress {
+            compression::decompress(py, self.0.payload(), self.0.encoding())?
+        } else {
+            self.payload()
+        };
+        match decode_registered(py, self.0.key_expr(), &payload)? {
+            Some(decoded) => Ok(decoded),
+            None => Ok(payload.into_pyobject(py)?.into_any().unbind()),
+        }
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -11,21 +11,43 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::ops::Deref;
+use std::{
+    ops::Deref,
+    time::{Duration, Instant},
+};
 
 use pyo3::{
+    exceptions::PyValueError,
     prelude::*,
     types::{PyDict, PyIterator, PyList, PyTuple, PyType},
     IntoPyObjectExt,
 };
+use zenoh::config::Locator;
 
 use crate::{
     config::{Config, WhatAmI, WhatAmIMatcher, ZenohId},
-    handlers::{into_handler, HandlerImpl},
-    macros::{option_wrapper, wrapper},
-    utils::{generic, wait},
+    handlers::{into_handler, HandlerImpl, CHECK_SIGNALS_INTERVAL},
+    macros::{option_wrapper, wrapper, zerror},
+    session::{open, Session},
+    utils::{duration, generic, wait, IntoPyResult},
 };
 
+const DEFAULT_SCOUT_TIMEOUT: Duration = Duration::from_secs(2);
+/// Locator protocols by order of preference, other protocols come last.
+const LOCATOR_PREFERENCE: [&str; 5] = ["tcp", "tls", "quic", "ws", "udp"];
+
+/// The locator with the most preferred protocol, the first advertised one among equals.
+fn best_locator(hello: &zenoh::scouting::Hello) -> Option<&Locator> {
+    let rank = |locator: &&Locator| {
+        let protocol = locator.protocol();
+        let rank = LOCATOR_PREFERENCE
+            .iter()
+            .position(|p| *p == protocol.as_str());
+        rank.unwrap_or(LOCATOR_PREFERENCE.len())
+    };
+    hello.locators().iter().min_by_key(rank)
+}
+
 wrapper!(zenoh::scouting::Hello);
 
 #[pymethods]
@@ -46,6 +68,11 @@ impl Hello {
         PyList::new(py, locators)
     }
 
+    #[getter]
+    fn best_locator(&self) -> Option<String> {
+        best_locator(&self.0).map(|loc| loc.to_string())
+    }
+
     fn __repr__(&self) -> String {
         format!("{:?}", self.0)
     }
@@ -106,17 +133,182 @@ impl Scout {
     }
 }
 
+/// Collects the hellos received during `timeout`, once per node, releasing the GIL, and
+/// checking for signals between polls.
+fn collect_hellos(
+    py: Python,
+    what: WhatAmIMatcher,
+    config: Config,
+    timeout: Duration,
+) -> PyResult<Vec<Hello>> {
+    let builder = zenoh::scout(what, config).with(zenoh::handlers::FifoChannel::default());
+    let scout = wait(py, builder)?;
+    let deadline = Instant::now() + timeout;
+    let mut hellos: Vec<zenoh::scouting::Hello> = Vec::new();
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let recv_timeout = || scout.recv_timeout(remaining.min(CHECK_SIGNALS_INTERVAL));
+        match py.allow_threads(recv_timeout).into_pyres()? {
+            Some(hello) if hellos.iter().any(|h| h.zid() == hello.zid()) => {}
+            Some(hello) => hellos.push(hello),
+            None if remaining.is_zero() => break,
+            None => py.check_signals()?,
+        }
+    }
+    py.allow_threads(|| scout.stop());
+    Ok(hellos.into_iter().map(Hello).collect())
+}
+
 #[pyfunction]
-#[pyo3(signature = (handler = None, what = None, config = None))]
+#[pyo3(signature = (handler = None, what = None, config = None, *, timeout = None))]
 pub(crate) fn scout(
     py: Python,
     handler: Option<&Bound<PyAny>>,
     #[pyo3(from_py_with = WhatAmIMatcher::from_py_opt)] what: Option<WhatAmIMatcher>,
-    config: Option<Config>,
-) -> PyResult<Scout> {
+    #[pyo3(from_py_with = Config::from_py_opt)] config: Option<Config>,
+    #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+) -> PyResult<PyObject> {
     let what = what.unwrap_or_default();
     let config = config.unwrap_or_default();
+    // no hello would ever be received
+    let multicast = config.0.get_typed::<bool>("scouting/multicast/enabled");
+    if multicast.is_ok_and(|enabled| !enabled) {
+        return Err(zerror!(
+            "Cannot scout, multicast scouting is disabled in the configuration"
+        ));
+    }
+    if let Some(timeout) = timeout {
+        if handler.is_some() {
+            return Err(PyValueError::new_err("handler cannot be used with timeout"));
+        }
+        return collect_hellos(py, what, config, timeout)?.into_py_any(py);
+    }
     let (handler, _) = into_handler(py, handler, None)?;
     let builder = zenoh::scout(what, config).with(handler);
-    Ok(Scout(Some(wait(py, builder)?)))
+    Scout(Some(wait(py, builder)?)).into_py_any(py)
+}
+
+#[pyclass(frozen)]
+pub(crate) struct AutoOpenReport {
+    #[pyo3(get)]
+    mode: WhatAmI,
+    #[pyo3(get)]
+    router_zid: Option<ZenohId>,
+    #[pyo3(get)]
+    router_locator: Option<String>,
+    #[pyo3(get)]
+    scouting_duration: f64,
+}
+
+#[pymethods]
+impl AutoOpenReport {
+    fn __repr__(&self) -> String {
+        let router = match (&self.router_zid, &self.router_locator) {
+            (Some(zid), Some(locator)) => format!("{} at {locator}", zid.0),
+            _ => "None".to_string(),
+        };
+        format!(
+            "AutoOpenReport(mode={:?}, router={router}, scouting_duration={:.3}s)",
+            zenoh::config::WhatAmI::from(self.mode),
+            self.scouting_duration
+        )
+    }
+}
+
+/// Scouts for a router advertising a locator, releasing the GIL, and checking for signals
+/// between polls.
+fn scout_router(
+    py: Python,
+    config: &zenoh::Config,
+    timeout: Duration,
+) -> PyResult<Option<zenoh::scouting::Hello>> {
+    let what = zenoh::config::WhatAmI::Router;
+    let builder = zenoh::scout(what, config.clone()).with(zenoh::handlers::FifoChannel::default());
+    let scout = wait(py, builder)?;
+    let deadline = Instant::now() + timeout;
+    let hello = loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let recv_timeout = || scout.recv_timeout(remaining.min(CHECK_SIGNALS_INTERVAL));
+        match py.allow_threads(recv_timeout).into_pyres()? {
+            Some(hello) if best_locator(&hello).is_some() => break Some(hello),
+            Some(_) => {}
+            None if remaining.is_zero() => break None,
+            None => py.check_signals()?,
+        }
+    };
+    py.allow_threads(|| scout.stop());
+    Ok(hello)
+}
+
+fn open_peer(py: Python, config: &zenoh::Config) -> PyResult<Py<Session>> {
+    let mut config = config.clone();
+    config.insert_json5("mode", r#""peer""#).into_pyres()?;
+    config
+        .insert_json5("scouting/multicast/enabled", "true")
+        .into_pyres()?;
+    open(py, Config(config), None, None, None, None, None, None, None)
+}
+
+fn open_client(py: Python, config: &zenoh::Config, locator: &Locator) -> PyResult<Py<Session>> {
+    let mut config = config.clone();
+    config.insert_json5("mode", r#""client""#).into_pyres()?;
+    let endpoints = format!("[{:?}]", locator.as_str());
+    config
+        .insert_json5("connect/endpoints", &endpoints)
+        .into_pyres()?;
+    open(py, Config(config), None, None, None, None, None, None, None)
+}
+
+#[pyfunction]
+#[pyo3(signature = (config = None, *, prefer = "client", scout_timeout = None))]
+pub(crate) fn open_auto(
+    py: Python,
+    config: Option<Config>,
+    prefer: &str,
+    #[pyo3(from_py_with = duration)] scout_timeout: Option<Duration>,
+) -> PyResult<(Py<Session>, AutoOpenReport)> {
+    let config = config.unwrap_or_default().0;
+    let mut report = AutoOpenReport {
+        mode: WhatAmI::Peer,
+        router_zid: None,
+        router_locator: None,
+        scouting_duration: 0.0,
+    };
+    match prefer {
+        "client" => {}
+        "peer" => return Ok((open_peer(py, &config)?, report)),
+        _ => return Err(PyValueError::new_err("prefer must be 'client' or 'peer'")),
+    }
+    let timeout = scout_timeout.unwrap_or(DEFAULT_SCOUT_TIMEOUT);
+    let start = Instant::now();
+    let hello = scout_router(py, &config, timeout)?;
+    report.scouting_duration = start.elapsed().as_secs_f64();
+    let attempt = match hello {
+        Some(hello) => {
+            let locator = best_locator(&hello).unwrap();
+            match open_client(py, &config, locator) {
+                Ok(session) => {
+                    report.mode = WhatAmI::Client;
+                    report.router_zid = Some(ZenohId(hello.zid()));
+                    report.router_locator = Some(locator.to_string());
+                    return Ok((session, report));
+                }
+                Err(err) => format!(
+                    "connecting to router {} at {locator} failed: {}",
+                    hello.zid(),
+                    err.value(py)
+                ),
+            }
+        }
+        None => format!("no router found after scouting for {timeout:?}"),
+    };
+    match open_peer(py, &config) {
+        Ok(session) => Ok((session, report)),
+        Err(err) => {
+            let err = err.value(py);
+            Err(zerror!(
+                "{attempt}, then opening a peer session failed: {err}"
+            ))
+        }
+    }
 }
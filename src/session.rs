@@ -11,34 +11,237 @@
 // Contributors:
 //   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 //
-use std::time::Duration;
+use std::{
+    path::PathBuf,
+    sync::{atomic::AtomicUsize, Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use pyo3::{
+    exceptions::{PyKeyError, PyValueError},
     prelude::*,
     types::{PyDict, PyIterator, PyList, PyTuple},
     IntoPyObjectExt,
 };
-use zenoh::{session::EntityId, Wait};
+use zenoh::{
+    handlers::{Callback as RustCallback, IntoHandler},
+    session::EntityId,
+    Wait,
+};
 
 use crate::{
+    admin::{list_entities, wait_for_matching, EntityInfo, EntityWatcher},
     bytes::{Encoding, ZBytes},
     cancellation::CancellationToken,
-    config::{Config, WhatAmI, ZenohId},
-    handlers::{into_handler, HandlerImpl},
-    key_expr::KeyExpr,
+    compat::Compatibility,
+    compression::{compress, Compression},
+    config::{config_keys, Config, ConfigNotifier, WhatAmI, ZenohId},
+    debug::{self, PendingKey},
+    decoder::auto_decode_handler,
+    error::{new_zerror_with_code, ErrorCode},
+    executor::Executor,
+    files::{path_encoding, read_file, write_file},
+    get_cache::{self, cache_key, CacheMode},
+    group::{undeclare_concurrently, EntityGroups},
+    handlers::{
+        into_cancellable_handler, into_executor_handler, into_handler, into_lane_handler,
+        HandlerImpl, PriorityChannel, CHECK_SIGNALS_INTERVAL,
+    },
+    integrity::{attach, Integrity, IntegrityCheck},
+    json::encoded_payload,
+    key_expr::{invalid_key_expr, KeyExpr},
     liveliness::Liveliness,
-    macros::{build, option_wrapper, wrapper},
-    pubsub::{Publisher, Subscriber},
+    macros::{build, option_wrapper, wrapper, zerror},
+    metrics::timed_handler,
+    policy::subscriber_allowed_origin,
+    projection::Projection,
+    pubsub::{
+        rust_subscriber_handler, Publisher, Retained, SelfFilter, Subscriber, SubscriberLimits,
+    },
     qos::{CongestionControl, Priority, Reliability},
-    query::{Querier, QueryConsolidation, QueryTarget, Queryable, Reply, ReplyKeyExpr, Selector},
-    sample::{Locality, SampleKind, SourceInfo},
+    query::{
+        audited_handler, auto_reply_handler, defaulted_callback, get_concurrently, write_replies,
+        DefaultedQuery, GetHandle, GetState, MaxBreadth, OnceQueryable, PagedGet, Parameters,
+        Querier, QueryConsolidation, QueryFilter, QueryTarget, Queryable, Replies, Reply,
+        ReplyDefaults, ReplyFormat, ReplyKeyExpr, Selector,
+    },
+    report::dump_state,
+    ring::PayloadRing,
+    runtime::check_runtime,
+    sample::{Locality, Sample, SampleKind, SourceInfo},
+    shard::Shard,
+    spool::SpooledReplies,
     time::Timestamp,
     timestamp_stack::TimestampInstrumentation,
-    utils::{duration, wait, IntoPython, MapInto},
+    utils::{duration, wait, with_context, IntoPyResult, IntoPython, MapInto},
+    validation::validate_payload,
 };
 
-#[pyclass]
-pub(crate) struct Session(pub(crate) zenoh::Session);
+/// Zenoh default of the `queries_default_timeout` configuration.
+const DEFAULT_QUERY_TIMEOUT: Duration = Duration::from_secs(10);
+const CONNECTIVITY_POLL_PERIOD: Duration = Duration::from_millis(10);
+const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_MATCHING_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_CLOSE_PARALLELISM: usize = 8;
+const DEFAULT_UNDECLARE_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_CONFIRM_TIMEOUT: Duration = Duration::from_secs(2);
+/// Upper bound of the backoff between the polls of `put_sync`.
+const MAX_SYNC_BACKOFF: Duration = Duration::from_millis(200);
+const DEFAULT_GET_CONCURRENCY: usize = 16;
+const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+/// Prefix added by zenoh to the outgoing key expressions, and stripped from the incoming ones.
+const NAMESPACE_KEY: &str = "namespace";
+/// Time budget in milliseconds of the transport batches, after which they are pushed.
+const BATCHING_TIME_LIMIT_KEY: &str = "transport/link/tx/batching/time_limit";
+
+/// Raises a feature unavailable error if the linked zenoh has no batching time budget.
+fn require_batching_time_limit() -> PyResult<()> {
+    if config_keys()
+        .iter()
+        .any(|key| key == BATCHING_TIME_LIMIT_KEY)
+    {
+        return Ok(());
+    }
+    let msg = format!(
+        "batching time budget is not supported by the linked zenoh ({})",
+        zenoh::GIT_VERSION
+    );
+    Err(new_zerror_with_code(msg, ErrorCode::FeatureUnavailable))
+}
+
+#[pyclass(weakref)]
+pub(crate) struct Session(pub(crate) zenoh::Session, pub(crate) EntityGroups);
+
+/// Key expressions of a queryable, either a single one, or a list of key expressions or
+/// `(key_expr, complete)` pairs.
+fn queryable_key_exprs(
+    obj: &Bound<PyAny>,
+    complete: Option<bool>,
+) -> PyResult<Vec<(KeyExpr, Option<bool>)>> {
+    let Ok(list) = obj.downcast::<PyList>() else {
+        return Ok(vec![(KeyExpr::from_py(obj)?, complete)]);
+    };
+    list.iter()
+        .map(|item| match item.downcast::<PyTuple>() {
+            Ok(pair) => {
+                let (key_expr, complete) = pair.extract::<(Bound<PyAny>, bool)>()?;
+                Ok((KeyExpr::from_py(&key_expr)?, Some(complete)))
+            }
+            Err(_) => Ok((KeyExpr::from_py(&item)?, complete)),
+        })
+        .collect()
+}
+
+impl Session {
+    fn query_timeout(&self) -> Duration {
+        let timeout = self.0.config().get_typed::<u64>("queries_default_timeout");
+        timeout.map_or(DEFAULT_QUERY_TIMEOUT, Duration::from_millis)
+    }
+
+    /// Performs `put` with a temporary storage of `key_expr`, i.e. a subscriber storing the last
+    /// sample and a queryable replying with it, then queries it until the sample is returned,
+    /// releasing the GIL, and checking for signals between polls.
+    fn confirm_via_queryable(
+        &self,
+        py: Python,
+        key_expr: &zenoh::key_expr::KeyExpr<'static>,
+        put: impl FnOnce() -> PyResult<()>,
+        timeout: Duration,
+    ) -> PyResult<zenoh::sample::Sample> {
+        // the storage is undeclared when dropped, whatever the outcome
+        let stored = Arc::new(Mutex::new(None::<zenoh::sample::Sample>));
+        let store = stored.clone();
+        let builder = self.0.declare_subscriber(key_expr.clone());
+        let _subscriber = wait(
+            py,
+            builder.callback(move |sample| *store.lock().unwrap() = Some(sample)),
+        )?;
+        let builder = self.0.declare_queryable(key_expr.clone());
+        let _queryable = wait(
+            py,
+            builder.callback(move |query| {
+                if let Some(sample) = stored.lock().unwrap().clone() {
+                    query.reply_sample(sample).wait().ok();
+                }
+            }),
+        )?;
+        put()?;
+        let deadline = Instant::now() + timeout;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                let msg = format!("timed out after {timeout:?} waiting for the stored sample");
+                return Err(new_zerror_with_code(msg, ErrorCode::Timeout));
+            }
+            let replies = wait(py, self.0.get(key_expr.clone()).timeout(remaining))?;
+            let reply = py.allow_threads(|| replies.recv().ok());
+            if let Some(Ok(sample)) = reply.map(|reply| reply.into_result()) {
+                return Ok(sample);
+            }
+            py.allow_threads(|| std::thread::sleep(remaining.min(CONNECTIVITY_POLL_PERIOD)));
+            py.check_signals()?;
+        }
+    }
+
+    /// Waits until a router or peer transport is established, releasing the GIL, and checking
+    /// for signals between polls.
+    fn wait_connectivity(&self, py: Python, timeout: Duration) -> PyResult<()> {
+        let deadline = Instant::now() + timeout;
+        let info = self.0.info();
+        let is_connected = || {
+            info.routers_zid().wait().next().is_some() || info.peers_zid().wait().next().is_some()
+        };
+        loop {
+            if py.allow_threads(is_connected) {
+                return Ok(());
+            }
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                let msg = format!(
+                    "timed out after {timeout:?} waiting for connectivity, no router or peer is \
+                    connected"
+                );
+                return Err(new_zerror_with_code(msg, ErrorCode::Timeout));
+            }
+            py.allow_threads(|| std::thread::sleep(remaining.min(CONNECTIVITY_POLL_PERIOD)));
+            py.check_signals()?;
+        }
+    }
+}
+
+/// Receives samples until one satisfies `predicate`, releasing the GIL, and checking for signals
+/// between polls.
+fn next_sample(
+    py: Python,
+    handler: &zenoh::handlers::FifoChannelHandler<zenoh::sample::Sample>,
+    timeout: Option<Duration>,
+    predicate: Option<&Bound<PyAny>>,
+) -> PyResult<Py<Sample>> {
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    loop {
+        let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        let poll = remaining.map_or(CHECK_SIGNALS_INTERVAL, |r| r.min(CHECK_SIGNALS_INTERVAL));
+        let recv_timeout = || handler.recv_timeout(poll);
+        match py.allow_threads(recv_timeout).into_pyres()? {
+            Some(sample) => {
+                let sample = Py::new(py, Sample::from(sample))?;
+                match predicate {
+                    Some(predicate)
+                        if !predicate.call1((sample.clone_ref(py),))?.is_truthy()? => {}
+                    _ => return Ok(sample),
+                }
+            }
+            None if deadline.is_some_and(|deadline| Instant::now() >= deadline) => {
+                let msg = format!(
+                    "timed out after {:?} waiting for a sample",
+                    timeout.unwrap()
+                );
+                return Err(new_zerror_with_code(msg, ErrorCode::Timeout));
+            }
+            None => py.check_signals()?,
+        }
+    }
+}
 
 #[pymethods]
 impl Session {
@@ -53,7 +256,10 @@ impl Session {
         _args: &Bound<PyTuple>,
         _kwargs: Option<&Bound<PyDict>>,
     ) -> PyResult<PyObject> {
-        self.close(py)?;
+        // the session may have been closed explicitly within the `with` block
+        if !self.0.is_closed() {
+            self.close(py, false, DEFAULT_CLOSE_PARALLELISM, None, None)?;
+        }
         Ok(py.None())
     }
 
@@ -62,12 +268,41 @@ impl Session {
         self.0.id().into()
     }
 
+    #[getter]
+    fn namespace(&self) -> Option<String> {
+        self.0.config().get_typed::<String>(NAMESPACE_KEY).ok()
+    }
+
     fn zid(&self) -> PyResult<ZenohId> {
         Ok(self.0.zid().into())
     }
 
-    fn close(&self, py: Python) -> PyResult<()> {
-        wait(py, self.0.close())
+    #[pyo3(signature = (*, force = false, parallelism = DEFAULT_CLOSE_PARALLELISM, entity_timeout = None, on_progress = None))]
+    fn close(
+        &self,
+        py: Python,
+        force: bool,
+        parallelism: usize,
+        #[pyo3(from_py_with = duration)] entity_timeout: Option<Duration>,
+        on_progress: Option<&Bound<PyAny>>,
+    ) -> PyResult<Vec<(String, String)>> {
+        let open_entities = self.1.open_entities(py);
+        if open_entities > 0 && !force {
+            return Err(zerror!(
+                "Cannot close session, entity groups hold {open_entities} open entities, \
+                close them first or use `force=True`"
+            ));
+        }
+        let _pending = debug::pending(py, "close", || PendingKey::None);
+        let failures = undeclare_concurrently(
+            py,
+            self.1.take_open_entities(py),
+            parallelism,
+            entity_timeout.unwrap_or(DEFAULT_UNDECLARE_TIMEOUT),
+            on_progress,
+        )?;
+        wait(py, self.0.close())?;
+        Ok(failures)
     }
 
     fn is_closed(&self) -> bool {
@@ -86,44 +321,56 @@ impl Session {
         self.0.new_timestamp().into()
     }
 
-    fn declare_keyexpr(
-        &self,
-        py: Python,
-        #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
-    ) -> PyResult<KeyExpr> {
-        wait(py, self.0.declare_keyexpr(key_expr)).map_into()
+    fn declare_keyexpr(&self, py: Python, key_expr: &Bound<PyAny>) -> PyResult<KeyExpr> {
+        with_context("declare_keyexpr", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            wait(py, self.0.declare_keyexpr(key_expr)).map_into()
+        })
     }
 
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (key_expr, payload, *, encoding = None, congestion_control = None, priority = None, express = None, attachment = None, timestamp = None, timestamp_instrumentation = None, allowed_destination = None, source_info = None))]
+    #[pyo3(signature = (key_expr, payload, *, encoding = None, congestion_control = None, priority = None, express = None, attachment = None, timestamp = None, timestamp_instrumentation = None, allowed_destination = None, source_info = None, compression = None, integrity = None, validate = None))]
     fn put(
         &self,
         py: Python,
-        #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
-        #[pyo3(from_py_with = ZBytes::from_py)] payload: ZBytes,
+        key_expr: &Bound<PyAny>,
+        payload: &Bound<PyAny>,
         #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
         congestion_control: Option<CongestionControl>,
         priority: Option<Priority>,
         express: Option<bool>,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         timestamp: Option<Timestamp>,
         timestamp_instrumentation: Option<TimestampInstrumentation>,
         allowed_destination: Option<Locality>,
         source_info: Option<SourceInfo>,
+        compression: Option<Compression>,
+        integrity: Option<Integrity>,
+        validate: Option<bool>,
     ) -> PyResult<()> {
-        let build = build!(
-            self.0.put(key_expr, payload),
-            encoding,
-            congestion_control,
-            priority,
-            express,
-            attachment,
-            timestamp,
-            timestamp_instrumentation,
-            allowed_destination,
-            source_info,
-        );
-        wait(py, build)
+        with_context("put", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            let (payload, payload_encoding) = encoded_payload(payload)?;
+            let encoding = encoding.or(payload_encoding);
+            if let Some(encoding) = &encoding {
+                validate_payload(validate, &payload, &encoding.0)?;
+            }
+            let (payload, encoding) = compress(py, compression, payload, encoding)?;
+            let attachment = attach(py, integrity, &payload, attachment);
+            let build = build!(
+                self.0.put(key_expr, payload),
+                encoding,
+                congestion_control,
+                priority,
+                express,
+                attachment,
+                timestamp,
+                timestamp_instrumentation,
+                allowed_destination,
+                source_info,
+            );
+            wait(py, build)
+        })
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -131,37 +378,41 @@ impl Session {
     fn delete(
         &self,
         py: Python,
-        #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
+        key_expr: &Bound<PyAny>,
         congestion_control: Option<CongestionControl>,
         priority: Option<Priority>,
         express: Option<bool>,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         timestamp: Option<Timestamp>,
         timestamp_instrumentation: Option<TimestampInstrumentation>,
         allowed_destination: Option<Locality>,
         source_info: Option<SourceInfo>,
     ) -> PyResult<()> {
-        let build = build!(
-            self.0.delete(key_expr),
-            congestion_control,
-            priority,
-            express,
-            attachment,
-            timestamp,
-            timestamp_instrumentation,
-            allowed_destination,
-            source_info
-        );
-        wait(py, build)
+        with_context("delete", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            let build = build!(
+                self.0.delete(key_expr),
+                congestion_control,
+                priority,
+                express,
+                attachment,
+                timestamp,
+                timestamp_instrumentation,
+                allowed_destination,
+                source_info
+            );
+            wait(py, build)
+        })
     }
 
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (selector, handler = None, *, target = None, consolidation = None, accept_replies = None, timeout = None, congestion_control = None, priority = None, express = None, payload = None, encoding = None, attachment = None, allowed_destination = None, source_info = None, cancellation_token = None, timestamp_instrumentation = None))]
+    #[pyo3(signature = (selector, handler = None, *, parameters = None, target = None, consolidation = None, accept_replies = None, timeout = None, congestion_control = None, priority = None, express = None, payload = None, encoding = None, attachment = None, allowed_destination = None, source_info = None, cancellation_token = None, timestamp_instrumentation = None, require_connectivity = false))]
     fn get(
         &self,
         py: Python,
-        #[pyo3(from_py_with = Selector::from_py)] selector: Selector,
+        selector: &Bound<PyAny>,
         handler: Option<&Bound<PyAny>>,
+        #[pyo3(from_py_with = Parameters::from_py_opt)] parameters: Option<Parameters>,
         target: Option<QueryTarget>,
         #[pyo3(from_py_with = QueryConsolidation::from_py_opt)] consolidation: Option<
             QueryConsolidation,
@@ -173,32 +424,260 @@ impl Session {
         express: Option<bool>,
         #[pyo3(from_py_with = ZBytes::from_py_opt)] payload: Option<ZBytes>,
         #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
-        #[pyo3(from_py_with = ZBytes::from_py_opt)] attachment: Option<ZBytes>,
+        #[pyo3(from_py_with = ZBytes::attachment_from_py_opt)] attachment: Option<ZBytes>,
         allowed_destination: Option<Locality>,
         source_info: Option<SourceInfo>,
         cancellation_token: Option<CancellationToken>,
         timestamp_instrumentation: Option<TimestampInstrumentation>,
+        require_connectivity: bool,
     ) -> PyResult<HandlerImpl<Reply>> {
-        let (handler, _) = into_handler(py, handler, cancellation_token.as_ref())?;
-        let builder = build!(
-            self.0.get(selector),
-            target,
-            consolidation,
-            accept_replies,
-            timeout,
-            congestion_control,
-            priority,
-            express,
-            payload,
-            encoding,
-            attachment,
-            allowed_destination,
-            source_info,
-            cancellation_token,
-            timestamp_instrumentation
-        );
-
-        wait(py, builder.with(handler)).map_into()
+        with_context("get", selector, || {
+            let mut selector = Selector::from_py(selector)?.0;
+            if let Some(parameters) = parameters {
+                let (key_expr, mut selector_parameters) = selector.split();
+                selector_parameters.extend(&parameters.0);
+                selector = (key_expr, selector_parameters).into();
+            }
+            if require_connectivity {
+                let wait_timeout = timeout.unwrap_or_else(|| self.query_timeout());
+                self.wait_connectivity(py, wait_timeout)?;
+            }
+            let (handler, _) = into_handler(py, handler, cancellation_token.as_ref())?;
+            let builder = build!(
+                self.0.get(selector),
+                target,
+                consolidation,
+                accept_replies,
+                timeout,
+                congestion_control,
+                priority,
+                express,
+                payload,
+                encoding,
+                attachment,
+                allowed_destination,
+                source_info,
+                cancellation_token,
+                timestamp_instrumentation
+            );
+            wait(py, builder.with(handler)).map_into()
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (selector, handler = None, *, target = None, consolidation = None, timeout = None, raise_on_timeout = false, executor = None, project = None))]
+    fn get_handle(
+        &self,
+        py: Python,
+        selector: &Bound<PyAny>,
+        handler: Option<&Bound<PyAny>>,
+        target: Option<QueryTarget>,
+        #[pyo3(from_py_with = QueryConsolidation::from_py_opt)] consolidation: Option<
+            QueryConsolidation,
+        >,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+        raise_on_timeout: bool,
+        executor: Option<&Bound<Executor>>,
+        #[pyo3(from_py_with = Projection::from_py_opt)] project: Option<Projection>,
+    ) -> PyResult<GetHandle> {
+        with_context("get_handle", selector, || {
+            // listed by `debug::pending_operations` while receiving the replies
+            let pending_selector = selector.clone().unbind();
+            let mut selector = Selector::from_py(selector)?.0;
+            if let Some(projection) = &project {
+                selector = projection.with_parameter(selector);
+            }
+            let deadline = raise_on_timeout
+                .then(|| Instant::now() + timeout.unwrap_or_else(|| self.query_timeout()));
+            let state = Arc::new(GetState::new(deadline, project));
+            let (callback, handler) = match executor {
+                // the replies are handled in order by a single worker
+                Some(executor) => {
+                    into_lane_handler(handler, executor, Some(state.cancelled()))?.into_handler()
+                }
+                None => into_cancellable_handler(py, handler, None, Some(state.cancelled()))?
+                    .0
+                    .into_handler(),
+            };
+            let callback = state.wrap_callback(callback);
+            // the token is needed by `GetHandle::cancel`
+            let token = zenoh::cancellation::CancellationToken::default();
+            let builder = build!(self.0.get(selector), target, consolidation, timeout);
+            let builder = builder.cancellation_token(token.clone());
+            let handler = wait(py, builder.with((callback, handler)))?;
+            Ok(GetHandle::new(handler, state, token, pending_selector))
+        })
+    }
+
+    #[pyo3(signature = (selector, *, refresh = false, target = None, consolidation = None, timeout = None))]
+    fn get_cached(
+        &self,
+        py: Python,
+        selector: &Bound<PyAny>,
+        refresh: bool,
+        target: Option<QueryTarget>,
+        #[pyo3(from_py_with = QueryConsolidation::from_py_opt)] consolidation: Option<
+            QueryConsolidation,
+        >,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<Replies> {
+        with_context("get_cached", selector, || {
+            let selector = Selector::from_py(selector)?.0;
+            let key = cache_key(&selector);
+            let mode = if refresh {
+                CacheMode::Refresh
+            } else {
+                CacheMode::Use
+            };
+            let replies = match get_cache::lookup(&key, mode)? {
+                Some(replies) => replies,
+                None => {
+                    let builder = build!(self.0.get(selector), target, consolidation, timeout);
+                    let replies = py.allow_threads(|| {
+                        let replies = builder.wait().into_pyres()?;
+                        PyResult::Ok(replies.iter().collect::<Vec<_>>())
+                    })?;
+                    get_cache::store(key, &replies);
+                    replies
+                }
+            };
+            Replies::new(py, replies)
+        })
+    }
+
+    #[pyo3(signature = (selector, max_memory_bytes, *, target = None, consolidation = None, timeout = None))]
+    fn get_spooled(
+        &self,
+        py: Python,
+        selector: &Bound<PyAny>,
+        max_memory_bytes: usize,
+        target: Option<QueryTarget>,
+        #[pyo3(from_py_with = QueryConsolidation::from_py_opt)] consolidation: Option<
+            QueryConsolidation,
+        >,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<SpooledReplies> {
+        with_context("get_spooled", selector, || {
+            let selector = Selector::from_py(selector)?.0;
+            let builder = build!(self.0.get(selector), target, consolidation, timeout);
+            py.allow_threads(|| {
+                let replies = builder.wait().into_pyres()?;
+                SpooledReplies::collect(replies.iter(), max_memory_bytes)
+            })
+        })
+    }
+
+    #[pyo3(signature = (selector, *, page_size = 1000, target = None, consolidation = None, timeout = None))]
+    fn get_paged(
+        &self,
+        selector: &Bound<PyAny>,
+        page_size: usize,
+        target: Option<QueryTarget>,
+        #[pyo3(from_py_with = QueryConsolidation::from_py_opt)] consolidation: Option<
+            QueryConsolidation,
+        >,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<PagedGet> {
+        with_context("get_paged", selector, || {
+            let selector = Selector::from_py(selector)?;
+            let session = self.0.clone();
+            PagedGet::new(
+                session,
+                selector.0,
+                page_size,
+                target,
+                consolidation,
+                timeout,
+            )
+        })
+    }
+
+    #[pyo3(signature = (selectors, *, max_concurrency = DEFAULT_GET_CONCURRENCY, target = None, consolidation = None, timeout = None))]
+    fn get_many<'py>(
+        &self,
+        py: Python<'py>,
+        selectors: &Bound<'py, PyAny>,
+        max_concurrency: usize,
+        target: Option<QueryTarget>,
+        #[pyo3(from_py_with = QueryConsolidation::from_py_opt)] consolidation: Option<
+            QueryConsolidation,
+        >,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        if max_concurrency == 0 {
+            return Err(PyValueError::new_err("max_concurrency must be positive"));
+        }
+        // invalid selectors are reported in the results, like failed gets
+        let mut names = Vec::new();
+        let mut errors = Vec::new();
+        let mut queries = Vec::new();
+        for selector in selectors.try_iter()? {
+            let selector = selector?;
+            names.push(selector.str()?.to_string());
+            match with_context("get", &selector, || Selector::from_py(&selector)) {
+                Ok(selector) => {
+                    queries.push(selector.0);
+                    errors.push(None);
+                }
+                Err(err) => errors.push(Some(err)),
+            }
+        }
+        let mut replies = py
+            .allow_threads(|| {
+                let session = &self.0;
+                get_concurrently(
+                    session,
+                    queries,
+                    max_concurrency,
+                    target,
+                    consolidation,
+                    timeout,
+                )
+            })
+            .into_iter();
+        let results = PyDict::new(py);
+        for (name, error) in names.into_iter().zip(errors) {
+            let result = match error {
+                Some(err) => Err(err),
+                None => replies.next().unwrap().into_pyres(),
+            };
+            match result {
+                Ok(replies) => results.set_item(&name, Replies::new(py, replies)?)?,
+                Err(err) => results.set_item(&name, err.into_value(py))?,
+            }
+        }
+        Ok(results)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (selector, writer, *, format = "ndjson", chunk_size = DEFAULT_CHUNK_SIZE, target = None, consolidation = None, timeout = None))]
+    fn get_to(
+        &self,
+        py: Python,
+        selector: &Bound<PyAny>,
+        writer: &Bound<PyAny>,
+        format: &str,
+        chunk_size: usize,
+        target: Option<QueryTarget>,
+        #[pyo3(from_py_with = QueryConsolidation::from_py_opt)] consolidation: Option<
+            QueryConsolidation,
+        >,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<usize> {
+        with_context("get_to", selector, || {
+            let format = ReplyFormat::new(format)?;
+            if chunk_size == 0 {
+                return Err(PyValueError::new_err("chunk_size must be positive"));
+            }
+            let selector = Selector::from_py(selector)?.0;
+            let builder = build!(self.0.get(selector), target, consolidation, timeout);
+            let replies = wait(py, builder)?;
+            write_replies(py, &replies, writer, format, chunk_size)
+        })
+    }
+
+    fn config(&self) -> ConfigNotifier {
+        ConfigNotifier(self.0.clone())
     }
 
     #[getter]
@@ -206,64 +685,487 @@ impl Session {
         self.0.info().into()
     }
 
+    fn info_canonical(&self, py: Python) -> PyResult<CanonicalInfo> {
+        let info = self.0.info();
+        let (zid, routers, peers, locators) = py.allow_threads(|| {
+            (
+                info.zid().wait().to_string(),
+                info.routers_zid()
+                    .wait()
+                    .map(|zid| zid.to_string())
+                    .collect::<Vec<_>>(),
+                info.peers_zid()
+                    .wait()
+                    .map(|zid| zid.to_string())
+                    .collect::<Vec<_>>(),
+                info.locators()
+                    .wait()
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>(),
+            )
+        });
+        let mode = self
+            .0
+            .config()
+            .get_typed::<Option<zenoh::config::WhatAmI>>("mode")
+            .ok()
+            // peer is zenoh default mode
+            .map(|mode| mode.unwrap_or(zenoh::config::WhatAmI::Peer).to_str());
+        Ok(CanonicalInfo([
+            zid.into_py_any(py)?,
+            routers.into_py_any(py)?,
+            peers.into_py_any(py)?,
+            mode.into_py_any(py)?,
+            locators.into_py_any(py)?,
+        ]))
+    }
+
+    fn info_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let info = self.info_canonical(py)?;
+        let dict = PyDict::new(py);
+        for (key, value) in CanonicalInfo::KEYS.iter().zip(&info.0) {
+            let value = value.bind(py);
+            // multi-valued entries are joined, as the properties of zenoh 0.x
+            let value = match value.downcast::<PyList>() {
+                Ok(list) => list.extract::<Vec<String>>()?.join(","),
+                Err(_) if value.is_none() => String::new(),
+                Err(_) => value.extract()?,
+            };
+            dict.set_item(key, value)?;
+        }
+        Ok(dict)
+    }
+
     #[pyo3(signature = (key_expr, handler = None, *, allowed_origin = None))]
     fn declare_subscriber(
         &self,
         py: Python,
-        #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
+        key_expr: &Bound<PyAny>,
         handler: Option<&Bound<PyAny>>,
         allowed_origin: Option<Locality>,
-    ) -> PyResult<Subscriber> {
-        let (handler, background) = into_handler(py, handler, None)?;
-        let builder = build!(self.0.declare_subscriber(key_expr), allowed_origin);
-        let mut subscriber = wait(py, builder.with(handler))?;
-        if background {
-            subscriber.set_background(true);
+    ) -> PyResult<Py<Subscriber>> {
+        let mut builder = self.subscriber_builder(key_expr);
+        builder.allowed_origin = allowed_origin;
+        builder.declare(py, handler)
+    }
+
+    fn subscriber_builder(&self, key_expr: &Bound<PyAny>) -> SubscriberBuilder {
+        SubscriberBuilder {
+            session: self.0.clone(),
+            key_expr: key_expr.clone().unbind(),
+            allowed_origin: None,
+            max_duration: None,
+            max_samples: None,
+            on_complete: None,
+            executor: None,
+            verify_integrity: false,
+            on_corrupt: None,
+            auto_decode: false,
+            shard: None,
+            compatibility: None,
+            time_callbacks: false,
+            ignore_self: false,
+            project: None,
         }
-        Ok(subscriber.into())
     }
 
-    #[pyo3(signature = (key_expr, handler = None, *, complete = None, allowed_origin = None))]
+    #[pyo3(signature = (key_expr, timeout = None, predicate = None))]
+    fn wait_for(
+        &self,
+        py: Python,
+        key_expr: &Bound<PyAny>,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+        predicate: Option<&Bound<PyAny>>,
+    ) -> PyResult<Py<Sample>> {
+        with_context("wait_for", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            let builder = self.0.declare_subscriber(key_expr.0);
+            let subscriber = wait(py, builder.with(zenoh::handlers::FifoChannel::default()))?;
+            let sample = next_sample(py, subscriber.handler(), timeout, predicate);
+            // the subscriber is undeclared whatever the outcome
+            let undeclared = wait(py, subscriber.undeclare());
+            let sample = sample?;
+            undeclared?;
+            Ok(sample)
+        })
+    }
+
+    fn sample_once(&self, py: Python, key_expr: &Bound<PyAny>) -> PyResult<Py<Sample>> {
+        self.wait_for(py, key_expr, None, None)
+    }
+
+    #[pyo3(signature = (key_expr, payload, *, encoding = None, timeout = None, via = "local_sub"))]
+    fn put_and_confirm(
+        &self,
+        py: Python,
+        key_expr: &Bound<PyAny>,
+        payload: &Bound<PyAny>,
+        #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+        via: &str,
+    ) -> PyResult<Py<Sample>> {
+        with_context("put_and_confirm", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?.0;
+            let (payload, payload_encoding) = encoded_payload(payload)?;
+            let encoding = encoding.or(payload_encoding);
+            let expected_encoding = encoding.as_ref().map(|e| e.0.clone()).unwrap_or_default();
+            let payload = payload.0;
+            let expected_payload = payload.to_bytes().into_owned();
+            let timeout = timeout.unwrap_or(DEFAULT_CONFIRM_TIMEOUT);
+            let put = || wait(py, build!(self.0.put(key_expr.clone(), payload), encoding));
+            let sample = match via {
+                "local_sub" => {
+                    let builder = self.0.declare_subscriber(key_expr.clone());
+                    let builder = builder.with(zenoh::handlers::FifoChannel::default());
+                    let subscriber = wait(py, builder)?;
+                    let handler = subscriber.handler();
+                    let sample = put().and_then(|_| next_sample(py, handler, Some(timeout), None));
+                    // the subscriber is undeclared whatever the outcome
+                    let undeclared = wait(py, subscriber.undeclare());
+                    let sample = sample?;
+                    undeclared?;
+                    sample
+                }
+                "queryable" => {
+                    let sample = self.confirm_via_queryable(py, &key_expr, put, timeout)?;
+                    Py::new(py, Sample::from(sample))?
+                }
+                _ => {
+                    return Err(PyValueError::new_err(
+                        "via must be 'local_sub' or 'queryable'",
+                    ));
+                }
+            };
+            let received = sample.borrow(py);
+            if received.0.payload().to_bytes()[..] != expected_payload[..] {
+                return Err(zerror!("confirmed sample payload differs from the put one"));
+            }
+            if *received.0.encoding() != expected_encoding {
+                return Err(zerror!(
+                    "confirmed sample encoding '{}' differs from the put one '{expected_encoding}'",
+                    received.0.encoding()
+                ));
+            }
+            drop(received);
+            Ok(sample)
+        })
+    }
+
+    #[pyo3(signature = (key_expr, payload, *, encoding = None, verify_selector = None, timeout = None))]
+    fn put_sync(
+        &self,
+        py: Python,
+        key_expr: &Bound<PyAny>,
+        payload: &Bound<PyAny>,
+        #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
+        verify_selector: Option<&Bound<PyAny>>,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<usize> {
+        with_context("put_sync", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?.0;
+            let selector = match verify_selector {
+                Some(selector) => Selector::from_py(selector)?.0,
+                None => key_expr.clone().into(),
+            };
+            let (payload, payload_encoding) = encoded_payload(payload)?;
+            let encoding = encoding.or(payload_encoding);
+            let expected_encoding = encoding.as_ref().map(|e| e.0.clone()).unwrap_or_default();
+            let payload = payload.0;
+            let expected_payload = payload.to_bytes().into_owned();
+            let timeout = timeout.unwrap_or(DEFAULT_CONFIRM_TIMEOUT);
+            wait(py, build!(self.0.put(key_expr, payload), encoding))?;
+            let deadline = Instant::now() + timeout;
+            let mut backoff = CONNECTIVITY_POLL_PERIOD;
+            let mut last_observed = None;
+            for polls in 1.. {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                let replies = wait(py, self.0.get(selector.clone()).timeout(remaining))?;
+                while let Some(reply) = py.allow_threads(|| replies.recv().ok()) {
+                    let Ok(sample) = reply.into_result() else {
+                        continue;
+                    };
+                    if sample.payload().to_bytes()[..] == expected_payload[..]
+                        && *sample.encoding() == expected_encoding
+                    {
+                        return Ok(polls);
+                    }
+                    last_observed = Some(sample);
+                }
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    break;
+                }
+                py.allow_threads(|| std::thread::sleep(remaining.min(backoff)));
+                py.check_signals()?;
+                backoff = (backoff * 2).min(MAX_SYNC_BACKOFF);
+            }
+            let last_observed = match last_observed {
+                Some(sample) => format!(
+                    "'{}' with encoding '{}'",
+                    String::from_utf8_lossy(&sample.payload().to_bytes()),
+                    sample.encoding()
+                ),
+                None => "no reply".to_string(),
+            };
+            let msg = format!(
+                "timed out after {timeout:?} waiting for '{selector}' to return the put value, \
+                last observed {last_observed}"
+            );
+            Err(new_zerror_with_code(msg, ErrorCode::Timeout))
+        })
+    }
+
+    #[pyo3(signature = (key_expr, path, encoding = None))]
+    fn put_file(
+        &self,
+        py: Python,
+        key_expr: &Bound<PyAny>,
+        path: PathBuf,
+        #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
+    ) -> PyResult<()> {
+        with_context("put_file", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            let encoding = encoding.map_or_else(|| path_encoding(&path), |encoding| encoding.0);
+            let payload = read_file(py, &path)?;
+            wait(py, self.0.put(key_expr, payload).encoding(encoding))
+        })
+    }
+
+    #[pyo3(signature = (selector, dest_path, *, timeout = None))]
+    fn get_file(
+        &self,
+        py: Python,
+        selector: &Bound<PyAny>,
+        dest_path: PathBuf,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<Py<Sample>> {
+        with_context("get_file", selector, || {
+            let selector = Selector::from_py(selector)?.0;
+            let builder = build!(self.0.get(selector), timeout);
+            let replies = wait(py, builder)?;
+            loop {
+                let recv_timeout = || replies.recv_timeout(CHECK_SIGNALS_INTERVAL);
+                match py.allow_threads(recv_timeout) {
+                    Ok(Some(reply)) => {
+                        if let Ok(sample) = reply.into_result() {
+                            write_file(py, &dest_path, sample.payload())?;
+                            return Py::new(py, Sample::from(sample));
+                        }
+                    }
+                    Ok(None) => py.check_signals()?,
+                    // the channel is closed once the query is finalized
+                    Err(_) => return Err(zerror!("no ok reply received")),
+                }
+            }
+        })
+    }
+
+    #[pyo3(signature = (kind, callback, *, interval = None))]
+    fn watch_entities(
+        &self,
+        kind: &str,
+        callback: PyObject,
+        #[pyo3(from_py_with = duration)] interval: Option<Duration>,
+    ) -> PyResult<EntityWatcher> {
+        let interval = interval.unwrap_or(DEFAULT_WATCH_INTERVAL);
+        EntityWatcher::start(&self.0, kind, callback, interval)
+    }
+
+    #[pyo3(signature = (kind, *, timeout = None))]
+    fn list_entities(
+        &self,
+        py: Python,
+        kind: &str,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<Vec<EntityInfo>> {
+        list_entities(py, &self.0, kind, timeout)
+    }
+
+    #[pyo3(signature = (kind, key_expr, count = 1, timeout = None))]
+    fn wait_for_matching(
+        &self,
+        py: Python,
+        kind: &str,
+        key_expr: &Bound<PyAny>,
+        count: usize,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<usize> {
+        with_context("wait_for_matching", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?.0;
+            let timeout = timeout.unwrap_or(DEFAULT_MATCHING_TIMEOUT);
+            wait_for_matching(py, &self.0, kind, key_expr, count, timeout)
+        })
+    }
+
+    #[pyo3(signature = (key_expr, ring, *, allowed_origin = None))]
+    fn subscribe_into(
+        &self,
+        py: Python,
+        key_expr: &Bound<PyAny>,
+        ring: &Bound<PayloadRing>,
+        allowed_origin: Option<Locality>,
+    ) -> PyResult<Subscriber> {
+        with_context("subscribe_into", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            let allowed_origin = allowed_origin.or_else(|| subscriber_allowed_origin(&key_expr));
+            let state = ring.get().state();
+            let callback = RustCallback::new(Arc::new(move |sample| state.on_sample(sample)));
+            let handler = HandlerImpl::Python(ring.clone().into_any().unbind());
+            let handler = rust_subscriber_handler(
+                callback,
+                handler,
+                allowed_origin,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            let builder = build!(self.0.declare_subscriber(key_expr), allowed_origin);
+            Ok(wait(py, builder.with(handler))?.into())
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (key_expr, handler = None, *, complete = None, allowed_origin = None, executor = None, max_breadth = None, reply_encoding = None, reply_express = None, compatibility = None, audit = None, filter = None, auto_reply = false))]
     fn declare_queryable(
         &self,
         py: Python,
-        #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
+        key_expr: &Bound<PyAny>,
         handler: Option<&Bound<PyAny>>,
         complete: Option<bool>,
         allowed_origin: Option<Locality>,
-    ) -> PyResult<Queryable> {
-        let (handler, background) = into_handler(py, handler, None)?;
-        let builder = build!(self.0.declare_queryable(key_expr), complete, allowed_origin);
-        let mut queryable = wait(py, builder.with(handler))?;
-        if background {
-            queryable.set_background(true);
+        executor: Option<&Bound<Executor>>,
+        #[pyo3(from_py_with = MaxBreadth::from_py_opt)] max_breadth: Option<MaxBreadth>,
+        #[pyo3(from_py_with = Encoding::from_py_opt)] reply_encoding: Option<Encoding>,
+        reply_express: Option<bool>,
+        #[pyo3(from_py_with = Compatibility::from_py_opt)] compatibility: Option<Compatibility>,
+        audit: Option<&Bound<PyAny>>,
+        #[pyo3(from_py_with = QueryFilter::from_py_opt)] filter: Option<QueryFilter>,
+        auto_reply: bool,
+    ) -> PyResult<Py<Queryable>> {
+        if let Some(compatibility) = compatibility {
+            let options = [("allowed_origin", allowed_origin.is_some())];
+            compatibility.check("declare_queryable", &options)?;
         }
-        Ok(queryable.into())
+        with_context("declare_queryable", key_expr, || {
+            let mut key_exprs = queryable_key_exprs(key_expr, complete)?.into_iter();
+            let Some((key_expr, complete)) = key_exprs.next() else {
+                return Err(PyValueError::new_err("no key expression"));
+            };
+            let auto_replied;
+            let handler = if auto_reply {
+                auto_replied = auto_reply_handler(handler)?;
+                Some(&auto_replied)
+            } else {
+                handler
+            };
+            let audited;
+            let handler = match audit {
+                Some(audit) => {
+                    audited = audited_handler(handler, audit)?;
+                    Some(&audited)
+                }
+                None => handler,
+            };
+            let (handler, background) =
+                into_executor_handler::<DefaultedQuery>(py, handler, executor)?;
+            let (callback, handler) = handler.into_handler();
+            let defaults = Arc::new(ReplyDefaults {
+                encoding: reply_encoding,
+                express: reply_express,
+            });
+            let mut callback = defaulted_callback(callback, defaults.clone());
+            let rejected = Arc::<AtomicUsize>::default();
+            if let Some(max_breadth) = max_breadth {
+                callback = max_breadth.wrap_callback(callback, rejected.clone());
+            }
+            if let Some(filter) = filter {
+                callback = filter.wrap_callback(callback);
+            }
+            let builder = build!(self.0.declare_queryable(key_expr), complete, allowed_origin);
+            let mut queryable = wait(py, builder.with((callback.clone(), handler)))?;
+            // queryables already declared are undeclared when dropped in case of error
+            let mut others = Vec::new();
+            for (key_expr, complete) in key_exprs {
+                let builder = build!(self.0.declare_queryable(key_expr), complete, allowed_origin);
+                others.push(wait(py, builder.with(callback.clone()))?);
+            }
+            if background {
+                queryable.set_background(true);
+                others.iter_mut().for_each(|q| q.set_background(true));
+            }
+            let queryable = Queryable(Some(queryable), others, rejected, defaults);
+            let queryable = Bound::new(py, queryable)?;
+            debug::track(&queryable, Some(&self.0), background)?;
+            Ok(queryable.unbind())
+        })
+    }
+
+    #[pyo3(signature = (key_expr, callback = None, *, timeout = None))]
+    fn queryable_once(
+        &self,
+        py: Python,
+        key_expr: &Bound<PyAny>,
+        callback: Option<PyObject>,
+        #[pyo3(from_py_with = duration)] timeout: Option<Duration>,
+    ) -> PyResult<Py<OnceQueryable>> {
+        with_context("queryable_once", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            let has_callback = callback.is_some();
+            let queryable = OnceQueryable::declare(py, &self.0, key_expr, timeout, has_callback)?;
+            let queryable = Bound::new(py, queryable)?;
+            if let Some(callback) = callback {
+                OnceQueryable::spawn_callback(&queryable, callback)?;
+            }
+            Ok(queryable.unbind())
+        })
     }
 
     #[allow(clippy::too_many_arguments)]
-    #[pyo3(signature = (key_expr, *, encoding = None, congestion_control = None, priority = None, express = None, reliability = None, allowed_destination = None))]
+    #[pyo3(signature = (key_expr, *, encoding = None, congestion_control = None, priority = None, express = None, reliability = None, allowed_destination = None, retain = false, compatibility = None))]
     fn declare_publisher(
         &self,
         py: Python,
-        #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
+        key_expr: &Bound<PyAny>,
         #[pyo3(from_py_with = Encoding::from_py_opt)] encoding: Option<Encoding>,
         congestion_control: Option<CongestionControl>,
         priority: Option<Priority>,
         express: Option<bool>,
         reliability: Option<Reliability>,
         allowed_destination: Option<Locality>,
-    ) -> PyResult<Publisher> {
-        let builder = build!(
-            self.0.declare_publisher(key_expr),
-            encoding,
-            congestion_control,
-            priority,
-            express,
-            reliability,
-            allowed_destination,
-        );
-        wait(py, builder).map_into()
+        retain: bool,
+        #[pyo3(from_py_with = Compatibility::from_py_opt)] compatibility: Option<Compatibility>,
+    ) -> PyResult<Py<Publisher>> {
+        if let Some(compatibility) = compatibility {
+            let options = [
+                ("allowed_destination", allowed_destination.is_some()),
+                ("reliability", reliability.is_some()),
+                ("retain", retain),
+            ];
+            compatibility.check("declare_publisher", &options)?;
+        }
+        with_context("declare_publisher", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            let retained = retain
+                .then(|| Retained::declare(py, &self.0, &key_expr.0))
+                .transpose()?;
+            let builder = build!(
+                self.0.declare_publisher(key_expr),
+                encoding,
+                congestion_control,
+                priority,
+                express,
+                reliability,
+                allowed_destination,
+            );
+            let publisher = Publisher(Some(wait(py, builder)?), retained);
+            let publisher = Bound::new(py, publisher)?;
+            debug::track(&publisher, Some(&self.0), false)?;
+            Ok(publisher.unbind())
+        })
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -271,7 +1173,7 @@ impl Session {
     fn declare_querier(
         &self,
         py: Python,
-        #[pyo3(from_py_with = KeyExpr::from_py)] key_expr: KeyExpr,
+        key_expr: &Bound<PyAny>,
         target: Option<QueryTarget>,
         #[pyo3(from_py_with = QueryConsolidation::from_py_opt)] consolidation: Option<
             QueryConsolidation,
@@ -283,18 +1185,50 @@ impl Session {
         express: Option<bool>,
         allowed_destination: Option<Locality>,
     ) -> PyResult<Querier> {
-        let builder = build!(
-            self.0.declare_querier(key_expr),
-            target,
-            consolidation,
-            accept_replies,
-            timeout,
-            congestion_control,
-            priority,
-            express,
-            allowed_destination,
-        );
-        wait(py, builder).map_into()
+        with_context("declare_querier", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            let builder = build!(
+                self.0.declare_querier(key_expr),
+                target,
+                consolidation,
+                accept_replies,
+                timeout,
+                congestion_control,
+                priority,
+                express,
+                allowed_destination,
+            );
+            wait(py, builder).map_into()
+        })
+    }
+
+    fn transport_info(&self, py: Python) -> Vec<TransportInfo> {
+        let info = self.0.info();
+        let (transports, links): (Vec<_>, Vec<_>) = py.allow_threads(|| {
+            (
+                info.transports().wait().collect(),
+                info.links().wait().collect(),
+            )
+        });
+        let config = self.0.config();
+        let batch_size = config.get_typed::<u16>("transport/link/tx/batch_size").ok();
+        let mut infos = Vec::new();
+        for transport in transports {
+            for link in links.iter().filter(|l| l.zid() == transport.zid()) {
+                infos.push(TransportInfo {
+                    transport: transport.clone(),
+                    link: link.clone(),
+                    // the negotiated batch size isn't exposed, this is only its upper bound
+                    batch_size: batch_size.map(|size| size.min(link.mtu())),
+                });
+            }
+        }
+        infos
+    }
+
+    #[pyo3(signature = (*, format = "text"))]
+    fn dump_state(&self, py: Python, format: &str) -> PyResult<String> {
+        dump_state(py, self, format)
     }
 
     fn liveliness(&self) -> Liveliness {
@@ -308,28 +1242,314 @@ impl Session {
 
 impl Drop for Session {
     fn drop(&mut self) {
-        Python::with_gil(|gil| self.close(gil)).unwrap()
+        // a session dropped with failing entities must not panic, so the failures are logged
+        let closed =
+            Python::with_gil(|gil| self.close(gil, true, DEFAULT_CLOSE_PARALLELISM, None, None));
+        match closed {
+            Ok(failures) => {
+                for (key, err) in failures {
+                    tracing::warn!("Failed to undeclare {key} when dropping session: {err}");
+                }
+            }
+            Err(err) => tracing::warn!("Failed to close dropped session: {err}"),
+        }
     }
 }
 
+/// The opt-in features of a subscriber, declared with `SubscriberBuilder::declare`;
+/// see `Session::subscriber_builder`.
+#[pyclass]
+pub(crate) struct SubscriberBuilder {
+    session: zenoh::Session,
+    key_expr: PyObject,
+    allowed_origin: Option<Locality>,
+    max_duration: Option<Duration>,
+    max_samples: Option<usize>,
+    on_complete: Option<PyObject>,
+    executor: Option<Py<Executor>>,
+    verify_integrity: bool,
+    on_corrupt: Option<PyObject>,
+    auto_decode: bool,
+    shard: Option<Shard>,
+    compatibility: Option<Compatibility>,
+    time_callbacks: bool,
+    ignore_self: bool,
+    project: Option<PyObject>,
+}
+
+#[pymethods]
+impl SubscriberBuilder {
+    fn allowed_origin(mut this: PyRefMut<Self>, allowed_origin: Locality) -> PyRefMut<Self> {
+        this.allowed_origin = Some(allowed_origin);
+        this
+    }
+
+    #[pyo3(signature = (*, max_duration = None, max_samples = None, on_complete = None))]
+    fn limits<'py>(
+        mut this: PyRefMut<'py, Self>,
+        #[pyo3(from_py_with = duration)] max_duration: Option<Duration>,
+        max_samples: Option<usize>,
+        on_complete: Option<PyObject>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        if max_samples == Some(0) {
+            return Err(PyValueError::new_err("max_samples must be positive"));
+        }
+        if max_duration.is_none() && max_samples.is_none() {
+            return Err(PyValueError::new_err(
+                "limits requires max_duration or max_samples",
+            ));
+        }
+        this.max_duration = max_duration;
+        this.max_samples = max_samples;
+        this.on_complete = on_complete;
+        Ok(this)
+    }
+
+    fn executor(mut this: PyRefMut<Self>, executor: Py<Executor>) -> PyRefMut<Self> {
+        this.executor = Some(executor);
+        this
+    }
+
+    #[pyo3(signature = (on_corrupt = None))]
+    fn verify_integrity(mut this: PyRefMut<Self>, on_corrupt: Option<PyObject>) -> PyRefMut<Self> {
+        this.verify_integrity = true;
+        this.on_corrupt = on_corrupt;
+        this
+    }
+
+    fn auto_decode(mut this: PyRefMut<Self>) -> PyRefMut<Self> {
+        this.auto_decode = true;
+        this
+    }
+
+    fn shard<'py>(
+        mut this: PyRefMut<'py, Self>,
+        #[pyo3(from_py_with = Shard::from_py_opt)] shard: Option<Shard>,
+    ) -> PyRefMut<'py, Self> {
+        this.shard = shard;
+        this
+    }
+
+    fn compatibility<'py>(
+        mut this: PyRefMut<'py, Self>,
+        #[pyo3(from_py_with = Compatibility::from_py_opt)] compatibility: Option<Compatibility>,
+    ) -> PyRefMut<'py, Self> {
+        this.compatibility = compatibility;
+        this
+    }
+
+    fn time_callbacks(mut this: PyRefMut<Self>) -> PyRefMut<Self> {
+        this.time_callbacks = true;
+        this
+    }
+
+    fn ignore_self(mut this: PyRefMut<Self>) -> PyRefMut<Self> {
+        this.ignore_self = true;
+        this
+    }
+
+    fn project<'py>(
+        mut this: PyRefMut<'py, Self>,
+        project: &Bound<'py, PyAny>,
+    ) -> PyResult<PyRefMut<'py, Self>> {
+        // validated now, parsed again by each declaration as projections are stateful
+        Projection::from_py_opt(project)?;
+        this.project = Some(project.clone().unbind());
+        Ok(this)
+    }
+
+    #[pyo3(signature = (handler = None))]
+    fn declare(&self, py: Python, handler: Option<&Bound<PyAny>>) -> PyResult<Py<Subscriber>> {
+        if let Some(compatibility) = self.compatibility {
+            let options = [
+                ("allowed_origin", self.allowed_origin.is_some()),
+                ("verify_integrity", self.verify_integrity),
+                ("auto_decode", self.auto_decode),
+            ];
+            compatibility.check("declare_subscriber", &options)?;
+        }
+        let auto_decoded;
+        let handler = if self.auto_decode {
+            auto_decoded = auto_decode_handler(handler)?;
+            Some(&auto_decoded)
+        } else {
+            handler
+        };
+        let executor = self.executor.as_ref().map(|e| e.bind(py));
+        let key_expr = self.key_expr.bind(py);
+        with_context("declare_subscriber", key_expr, || {
+            let key_expr = KeyExpr::from_py(key_expr)?;
+            // the subscriber policy locality is ignored in compatibility mode
+            let allowed_origin = self.allowed_origin.or_else(|| {
+                subscriber_allowed_origin(&key_expr).filter(|_| self.compatibility.is_none())
+            });
+            let on_complete = self.on_complete.as_ref().map(|f| f.clone_ref(py));
+            let limits = SubscriberLimits::new(self.max_duration, self.max_samples, on_complete);
+            let on_corrupt = self.on_corrupt.as_ref().map(|f| f.clone_ref(py));
+            let integrity = self
+                .verify_integrity
+                .then(|| IntegrityCheck::new(on_corrupt));
+            let project = match &self.project {
+                Some(project) => Projection::from_py_opt(project.bind(py))?,
+                None => None,
+            };
+            let timed = handler
+                .filter(|h| self.time_callbacks && h.is_callable())
+                .map(|h| timed_handler(h, key_expr.0.as_str()))
+                .transpose()?;
+            let (handler, callback_stats) = match &timed {
+                Some((handler, stats)) => (Some(handler), Some(stats.clone())),
+                None => (handler, None),
+            };
+            let priority_channel = handler.and_then(|h| h.extract::<PriorityChannel>().ok());
+            let (callback, handler, background) = match priority_channel {
+                Some(channel) if executor.is_none() => {
+                    let (callback, handler) = channel.into_handler(py);
+                    (callback, handler, false)
+                }
+                _ => {
+                    let (handler, background) = into_executor_handler(py, handler, executor)?;
+                    let (callback, handler) = handler.into_handler();
+                    (callback, handler, background)
+                }
+            };
+            let handler = rust_subscriber_handler(
+                callback,
+                handler,
+                allowed_origin,
+                limits,
+                integrity,
+                self.shard,
+                callback_stats,
+                self.ignore_self
+                    .then(|| SelfFilter::new(self.session.zid())),
+                project,
+            );
+            let builder = build!(self.session.declare_subscriber(key_expr), allowed_origin);
+            let mut subscriber = wait(py, builder.with(handler))?;
+            if background {
+                subscriber.set_background(true);
+            }
+            let subscriber = Bound::new(py, Subscriber::from(subscriber))?;
+            Subscriber::register_limits(&subscriber, background)?;
+            debug::track(&subscriber, Some(&self.session), background)?;
+            Ok(subscriber.unbind())
+        })
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 #[pyfunction]
-#[pyo3(signature = (config, *, timestamp_callback=None))]
+#[pyo3(signature = (config, *, timestamp_callback=None, autoflush_interval_ms=None, credentials=None, tls=None, namespace=None, runtime=None, runtime_stack_kb=None))]
 pub(crate) fn open(
     py: Python,
-    config: Config,
+    #[pyo3(from_py_with = Config::from_py)] mut config: Config,
     timestamp_callback: Option<Py<PyAny>>,
-) -> PyResult<Session> {
+    autoflush_interval_ms: Option<u64>,
+    credentials: Option<(String, String)>,
+    tls: Option<&Bound<PyDict>>,
+    namespace: Option<String>,
+    runtime: Option<&str>,
+    runtime_stack_kb: Option<usize>,
+) -> PyResult<Py<Session>> {
+    check_runtime(runtime, runtime_stack_kb)?;
+    if let Some(interval) = autoflush_interval_ms {
+        require_batching_time_limit()?;
+        let time_limit = interval.to_string();
+        config
+            .0
+            .insert_json5(BATCHING_TIME_LIMIT_KEY, &time_limit)
+            .into_pyres()?;
+    }
+    if let Some(namespace) = namespace {
+        let key_expr =
+            zenoh::key_expr::OwnedKeyExpr::try_from(namespace).map_err(invalid_key_expr)?;
+        if key_expr.is_wild() {
+            return Err(PyValueError::new_err(
+                "namespace must not contain wildcards",
+            ));
+        }
+        if !config_keys().iter().any(|key| key == NAMESPACE_KEY) {
+            let msg = format!(
+                "namespace is not supported by the linked zenoh ({})",
+                zenoh::GIT_VERSION
+            );
+            return Err(new_zerror_with_code(msg, ErrorCode::FeatureUnavailable));
+        }
+        let namespace = serde_json::to_string(key_expr.as_str()).unwrap();
+        config
+            .0
+            .insert_json5(NAMESPACE_KEY, &namespace)
+            .into_pyres()?;
+    }
+    // the helpers take precedence over the configuration values, e.g. loaded from a file
+    if credentials.is_some() || tls.is_some() {
+        let helpers = Bound::new(py, config)?;
+        if let Some((user, password)) = credentials {
+            helpers.call_method1("set_credentials", (user, password))?;
+        }
+        if let Some(tls) = tls {
+            helpers.call_method("set_tls", (), Some(tls))?;
+        }
+        config = helpers.borrow().clone();
+    }
     let builder = zenoh::open(config);
     let builder = if let Some(callback) = timestamp_callback {
         builder.with_timestamp_callback(crate::timestamp_stack::create_timestamp_callback(callback))
     } else {
         builder
     };
-    wait(py, builder).map(Session)
+    let session = Session(wait(py, builder)?, EntityGroups::default());
+    let session = Bound::new(py, session)?;
+    debug::track(&session, None, false)?;
+    Ok(session.unbind())
 }
 
 wrapper!(zenoh::session::SessionInfo);
 
+/// Session information with a fixed set of keys, see `Session::info_canonical`.
+#[pyclass(frozen, mapping)]
+pub(crate) struct CanonicalInfo([PyObject; 5]);
+
+impl CanonicalInfo {
+    // frozen, changing it would break user code
+    const KEYS: [&'static str; 5] = ["zid", "routers", "peers", "mode", "locators"];
+}
+
+#[pymethods]
+impl CanonicalInfo {
+    fn keys(&self) -> [&'static str; 5] {
+        Self::KEYS
+    }
+
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<PyObject> {
+        match Self::KEYS.iter().position(|k| *k == key) {
+            Some(index) => Ok(self.0[index].clone_ref(py)),
+            None => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        Self::KEYS.contains(&key)
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyList::new(py, Self::KEYS)?.as_any().try_iter()
+    }
+
+    fn __len__(&self) -> usize {
+        Self::KEYS.len()
+    }
+
+    fn __repr__(&self, py: Python) -> PyResult<String> {
+        let dict = PyDict::new(py);
+        for (key, value) in Self::KEYS.iter().zip(&self.0) {
+            dict.set_item(key, value)?;
+        }
+        Ok(format!("CanonicalInfo({})", dict.repr()?))
+    }
+}
+
 wrapper!(zenoh::session::Transport);
 
 #[pymethods]
@@ -436,6 +1656,60 @@ impl Link {
     }
 }
 
+/// Wire-level parameters of an established link, see `Session::transport_info`.
+#[pyclass]
+pub(crate) struct TransportInfo {
+    transport: zenoh::session::Transport,
+    link: zenoh::session::Link,
+    batch_size: Option<u16>,
+}
+
+#[pymethods]
+impl TransportInfo {
+    #[getter]
+    fn transport(&self) -> Transport {
+        self.transport.clone().into()
+    }
+
+    #[getter]
+    fn link(&self) -> Link {
+        self.link.clone().into()
+    }
+
+    #[getter]
+    fn batch_size(&self) -> Option<u16> {
+        self.batch_size
+    }
+
+    // not exposed by zenoh
+    #[getter]
+    fn fragmentation(&self) -> Option<bool> {
+        None
+    }
+
+    // not exposed by zenoh
+    #[getter]
+    fn compression(&self) -> Option<bool> {
+        None
+    }
+
+    // not exposed by zenoh
+    #[getter]
+    fn sn_resolution(&self) -> Option<u8> {
+        None
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "TransportInfo(zid={}, src={}, dst={}, batch_size={:?})",
+            self.transport.zid(),
+            self.link.src(),
+            self.link.dst(),
+            self.batch_size,
+        )
+    }
+}
+
 wrapper!(zenoh::session::TransportEvent);
 
 #[pymethods]
@@ -0,0 +1,126 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::key_expr::KeyExpr;
+
+/// First characters of the sharded chunk: a chunk starting with the n-th character belongs to
+/// shard `n % shard_count`, and any other chunk to shard 0.
+const SHARD_ALPHABET: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz-_.~@+=:,;!%&'()[]<>^`|";
+const DEFAULT_CHUNK_INDEX: usize = 1;
+
+/// Shard of a subscriber, filtering out the samples of the other shards.
+#[derive(Clone, Copy)]
+pub(crate) struct Shard {
+    index: usize,
+    count: usize,
+    chunk_index: usize,
+}
+
+impl Shard {
+    fn new(index: usize, count: usize, chunk_index: usize) -> PyResult<Self> {
+        if count == 0 || index >= count {
+            return Err(PyValueError::new_err(format!(
+                "invalid shard {index} of {count}, expected 0 <= shard_index < shard_count"
+            )));
+        }
+        Ok(Self {
+            index,
+            count,
+            chunk_index,
+        })
+    }
+
+    /// Extracts `(shard_index, shard_count)` or `(shard_index, shard_count, chunk_index)`.
+    pub(crate) fn from_py_opt(obj: &Bound<PyAny>) -> PyResult<Option<Self>> {
+        if obj.is_none() {
+            return Ok(None);
+        }
+        if let Ok((index, count)) = obj.extract::<(usize, usize)>() {
+            return Self::new(index, count, DEFAULT_CHUNK_INDEX).map(Some);
+        }
+        let (index, count, chunk_index) = obj.extract::<(usize, usize, usize)>()?;
+        Self::new(index, count, chunk_index).map(Some)
+    }
+
+    fn shard_of(&self, key: &str) -> usize {
+        let first = key
+            .split('/')
+            .nth(self.chunk_index)
+            .and_then(|c| c.bytes().next());
+        let position = first.and_then(|c| SHARD_ALPHABET.iter().position(|&a| a == c));
+        position.map_or(0, |position| position % self.count)
+    }
+
+    pub(crate) fn matches(&self, key: &str) -> bool {
+        self.shard_of(key) == self.index
+    }
+}
+
+#[pyfunction]
+#[pyo3(signature = (base_expr, shard_index, shard_count, *, by = "chunk", chunk_index = DEFAULT_CHUNK_INDEX))]
+pub(crate) fn shard_key_expr(
+    #[pyo3(from_py_with = KeyExpr::from_py)] base_expr: KeyExpr,
+    shard_index: usize,
+    shard_count: usize,
+    by: &str,
+    chunk_index: usize,
+) -> PyResult<Vec<KeyExpr>> {
+    if by != "chunk" {
+        return Err(PyValueError::new_err(format!(
+            "invalid sharding '{by}', expected 'chunk'"
+        )));
+    }
+    let shard = Shard::new(shard_index, shard_count, chunk_index)?;
+    let chunks = base_expr.0.as_str().split('/').collect::<Vec<_>>();
+    let wildcard = match chunks.get(chunk_index) {
+        Some(chunk @ ("*" | "**")) if !chunks[..chunk_index].contains(&"**") => *chunk,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "the chunk {chunk_index} of '{base_expr}' must be a wildcard, \
+                without '**' before it",
+                base_expr = base_expr.0
+            )));
+        }
+    };
+    // shard 0 also gets the chunks starting with characters outside of the alphabet, which
+    // cannot be expressed as a key expression, so it subscribes to everything
+    if shard.index == 0 {
+        return Ok(vec![base_expr]);
+    }
+    let mut chunks = chunks.into_iter().map(String::from).collect::<Vec<_>>();
+    let positions = (0..SHARD_ALPHABET.len()).filter(|n| n % shard.count == shard.index);
+    positions
+        .map(|n| {
+            let first = SHARD_ALPHABET[n] as char;
+            chunks[chunk_index] = match wildcard {
+                "**" => format!("{first}$*/**"),
+                _ => format!("{first}$*"),
+            };
+            KeyExpr::new(chunks.join("/"))
+        })
+        .collect()
+}
+
+#[pyfunction]
+#[pyo3(signature = (key, shard_index, shard_count, *, chunk_index = DEFAULT_CHUNK_INDEX))]
+pub(crate) fn shard_matches(
+    #[pyo3(from_py_with = KeyExpr::from_py)] key: KeyExpr,
+    shard_index: usize,
+    shard_count: usize,
+    chunk_index: usize,
+) -> PyResult<bool> {
+    Ok(Shard::new(shard_index, shard_count, chunk_index)?.matches(key.0.as_str()))
+}
@@ -3,6 +3,7 @@ use std::{num::NonZeroUsize, str, sync::Arc};
 use pyo3::{
     exceptions::{PyTypeError, PyValueError},
     prelude::*,
+    sync::with_critical_section,
     types::{PyByteArray, PyBytes, PySlice, PyString, PyType},
 };
 use zenoh::shm::{ChunkAllocResult, PosixShmProviderBackend, ShmBuf};
@@ -272,7 +273,7 @@ impl ZShmMut {
                 Ok(())
             };
             if let Ok(bytes) = value.downcast::<PyByteArray>() {
-                return copy_bytes(unsafe { bytes.as_bytes() });
+                return with_critical_section(bytes, || copy_bytes(unsafe { bytes.as_bytes() }));
             } else if let Ok(bytes) = value.downcast::<PyBytes>() {
                 return copy_bytes(bytes.as_bytes());
             }
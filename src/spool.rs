@@ -0,0 +1,209 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use pyo3::{
+    prelude::*,
+    types::{PyDict, PyTuple},
+};
+use zenoh::bytes::ZBytes;
+
+use crate::{
+    files::io_error,
+    query::{first_ok, reply_results, Reply},
+};
+
+/// Position of a payload in the spool file.
+struct Spooled {
+    offset: u64,
+    len: usize,
+}
+
+struct Spool {
+    path: PathBuf,
+    file: File,
+}
+
+impl Spool {
+    fn create() -> PyResult<Self> {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+        let name = format!(
+            "zenoh-get-spool-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(name);
+        let options = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .clone();
+        let file = options
+            .open(&path)
+            .map_err(|err| io_error("create", &path, err))?;
+        Ok(Self { path, file })
+    }
+
+    fn write(&mut self, payload: &ZBytes) -> PyResult<Spooled> {
+        let write = |file: &mut File| {
+            let offset = file.seek(SeekFrom::End(0))?;
+            file.write_all(&payload.to_bytes())?;
+            Ok(offset)
+        };
+        let offset = write(&mut self.file).map_err(|err| io_error("write", &self.path, err))?;
+        Ok(Spooled {
+            offset,
+            len: payload.len(),
+        })
+    }
+
+    fn read(&mut self, spooled: &Spooled) -> PyResult<ZBytes> {
+        let mut bytes = vec![0; spooled.len];
+        let read = |file: &mut File| {
+            file.seek(SeekFrom::Start(spooled.offset))?;
+            file.read_exact(&mut bytes)
+        };
+        read(&mut self.file).map_err(|err| io_error("read", &self.path, err))?;
+        Ok(bytes.into())
+    }
+}
+
+impl Drop for Spool {
+    fn drop(&mut self) {
+        std::fs::remove_file(&self.path).ok();
+    }
+}
+
+/// The replies of a get, whose payloads beyond a memory budget are spilled to a temporary file,
+/// see `Session::get_spooled`.
+///
+/// Only the payloads are spilled, the other reply fields being kept in memory.
+#[pyclass]
+pub(crate) struct SpooledReplies {
+    replies: VecDeque<(zenoh::query::Reply, Option<Spooled>)>,
+    spool: Option<Spool>,
+}
+
+impl SpooledReplies {
+    /// Collects the replies, spilling the payloads once `max_memory_bytes` is exceeded; the
+    /// following ones are all spilled, the replies order being kept anyway.
+    pub(crate) fn collect(
+        replies: impl IntoIterator<Item = zenoh::query::Reply>,
+        max_memory_bytes: usize,
+    ) -> PyResult<Self> {
+        let mut spooled_replies = Self {
+            replies: VecDeque::new(),
+            spool: None,
+        };
+        let mut memory_bytes = 0;
+        for mut reply in replies {
+            let Ok(sample) = reply.result_mut() else {
+                spooled_replies.replies.push_back((reply, None));
+                continue;
+            };
+            memory_bytes += sample.payload().len();
+            if memory_bytes <= max_memory_bytes {
+                spooled_replies.replies.push_back((reply, None));
+                continue;
+            }
+            let spool = match &mut spooled_replies.spool {
+                Some(spool) => spool,
+                spool => spool.insert(Spool::create()?),
+            };
+            let spooled = spool.write(sample.payload())?;
+            *sample.payload_mut() = ZBytes::new();
+            spooled_replies.replies.push_back((reply, Some(spooled)));
+        }
+        Ok(spooled_replies)
+    }
+}
+
+#[pymethods]
+impl SpooledReplies {
+    #[getter]
+    fn spool_path(&self) -> Option<PathBuf> {
+        self.spool.as_ref().map(|spool| spool.path.clone())
+    }
+
+    #[getter]
+    fn spooled_count(&self) -> usize {
+        self.replies
+            .iter()
+            .filter(|(_, spooled)| spooled.is_some())
+            .count()
+    }
+
+    fn close(&mut self, py: Python) {
+        self.replies.clear();
+        py.allow_threads(|| drop(self.spool.take()));
+    }
+
+    fn __enter__<'a, 'py>(this: &'a Bound<'py, Self>) -> &'a Bound<'py, Self> {
+        this
+    }
+
+    #[pyo3(signature = (*_args, **_kwargs))]
+    fn __exit__(
+        &mut self,
+        py: Python,
+        _args: &Bound<PyTuple>,
+        _kwargs: Option<&Bound<PyDict>>,
+    ) -> PyResult<PyObject> {
+        self.close(py);
+        Ok(py.None())
+    }
+
+    fn ok(this: &Bound<Self>) -> PyResult<Vec<PyObject>> {
+        reply_results(this.as_any(), true)
+    }
+
+    fn errors(this: &Bound<Self>) -> PyResult<Vec<PyObject>> {
+        reply_results(this.as_any(), false)
+    }
+
+    fn first(this: &Bound<Self>) -> PyResult<Option<PyObject>> {
+        first_ok(this.as_any())
+    }
+
+    fn __len__(&self) -> usize {
+        self.replies.len()
+    }
+
+    fn __iter__(this: Py<Self>) -> Py<Self> {
+        this
+    }
+
+    fn __next__(&mut self, py: Python) -> PyResult<Option<Reply>> {
+        let Some((mut reply, spooled)) = self.replies.pop_front() else {
+            return Ok(None);
+        };
+        if let (Some(spooled), Some(spool)) = (spooled, &mut self.spool) {
+            let payload = py.allow_threads(|| spool.read(&spooled))?;
+            if let Ok(sample) = reply.result_mut() {
+                *sample.payload_mut() = payload;
+            }
+        }
+        if self.replies.is_empty() {
+            // the spool file is removed as soon as it has been read
+            self.close(py);
+        }
+        Ok(Some(reply.into()))
+    }
+}
@@ -13,20 +13,95 @@
 //
 use std::{
     hash::{Hash, Hasher},
+    sync::atomic::{AtomicBool, Ordering},
     time::{Duration, SystemTime},
 };
 
 use pyo3::{
-    exceptions::PyTypeError,
+    exceptions::{PyTypeError, PyValueError},
     prelude::*,
-    types::{PyBytes, PyDateTime, PyType},
+    types::{IntoPyDict, PyBytes, PyDateTime, PyType, PyTzInfo},
 };
 
 use crate::{
-    macros::{downcast_or_new, wrapper},
+    macros::{downcast_or_new, import, wrapper},
+    session::Session,
     utils::{IntoPyErr, IntoPyResult},
 };
 
+/// Encoding schema of datetimes serialized as RFC3339 text.
+pub(crate) const DATETIME_SCHEMA: &str = "rfc3339";
+/// Version of the binary formats of timestamps and zenoh ids, written as their first byte.
+///
+/// The formats are stable, any change requires a new version, while still decoding the previous
+/// ones:
+/// - timestamp: version, NTP64 time as big-endian u64, id significant bytes in little-endian
+/// - zenoh id: version, id significant bytes in little-endian
+pub(crate) const BINARY_FORMAT_VERSION: u8 = 1;
+
+/// Returns the payload of a binary formatted value, after checking its version.
+pub(crate) fn binary_format_payload<'a>(bytes: &'a [u8], name: &str) -> PyResult<&'a [u8]> {
+    match bytes.split_first() {
+        Some((&BINARY_FORMAT_VERSION, payload)) => Ok(payload),
+        Some((version, _)) => Err(PyValueError::new_err(format!(
+            "unsupported {name} binary format version {version}"
+        ))),
+        None => Err(PyValueError::new_err(format!("empty {name} bytes"))),
+    }
+}
+
+static NAIVE_DATETIME_AS_UTC: AtomicBool = AtomicBool::new(false);
+
+#[pyfunction]
+pub(crate) fn set_naive_datetime_policy(policy: &str) -> PyResult<()> {
+    let as_utc = match policy {
+        "error" => false,
+        "utc" => true,
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "invalid naive datetime policy '{policy}', expected 'error' or 'utc'"
+            )));
+        }
+    };
+    NAIVE_DATETIME_AS_UTC.store(as_utc, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Formats a datetime as RFC3339, keeping microseconds and UTC offset.
+///
+/// Naive datetimes are rejected, unless the naive datetime policy is "utc".
+pub(crate) fn datetime_to_rfc3339(datetime: &Bound<PyDateTime>) -> PyResult<String> {
+    let py = datetime.py();
+    let mut datetime = datetime.as_any().clone();
+    if datetime.call_method0("utcoffset")?.is_none() {
+        if !NAIVE_DATETIME_AS_UTC.load(Ordering::Relaxed) {
+            return Err(PyValueError::new_err(format!(
+                "naive datetime '{datetime}' has no timezone, attach one with `tzinfo`, \
+                or call `zenoh.set_naive_datetime_policy(\"utc\")`"
+            )));
+        }
+        let kwargs = [("tzinfo", PyTzInfo::utc(py)?)].into_py_dict(py)?;
+        datetime = datetime.call_method("replace", (), Some(&kwargs))?;
+    }
+    datetime.call_method0("isoformat")?.extract()
+}
+
+/// Parses an RFC3339 text into an aware datetime.
+pub(crate) fn datetime_from_rfc3339<'py>(py: Python<'py>, s: &str) -> PyResult<Bound<'py, PyAny>> {
+    // `fromisoformat` doesn't support "Z" suffix before Python 3.11
+    let s = match s.strip_suffix(['Z', 'z']) {
+        Some(s) => format!("{s}+00:00"),
+        None => s.to_string(),
+    };
+    let datetime = import!(py, datetime.datetime).call_method1("fromisoformat", (&s,))?;
+    if datetime.call_method0("utcoffset")?.is_none() {
+        return Err(PyValueError::new_err(format!(
+            "invalid RFC3339 datetime '{s}': missing UTC offset"
+        )));
+    }
+    Ok(datetime)
+}
+
 wrapper!(zenoh::time::TimestampId: Copy, Clone, PartialEq, PartialOrd);
 downcast_or_new!(TimestampId => Vec<u8>);
 
@@ -77,12 +152,25 @@ impl Timestamp {
                 .into()
         } else if let Ok(ntp) = time.extract::<NTP64>() {
             ntp.0
+        } else if let Ok(secs) = time.extract::<f64>() {
+            // seconds since the Unix epoch, like `time.time()`
+            Duration::try_from_secs_f64(secs)
+                .map_err(|_| PyValueError::new_err(format!("invalid timestamp time {secs}")))?
+                .into()
         } else {
-            return Err(PyTypeError::new_err("expected a `datetime` or a `NTP64`"));
+            return Err(PyTypeError::new_err(
+                "expected a `datetime`, a `NTP64` or a float",
+            ));
         };
         Ok(Self(zenoh::time::Timestamp::new(ntp, id.0)))
     }
 
+    /// Same as `Session::new_timestamp`, for symmetry with `datetime.now`.
+    #[classmethod]
+    fn now(_cls: &Bound<PyType>, session: &Session) -> Self {
+        session.0.new_timestamp().into()
+    }
+
     fn get_time(&self) -> SystemTime {
         self.0.get_time().to_system_time()
     }
@@ -95,6 +183,29 @@ impl Timestamp {
         (*self.0.get_id()).into()
     }
 
+    #[getter]
+    fn ntp64(&self) -> u64 {
+        self.0.get_time().as_u64()
+    }
+
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        let id = self.0.get_id();
+        let mut bytes = vec![BINARY_FORMAT_VERSION];
+        bytes.extend_from_slice(&self.0.get_time().as_u64().to_be_bytes());
+        bytes.extend_from_slice(&id.to_le_bytes()[..id.size()]);
+        PyBytes::new(py, &bytes)
+    }
+
+    #[classmethod]
+    fn from_bytes(_cls: &Bound<PyType>, bytes: &[u8]) -> PyResult<Self> {
+        let payload = binary_format_payload(bytes, "timestamp")?;
+        let invalid = || PyValueError::new_err(format!("invalid timestamp bytes {bytes:?}"));
+        let (time, id) = payload.split_first_chunk::<8>().ok_or_else(invalid)?;
+        let id = zenoh::time::TimestampId::try_from(id).map_err(|_| invalid())?;
+        let time = zenoh::time::NTP64(u64::from_be_bytes(*time));
+        Ok(Self(zenoh::time::Timestamp::new(time, id)))
+    }
+
     fn get_diff_duration(&self, other: Timestamp) -> Duration {
         self.0.get_diff_duration(&other.0)
     }
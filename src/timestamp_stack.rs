@@ -120,7 +120,7 @@ impl TimestampInstrumentationBuilder {
         self.0
             .build()
             .map(TimestampInstrumentation)
-            .map_err(|e| crate::ZError::new_err(e.to_string()))
+            .map_err(|e| crate::error::new_zerror(e.to_string()))
     }
 }
 
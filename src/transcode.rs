@@ -0,0 +1,94 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use serde_json::{Map, Value};
+use zenoh::bytes::Encoding;
+
+/// Encoding of `key=value` pairs separated by `;`, like selector parameters.
+const PROPERTIES: &str = "application/properties";
+
+#[derive(Clone, Copy)]
+enum Format {
+    Json,
+    Properties,
+    Text,
+}
+
+impl Format {
+    fn of(encoding: &Encoding) -> Option<Self> {
+        let id = encoding.id();
+        if [Encoding::TEXT_JSON.id(), Encoding::APPLICATION_JSON.id()].contains(&id) {
+            Some(Self::Json)
+        } else if [Encoding::TEXT_PLAIN.id(), Encoding::ZENOH_STRING.id()].contains(&id) {
+            Some(Self::Text)
+        } else if *encoding == Encoding::from(PROPERTIES) {
+            Some(Self::Properties)
+        } else {
+            None
+        }
+    }
+}
+
+fn json_to_properties(text: &str) -> Result<String, String> {
+    let Ok(Value::Object(map)) = serde_json::from_str::<Value>(text) else {
+        return Err("only JSON objects can be converted to properties".into());
+    };
+    let mut properties = Vec::with_capacity(map.len());
+    for (key, value) in map {
+        let value = match value {
+            Value::String(s) => s,
+            Value::Number(_) | Value::Bool(_) => value.to_string(),
+            _ => return Err(format!("JSON value of '{key}' is not a scalar")),
+        };
+        if [&key, &value].iter().any(|s| s.contains([';', '='])) {
+            return Err(format!("property '{key}' contains ';' or '='"));
+        }
+        properties.push(format!("{key}={value}"));
+    }
+    Ok(properties.join(";"))
+}
+
+fn properties_to_json(text: &str) -> String {
+    let parameters = zenoh::query::Parameters::from(text);
+    let map = parameters
+        .iter()
+        .map(|(k, v)| (k.to_string(), Value::from(v)));
+    Value::Object(map.collect::<Map<_, _>>()).to_string()
+}
+
+/// Transcodes a payload between the JSON, properties and text encodings.
+///
+/// Returns `None` if there is no transcoding path between the encodings, or an error if the
+/// payload can't be transcoded along the path. JSON and properties are converted to text as is,
+/// text to a JSON string, and JSON objects of scalars to properties and back, values becoming
+/// strings.
+pub(crate) fn transcode(
+    payload: &[u8],
+    from: &Encoding,
+    to: &Encoding,
+) -> Option<Result<Vec<u8>, String>> {
+    let (from, to) = (Format::of(from)?, Format::of(to)?);
+    let Ok(text) = std::str::from_utf8(payload) else {
+        return Some(Err("payload is not valid UTF-8".into()));
+    };
+    let transcoded = match (from, to) {
+        (Format::Json, Format::Json)
+        | (Format::Properties, Format::Properties)
+        | (_, Format::Text) => Ok(text.to_string()),
+        (Format::Text, Format::Json) => Ok(Value::from(text).to_string()),
+        (Format::Json, Format::Properties) => json_to_properties(text),
+        (Format::Properties, Format::Json) => Ok(properties_to_json(text)),
+        (Format::Text, Format::Properties) => return None,
+    };
+    Some(transcoded.map(String::into_bytes))
+}
@@ -16,6 +16,8 @@ use std::time::Duration;
 use pyo3::{exceptions::PyValueError, prelude::*, types::PyType, IntoPyObjectExt};
 
 use crate::{
+    debug::{self, PendingKey},
+    error::{new_zerror_with_code, ErrorCode},
     macros::{import, into_rust},
     ZError,
 };
@@ -39,9 +41,9 @@ impl<T, E> IntoResult<T, E> for Result<T, E> {
 pub(crate) trait IntoPyErr {
     fn into_pyerr(self) -> PyErr;
 }
-impl<E: ToString> IntoPyErr for E {
+impl<E: ToString + 'static> IntoPyErr for E {
     fn into_pyerr(self) -> PyErr {
-        ZError::new_err(self.to_string())
+        new_zerror_with_code(self.to_string(), ErrorCode::of(&self))
     }
 }
 pub(crate) trait IntoPyResult<T> {
@@ -129,3 +131,40 @@ pub(crate) fn duration(obj: &Bound<PyAny>) -> PyResult<Option<Duration>> {
         .map(Some)
         .map_err(|_| PyValueError::new_err("negative timeout"))
 }
+
+/// Run `f`, prefixing any [`ZError`] it raises with the operation name and the
+/// key expression/selector it was applied to.
+///
+/// Both are also stored as `operation` and `key_expr` attributes of the exception,
+/// while the `code` of the original error is kept. The call is listed by
+/// `debug::pending_operations` while running.
+pub(crate) fn with_context<T>(
+    operation: &'static str,
+    key_expr: &Bound<PyAny>,
+    f: impl FnOnce() -> PyResult<T>,
+) -> PyResult<T> {
+    let py = key_expr.py();
+    let key = || PendingKey::Python(key_expr.clone().unbind());
+    let _pending = debug::pending(py, operation, key);
+    f().map_err(|err| {
+        if !err.is_instance_of::<ZError>(py) {
+            return err;
+        }
+        let key_expr = match key_expr.str() {
+            Ok(s) => s.to_string(),
+            Err(_) => return err,
+        };
+        let Ok(code) = err.value(py).getattr("code") else {
+            return err;
+        };
+        let new_err = ZError::new_err(format!("{operation} on '{key_expr}': {}", err.value(py)));
+        let value = new_err.value(py);
+        if value.setattr("operation", operation).is_err()
+            || value.setattr("key_expr", key_expr).is_err()
+            || value.setattr("code", code).is_err()
+        {
+            return err;
+        }
+        new_err
+    })
+}
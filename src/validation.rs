@@ -0,0 +1,58 @@
+//
+// Copyright (c) 2025 ZettaScale Technology
+//
+// This program and the accompanying materials are made available under the
+// terms of the Eclipse Public License 2.0 which is available at
+// http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+// which is available at https://www.apache.org/licenses/LICENSE-2.0.
+//
+// SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+//
+// Contributors:
+//   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+//
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+use zenoh::bytes::Encoding;
+
+use crate::bytes::ZBytes;
+
+static PUBLISH_VALIDATION: AtomicBool = AtomicBool::new(false);
+
+#[pyfunction]
+pub(crate) fn set_publish_validation(enabled: bool) {
+    PUBLISH_VALIDATION.store(enabled, Ordering::Relaxed);
+}
+
+/// Checks that payloads with text encodings are valid UTF-8, and valid JSON for JSON encodings,
+/// if `validate` is set, or if it is not given and publish validation is enabled.
+pub(crate) fn validate_payload(
+    validate: Option<bool>,
+    payload: &ZBytes,
+    encoding: &Encoding,
+) -> PyResult<()> {
+    if !validate.unwrap_or_else(|| PUBLISH_VALIDATION.load(Ordering::Relaxed)) {
+        return Ok(());
+    }
+    let id = encoding.id();
+    let json = [Encoding::TEXT_JSON.id(), Encoding::APPLICATION_JSON.id()].contains(&id);
+    if !json && id != Encoding::TEXT_PLAIN.id() {
+        return Ok(());
+    }
+    let bytes = payload.0.to_bytes();
+    let text = std::str::from_utf8(&bytes).map_err(|err| {
+        let offset = err.valid_up_to();
+        PyValueError::new_err(format!(
+            "invalid UTF-8 payload for encoding '{encoding}' at byte offset {offset}"
+        ))
+    })?;
+    if json {
+        if let Err(err) = serde_json::from_str::<serde_json::Value>(text) {
+            return Err(PyValueError::new_err(format!(
+                "invalid JSON payload for encoding '{encoding}': {err}"
+            )));
+        }
+    }
+    Ok(())
+}
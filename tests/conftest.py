@@ -0,0 +1,39 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+from contextlib import ExitStack
+
+import pytest
+
+import zenoh
+
+
+@pytest.fixture
+def open_session():
+    """Opens sessions with multicast scouting disabled, closed on teardown.
+
+    Keyword arguments are passed to ``zenoh.open``."""
+    with ExitStack() as stack:
+
+        def open_session(**kwargs) -> zenoh.Session:
+            conf = zenoh.Config()
+            conf.insert_json5("scouting/multicast/enabled", "false")
+            return stack.enter_context(zenoh.open(conf, **kwargs))
+
+        yield open_session
+
+
+@pytest.fixture
+def session(open_session) -> zenoh.Session:
+    """A session with multicast scouting disabled, closed on teardown."""
+    return open_session()
@@ -0,0 +1,77 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import queue
+
+import pytest
+
+import zenoh
+from zenoh import Query, ZBytes
+
+ATTACHMENT = {"trace-id": "42", b"\xff\xfe": b"\x00\x80", "empty": ""}
+
+
+def test_put_attachment(session: zenoh.Session):
+    subscriber = session.declare_subscriber("attachment/**")
+    session.put("attachment/put", "value", attachment=ATTACHMENT)
+    assert subscriber.recv().attachment == ATTACHMENT
+    session.delete("attachment/delete", attachment=ATTACHMENT)
+    assert subscriber.recv().attachment == ATTACHMENT
+    publisher = session.declare_publisher("attachment/pub")
+    publisher.put("value", attachment={"key": b"value"})
+    assert subscriber.recv().attachment == {"key": "value"}
+    # dicts are serialized by ZBytes.from_dict, for the other bindings to read them
+    publisher.put("value", attachment=ZBytes.from_dict(ATTACHMENT))
+    assert subscriber.recv().attachment == ATTACHMENT
+    # other attachments are kept as is
+    publisher.put("value", attachment=b"raw")
+    sample = subscriber.recv()
+    assert sample.raw_attachment.to_bytes() == b"raw"
+    with pytest.raises(ValueError):
+        sample.attachment
+    publisher.put("value")
+    sample = subscriber.recv()
+    assert sample.attachment is None
+    assert sample.raw_attachment is None
+    publisher.undeclare()
+    subscriber.undeclare()
+
+
+def test_query_attachment(session: zenoh.Session):
+    queries: queue.Queue[dict] = queue.Queue()
+
+    def reply(query: Query):
+        queries.put(query.attachment)
+        query.reply(query.key_expr, "value", attachment={"reply": "yes"})
+
+    queryable = session.declare_queryable("attachment/query", reply)
+    [received] = session.get("attachment/query", attachment=ATTACHMENT, timeout=1)
+    assert queries.get(timeout=1) == ATTACHMENT
+    assert received.ok.attachment == {"reply": "yes"}
+    queryable.undeclare()
+
+
+def test_sample_attachment():
+    sample = zenoh.Sample("attachment/sample", "value", attachment=ATTACHMENT)
+    assert sample.attachment == ATTACHMENT
+    assert sample.with_attachment({"key": "value"}).attachment == {"key": "value"}
+    assert sample.with_attachment(None).attachment is None
+
+
+def test_invalid_attachment(session: zenoh.Session):
+    with pytest.raises(ValueError):
+        ZBytes(b"\x05").to_dict()
+    with pytest.raises(TypeError):
+        ZBytes.from_dict({"key": 1})
+    with pytest.raises(TypeError):
+        session.put("attachment/a", "value", attachment={"key": 1})
@@ -0,0 +1,77 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import array
+
+import pytest
+
+import zenoh
+from zenoh import Encoding, ZBytes
+
+
+def test_zbytes_from_buffer():
+    data = bytes(range(16))
+    assert ZBytes(memoryview(data)[4:10]).to_bytes() == data[4:10]
+    # non-contiguous views are copied in C order
+    assert ZBytes(memoryview(data)[1::2]).to_bytes() == data[1::2]
+    values = array.array("H", [1, 2, 3])
+    assert ZBytes(values).to_bytes() == values.tobytes()
+    with pytest.raises(TypeError):
+        ZBytes(42)
+
+
+def test_put_buffer(session: zenoh.Session):
+    data = bytearray(range(32))
+    subscriber = session.declare_subscriber("buffer/**")
+    session.put("buffer/view", memoryview(data)[8:16])
+    sample = subscriber.recv()
+    assert sample.payload.to_bytes() == bytes(data[8:16])
+    assert sample.encoding == Encoding.APPLICATION_OCTET_STREAM
+    # bytearray and str keep the default encoding, an explicit one is kept
+    session.put("buffer/bytearray", data)
+    assert subscriber.recv().encoding == Encoding.ZENOH_BYTES
+    session.put("buffer/str", "text")
+    assert subscriber.recv().encoding == Encoding.ZENOH_BYTES
+    session.put("buffer/view", memoryview(data), encoding="text/plain")
+    assert subscriber.recv().encoding == Encoding.TEXT_PLAIN
+    subscriber.undeclare()
+
+
+def test_put_readonly_numpy_array(session: zenoh.Session):
+    np = pytest.importorskip("numpy")
+    values = np.arange(12, dtype=np.uint16).reshape(3, 4)
+    values.flags.writeable = False
+    subscriber = session.declare_subscriber("buffer/numpy")
+    session.put("buffer/numpy", values)
+    sample = subscriber.recv()
+    assert sample.payload.to_bytes() == values.tobytes()
+    assert sample.encoding == Encoding.APPLICATION_OCTET_STREAM
+    # a column is not contiguous
+    session.put("buffer/numpy", values[:, 1])
+    assert subscriber.recv().payload.to_bytes() == values[:, 1].tobytes()
+    subscriber.undeclare()
+
+
+def test_bool_and_none(session: zenoh.Session):
+    assert ZBytes(True).to_bytes() == b"true"
+    assert ZBytes(False).to_bool() is False
+    assert ZBytes(True).to_bool() is True
+    assert ZBytes(None).to_bytes() == b""
+    with pytest.raises(ValueError):
+        ZBytes("1").to_bool()
+    subscriber = session.declare_subscriber("buffer/bool")
+    session.put("buffer/bool", True)
+    sample = subscriber.recv()
+    assert sample.payload.to_bool() is True
+    assert sample.encoding == Encoding.APPLICATION_JSON
+    subscriber.undeclare()
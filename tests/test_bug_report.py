@@ -0,0 +1,63 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import json
+
+import pytest
+
+import zenoh
+from zenoh import EntityGroup
+
+PASSWORD = "hunter2-do-not-leak"
+CREDENTIALS = ("alice", PASSWORD)
+
+
+def test_dump_state_text(open_session):
+    session = open_session(credentials=CREDENTIALS)
+    group = EntityGroup(session)
+    group.declare_subscriber("report/sub", lambda s: None)
+    dump = session.dump_state()
+    for section in (
+        "versions",
+        "session",
+        "config",
+        "transports",
+        "entities",
+        "counters",
+    ):
+        assert f"[{section}]" in dump
+    assert str(session.zid()) in dump
+    assert "report/sub" in dump
+    assert "transport/auth/usrpwd/user = alice" in dump
+    assert "transport/auth/usrpwd/password = <redacted>" in dump
+    assert PASSWORD not in dump
+    group.close_all()
+
+
+def test_dump_state_json(open_session):
+    session = open_session(credentials=CREDENTIALS)
+    state = json.loads(session.dump_state(format="json"))
+    assert state["format_version"] == 1
+    assert state["versions"]["zenoh-python"]
+    assert state["session"]["zid"] == str(session.zid())
+    assert state["config"]["transport/auth/usrpwd/password"] == "<redacted>"
+    assert PASSWORD not in json.dumps(state)
+    with pytest.raises(ValueError):
+        session.dump_state(format="yaml")
+
+
+def test_bug_report(open_session, tmp_path):
+    path = tmp_path / "report.txt"
+    session = open_session(credentials=CREDENTIALS)
+    zenoh.bug_report(session, path)
+    assert path.read_text() == session.dump_state()
@@ -0,0 +1,83 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import threading
+import time
+
+import pytest
+
+import zenoh
+from zenoh import ErrorCode, Query, ZError
+
+
+class MockClock:
+    def __init__(self):
+        self.time = 0.0
+
+    def now(self) -> float:
+        return self.time
+
+    def advance(self, seconds: float):
+        self.time += seconds
+
+
+@pytest.fixture
+def mock_clock():
+    clock = MockClock()
+    try:
+        zenoh.debug.set_mock_clock(clock)
+    except ZError as err:
+        assert err.code == ErrorCode.FEATURE_UNAVAILABLE
+        pytest.skip("built without the mock-clock feature")
+    yield clock
+    zenoh.debug.set_mock_clock(None)
+
+
+def test_get_cache_ttl(session: zenoh.Session, mock_clock: MockClock):
+    queries = []
+
+    def reply(query: Query):
+        queries.append(query)
+        query.reply(query.key_expr, str(len(queries)))
+
+    session.declare_queryable("clock/cache", reply)
+    zenoh.enable_get_cache(ttl=30)
+    for _ in range(3):
+        session.get_cached("clock/cache")
+    mock_clock.advance(29)
+    session.get_cached("clock/cache")
+    assert len(queries) == 1
+    mock_clock.advance(1)
+    [reply] = session.get_cached("clock/cache")
+    assert reply.ok.payload.to_string() == "2"
+
+
+def test_watchdog(session: zenoh.Session, mock_clock: MockClock):
+    subscriber = session.declare_subscriber("clock/watchdog")
+    reported = []
+    zenoh.debug.enable_watchdog(0.05, callback=reported.append)
+    try:
+        thread = threading.Thread(target=subscriber.recv)
+        thread.start()
+        time.sleep(0.2)
+        # no time elapsed for the mock clock
+        assert reported == []
+        mock_clock.advance(1)
+        deadline = time.monotonic() + 5
+        while not reported and time.monotonic() < deadline:
+            time.sleep(0.01)
+        assert [(r.thread_id, r.age) for r in reported] == [(thread.ident, 1.0)]
+        session.put("clock/watchdog", "release")
+        thread.join()
+    finally:
+        zenoh.debug.disable_watchdog()
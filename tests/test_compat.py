@@ -0,0 +1,75 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import pytest
+
+import zenoh
+from zenoh import Locality, Reliability
+
+
+@pytest.mark.parametrize(
+    "option, enable",
+    [
+        ("allowed_origin", lambda builder: builder.allowed_origin(Locality.ANY)),
+        ("verify_integrity", lambda builder: builder.verify_integrity()),
+        ("auto_decode", lambda builder: builder.auto_decode()),
+    ],
+)
+def test_pico_subscriber_unsupported(session: zenoh.Session, option, enable):
+    builder = session.subscriber_builder("compat/a").compatibility("pico")
+    enable(builder)
+    with pytest.raises(ValueError, match=option):
+        builder.declare(lambda *_: None)
+
+
+def test_pico_queryable_unsupported(session: zenoh.Session):
+    with pytest.raises(ValueError, match="allowed_origin"):
+        session.declare_queryable(
+            "compat/a", compatibility="pico", allowed_origin=Locality.ANY
+        )
+
+
+@pytest.mark.parametrize(
+    "kwargs",
+    [
+        {"allowed_destination": Locality.ANY},
+        {"reliability": Reliability.RELIABLE},
+        {"retain": True},
+    ],
+)
+def test_pico_publisher_unsupported(session: zenoh.Session, kwargs):
+    with pytest.raises(ValueError, match=next(iter(kwargs))):
+        session.declare_publisher("compat/a", compatibility="pico", **kwargs)
+
+
+def test_unknown_compatibility(session: zenoh.Session):
+    with pytest.raises(ValueError):
+        session.declare_publisher("compat/a", compatibility="micro")
+
+
+def test_pico_compatible_declarations(session: zenoh.Session):
+    builder = session.subscriber_builder("compat/**").compatibility("pico")
+    subscriber = builder.declare()
+    queryable = session.declare_queryable(
+        "compat/**",
+        lambda query: query.reply(query.key_expr, "reply"),
+        compatibility="pico",
+    )
+    publisher = session.declare_publisher("compat/a", compatibility="pico")
+    publisher.put("value")
+    assert subscriber.recv().payload.to_string() == "value"
+    [reply] = session.get("compat/a", timeout=1)
+    assert reply.ok.payload.to_string() == "reply"
+    publisher.undeclare()
+    queryable.undeclare()
+    subscriber.undeclare()
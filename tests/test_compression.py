@@ -0,0 +1,75 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import json
+import os
+
+import pytest
+
+import zenoh
+from zenoh import Encoding, ZError
+
+PAYLOADS = [
+    json.dumps([{"sensor": i, "value": 21.5} for i in range(100)]).encode(),
+    os.urandom(4096),
+    b"",
+]
+
+
+@pytest.mark.parametrize("compression", ["zstd", "lz4"])
+def test_compression_roundtrip(session: zenoh.Session, compression: str):
+    subscriber = session.declare_subscriber("test/compression")
+    publisher = session.declare_publisher(
+        "test/compression", encoding=Encoding.APPLICATION_JSON
+    )
+    for payload in PAYLOADS:
+        session.put(
+            "test/compression",
+            payload,
+            encoding=Encoding.TEXT_PLAIN,
+            compression=compression,
+        )
+        sample = subscriber.recv()
+        assert str(sample.encoding) == f"text/plain;{compression}"
+        assert sample.decode().to_bytes() == payload
+        assert sample.decode(decompress=False).to_bytes() == sample.payload.to_bytes()
+
+        publisher.put(payload, compression=compression)
+        sample = subscriber.recv()
+        assert str(sample.encoding) == f"application/json;{compression}"
+        assert sample.decode().to_bytes() == payload
+
+    # compressible payloads are actually compressed
+    session.put("test/compression", PAYLOADS[0], compression=compression)
+    sample = subscriber.recv()
+    assert str(sample.encoding) == f"zenoh/bytes;{compression}"
+    assert len(sample.payload) < len(PAYLOADS[0])
+    publisher.undeclare()
+    subscriber.undeclare()
+
+
+def test_compression_errors(session: zenoh.Session):
+    subscriber = session.declare_subscriber("test/compression")
+    with pytest.raises(ValueError):
+        session.put("test/compression", "value", compression="zip")
+
+    session.put("test/compression", "value", encoding="text/plain;gzip")
+    with pytest.raises(ZError, match="gzip"):
+        subscriber.recv().decode()
+    session.put("test/compression", "value", encoding="text/plain;zstd")
+    with pytest.raises(ZError, match="decompress"):
+        subscriber.recv().decode()
+    # other suffixes are schemas, left as is
+    session.put("test/compression", "value", encoding="text/plain;utf8")
+    assert subscriber.recv().decode().to_string() == "value"
+    subscriber.undeclare()
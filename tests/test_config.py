@@ -0,0 +1,157 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import json
+import queue
+
+import pytest
+
+import zenoh
+from zenoh import Config, ErrorCode, ZError
+
+PASSWORD = "hunter2-do-not-leak"
+
+
+def test_config_keys():
+    keys = Config.keys()
+    assert "timestamping/enabled" in keys
+    assert "routing/router/peers_failover_brokering" in keys
+    assert "transport/unicast/lowlatency" in keys
+    config = Config()
+    for key in keys:
+        assert Config.validate_key(key) == key
+        config.get_json(key)
+
+
+def test_validate_key():
+    assert Config.validate_key("timestamping.enabled") == "timestamping/enabled"
+    assert Config.validate_key("/scouting/multicast/") == "scouting/multicast"
+    assert Config.validate_key("plugins.rest.http_port") == "plugins/rest/http_port"
+    with pytest.raises(ValueError, match="did you mean 'timestamping/enabled'"):
+        Config.validate_key("timestamping.enabeld")
+    with pytest.raises(ValueError, match="did you mean 'transport/unicast/lowlatency'"):
+        Config.validate_key("transport/unicast/low_latency")
+    with pytest.raises(ValueError, match="unknown config key 'unknown'$"):
+        Config.validate_key("unknown")
+
+
+def test_set_credentials():
+    config = Config()
+    config.set_credentials("alice", PASSWORD)
+    assert json.loads(config.get_json("transport/auth/usrpwd/user")) == "alice"
+    usrpwd = json.loads(config.to_json())["transport"]["auth"]["usrpwd"]
+    assert usrpwd["user"] == "alice"
+    assert usrpwd["password"] == "<redacted>"
+    for text in (config.to_json(), str(config), repr(config)):
+        assert PASSWORD not in text
+    with pytest.raises(ValueError):
+        config.set_credentials("", PASSWORD)
+
+
+def test_set_tls(tmp_path):
+    root_ca, cert, key = (tmp_path / name for name in ("ca.pem", "cert.pem", "key.pem"))
+    for path in (root_ca, cert, key):
+        path.write_text("-----BEGIN CERTIFICATE-----")
+    config = Config()
+    config.set_tls(root_ca=root_ca, cert=str(cert), key=key, verify_name=False)
+    tls = json.loads(config.to_json())["transport"]["link"]["tls"]
+    assert tls["root_ca_certificate"] == str(root_ca)
+    assert tls["listen_certificate"] == tls["connect_certificate"] == str(cert)
+    assert tls["listen_private_key"] == tls["connect_private_key"] == "<redacted>"
+    assert tls["verify_name_on_connect"] is False
+
+    missing = tmp_path / "missing.pem"
+    config = Config()
+    with pytest.raises(ZError, match="missing.pem") as excinfo:
+        config.set_tls(root_ca=root_ca, cert=cert, key=missing)
+    assert excinfo.value.code == ErrorCode.IO
+    # the configuration is left unchanged
+    tls = json.loads(config.to_json())["transport"]["link"]["tls"]
+    assert tls["root_ca_certificate"] is None
+    with pytest.raises(ZError, match=str(tmp_path)):
+        config.set_tls(root_ca=tmp_path)
+    with pytest.raises(ValueError):
+        config.set_tls(cert=cert)
+
+
+def test_helpers_precedence(tmp_path):
+    path = tmp_path / "config.json5"
+    usrpwd = {"user": "file-user", "password": "file-password"}
+    path.write_text(
+        json.dumps(
+            {
+                "scouting": {"multicast": {"enabled": False}},
+                "transport": {"auth": {"usrpwd": usrpwd}},
+            }
+        )
+    )
+    config = Config.from_file(path)
+    with zenoh.open(config, credentials=("alice", PASSWORD)) as session:
+        state = json.loads(session.dump_state(format="json"))
+        assert state["config"]["transport/auth/usrpwd/user"] == "alice"
+    # the configuration passed to open is left unchanged
+    assert json.loads(config.get_json("transport/auth/usrpwd/user")) == "file-user"
+    config.set_credentials("bob", PASSWORD)
+    assert json.loads(config.get_json("transport/auth/usrpwd/user")) == "bob"
+    with pytest.raises(ZError, match="missing.pem"):
+        zenoh.open(config, tls={"root_ca": tmp_path / "missing.pem"})
+
+
+def test_open_with_config_object():
+    config = Config.from_json5("{scouting: {multicast: {enabled: false}}}")
+    config.insert_json5("mode", '"peer"')
+    # the peer keeps trying to connect in background
+    config.insert_json5("connect/endpoints", '["tcp/127.0.0.1:17469"]')
+    assert json.loads(config.get_json("mode")) == "peer"
+    with zenoh.open(config) as session:
+        opened = json.loads(session.config().to_json())
+        assert opened["mode"] == "peer"
+        assert opened["connect"]["endpoints"] == ["tcp/127.0.0.1:17469"]
+    legacy = {"mode": "peer", "scouting": {"multicast": {"enabled": False}}}
+    with zenoh.open(legacy) as session:
+        assert json.loads(session.config().get_json("mode")) == "peer"
+        # the configuration of the session is the live one
+        session.config().insert_json5("queries_default_timeout", "5000")
+        timeout = session.config().get_json("queries_default_timeout")
+        assert json.loads(timeout) == 5000
+
+
+def test_config_errors():
+    with pytest.raises(ZError):
+        Config.from_json5("{mode: ")
+    with pytest.raises(ZError):
+        Config().insert_json5("unknown/path", "true")
+    with pytest.raises(ZError):
+        Config().insert_json5("mode", '"router-ish"')
+
+
+def test_config_on_change():
+    config = Config.from_json5("{scouting: {multicast: {enabled: false}}}")
+    with zenoh.open(config) as session:
+        changes = queue.Queue()
+        config = session.config()
+        watcher = config.on_change("connect", lambda *change: changes.put(change))
+        # not under the prefix
+        config.insert_json5("queries_default_timeout", "5000")
+        config.insert_json5("connect/endpoints", '["tcp/127.0.0.1:17470"]')
+        key, value = changes.get(timeout=5)
+        assert key == "connect/endpoints"
+        assert value == ["tcp/127.0.0.1:17470"]
+        assert json.loads(config.get_json("queries_default_timeout")) == 5000
+        with pytest.raises(ZError):
+            config.insert_json5("unknown/path", "true")
+        watcher.unsubscribe()
+        assert watcher.unsubscribed
+        config.insert_json5("connect/endpoints", "[]")
+        with pytest.raises(queue.Empty):
+            changes.get(timeout=0.5)
@@ -0,0 +1,131 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import gc
+import json
+import weakref
+from dataclasses import InitVar, asdict, dataclass, field
+from typing import ClassVar, Optional, TypedDict
+
+import pytest
+
+import zenoh
+from zenoh import Encoding, ZBytes
+
+
+class Tags(TypedDict):
+    unit: str
+    labels: list
+
+
+@dataclass
+class Position:
+    x: float
+    y: float
+
+
+@dataclass
+class Reading:
+    sensor: str
+    value: Optional[float]
+    position: Position
+    history: list
+    tags: Tags
+    previous: Optional["Reading"] = None
+    secret: str = field(default="hidden", metadata={"zenoh_exclude": True})
+    count: ClassVar[int] = 0
+    init_only: InitVar[int] = 0
+
+
+def reading() -> Reading:
+    return Reading(
+        sensor="temp-1",
+        value=21.5,
+        position=Position(1.0, -2.5),
+        history=[1, 2.5, None, (True, "a")],
+        tags={"unit": "celsius", "labels": ["indoor"]},
+        previous=Reading(
+            "temp-0", None, Position(0, 0), [], {"unit": "", "labels": []}
+        ),
+    )
+
+
+def expected(obj) -> dict:
+    # excluded fields are not serialized, and tuples become lists
+    def strip(value):
+        if isinstance(value, dict):
+            return {k: strip(v) for k, v in value.items() if k != "secret"}
+        if isinstance(value, (list, tuple)):
+            return [strip(v) for v in value]
+        return value
+
+    return strip(asdict(obj))
+
+
+def test_dataclass_zbytes():
+    obj = reading()
+    assert json.loads(ZBytes(obj).to_string()) == expected(obj)
+    # the field list cached per type is reused
+    assert json.loads(ZBytes(obj).to_string()) == expected(obj)
+    with pytest.raises(TypeError):
+        ZBytes(Reading)
+
+
+def test_dataclass_put(session: zenoh.Session):
+    subscriber = session.declare_subscriber("test/dataclass")
+    obj = reading()
+
+    session.put("test/dataclass", obj)
+    sample = subscriber.recv()
+    assert sample.encoding == Encoding.APPLICATION_JSON
+    assert json.loads(sample.payload.to_string()) == expected(obj)
+
+    session.put("test/dataclass", obj, encoding=Encoding.TEXT_JSON)
+    assert subscriber.recv().encoding == Encoding.TEXT_JSON
+
+    publisher = session.declare_publisher("test/dataclass")
+    publisher.put(Position(3, 4))
+    sample = subscriber.recv()
+    assert sample.encoding == Encoding.APPLICATION_JSON
+    assert json.loads(sample.payload.to_string()) == {"x": 3, "y": 4}
+
+    # the publisher encoding is not overridden
+    publisher = session.declare_publisher(
+        "test/dataclass", encoding=Encoding.TEXT_JSON5
+    )
+    publisher.put(Position(3, 4))
+    assert subscriber.recv().encoding == Encoding.TEXT_JSON5
+
+
+def test_dataclass_unsupported_field():
+    obj = reading()
+    obj.previous.history = [0, {1, 2}]
+    with pytest.raises(TypeError, match=r"previous\.history\[1\]"):
+        ZBytes(obj)
+    obj = reading()
+    obj.tags["labels"] = [float("nan")]
+    with pytest.raises(ValueError, match=r"tags\.labels\[0\]"):
+        ZBytes(obj)
+
+
+def test_dataclass_type_collected():
+    @dataclass
+    class Local:
+        a: int
+
+    assert json.loads(ZBytes(Local(1)).to_string()) == {"a": 1}
+    ref = weakref.ref(Local)
+    del Local
+    gc.collect()
+    # serialized types are not kept alive
+    assert ref() is None
@@ -0,0 +1,87 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+from datetime import datetime, timedelta, timezone
+
+import pytest
+
+import zenoh
+from zenoh import Encoding, Parameters, Selector, ZBytes
+
+DATETIMES = [
+    datetime(2025, 3, 14, 15, 9, 26, 535897, tzinfo=timezone.utc),
+    datetime(2025, 3, 14, 15, 9, 26, tzinfo=timezone(timedelta(hours=5, minutes=30))),
+    datetime(1999, 12, 31, 23, 59, 59, 1, tzinfo=timezone(timedelta(hours=-8))),
+]
+
+
+def test_zbytes_round_trip():
+    for dt in DATETIMES:
+        payload = ZBytes(dt)
+        assert payload.to_string() == dt.isoformat()
+        decoded = payload.to_datetime()
+        assert decoded == dt
+        assert decoded.microsecond == dt.microsecond
+        assert decoded.utcoffset() == dt.utcoffset()
+
+
+def test_zbytes_parse_utc_suffix():
+    dt = ZBytes("2025-03-14T15:09:26.5Z").to_datetime()
+    assert dt == datetime(2025, 3, 14, 15, 9, 26, 500000, tzinfo=timezone.utc)
+
+
+def test_zbytes_invalid_datetime():
+    with pytest.raises(ValueError):
+        ZBytes("not a datetime").to_datetime()
+    with pytest.raises(ValueError):
+        ZBytes("2025-03-14T15:09:26").to_datetime()
+
+
+def test_encoding():
+    assert str(Encoding.ZENOH_DATETIME) == "zenoh/string;rfc3339"
+
+
+def test_parameters():
+    dt = DATETIMES[1]
+    params = Parameters({"since": dt, "limit": "10"})
+    assert params["since"] == dt.isoformat()
+    assert ZBytes(params["since"]).to_datetime() == dt
+    params.insert("until", DATETIMES[0])
+    assert params["until"] == DATETIMES[0].isoformat()
+    selector = Selector("demo/**", {"since": dt})
+    assert selector.parameters["since"] == dt.isoformat()
+
+
+def test_naive_datetime_policy():
+    naive = datetime(2025, 3, 14, 15, 9, 26, 535897)
+    with pytest.raises(ValueError, match="timezone"):
+        ZBytes(naive)
+    with pytest.raises(ValueError, match="timezone"):
+        Parameters({"since": naive})
+    with pytest.raises(ValueError):
+        zenoh.set_naive_datetime_policy("local")
+    zenoh.set_naive_datetime_policy("utc")
+    try:
+        assert ZBytes(naive).to_datetime() == naive.replace(tzinfo=timezone.utc)
+        params = Parameters({"since": naive})
+        assert params["since"] == "2025-03-14T15:09:26.535897+00:00"
+    finally:
+        zenoh.set_naive_datetime_policy("error")
+
+
+def test_put_datetime(session: zenoh.Session):
+    subscriber = session.declare_subscriber("test/datetime")
+    session.put("test/datetime", DATETIMES[0], encoding=Encoding.ZENOH_DATETIME)
+    sample = subscriber.recv()
+    assert sample.encoding == Encoding.ZENOH_DATETIME
+    assert sample.payload.to_datetime() == DATETIMES[0]
@@ -0,0 +1,112 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import inspect
+import subprocess
+import sys
+import textwrap
+import threading
+import time
+
+import zenoh
+
+
+def lineno() -> int:
+    return inspect.currentframe().f_back.f_lineno
+
+
+def test_open_handles(open_session):
+    zenoh.debug.track_handles(True)
+    try:
+        session = open_session()
+        subscriber = session.declare_subscriber("debug/leak")
+        line = lineno() - 1
+        # callback subscribers run in background, and are not undeclared when dropped
+        session.declare_subscriber("debug/background", lambda s: None)
+        background_line = lineno() - 1
+        handles = zenoh.debug.open_handles()
+        assert len(handles) == 3
+        assert handles[0].entity is session
+        assert "in open_session" in handles[0].traceback
+        assert handles[1].entity is subscriber
+        assert handles[1].location == f"{__file__}:{line}"
+        assert "in test_open_handles" in handles[1].traceback
+        assert handles[2].location == f"{__file__}:{background_line}"
+        subscriber.undeclare()
+        assert len(zenoh.debug.open_handles()) == 2
+        session.close()
+        assert zenoh.debug.open_handles() == []
+    finally:
+        zenoh.debug.track_handles(False)
+
+
+def test_disabled(session: zenoh.Session):
+    session.declare_subscriber("debug/leak")
+    assert zenoh.debug.open_handles() == []
+
+
+def test_report_at_exit():
+    script = textwrap.dedent(
+        """
+        import zenoh
+        zenoh.debug.track_handles(True)
+        conf = zenoh.Config()
+        conf.insert_json5("scouting/multicast/enabled", "false")
+        session = zenoh.open(conf)
+        subscriber = session.declare_subscriber("debug/leak")
+        """
+    )
+    result = subprocess.run(
+        [sys.executable, "-c", script], capture_output=True, text=True, timeout=60
+    )
+    assert "zenoh: 2 handles still open at exit" in result.stderr
+    assert "Subscriber created at <string>:7" in result.stderr
+
+
+def pending_gets() -> list[zenoh.debug.PendingOperation]:
+    return [p for p in zenoh.debug.pending_operations() if p.operation == "get"]
+
+
+def test_pending_disabled(session: zenoh.Session):
+    queryable = session.declare_queryable("debug/pending")
+    replies = session.get_handle("debug/pending", timeout=0.5)
+    thread = threading.Thread(target=lambda: list(replies))
+    thread.start()
+    time.sleep(0.2)
+    assert not any(p.thread_id == thread.ident for p in pending_gets())
+    thread.join()
+    queryable.undeclare()
+
+
+def test_pending_operations(session: zenoh.Session):
+    # the queryable never replies
+    queryable = session.declare_queryable("debug/pending")
+    reported = []
+    zenoh.debug.enable_watchdog(0.2, callback=reported.append)
+    try:
+        replies = session.get_handle("debug/pending", timeout=1.5)
+        thread = threading.Thread(target=lambda: list(replies))
+        thread.start()
+        time.sleep(0.5)
+        [pending] = [p for p in pending_gets() if p.thread_id == thread.ident]
+        assert pending.key_expr == "debug/pending"
+        time.sleep(0.2)
+        [later] = [p for p in pending_gets() if p.thread_id == thread.ident]
+        assert later.age > pending.age >= 0.5
+        thread.join()
+        assert not any(p.thread_id == thread.ident for p in pending_gets())
+        assert [(r.operation, r.thread_id) for r in reported] == [("get", thread.ident)]
+    finally:
+        zenoh.debug.disable_watchdog()
+        zenoh.debug.track_pending(False)
+    queryable.undeclare()
@@ -0,0 +1,115 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import time
+from dataclasses import dataclass
+from typing import Any
+
+import pytest
+
+import zenoh
+from zenoh import Sample, ZBytes
+
+SLEEP = 1
+
+
+@dataclass
+class Pose:
+    x: float
+    y: float
+
+    @staticmethod
+    def parse(payload: bytes) -> "Pose":
+        x, y = payload.split(b",")
+        return Pose(float(x), float(y))
+
+
+def test_register_type(session: zenoh.Session):
+    subscriber = session.declare_subscriber("test/fleet/**")
+    poses = zenoh.register_type("test/fleet/*/pose", Pose.parse)
+    raw = zenoh.register_type("test/fleet/**", lambda payload: ("raw", payload))
+    try:
+        # first registered match wins
+        session.put("test/fleet/robot1/pose", "1.5,2")
+        assert subscriber.recv().decode() == Pose(1.5, 2)
+        session.put("test/fleet/robot1/battery", "42")
+        assert subscriber.recv().decode() == ("raw", b"42")
+
+        assert poses.unregister()
+        assert not poses.unregister()
+        session.put("test/fleet/robot1/pose", "1.5,2")
+        assert subscriber.recv().decode() == ("raw", b"1.5,2")
+
+        # fallback to the payload
+        assert raw.unregister()
+        session.put("test/fleet/robot1/pose", "1.5,2")
+        decoded = subscriber.recv().decode()
+        assert isinstance(decoded, ZBytes) and decoded.to_string() == "1.5,2"
+    finally:
+        poses.unregister()
+        raw.unregister()
+
+
+def test_register_type_error(session: zenoh.Session):
+    subscriber = session.declare_subscriber("test/fleet/**")
+    registration = zenoh.register_type("test/fleet/*/pose", Pose.parse)
+    try:
+        session.put("test/fleet/robot1/pose", "invalid")
+        with pytest.raises(ValueError):
+            subscriber.recv().decode()
+    finally:
+        registration.unregister()
+    with pytest.raises(TypeError):
+        zenoh.register_type("test/fleet/**", None)
+
+
+def test_auto_decode(session: zenoh.Session):
+    registration = zenoh.register_type("test/fleet/*/pose", Pose.parse)
+    received: list[tuple[Sample, Any]] = []
+    subscriber = (
+        session.subscriber_builder("test/fleet/**")
+        .auto_decode()
+        .declare(lambda sample, decoded: received.append((sample, decoded)))
+    )
+    # the drop callback of `Callback` handlers is kept
+    poses: list[Pose] = []
+    dropped = []
+    other = (
+        session.subscriber_builder("test/fleet/*/pose")
+        .auto_decode()
+        .declare(
+            zenoh.handlers.Callback(
+                lambda _, decoded: poses.append(decoded), lambda: dropped.append(True)
+            )
+        )
+    )
+    time.sleep(SLEEP)
+    try:
+        session.put("test/fleet/robot1/pose", "3,4")
+        session.put("test/fleet/robot1/battery", "42")
+        time.sleep(SLEEP)
+        assert [str(s.key_expr) for s, _ in received] == [
+            "test/fleet/robot1/pose",
+            "test/fleet/robot1/battery",
+        ]
+        assert received[0][1] == Pose(3, 4)
+        assert received[1][1].to_string() == "42"
+        assert poses == [Pose(3, 4)]
+
+        other.undeclare()
+        assert dropped == [True]
+        with pytest.raises(ValueError):
+            session.subscriber_builder("test/fleet/**").auto_decode().declare()
+    finally:
+        registration.unregister()
+        subscriber.undeclare()
@@ -0,0 +1,68 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import pytest
+
+import zenoh
+from zenoh import Encoding, Query
+
+# a prefix unknown to the bindings, as another binding could produce
+EXOTIC_PREFIX = 4242
+
+
+def test_prefix_id():
+    assert Encoding.ZENOH_BYTES.prefix_id == 0
+    assert Encoding("application/json").prefix_id == Encoding.APPLICATION_JSON.prefix_id
+    exotic = Encoding.from_prefix_id(EXOTIC_PREFIX, "v2")
+    assert exotic.prefix_id == EXOTIC_PREFIX
+    assert exotic != Encoding.from_prefix_id(EXOTIC_PREFIX)
+    assert Encoding.from_prefix_id(EXOTIC_PREFIX, "v2") == exotic
+    with pytest.raises(OverflowError):
+        Encoding.from_prefix_id(1 << 16)
+
+
+def test_exotic_prefix_passthrough(session: zenoh.Session):
+    exotic = Encoding.from_prefix_id(EXOTIC_PREFIX, "v2")
+    subscriber = session.declare_subscriber("encoding/**")
+    session.put("encoding/original", b"\x01\x02", encoding=exotic)
+    received = subscriber.recv()
+    assert received.encoding.prefix_id == EXOTIC_PREFIX
+    # republishing the received encoding emits the same one
+    encoding = received.encoding
+    session.put("encoding/republished", received.payload, encoding=encoding)
+    republished = subscriber.recv()
+    assert republished.encoding == exotic
+    assert republished.encoding.prefix_id == EXOTIC_PREFIX
+    subscriber.undeclare()
+
+
+def test_transcoding_error_prefix(session: zenoh.Session):
+    subscriber = session.declare_subscriber("encoding/exotic")
+    exotic = Encoding.from_prefix_id(EXOTIC_PREFIX)
+    session.put("encoding/exotic", b"\x01", encoding=exotic)
+    stored = subscriber.recv()
+    errors = []
+
+    def callback(query: Query):
+        try:
+            query.reply_negotiated(stored, strict=True)
+        except ValueError as err:
+            errors.append(str(err))
+
+    queryable = session.declare_queryable("encoding/exotic", callback)
+    selector = "encoding/exotic?_accept=application/json"
+    assert list(session.get(selector, timeout=1)) == []
+    assert len(errors) == 1
+    assert f"prefix id {EXOTIC_PREFIX}" in errors[0]
+    queryable.undeclare()
+    subscriber.undeclare()
@@ -0,0 +1,98 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import time
+
+import pytest
+
+import zenoh
+from zenoh import EntityGroup, ZError
+
+
+def test_close_all(session: zenoh.Session):
+    group = EntityGroup(session)
+    subscriber = group.declare_subscriber("group/sub", lambda s: None)
+    queryable = group.declare_queryable("group/qbl", lambda q: None)
+    publisher = group.declare_publisher("group/pub")
+    querier = group.declare_querier("group/qrr")
+    token = group.declare_liveliness_token("group/token")
+    assert len(group) == 5
+
+    with pytest.raises(ZError, match="5 open entities"):
+        session.close()
+    assert not session.is_closed()
+
+    # individually closed entities are skipped
+    publisher.undeclare()
+    assert len(group) == 4
+    group.close_all()
+    assert len(group) == 0
+    for entity in (subscriber, queryable, publisher, querier, token):
+        with pytest.raises(ZError):
+            entity.undeclare()
+    session.close()
+    assert session.is_closed()
+
+
+def test_context_manager(session: zenoh.Session):
+    with EntityGroup(session) as group:
+        subscriber = group.declare_subscriber("group/sub")
+        group.add(session.declare_publisher("group/pub"))
+        assert len(group) == 2
+    assert len(group) == 0
+    with pytest.raises(ZError):
+        subscriber.undeclare()
+
+
+def test_close_force(session: zenoh.Session):
+    group = EntityGroup(session)
+    group.declare_subscriber("group/sub")
+    session.close(force=True)
+    assert session.is_closed()
+    # undeclaring after session closing is a no-op
+    group.close_all()
+
+
+class SlowEntity:
+    def __init__(self, key_expr: str, delay: float):
+        self.key_expr = key_expr
+        self.delay = delay
+
+    def undeclare(self):
+        time.sleep(self.delay)
+
+
+def test_close_force_concurrently(session: zenoh.Session):
+    group = EntityGroup(session)
+    for i in range(47):
+        group.declare_subscriber(f"group/sub/{i}", lambda s: None)
+    for i in range(3):
+        group.add(SlowEntity(f"group/slow/{i}", 5))
+    assert len(group) == 50
+
+    progress = []
+    start = time.monotonic()
+    failures = session.close(
+        force=True,
+        parallelism=4,
+        entity_timeout=0.5,
+        on_progress=lambda done, total, key: progress.append((done, total, key)),
+    )
+    assert time.monotonic() - start < 3
+    assert session.is_closed()
+    assert sorted(key for key, _ in failures) == [f"group/slow/{i}" for i in range(3)]
+    assert all("timed out" in error for _, error in failures)
+    assert [done for done, _, _ in progress] == list(range(1, 51))
+    assert all(total == 50 for _, total, _ in progress)
+    assert {key for _, _, key in progress} >= {"group/sub/0", "group/slow/0"}
+    assert len(group) == 0
@@ -0,0 +1,124 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import multiprocessing
+import pickle
+
+import pytest
+
+import zenoh
+from zenoh import ErrorCode, ZError
+
+INVALID_KEY = "test//invalid"
+
+
+def test_error_context_put(session: zenoh.Session):
+    with pytest.raises(ZError) as excinfo:
+        session.put(INVALID_KEY, "value")
+    assert str(excinfo.value).startswith(f"put on '{INVALID_KEY}': ")
+    assert excinfo.value.operation == "put"
+    assert excinfo.value.key_expr == INVALID_KEY
+
+
+def test_error_context_declare_subscriber(session: zenoh.Session):
+    with pytest.raises(ZError) as excinfo:
+        session.declare_subscriber(INVALID_KEY)
+    assert str(excinfo.value).startswith(f"declare_subscriber on '{INVALID_KEY}': ")
+    assert excinfo.value.operation == "declare_subscriber"
+    assert excinfo.value.key_expr == INVALID_KEY
+
+
+def test_error_context_get(session: zenoh.Session):
+    selector = f"{INVALID_KEY}?arg=1"
+    with pytest.raises(ZError) as excinfo:
+        session.get(selector)
+    assert str(excinfo.value).startswith(f"get on '{selector}': ")
+    assert excinfo.value.operation == "get"
+    assert excinfo.value.key_expr == selector
+
+
+def test_error_without_context():
+    with pytest.raises(ZError) as excinfo:
+        zenoh.KeyExpr(INVALID_KEY)
+    assert excinfo.value.operation is None
+    assert excinfo.value.key_expr is None
+
+
+@pytest.mark.parametrize(
+    "construct, error",
+    [
+        (lambda: zenoh.KeyExpr(INVALID_KEY), ZError),
+        (lambda: zenoh.KeyExpr(["test"]), TypeError),
+        (lambda: zenoh.KeyExpr("test/a").intersects(["test"]), TypeError),
+        (lambda: zenoh.Selector(["test"]), TypeError),
+        (lambda: zenoh.Sample(["test"]), TypeError),
+        (lambda: zenoh.Sample(INVALID_KEY), ZError),
+        (lambda: zenoh.Sample("test/a", [object()]), TypeError),
+        (lambda: zenoh.Sample("test/a", encoding=42), TypeError),
+        (lambda: zenoh.ZBytes(object()), TypeError),
+        (lambda: zenoh.Encoding(42), TypeError),
+        (lambda: zenoh.Parameters(42), TypeError),
+    ],
+)
+def test_constructor_invalid_arguments(construct, error):
+    # invalid arguments raise an exception instead of panicking
+    with pytest.raises(error):
+        construct()
+
+
+def test_error_code(session: zenoh.Session):
+    with pytest.raises(ZError) as excinfo:
+        zenoh.KeyExpr(INVALID_KEY)
+    assert excinfo.value.code == ErrorCode.INVALID_KEYEXPR
+
+    with pytest.raises(ZError) as excinfo:
+        session.put(INVALID_KEY, "value")
+    assert excinfo.value.code == ErrorCode.INVALID_KEYEXPR
+    session.close()
+    with pytest.raises(ZError) as excinfo:
+        session.put("test/closed", "value")
+    assert excinfo.value.code == ErrorCode.SESSION_CLOSED
+
+    # the code doesn't depend on the message
+    with pytest.raises(ZError) as excinfo:
+        zenoh.Config.from_json5('{"timeout": }')
+    assert excinfo.value.code == ErrorCode.OTHER
+    assert ZError("raised from Python").code == ErrorCode.OTHER
+
+
+def test_error_pickle(session: zenoh.Session):
+    with pytest.raises(ZError) as excinfo:
+        session.put(INVALID_KEY, "value")
+    err = pickle.loads(pickle.dumps(excinfo.value))
+    assert type(err) is ZError
+    assert str(err) == str(excinfo.value)
+    assert err.code == ErrorCode.INVALID_KEYEXPR
+    assert err.operation == "put"
+    assert err.key_expr == INVALID_KEY
+
+
+def put_invalid_key(key_expr: str):
+    # runs in a spawned worker process, where the pytest fixtures are not available
+    conf = zenoh.Config()
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    with zenoh.open(conf) as session:
+        session.put(key_expr, "value")
+
+
+def test_error_across_processes():
+    with multiprocessing.get_context("spawn").Pool(1) as pool:
+        with pytest.raises(ZError) as excinfo:
+            pool.apply(put_invalid_key, (INVALID_KEY,))
+    assert excinfo.value.code == ErrorCode.INVALID_KEYEXPR
+    assert excinfo.value.operation == "put"
+    assert excinfo.value.key_expr == INVALID_KEY
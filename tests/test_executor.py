@@ -0,0 +1,155 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import threading
+import time
+
+import pytest
+
+import zenoh
+from zenoh import Executor, Query, Sample
+
+MSG_COUNT = 200
+
+
+def test_executor_mixed_load(session: zenoh.Session):
+    executor = Executor(threads=2)
+    assert executor.threads == 2
+    received = []
+    worker_threads = set()
+
+    def on_sample(sample: Sample):
+        worker_threads.add(threading.current_thread().name)
+        time.sleep(0.001)
+        received.append(int(sample.payload.to_string()))
+
+    def on_query(query: Query):
+        worker_threads.add(threading.current_thread().name)
+        query.reply(query.key_expr, "reply")
+
+    builder = session.subscriber_builder("test/executor/sub").executor(executor)
+    builder.declare(on_sample)
+    session.declare_queryable("test/executor/query", on_query, executor=executor)
+    for i in range(MSG_COUNT):
+        session.put("test/executor/sub", str(i))
+        if i % 20 == 0:
+            replies = list(session.get("test/executor/query", timeout=5))
+            assert [r.ok.payload.to_string() for r in replies] == ["reply"]
+
+    # queued invocations are processed before the workers terminate
+    assert executor.shutdown(wait=True, timeout=10)
+    assert executor.is_shutdown
+    assert (executor.queue_depth, executor.busy_workers) == (0, 0)
+    assert sorted(received) == list(range(MSG_COUNT))
+    assert worker_threads <= {"zenoh-executor-0", "zenoh-executor-1"}
+
+    # entities bound to a shut down executor no longer deliver
+    for i in range(10):
+        session.put("test/executor/sub", str(i))
+    assert list(session.get("test/executor/query", timeout=1)) == []
+    assert len(received) == MSG_COUNT
+    assert executor.dropped == 11
+
+
+def test_executor_max_queue(session: zenoh.Session):
+    started = threading.Event()
+    release = threading.Event()
+    received = []
+
+    def on_sample(sample: Sample):
+        started.set()
+        release.wait()
+        received.append(sample)
+
+    with Executor(max_queue=5) as executor:
+        builder = session.subscriber_builder("test/executor").executor(executor)
+        builder.declare(on_sample)
+        try:
+            session.put("test/executor", "0")
+            assert started.wait(5)
+            for i in range(1, 20):
+                session.put("test/executor", str(i))
+            # one invocation in progress, five queued
+            assert executor.busy_workers == 1
+            assert executor.queue_depth == 5
+        finally:
+            release.set()
+    assert len(received) == 6
+    assert executor.dropped == 14
+
+
+def test_executor_session_close(session: zenoh.Session):
+    # a callback waiting for a lock held while closing the session cannot deadlock
+    lock = threading.Lock()
+    received = []
+
+    def on_sample(sample: Sample):
+        with lock:
+            received.append(sample)
+
+    executor = Executor()
+    session.subscriber_builder("test/executor").executor(executor).declare(on_sample)
+    with lock:
+        for i in range(5):
+            session.put("test/executor", str(i))
+        time.sleep(0.2)
+        session.close()
+    assert executor.shutdown(timeout=5)
+    assert len(received) == 5
+
+
+def test_executor_requires_callback(session: zenoh.Session):
+    executor = Executor()
+    with pytest.raises(ValueError):
+        session.subscriber_builder("test/executor").executor(executor).declare()
+    executor.shutdown()
+
+
+def test_executor_get_lane(session: zenoh.Session):
+    executor = Executor(threads=4)
+
+    def reply_many(query: Query):
+        for i in range(MSG_COUNT):
+            query.reply(f"test/executor/{i}", str(i))
+
+    queryable = session.declare_queryable("test/executor/**", reply_many)
+    calls = {}
+    done = {}
+
+    def get(name: str):
+        calls[name] = []
+
+        def on_reply(reply: zenoh.Reply):
+            index = int(reply.ok.payload.to_string())
+            calls[name].append((threading.get_ident(), index))
+            # lets the other workers pick up the next replies, if they could
+            time.sleep(0.001)
+
+        def on_done():
+            done[name] = len(calls[name])
+
+        callback = zenoh.handlers.Callback(on_reply, on_done)
+        return session.get_handle("test/executor/**", callback, executor=executor)
+
+    handles = [get("first"), get("second")]
+    for handle in handles:
+        handle.wait()
+    assert executor.shutdown(timeout=10)
+    for name in ("first", "second"):
+        assert [i for _, i in calls[name]] == list(range(MSG_COUNT))
+        assert len({thread for thread, _ in calls[name]}) == 1
+        # the done callback ran after all the reply callbacks
+        assert done[name] == MSG_COUNT
+    # the gets were assigned different lanes
+    assert calls["first"][0][0] != calls["second"][0][0]
+    queryable.undeclare()
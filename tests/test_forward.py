@@ -0,0 +1,129 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import json
+import time
+
+import zenoh
+from zenoh import Encoding, Query, SampleKind, Selector
+
+SLEEP = 1
+
+
+def open_session(listen: list[str], connect: list[str]) -> zenoh.Session:
+    conf = zenoh.Config()
+    conf.insert_json5("listen/endpoints", json.dumps(listen))
+    conf.insert_json5("connect/endpoints", json.dumps(connect))
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    return zenoh.open(conf)
+
+
+def test_forward_chain():
+    backend_endpoint = "tcp/127.0.0.1:17455"
+    aggregator_endpoint = "tcp/127.0.0.1:17456"
+    backend = open_session([backend_endpoint], [])
+    aggregator = open_session([aggregator_endpoint], [backend_endpoint])
+    getter = open_session([], [aggregator_endpoint])
+
+    def backend_callback(query: Query):
+        query.reply("backend/sensors/1", "21.5", encoding=Encoding.TEXT_PLAIN)
+        query.reply("backend/sensors/2", "19.0")
+        query.reply_del("backend/sensors/3")
+        query.reply_err("sensor 4 is offline")
+
+    relayed = []
+
+    def aggregator_callback(query: Query):
+        count = query.forward(
+            aggregator, "backend/sensors/**", key_remap=("backend", "api"), timeout=5
+        )
+        relayed.append(count)
+
+    backend_queryable = backend.declare_queryable(
+        "backend/sensors/**", backend_callback
+    )
+    aggregator_queryable = aggregator.declare_queryable(
+        "api/sensors/**", aggregator_callback
+    )
+    time.sleep(SLEEP)
+
+    replies = list(getter.get("api/sensors/**", timeout=10))
+    assert relayed == [4]
+    samples = {r.ok.key_expr: r.ok for r in replies if r.ok is not None}
+    assert set(samples) == {"api/sensors/1", "api/sensors/2", "api/sensors/3"}
+    assert samples["api/sensors/1"].payload.to_string() == "21.5"
+    assert samples["api/sensors/1"].encoding == Encoding.TEXT_PLAIN
+    assert samples["api/sensors/2"].payload.to_string() == "19.0"
+    assert samples["api/sensors/3"].kind == SampleKind.DELETE
+    errors = [r.err for r in replies if r.err is not None]
+    assert [e.payload.to_string() for e in errors] == ["sensor 4 is offline"]
+
+    aggregator_queryable.undeclare()
+    backend_queryable.undeclare()
+    getter.close()
+    aggregator.close()
+    backend.close()
+
+
+def test_forward_local(session: zenoh.Session):
+    backend_queryable = session.declare_queryable(
+        "local/backend/value", lambda q: q.reply("local/backend/value", "value")
+    )
+    relay_queryable = session.declare_queryable(
+        "local/relay/value", lambda q: q.forward(session, "local/backend/value")
+    )
+    replies = list(
+        session.get("local/relay/value", accept_replies=zenoh.ReplyKeyExpr.ANY)
+    )
+    assert [str(r.ok.key_expr) for r in replies] == ["local/backend/value"]
+    relay_queryable.undeclare()
+    backend_queryable.undeclare()
+
+
+def test_selector_rewrite():
+    selector = Selector("api/value?since=2025-01-01T00%3A00%3A00Z;_anyke")
+    rewritten = selector.with_key_expr("backend/value")
+    assert str(rewritten.key_expr) == "backend/value"
+    assert str(rewritten.parameters) == str(selector.parameters)
+    assert str(selector.without_parameters()) == "api/value"
+    selector.key_expr = "other/value"
+    assert str(selector) == "other/value?since=2025-01-01T00%3A00%3A00Z;_anyke"
+    selector.parameters = {"a": "b"}
+    assert str(selector) == "other/value?a=b"
+
+
+def test_forward_rewritten_selector(session: zenoh.Session):
+    received = []
+
+    def backend_callback(query: Query):
+        received.append((str(query.key_expr), str(query.parameters)))
+        query.reply(query.key_expr, "value")
+
+    def relay_callback(query: Query):
+        query.forward(session, query.selector.with_key_expr("local/backend/value"))
+
+    backend_queryable = session.declare_queryable(
+        "local/backend/value", backend_callback
+    )
+    relay_queryable = session.declare_queryable("local/relay/value", relay_callback)
+    parameters = "from=a%20b;to=c%3Bd"
+    replies = list(
+        session.get(
+            f"local/relay/value?{parameters}", accept_replies=zenoh.ReplyKeyExpr.ANY
+        )
+    )
+    assert [r.ok.payload.to_string() for r in replies] == ["value"]
+    # `_anyke` is added by the getter as it accepts replies on any key expression
+    assert received == [("local/backend/value", f"{parameters};_anyke")]
+    relay_queryable.undeclare()
+    backend_queryable.undeclare()
@@ -0,0 +1,82 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import threading
+
+import zenoh
+from zenoh import Query
+
+THREADS = 16
+ITERATIONS = 20
+PUTS = 10
+
+
+def test_concurrent_stress(session: zenoh.Session, open_session):
+    lock = threading.Lock()
+    queried = 0
+    callbacks = 0
+
+    def on_query(query: Query):
+        nonlocal queried
+        with lock:
+            queried += 1
+        query.reply(query.key_expr, "value")
+
+    def on_sample(_sample):
+        nonlocal callbacks
+        with lock:
+            callbacks += 1
+
+    queryable = session.declare_queryable("test/threads/*/query", on_query)
+    ring = zenoh.PayloadRing(THREADS * PUTS, 16)
+    ring_subscriber = session.subscribe_into("test/threads/*/data", ring)
+    barrier = threading.Barrier(THREADS)
+    errors = []
+
+    def worker(i: int):
+        try:
+            barrier.wait()
+            for _ in range(ITERATIONS):
+                subscriber = session.declare_subscriber(f"test/threads/{i}/data")
+                callback_subscriber = session.declare_subscriber(
+                    f"test/threads/{i}/data",
+                    zenoh.handlers.Callback(on_sample, indirect=False),
+                )
+                for j in range(PUTS):
+                    payload = bytearray(f"{i}/{j}", "utf-8")
+                    session.put(f"test/threads/{i}/data", payload)
+                received = [subscriber.recv().payload.to_string() for _ in range(PUTS)]
+                assert received == [f"{i}/{j}" for j in range(PUTS)]
+                callback_subscriber.undeclare()
+                subscriber.undeclare()
+                replies = list(session.get(f"test/threads/{i}/query"))
+                assert [r.ok.payload.to_string() for r in replies] == ["value"]
+                # concurrent consumers of the same ring
+                ring.read_available()
+                other = open_session()
+                other.put(f"test/threads/{i}/other", "value")
+                other.close()
+        except BaseException as err:
+            errors.append(err)
+
+    threads = [threading.Thread(target=worker, args=(i,)) for i in range(THREADS)]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join()
+    assert errors == []
+    assert queried == THREADS * ITERATIONS
+    assert callbacks == THREADS * ITERATIONS * PUTS
+    assert ring.received == THREADS * ITERATIONS * PUTS
+    ring_subscriber.undeclare()
+    queryable.undeclare()
@@ -0,0 +1,89 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import time
+
+import pytest
+
+import zenoh
+from zenoh import Query
+
+
+def counting_queryable(session: zenoh.Session, key_expr: str) -> list[str]:
+    queries = []
+
+    def reply(query: Query):
+        queries.append(str(query.selector))
+        if "fail" in query.parameters:
+            query.reply_err("failed")
+        else:
+            query.reply(query.key_expr, f"reply {len(queries)}")
+
+    session.declare_queryable(key_expr, reply)
+    return queries
+
+
+def payloads(replies: list[zenoh.Reply]) -> list[str]:
+    return [reply.ok.payload.to_string() for reply in replies]
+
+
+def test_get_cache(session: zenoh.Session):
+    queries = counting_queryable(session, "cache/**")
+    zenoh.enable_get_cache(max_entries=2, ttl=30)
+    assert payloads(session.get_cached("cache/a?x=1;y=2")) == ["reply 1"]
+    # parameters order is not significant
+    assert payloads(session.get_cached("cache/a?y=2;x=1")) == ["reply 1"]
+    assert len(queries) == 1
+
+    replies = session.get_cached("cache/a?x=1;y=2", refresh=True)
+    assert payloads(replies) == ["reply 2"]
+    assert payloads(session.get_cached("cache/a?x=1;y=2")) == ["reply 2"]
+
+    # error replies are never cached
+    assert session.get_cached("cache/b?fail")[0].err is not None
+    assert session.get_cached("cache/b?fail")[0].err is not None
+    assert len(queries) == 4
+
+    # the least recently used entry is evicted
+    session.get_cached("cache/c")
+    session.get_cached("cache/a?x=1;y=2")
+    session.get_cached("cache/d")
+    assert payloads(session.get_cached("cache/a?x=1;y=2")) == ["reply 2"]
+    assert payloads(session.get_cached("cache/c")) == ["reply 7"]
+    stats = zenoh.get_cache_stats()
+    assert stats == {
+        "enabled": True,
+        "entries": 2,
+        "hits": 4,
+        "misses": 6,
+        "evictions": 2,
+    }
+
+
+def test_get_cache_ttl(session: zenoh.Session):
+    queries = counting_queryable(session, "cache/ttl")
+    zenoh.enable_get_cache(ttl=0.2)
+    assert payloads(session.get_cached("cache/ttl")) == ["reply 1"]
+    assert payloads(session.get_cached("cache/ttl")) == ["reply 1"]
+    time.sleep(0.3)
+    assert payloads(session.get_cached("cache/ttl")) == ["reply 2"]
+    assert len(queries) == 2
+
+
+def test_get_cache_invalid(session: zenoh.Session):
+    zenoh.enable_get_cache()
+    # cached gets return buffered replies, not handlers
+    with pytest.raises(TypeError):
+        session.get_cached("cache/a", lambda reply: None)
+    with pytest.raises(TypeError):
+        session.get_cached("cache/a", refresh="always")
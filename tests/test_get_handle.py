@@ -0,0 +1,243 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import threading
+import time
+
+import pytest
+
+import zenoh
+from zenoh import ConsolidationMode, ErrorCode, Query, Session, ZError
+
+KEYEXPR = "test/get_handle"
+REPLY_COUNT = 50
+
+
+def reply_stream(query: Query) -> threading.Thread:
+    def run():
+        for i in range(REPLY_COUNT):
+            query.reply(KEYEXPR, str(i))
+            time.sleep(0.01)
+        query.drop()
+
+    thread = threading.Thread(target=run)
+    thread.start()
+    return thread
+
+
+def test_get_handle(session: Session):
+    queryable = session.declare_queryable(KEYEXPR)
+    handle = session.get_handle(KEYEXPR, consolidation=ConsolidationMode.NONE)
+    assert isinstance(handle, zenoh.GetHandle)
+    reply_stream(queryable.recv()).join()
+    replies = [reply.ok.payload.to_string() for reply in handle]
+    assert replies == [str(i) for i in range(REPLY_COUNT)]
+    assert handle.replies_received == REPLY_COUNT
+    assert handle.is_done()
+
+
+def test_get_handle_cancel_iterator(session: Session):
+    queryable = session.declare_queryable(KEYEXPR)
+    handle = session.get_handle(KEYEXPR, consolidation=ConsolidationMode.NONE)
+    replier = reply_stream(queryable.recv())
+    received = 0
+    for _ in handle:
+        received += 1
+        if received == 3:
+            handle.cancel()
+    assert received == 3
+    assert handle.is_done()
+    replier.join()
+
+
+def test_get_handle_cancel_external_thread(session: Session):
+    queryable = session.declare_queryable(KEYEXPR)
+    received = []
+    reached = threading.Event()
+
+    def on_reply(reply: zenoh.Reply):
+        received.append(reply)
+        if len(received) == 5:
+            reached.set()
+
+    handle = session.get_handle(
+        KEYEXPR, on_reply, consolidation=ConsolidationMode.NONE
+    )
+    replier = reply_stream(queryable.recv())
+    canceller = threading.Thread(target=lambda: (reached.wait(), handle.cancel()))
+    canceller.start()
+    canceller.join()
+    received_at_cancel = len(received)
+    replier.join()
+    time.sleep(0.1)
+    assert received_at_cancel < REPLY_COUNT
+    assert len(received) == received_at_cancel
+    assert handle.is_done()
+
+
+def test_get_handle_cancel_in_callback(session: Session):
+    queryable = session.declare_queryable(KEYEXPR)
+    received = []
+    handle_ready = threading.Event()
+    handle = None
+
+    def on_reply(reply: zenoh.Reply):
+        handle_ready.wait()
+        received.append(reply)
+        if len(received) == 3:
+            handle.cancel()
+
+    handle = session.get_handle(
+        KEYEXPR, on_reply, consolidation=ConsolidationMode.NONE
+    )
+    handle_ready.set()
+    reply_stream(queryable.recv()).join()
+    time.sleep(0.1)
+    assert len(received) == 3
+    assert handle.is_done()
+
+
+def test_get_timeout(session: Session):
+    # the queries are kept in the channel, so only their timeout finalizes them
+    queryable = session.declare_queryable(KEYEXPR)
+    start = time.monotonic()
+    assert list(session.get_handle(KEYEXPR, timeout=0)) == []
+    assert time.monotonic() - start < 0.5
+    queryable.recv()
+
+    handle = session.get_handle(KEYEXPR, timeout=0.5)
+    query = queryable.recv()
+    query.reply(KEYEXPR, "partial")
+    # the replies received before the timeout are returned
+    assert [r.ok.payload.to_string() for r in handle] == ["partial"]
+
+    handle = session.get_handle(KEYEXPR, timeout=0.5, raise_on_timeout=True)
+    query = queryable.recv()
+    query.reply(KEYEXPR, "partial")
+    replies = []
+    with pytest.raises(ZError, match="1 replies received") as excinfo:
+        for reply in handle:
+            replies.append(reply.ok.payload.to_string())
+    assert replies == ["partial"]
+    assert excinfo.value.code == ErrorCode.TIMEOUT
+    query.drop()
+    queryable.undeclare()
+
+    # gets completed before their timeout don't raise
+    reply = lambda query: query.reply(KEYEXPR, "ok")
+    queryable = session.declare_queryable(KEYEXPR, reply)
+    handle = session.get_handle(KEYEXPR, timeout=5, raise_on_timeout=True)
+    assert [r.ok.payload.to_string() for r in handle] == ["ok"]
+    queryable.undeclare()
+
+
+def test_get_handle_streaming(session: Session):
+    replied = threading.Event()
+
+    def slow_reply(query: Query):
+        time.sleep(1)
+        query.reply(KEYEXPR, "slow")
+        replied.set()
+
+    fast = session.declare_queryable(
+        KEYEXPR, lambda query: query.reply(KEYEXPR, "fast")
+    )
+    slow = session.declare_queryable(KEYEXPR, slow_reply)
+    handle = session.get_handle(KEYEXPR, consolidation=ConsolidationMode.NONE)
+    # the first reply is available before the slow queryable answers
+    assert handle.recv().ok.payload.to_string() == "fast"
+    assert not replied.is_set()
+    handle.cancel()
+    assert list(handle) == []
+    fast.undeclare()
+    slow.undeclare()
+
+
+def test_get_handle_wait(session: Session):
+    queryable = session.declare_queryable(KEYEXPR)
+    received = []
+
+    def on_reply(reply: zenoh.Reply):
+        received.append(reply.ok.payload.to_string())
+        if len(received) == 1:
+            raise RuntimeError("logged, not propagated")
+
+    handle = session.get_handle(
+        KEYEXPR, on_reply, consolidation=ConsolidationMode.NONE
+    )
+    assert not handle.wait(timeout=0.1)
+    replier = reply_stream(queryable.recv())
+    assert handle.wait(timeout=5)
+    assert received == [str(i) for i in range(REPLY_COUNT)]
+    assert handle.is_done()
+    replier.join()
+
+
+def test_replies_accessors_streaming(session: Session):
+    queryable = session.declare_queryable(KEYEXPR)
+    handle = session.get_handle(KEYEXPR, consolidation=ConsolidationMode.NONE)
+    query = queryable.recv()
+    query.reply_err("failed")
+    for i in range(3):
+        query.reply(KEYEXPR, str(i))
+    time.sleep(0.1)
+    assert handle.__length_hint__() == 4
+    # the error reply is consumed
+    assert handle.first().payload.to_string() == "0"
+    assert handle.__length_hint__() == 2
+    query.reply_err("failed again")
+    query.drop()
+    assert [sample.payload.to_string() for sample in handle.ok()] == ["1", "2"]
+    assert handle.errors() == []
+    assert handle.first() is None
+
+    handle = session.get_handle(KEYEXPR, consolidation=ConsolidationMode.NONE)
+    query = queryable.recv()
+    query.reply_err("failed")
+    query.reply(KEYEXPR, "value")
+    query.drop()
+    errors = handle.errors()
+    assert [error.payload.to_string() for error in errors] == ["failed"]
+    queryable.undeclare()
+
+
+def test_replies_accessors_buffered(session: Session):
+    def reply(query: Query):
+        query.reply_err("failed")
+        for i in range(3):
+            query.reply(KEYEXPR, str(i))
+
+    queryable = session.declare_queryable(KEYEXPR, reply)
+    zenoh.enable_get_cache(max_entries=1, ttl=30)
+    results = session.get_many([KEYEXPR], consolidation=ConsolidationMode.NONE)
+    replies = results[KEYEXPR]
+    assert isinstance(replies, zenoh.Replies)
+    assert len(replies) == 4
+    assert replies[0].err.payload.to_string() == "failed"
+    assert replies[-1].ok.payload.to_string() == "2"
+    with pytest.raises(IndexError):
+        replies[4]
+    # buffered replies are not consumed
+    for _ in range(2):
+        assert [reply.err is not None for reply in replies] == [True] + [False] * 3
+        assert len(list(replies)) == 4
+        assert [s.payload.to_string() for s in replies.ok()] == ["0", "1", "2"]
+        assert len(replies.errors()) == 1
+        assert replies.first().payload.to_string() == "0"
+
+    replies = session.get_cached(
+        KEYEXPR, consolidation=ConsolidationMode.NONE, refresh=True
+    )
+    assert isinstance(replies, zenoh.Replies)
+    assert [s.payload.to_string() for s in replies.ok()] == ["0", "1", "2"]
+    queryable.undeclare()
@@ -0,0 +1,96 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import base64
+import io
+import json
+import struct
+
+import pytest
+
+import zenoh
+from zenoh import Query
+
+PAYLOADS = {"export/a": b"alpha", "export/b": b"\xff\x00", "export/c": b""}
+
+
+def reply(query: Query):
+    for key, payload in PAYLOADS.items():
+        query.reply(key, payload)
+    query.reply_err("failed", encoding="text/plain")
+
+
+class ChunkWriter:
+    def __init__(self):
+        self.chunks: list[bytes] = []
+
+    def write(self, chunk: bytes):
+        self.chunks.append(chunk)
+
+
+def test_get_to_ndjson(session: zenoh.Session):
+    queryable = session.declare_queryable("export/**", reply)
+    output = io.BytesIO()
+    assert session.get_to("export/**", output, timeout=1) == 4
+    records = [json.loads(line) for line in output.getvalue().splitlines()]
+    errors = [record for record in records if "error" in record]
+    assert errors == [{"error": "failed", "encoding": "text/plain"}]
+    samples = {r["key_expr"]: r for r in records if "error" not in r}
+    assert samples.keys() == PAYLOADS.keys()
+    assert samples["export/a"]["payload"] == "alpha"
+    assert samples["export/a"]["kind"] == "PUT"
+    assert samples["export/a"]["attachment"] is None
+    encoded = samples["export/b"]["payload"]["base64"]
+    assert base64.b64decode(encoded) == PAYLOADS["export/b"]
+    queryable.undeclare()
+
+
+def test_get_to_raw(session: zenoh.Session):
+    queryable = session.declare_queryable("export/**", reply)
+    output = io.BytesIO()
+    # error replies are skipped
+    assert session.get_to("export/**", output, format="raw", timeout=1) == 3
+    data, payloads = output.getvalue(), []
+    while data:
+        (length,) = struct.unpack("<I", data[:4])
+        payloads.append(data[4 : 4 + length])
+        data = data[4 + length :]
+    assert sorted(payloads) == sorted(PAYLOADS.values())
+    queryable.undeclare()
+
+
+def test_get_to_chunks(session: zenoh.Session):
+    queryable = session.declare_queryable("export/**", reply)
+    writer = ChunkWriter()
+    count = session.get_to(
+        "export/**", writer, format="raw", chunk_size=8, timeout=1
+    )
+    assert count == 3
+    assert all(len(chunk) >= 8 for chunk in writer.chunks[:-1])
+    assert len(b"".join(writer.chunks)) == 3 * 4 + 7
+    queryable.undeclare()
+
+
+def test_get_to_errors(session: zenoh.Session):
+    class FailingWriter:
+        def write(self, chunk: bytes):
+            raise OSError("disk full")
+
+    queryable = session.declare_queryable("export/**", reply)
+    with pytest.raises(OSError, match="disk full"):
+        session.get_to("export/**", FailingWriter(), timeout=1)
+    with pytest.raises(ValueError):
+        session.get_to("export/**", io.BytesIO(), format="csv")
+    with pytest.raises(ValueError):
+        session.get_to("export/**", io.BytesIO(), chunk_size=0)
+    queryable.undeclare()
@@ -0,0 +1,101 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import time
+
+import pytest
+
+import zenoh
+from zenoh import Sample
+
+SLEEP = 1
+
+
+@pytest.mark.parametrize("integrity", ["crc32c", "xxh3"])
+def test_integrity_roundtrip(session: zenoh.Session, integrity: str):
+    builder = session.subscriber_builder("test/integrity")
+    subscriber = builder.verify_integrity().declare()
+    publisher = session.declare_publisher("test/integrity")
+
+    session.put("test/integrity", b"payload", integrity=integrity)
+    sample = subscriber.recv()
+    assert sample.payload.to_bytes() == b"payload"
+    assert sample.attachment is None
+
+    publisher.put(b"payload", attachment=b"attachment", integrity=integrity)
+    sample = subscriber.recv()
+    assert sample.payload.to_bytes() == b"payload"
+    assert sample.raw_attachment.to_bytes() == b"attachment"
+
+    # the digest is computed on the compressed payload
+    session.put("test/integrity", b"x" * 1024, compression="zstd", integrity=integrity)
+    assert subscriber.recv().decode().to_bytes() == b"x" * 1024
+
+    assert subscriber.corrupt_count == 0
+    assert subscriber.unverified_count == 0
+
+
+def test_integrity_tampered_bridge(session: zenoh.Session):
+    received: list[Sample] = []
+    corrupted: list[Sample] = []
+    subscriber = (
+        session.subscriber_builder("test/integrity/out")
+        .verify_integrity(on_corrupt=corrupted.append)
+        .declare(received.append)
+    )
+
+    # bridging transform altering the payload, but forwarding the attachment as is
+    def bridge(sample: Sample):
+        payload = sample.payload.to_bytes()
+        if payload.startswith(b"tamper"):
+            payload = payload.upper()
+        session.put("test/integrity/out", payload, attachment=sample.raw_attachment)
+
+    bridge_subscriber = session.declare_subscriber("test/integrity/in", bridge)
+    time.sleep(SLEEP)
+
+    session.put("test/integrity/in", b"intact", integrity="crc32c")
+    session.put("test/integrity/in", b"tampered", integrity="crc32c")
+    session.put("test/integrity/in", b"tampered", integrity="xxh3")
+    time.sleep(SLEEP)
+
+    assert [s.payload.to_bytes() for s in received] == [b"intact"]
+    assert [s.payload.to_bytes() for s in corrupted] == [b"TAMPERED"] * 2
+    assert subscriber.corrupt_count == 2
+    assert subscriber.unverified_count == 0
+
+    bridge_subscriber.undeclare()
+    subscriber.undeclare()
+
+
+def test_integrity_unverified(session: zenoh.Session):
+    builder = session.subscriber_builder("test/integrity")
+    subscriber = builder.verify_integrity().declare()
+    session.put("test/integrity", b"payload", attachment=b"attachment")
+    session.delete("test/integrity")
+    sample = subscriber.recv()
+    assert sample.raw_attachment.to_bytes() == b"attachment"
+    assert subscriber.recv().kind == zenoh.SampleKind.DELETE
+    assert subscriber.unverified_count == 2
+    assert subscriber.corrupt_count == 0
+
+    # the digest is left in the attachment if not verified
+    other = session.declare_subscriber("test/integrity")
+    session.put("test/integrity", b"payload", integrity="xxh3")
+    assert other.recv().raw_attachment is not None
+    assert subscriber.recv().attachment is None
+
+
+def test_integrity_invalid(session: zenoh.Session):
+    with pytest.raises(ValueError):
+        session.put("test/integrity", b"payload", integrity="md5")
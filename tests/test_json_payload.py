@@ -0,0 +1,58 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import pytest
+
+import zenoh
+from zenoh import Encoding, ZBytes
+
+NESTED = {
+    "name": "capteur-température",
+    "position": {"x": 1.5, "y": -2, "tags": ["a", "ü", "日本"]},
+    "readings": [1, 2.5, 2**63, -(2**63)],
+    "enabled": True,
+    "offset": None,
+}
+
+
+def test_json_roundtrip():
+    assert ZBytes(NESTED).to_json_value() == NESTED
+    assert ZBytes([1, 2, 3]).to_json_value() == [1, 2, 3]
+    assert ZBytes({"a": "b"}).to_string() == '{"a":"b"}'
+    assert ZBytes({}).to_json_value() == {}
+    # unicode strings are kept as is
+    assert "日本" in ZBytes(NESTED).to_string()
+
+
+def test_json_errors():
+    with pytest.raises(ValueError, match="non-string dict key"):
+        ZBytes({1: "a"})
+    with pytest.raises(ValueError, match="non-finite float"):
+        ZBytes([float("nan")])
+    with pytest.raises(TypeError, match=r"'position\.at'"):
+        ZBytes({"position": {"at": object()}})
+    with pytest.raises(ValueError):
+        ZBytes("not json").to_json_value()
+
+
+def test_put_json(session: zenoh.Session):
+    subscriber = session.declare_subscriber("json/**")
+    session.put("json/nested", NESTED)
+    sample = subscriber.recv()
+    assert sample.encoding == Encoding.APPLICATION_JSON
+    assert sample.payload.to_json_value() == NESTED
+    session.put("json/list", [0.5, 1], encoding=Encoding.TEXT_JSON)
+    sample = subscriber.recv()
+    assert sample.encoding == Encoding.TEXT_JSON
+    assert sample.payload.to_json_value() == [0.5, 1]
+    subscriber.undeclare()
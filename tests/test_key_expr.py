@@ -0,0 +1,43 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import pytest
+
+from zenoh import KeyExpr, ZError
+
+
+def test_key_expr_eq_hash():
+    canonical = KeyExpr("demo/**")
+    equivalent = KeyExpr.autocanonize("demo/**/**")
+    assert canonical == equivalent
+    assert canonical == "demo/**"
+    assert hash(canonical) == hash(equivalent)
+    assert len({canonical, equivalent, KeyExpr("demo/*")}) == 2
+    assert {canonical: 1}[equivalent] == 1
+    assert canonical != KeyExpr("demo/*")
+    assert canonical != 42
+
+
+def test_key_expr_div():
+    assert KeyExpr("demo") / "room1" / "temp" == KeyExpr("demo/room1/temp")
+    assert str(KeyExpr("demo") / KeyExpr("*/temp")) == "demo/*/temp"
+    with pytest.raises(ZError):
+        KeyExpr("demo") / "room1//temp"
+    with pytest.raises(ZError):
+        KeyExpr("demo") / "room?"
+
+
+def test_key_expr_relations():
+    assert KeyExpr("demo/**").includes("demo/room1/temp")
+    assert not KeyExpr("demo/*").includes("demo/room1/temp")
+    assert KeyExpr("demo/*/temp").intersects("demo/room1/*")
@@ -0,0 +1,69 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import queue
+import time
+
+import zenoh
+from zenoh import Sample, SampleKind
+
+ENDPOINT = "tcp/127.0.0.1:17471"
+SLEEP = 1
+
+
+def open_peers() -> tuple[zenoh.Session, zenoh.Session]:
+    listener = zenoh.Config()
+    listener.insert_json5("listen/endpoints", f'["{ENDPOINT}"]')
+    listener.insert_json5("scouting/multicast/enabled", "false")
+    connector = zenoh.Config()
+    connector.insert_json5("connect/endpoints", f'["{ENDPOINT}"]')
+    connector.insert_json5("scouting/multicast/enabled", "false")
+    peer01 = zenoh.open(listener)
+    peer02 = zenoh.open(connector)
+    time.sleep(SLEEP)
+    return peer01, peer02
+
+
+def test_liveliness_token_lifecycle():
+    peer01, peer02 = open_peers()
+    samples: queue.Queue[Sample] = queue.Queue()
+    subscriber = peer02.liveliness().declare_subscriber("alive/**", samples.put)
+
+    token = peer01.liveliness().declare_token("alive/service")
+    sample = samples.get(timeout=5)
+    assert sample.kind == SampleKind.PUT
+    assert str(sample.key_expr) == "alive/service"
+    [reply] = peer02.liveliness().get("alive/**", timeout=1)
+    assert str(reply.ok.key_expr) == "alive/service"
+
+    token.undeclare()
+    sample = samples.get(timeout=5)
+    assert sample.kind == SampleKind.DELETE
+    assert str(sample.key_expr) == "alive/service"
+    assert list(peer02.liveliness().get("alive/**", timeout=1)) == []
+
+    subscriber.undeclare()
+    peer02.close()
+    peer01.close()
+
+
+def test_liveliness_history():
+    peer01, peer02 = open_peers()
+    with peer01.liveliness().declare_token("alive/history"):
+        time.sleep(SLEEP)
+        subscriber = peer02.liveliness().declare_subscriber("alive/**", history=True)
+        sample = subscriber.recv(timeout=5)
+        assert sample is not None and sample.kind == SampleKind.PUT
+        subscriber.undeclare()
+    peer02.close()
+    peer01.close()
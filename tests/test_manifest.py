@@ -0,0 +1,81 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import time
+
+import pytest
+
+import zenoh
+
+
+def manifest(queryable_handler: str = "on_query") -> dict:
+    return {
+        "publishers": [
+            {"key": "manifest/data", "name": "pub", "priority": "real_time"},
+        ],
+        "subscribers": [
+            {"key": "manifest/data", "name": "sub", "handler": "fifo", "capacity": 10},
+        ],
+        "queryables": [
+            {"key": "manifest/qbl", "handler": queryable_handler, "complete": True},
+        ],
+    }
+
+
+def test_apply_manifest(session: zenoh.Session):
+    queries = []
+    group = zenoh.apply_manifest(session, manifest(), {"on_query": queries.append})
+    assert group.names == ["pub", "sub", "manifest/qbl"]
+    assert len(group) == 3
+    assert "sub" in group and "manifest/data" not in group
+    assert group["pub"].priority == zenoh.Priority.REAL_TIME
+    group["pub"].put("hello")
+    sample = group["sub"].recv()
+    assert sample.payload.to_string() == "hello"
+    session.get("manifest/qbl")
+    time.sleep(0.5)
+    assert len(queries) == 1
+    with pytest.raises(KeyError):
+        group["unknown"]
+    group.close_all()
+
+
+def test_apply_manifest_invalid(session: zenoh.Session):
+    invalid = manifest()
+    invalid["subscribers"].append(
+        {"key": "manifest//data", "handler": "ring", "capacity": 0, "extra": 1}
+    )
+    invalid["publishers"].append({"name": "pub", "priority": "urgent"})
+    publisher = session.declare_publisher("manifest/data")
+    with pytest.raises(ValueError) as excinfo:
+        zenoh.apply_manifest(session, invalid, {"on_query": print})
+    message = str(excinfo.value)
+    for path in [
+        "/publishers/1/priority",
+        "/publishers/1: missing field 'key'",
+        "/subscribers/1/key",
+        "/subscribers/1/capacity",
+        "/subscribers/1/extra",
+    ]:
+        assert path in message
+    assert "5 errors" in message
+    # nothing was declared
+    assert not publisher.matching_status.matching
+
+
+def test_apply_manifest_rollback(session: zenoh.Session):
+    publisher = session.declare_publisher("manifest/data")
+    # the handler is only checked when declaring the queryable, after the others
+    with pytest.raises(ValueError, match="Invalid handler"):
+        zenoh.apply_manifest(session, manifest(), {"on_query": 42})
+    assert not publisher.matching_status.matching
@@ -0,0 +1,77 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import json
+import time
+
+import pytest
+
+import zenoh
+from zenoh import Query, ZError
+
+ENDPOINT = "tcp/127.0.0.1:7463"
+NAMESPACE = "tenants/acme"
+SLEEP = 1
+
+
+def config(key: str) -> zenoh.Config:
+    conf = zenoh.Config()
+    conf.insert_json5(key, json.dumps([ENDPOINT]))
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    return conf
+
+
+def test_namespace():
+    app = zenoh.open(config("listen/endpoints"), namespace=NAMESPACE)
+    other_app = zenoh.open(config("connect/endpoints"), namespace=NAMESPACE)
+    observer = zenoh.open(config("connect/endpoints"))
+    assert app.namespace == NAMESPACE
+    assert observer.namespace is None
+
+    app_samples = []
+    other_samples = []
+    wire_samples = []
+    app.declare_subscriber("cmd/**", app_samples.append)
+    other_app.declare_subscriber("sensor/**", other_samples.append)
+    observer.declare_subscriber("tenants/**", wire_samples.append)
+
+    def reply_key(query: Query):
+        query.reply(query.key_expr, str(query.key_expr))
+
+    other_app.declare_queryable("svc/**", reply_key)
+    time.sleep(SLEEP)
+
+    app.put("sensor/temp", "21")
+    observer.put("tenants/acme/cmd/stop", "now")
+    time.sleep(SLEEP)
+    assert [str(s.key_expr) for s in other_samples] == ["sensor/temp"]
+    assert [str(s.key_expr) for s in wire_samples] == ["tenants/acme/sensor/temp"]
+    assert [str(s.key_expr) for s in app_samples] == ["cmd/stop"]
+
+    # neither the queryable nor the querier see the prefix
+    [reply] = app.get("svc/status", timeout=5)
+    assert str(reply.ok.key_expr) == "svc/status"
+    assert reply.ok.payload.to_string() == "svc/status"
+    [reply] = observer.get("tenants/acme/svc/status", timeout=5)
+    assert str(reply.ok.key_expr) == "tenants/acme/svc/status"
+
+    for session in (observer, other_app, app):
+        session.close()
+
+
+def test_invalid_namespace():
+    conf = zenoh.Config()
+    with pytest.raises(ValueError):
+        zenoh.open(conf, namespace="tenants/*")
+    with pytest.raises(ZError):
+        zenoh.open(conf, namespace="tenants//acme")
@@ -0,0 +1,71 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import zenoh
+from zenoh import Query
+
+ENTRIES = {f"test/paging/{i:05}": str(i) for i in range(10_000)}
+
+
+def storage(query: Query):
+    keys = sorted(ENTRIES)
+    paging = query.paging()
+    if paging is not None:
+        offset, limit = paging
+        keys = keys[offset : offset + limit]
+    for key in keys:
+        query.reply(key, ENTRIES[key])
+
+
+def test_get_paged(session: zenoh.Session):
+    queryable = session.declare_queryable("test/paging/**", storage)
+
+    paged = session.get_paged("test/paging/**", page_size=1000)
+    received = [(str(r.ok.key_expr), r.ok.payload.to_string()) for r in paged]
+    assert len(received) == len(ENTRIES)
+    assert dict(received) == ENTRIES
+    # the last page is empty, as the entry count is a multiple of the page size
+    assert paged.pages == 11
+
+    paged = session.get_paged("test/paging/**", page_size=3000)
+    assert len({str(r.ok.key_expr) for r in paged}) == len(ENTRIES)
+    assert paged.pages == 4
+
+    queryable.undeclare()
+
+
+def test_query_paging(session: zenoh.Session):
+    pagings = []
+
+    def on_query(query: Query):
+        try:
+            pagings.append(query.paging())
+        except ValueError:
+            pagings.append("invalid")
+        query.reply("test/paging", "")
+
+    queryable = session.declare_queryable("test/paging", on_query)
+    for selector in [
+        "test/paging",
+        "test/paging?_offset=10;_limit=5",
+        "test/paging?_offset=10",
+        "test/paging?_offset=-1;_limit=5",
+    ]:
+        list(session.get(selector))
+    assert pagings == [None, (10, 5), "invalid", "invalid"]
+
+    # the paging parameters are added to the selector ones
+    list(session.get_paged("test/paging?_offset=42;other=value", page_size=2))
+    assert pagings[-1] == (0, 2)
+
+    queryable.undeclare()
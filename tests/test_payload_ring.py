@@ -0,0 +1,103 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import struct
+
+import zenoh
+from zenoh import PayloadRing
+
+HEADER = struct.Struct("<QQII")
+MSG_COUNT = 100_000
+BATCH = 100
+
+
+def read_slots(ring: PayloadRing) -> list[tuple[int, int, str, bytes]]:
+    slots = []
+    for slot in ring.read_available():
+        seq, timestamp, length, key_id = HEADER.unpack_from(slot)
+        payload = bytes(slot[PayloadRing.HEADER_SIZE :][:length])
+        slots.append((seq, timestamp, ring.key_expr(key_id), payload))
+    return slots
+
+
+def test_payload_ring(session: zenoh.Session):
+    ring = PayloadRing(2 * BATCH, 16)
+    subscriber = session.subscribe_into("test/ring/*", ring)
+    assert subscriber.handler is ring
+    publishers = [session.declare_publisher(f"test/ring/{i}") for i in range(2)]
+
+    expected_seq = 0
+    for batch in range(MSG_COUNT // BATCH):
+        for i in range(BATCH):
+            n = batch * BATCH + i
+            publishers[n % 2].put(n.to_bytes(8, "little"))
+        slots = read_slots(ring)
+        assert len(slots) == BATCH
+        for seq, timestamp, key_expr, payload in slots:
+            assert seq == expected_seq
+            assert timestamp == 0
+            assert key_expr == f"test/ring/{seq % 2}"
+            assert int.from_bytes(payload, "little") == seq
+            expected_seq += 1
+
+    assert expected_seq == MSG_COUNT
+    assert ring.received == MSG_COUNT
+    assert (ring.truncated, ring.skipped, ring.dropped) == (0, 0, 0)
+    subscriber.undeclare()
+
+
+def test_payload_ring_oversized(session: zenoh.Session):
+    truncating = PayloadRing(8, 4)
+    skipping = PayloadRing(8, 4, skip_oversized=True)
+    subscribers = [
+        session.subscribe_into("test/ring", truncating),
+        session.subscribe_into("test/ring", skipping),
+    ]
+    for payload in [b"abc", b"abcdefgh", b"abcd"]:
+        session.put("test/ring", payload)
+
+    slots = read_slots(truncating)
+    assert [(seq, payload) for seq, _, _, payload in slots] == [
+        (0, b"abc"),
+        (1, b"abcd"),
+        (2, b"abcd"),
+    ]
+    assert (truncating.truncated, truncating.skipped) == (1, 0)
+    slots = read_slots(skipping)
+    # skipped samples leave a gap in sequence numbers
+    assert [(seq, payload) for seq, _, _, payload in slots] == [
+        (0, b"abc"),
+        (2, b"abcd"),
+    ]
+    assert (skipping.truncated, skipping.skipped) == (0, 1)
+
+    for subscriber in subscribers:
+        subscriber.undeclare()
+
+
+def test_payload_ring_full(session: zenoh.Session):
+    ring = PayloadRing(4, 8)
+    subscriber = session.subscribe_into("test/ring", ring)
+    for i in range(6):
+        session.put("test/ring", bytes([i]))
+    slots = read_slots(ring)
+    assert [seq for seq, _, _, _ in slots] == [0, 1, 2, 3]
+    assert ring.dropped == 2
+    # slots are only released by the next read
+    session.put("test/ring", b"x")
+    assert ring.dropped == 3
+    assert read_slots(ring) == []
+    session.put("test/ring", b"y")
+    assert [payload for _, _, _, payload in read_slots(ring)] == [b"y"]
+    assert ring.received == 8
+    subscriber.undeclare()
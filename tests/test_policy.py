@@ -0,0 +1,60 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import zenoh
+from zenoh import Locality, SubscriberPolicy
+
+
+def test_subscriber_policy(session: zenoh.Session):
+    zenoh.set_subscriber_policy(
+        [
+            SubscriberPolicy("bulk/local/**", allowed_origin=Locality.SESSION_LOCAL),
+            SubscriberPolicy("bulk/**", allowed_origin=Locality.REMOTE),
+            SubscriberPolicy("ctrl/**"),
+        ]
+    )
+    try:
+        # first match wins
+        sub = session.declare_subscriber("bulk/local/a")
+        assert sub.allowed_origin == Locality.SESSION_LOCAL
+        sub = session.declare_subscriber("bulk/a/b")
+        assert sub.allowed_origin == Locality.REMOTE
+        # the rule must include the whole subscriber key expression
+        sub = session.declare_subscriber("bulk/*/**")
+        assert sub.allowed_origin == Locality.REMOTE
+        sub = session.declare_subscriber("**")
+        assert sub.allowed_origin == Locality.ANY
+        # matching rule without setting
+        sub = session.declare_subscriber("ctrl/a")
+        assert sub.allowed_origin == Locality.ANY
+        # explicit argument overrides the policy
+        sub = session.declare_subscriber("bulk/a", allowed_origin=Locality.ANY)
+        assert sub.allowed_origin == Locality.ANY
+    finally:
+        zenoh.set_subscriber_policy([])
+
+
+def test_subscriber_policy_applied(session: zenoh.Session):
+    zenoh.set_subscriber_policy(
+        [SubscriberPolicy("remote/**", allowed_origin=Locality.REMOTE)]
+    )
+    try:
+        sub = session.declare_subscriber("remote/key")
+        any_sub = session.declare_subscriber(
+            "remote/key", allowed_origin=Locality.ANY
+        )
+        session.put("remote/key", "value")
+        assert any_sub.recv().payload.to_string() == "value"
+        assert sub.try_recv() is None
+    finally:
+        zenoh.set_subscriber_policy([])
@@ -0,0 +1,80 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import pytest
+
+import zenoh
+from zenoh import Priority
+from zenoh.handlers import PriorityChannel
+
+
+def put_mixed(session: zenoh.Session):
+    # the background burst is published first
+    for i in range(6):
+        session.put("priority/bg", f"bg{i}", priority=Priority.BACKGROUND)
+    for i in range(4):
+        session.put("priority/rt", f"rt{i}", priority=Priority.REAL_TIME)
+
+
+def test_priority_order(session: zenoh.Session):
+    channel = PriorityChannel(32, starvation_ratio=None)
+    subscriber = session.declare_subscriber("priority/**", channel)
+    put_mixed(session)
+    received = [subscriber.recv().payload.to_string() for _ in range(10)]
+    assert received == [f"rt{i}" for i in range(4)] + [f"bg{i}" for i in range(6)]
+    assert subscriber.try_recv() is None
+    subscriber.undeclare()
+
+
+def test_starvation_prevention(session: zenoh.Session):
+    channel = PriorityChannel(32, starvation_ratio=2)
+    subscriber = session.declare_subscriber("priority/**", channel)
+    put_mixed(session)
+    received = [subscriber.recv().payload.to_string() for _ in range(10)]
+    assert received == ["rt0", "rt1", "bg0", "rt2", "rt3"] + [
+        f"bg{i}" for i in range(1, 6)
+    ]
+    counters = subscriber.handler.priority_counters
+    assert counters[Priority.REAL_TIME] == {
+        "enqueued": 4,
+        "delivered": 4,
+        "pending": 0,
+    }
+    assert counters[Priority.BACKGROUND]["delivered"] == 6
+    assert counters[Priority.DATA]["enqueued"] == 0
+    subscriber.undeclare()
+
+
+def test_pending_counters(session: zenoh.Session):
+    subscriber = session.declare_subscriber("priority/**", PriorityChannel(32))
+    put_mixed(session)
+    handler = subscriber.handler
+    subscriber.recv()
+    counters = handler.priority_counters
+    assert counters[Priority.REAL_TIME]["pending"] == 3
+    assert counters[Priority.BACKGROUND]["pending"] == 6
+    subscriber.undeclare()
+    # the pending samples are still received once undeclared
+    assert len(list(handler)) == 9
+
+
+def test_invalid_priority_channel(session: zenoh.Session):
+    with pytest.raises(ValueError):
+        PriorityChannel(0)
+    with pytest.raises(ValueError):
+        PriorityChannel(8, starvation_ratio=0)
+    with pytest.raises(ValueError):
+        session.get("priority/**", PriorityChannel(8))
+    subscriber = session.declare_subscriber("priority/a")
+    assert subscriber.handler.priority_counters is None
+    subscriber.undeclare()
@@ -0,0 +1,92 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import json
+
+import pytest
+
+import zenoh
+from zenoh import Encoding, Query
+
+DOCUMENT = {
+    "pose": {"x": 1.5, "y": -2.0, "z": 0.0},
+    "joints": [{"angle": 10}, {"angle": 20}],
+    "name": "arm",
+}
+
+
+def test_subscriber_projection(session: zenoh.Session):
+    subscriber = (
+        session.subscriber_builder("projection/**")
+        .project(["pose.x", "joints.1.angle", "missing.field"])
+        .declare()
+    )
+    session.put("projection/json", DOCUMENT)
+    assert json.loads(subscriber.recv().payload.to_string()) == {
+        "pose": {"x": 1.5},
+        "joints": {"1": {"angle": 20}},
+        "missing": {"field": None},
+    }
+    session.put(
+        "projection/text", json.dumps(DOCUMENT), encoding=Encoding.TEXT_JSON
+    )
+    sample = subscriber.recv()
+    assert sample.encoding == Encoding.TEXT_JSON
+    assert json.loads(sample.payload.to_string())["pose"] == {"x": 1.5}
+    # non-JSON payloads are passed through unmodified
+    session.put("projection/plain", "pose.x", encoding=Encoding.TEXT_PLAIN)
+    session.put("projection/invalid", "{", encoding=Encoding.APPLICATION_JSON)
+    assert subscriber.recv().payload.to_string() == "pose.x"
+    assert subscriber.recv().payload.to_string() == "{"
+    assert subscriber.unprojected_count == 2
+    subscriber.undeclare()
+
+
+def test_get_projection(session: zenoh.Session):
+    requested = []
+
+    def reply(query: Query):
+        requested.append(query.projection)
+        json_encoding = Encoding.APPLICATION_JSON
+        # a cooperative queryable projects at the source
+        if query.projection == ["name", "pose.y"]:
+            projected = {"name": "arm", "pose": {"y": -2.0}}
+            query.reply("projection/source", projected, encoding=json_encoding)
+        query.reply("projection/doc", DOCUMENT, encoding=json_encoding)
+        query.reply("projection/raw", b"\x00\x01")
+
+    queryable = session.declare_queryable("projection/**", reply)
+    handle = session.get_handle(
+        "projection/**", project=["pose.y", "name"], timeout=1
+    )
+    payloads = {str(r.ok.key_expr): r.ok.payload.to_bytes() for r in handle}
+    expected = {"name": "arm", "pose": {"y": -2.0}}
+    assert json.loads(payloads["projection/doc"]) == expected
+    assert json.loads(payloads["projection/source"]) == expected
+    assert payloads["projection/raw"] == b"\x00\x01"
+    assert handle.unprojected_count == 1
+    assert requested == [["name", "pose.y"]]
+    list(session.get("projection/**", timeout=1))
+    assert requested[-1] is None
+    queryable.undeclare()
+
+
+def test_invalid_projection(session: zenoh.Session):
+    builder = session.subscriber_builder("projection/**")
+    with pytest.raises(TypeError):
+        builder.project("pose.x")
+    for project in [[], ["pose..x"], ["pose|x"]]:
+        with pytest.raises(ValueError):
+            builder.project(project)
+    with pytest.raises(ValueError):
+        session.get_handle("projection/**", project=["pose..x"])
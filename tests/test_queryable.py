@@ -0,0 +1,563 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import os
+import threading
+import time
+
+import pytest
+
+import zenoh
+from zenoh import Query, ZError
+
+
+def get_values(session: zenoh.Session, selector: str) -> list[str]:
+    return [r.ok.payload.to_string() for r in session.get(selector, timeout=1)]
+
+
+def test_multi_key_queryable(session: zenoh.Session):
+    def callback(query: Query):
+        query.reply(query.key_expr, f"value of {query.key_expr}")
+
+    queryable = session.declare_queryable(
+        ["config/**", ("status/**", True)], callback
+    )
+    assert [str(k) for k in queryable.key_exprs] == ["config/**", "status/**"]
+    assert get_values(session, "config/a") == ["value of config/a"]
+    assert get_values(session, "status/b") == ["value of status/b"]
+
+    queryable.undeclare()
+    assert get_values(session, "config/a") == []
+    assert get_values(session, "status/b") == []
+    with pytest.raises(ZError):
+        queryable.undeclare()
+
+
+def test_multi_key_queryable_channel(session: zenoh.Session):
+    queryable = session.declare_queryable(["config/a", "status/b"])
+    handles = [session.get(ke, timeout=1) for ke in ["config/a", "status/b"]]
+    queries = [queryable.recv(), queryable.recv()]
+    assert sorted(str(q.key_expr) for q in queries) == ["config/a", "status/b"]
+    for query in queries:
+        query.reply(query.key_expr, "value")
+        query.drop()
+    assert [len(list(handle)) for handle in handles] == [1, 1]
+    queryable.undeclare()
+
+
+def test_multi_key_queryable_invalid(session: zenoh.Session):
+    with pytest.raises(ZError):
+        session.declare_queryable(["config/**", "status/**/**/invalid$"], print)
+    # no queryable is left declared
+    assert get_values(session, "config/a") == []
+    with pytest.raises(ValueError):
+        session.declare_queryable([])
+
+
+def test_queryable_set_callback(session: zenoh.Session):
+    queryable = session.declare_queryable(
+        ["config/**", "status/**"], lambda q: q.reply(q.key_expr, "old")
+    )
+    assert get_values(session, "config/a") == ["old"]
+    queryable.set_callback(lambda q: q.reply(q.key_expr, "new"))
+    assert get_values(session, "config/a") == ["new"]
+    assert get_values(session, "status/b") == ["new"]
+    with pytest.raises(ValueError):
+        queryable.set_callback(None)
+    queryable.undeclare()
+
+
+def test_query_target_consolidation(session: zenoh.Session):
+    observed = []
+
+    def callback(query: Query):
+        observed.append((query.target, query.consolidation))
+        query.reply(query.key_expr, "value")
+
+    queryable = session.declare_queryable("test/target", callback, complete=True)
+    # the arguments of get are not visible to the queryable
+    list(session.get("test/target", target=zenoh.QueryTarget.ALL))
+    list(session.get("test/target", consolidation=zenoh.ConsolidationMode.LATEST))
+    assert observed == [
+        (zenoh.QueryTarget.BEST_MATCHING, zenoh.ConsolidationMode.AUTO),
+    ] * 2
+    queryable.undeclare()
+
+
+def test_queryable_max_breadth(session: zenoh.Session):
+    queries = []
+
+    def callback(query: Query):
+        queries.append(str(query.selector))
+        query.reply(query.key_expr, "value")
+
+    storage = session.declare_queryable("storage/**", callback, max_breadth=0)
+    for selector in ["**", "storage/*/a"]:
+        replies = list(session.get(selector, timeout=1))
+        assert len(replies) == 1
+        assert replies[0].err.payload.to_string() == "query too broad"
+    assert queries == []
+    assert get_values(session, "storage/a") == ["value"]
+    assert queries == ["storage/a"]
+    assert storage.rejected_count == 2
+
+    predicate = session.declare_queryable(
+        "archive/**",
+        lambda q: q.reply(q.key_expr, "archived"),
+        max_breadth=lambda selector: selector.parameters.get("limit") is not None,
+    )
+    replies = list(session.get("archive/a", timeout=1))
+    assert replies[0].err.payload.to_string() == "query too broad"
+    assert get_values(session, "archive/a?limit=10") == ["archived"]
+    assert predicate.rejected_count == 1
+
+
+def test_replies_to_columns(session: zenoh.Session):
+    payloads = {
+        "float": (["1.5", "-2", " 3e2 "], [1.5, -2.0, 300.0]),
+        "int": (["1", "-2", "300"], [1, -2, 300]),
+        "bytes": ([b"\x00", b"", b"\xff\x01"], [b"\x00", b"", b"\xff\x01"]),
+        "json": (['{"a": 1}', "[1, 2]", "null"], [{"a": 1}, [1, 2], None]),
+    }
+    values = []
+    timestamps = [session.new_timestamp() for _ in range(3)]
+
+    def callback(query: Query):
+        for i, value in enumerate(values):
+            query.reply(f"columns/{i}", value, timestamp=timestamps[i])
+        query.reply_err("failed")
+
+    queryable = session.declare_queryable("columns/**", callback)
+    # without consolidation, replies are received in order
+    none = zenoh.ConsolidationMode.NONE
+    for payload_as, (values, expected) in payloads.items():
+        replies = list(session.get("columns/**", consolidation=none, timeout=1))
+        columns = zenoh.replies_to_columns(replies, payload_as)
+        assert columns.keys() == {"key", "time", "payload", "errors"}
+        assert columns["key"] == ["columns/0", "columns/1", "columns/2"]
+        assert columns["time"] == [ts.get_time() for ts in timestamps]
+        assert columns["payload"] == expected
+        assert [e.payload.to_string() for e in columns["errors"]] == ["failed"]
+
+    values = ["1", "not a number", "3"]
+    replies = list(session.get("columns/**", consolidation=none, timeout=1))
+    with pytest.raises(ValueError, match="reply 1"):
+        zenoh.replies_to_columns(replies, "float")
+    with pytest.raises(ValueError):
+        zenoh.replies_to_columns(replies, "str")
+    queryable.undeclare()
+
+
+def test_reply_negotiated(session: zenoh.Session):
+    subscriber = session.declare_subscriber("negotiated/**")
+    session.put(
+        "negotiated/config",
+        '{"rate": 10, "mode": "fast"}',
+        encoding=zenoh.Encoding.APPLICATION_JSON,
+        attachment=b"meta",
+    )
+    stored = subscriber.recv()
+    strict = []
+
+    def callback(query: Query):
+        try:
+            query.reply_negotiated(stored, strict="strict" in query.parameters)
+        except ValueError as err:
+            strict.append(err)
+
+    queryable = session.declare_queryable("negotiated/**", callback)
+
+    [reply] = session.get("negotiated/config?_accept=application/properties")
+    assert str(reply.ok.encoding) == str(zenoh.Encoding("application/properties"))
+    properties = reply.ok.payload.to_string().split(";")
+    assert sorted(properties) == ["mode=fast", "rate=10"]
+    assert reply.ok.raw_attachment.to_bytes() == b"meta"
+
+    # the first accepted encoding with a transcoding path is used
+    [reply] = session.get("negotiated/config?_accept=image/png|text/plain")
+    assert reply.ok.encoding == zenoh.Encoding.TEXT_PLAIN
+    assert reply.ok.payload.to_string() == '{"rate": 10, "mode": "fast"}'
+
+    # accepted or unspecified encodings are not transcoded
+    for parameters in ("", "?_accept=application/json"):
+        [reply] = session.get(f"negotiated/config{parameters}")
+        assert reply.ok.encoding == zenoh.Encoding.APPLICATION_JSON
+
+    # without transcoding path, the sample is sent as is, unless strict
+    [reply] = session.get("negotiated/config?_accept=image/png")
+    assert reply.ok.encoding == zenoh.Encoding.APPLICATION_JSON
+    selector = "negotiated/config?_accept=image/png;strict"
+    assert list(session.get(selector, timeout=1)) == []
+    assert len(strict) == 1
+    queryable.undeclare()
+    subscriber.undeclare()
+
+
+def test_get_releases_gil(session: zenoh.Session):
+    def slow_reply(query: Query):
+        time.sleep(2)
+        query.reply(query.key_expr, "slow")
+
+    received = []
+    queryable = session.declare_queryable("slow/query", slow_reply)
+    subscriber = session.declare_subscriber(
+        "slow/ticks", lambda sample: received.append(time.monotonic())
+    )
+    stop = threading.Event()
+
+    def publish():
+        while not stop.wait(0.1):
+            session.put("slow/ticks", "tick")
+
+    publisher = threading.Thread(target=publish)
+    publisher.start()
+    try:
+        replies = list(session.get("slow/query", timeout=5))
+        replied_at = time.monotonic()
+    finally:
+        stop.set()
+        publisher.join()
+    assert [reply.ok.payload.to_string() for reply in replies] == ["slow"]
+    # both the publishing thread and the subscriber callback ran during the get
+    assert len([t for t in received if t < replied_at]) >= 5
+    subscriber.undeclare()
+    queryable.undeclare()
+
+
+def test_get_many(session: zenoh.Session):
+    def delayed_reply(query: Query):
+        delay = int(str(query.key_expr).split("/")[1]) / 10
+        time.sleep(delay)
+        query.reply(query.key_expr, str(delay))
+
+    queryable = session.declare_queryable("delayed/*", delayed_reply)
+    selectors = [f"delayed/{i}" for i in range(1, 11)]
+    start = time.monotonic()
+    results = session.get_many(selectors + ["delayed//invalid"], timeout=5)
+    elapsed = time.monotonic() - start
+    # close to the longest delay, rather than their 5.5s sum
+    assert elapsed < 3
+    assert list(results) == selectors + ["delayed//invalid"]
+    for i, selector in enumerate(selectors, start=1):
+        assert [r.ok.payload.to_string() for r in results[selector]] == [str(i / 10)]
+    assert isinstance(results["delayed//invalid"], ZError)
+
+    start = time.monotonic()
+    session.get_many(selectors[-3:], max_concurrency=1, timeout=5)
+    assert time.monotonic() - start >= 2.7
+
+    with pytest.raises(ValueError):
+        session.get_many(selectors, max_concurrency=0)
+    queryable.undeclare()
+
+
+def test_reply_delete_sample(session: zenoh.Session):
+    timestamp = session.new_timestamp()
+
+    def callback(query: Query):
+        kind = zenoh.SampleKind.DELETE
+        sample = zenoh.Sample(query.key_expr, kind=kind, timestamp=timestamp)
+        query.reply_sample(sample)
+
+    queryable = session.declare_queryable("samples/deleted", callback)
+    [reply] = session.get("samples/deleted", timeout=1)
+    assert reply.ok.kind == zenoh.SampleKind.DELETE
+    assert reply.ok.timestamp == timestamp
+    queryable.undeclare()
+
+    sample = zenoh.Sample("samples/put", "value", encoding="text/plain")
+    assert sample.kind == zenoh.SampleKind.PUT
+    assert sample.payload.to_string() == "value"
+    assert sample.encoding == zenoh.Encoding.TEXT_PLAIN
+    with pytest.raises(ValueError):
+        zenoh.Sample("samples/deleted", "value", kind=zenoh.SampleKind.DELETE)
+
+
+def test_reply_sample_qos(session: zenoh.Session):
+    def callback(query: Query):
+        sample = zenoh.Sample(
+            query.key_expr,
+            "value",
+            priority=zenoh.Priority.REAL_TIME,
+            congestion_control=zenoh.CongestionControl.BLOCK,
+        )
+        query.reply_sample(sample)
+
+    queryable = session.declare_queryable("samples/qos", callback)
+    [reply] = session.get("samples/qos", timeout=1)
+    assert reply.ok.priority == zenoh.Priority.REAL_TIME
+    queryable.undeclare()
+
+    sample = zenoh.Sample("samples/qos", "value", express=True)
+    assert sample.express
+    assert sample.priority == zenoh.Priority.DEFAULT
+    assert sample.congestion_control == zenoh.CongestionControl.DEFAULT
+    sample = zenoh.Sample(
+        "samples/qos", kind=zenoh.SampleKind.DELETE, priority=zenoh.Priority.DATA_LOW
+    )
+    assert sample.priority == zenoh.Priority.DATA_LOW
+
+
+def test_reply_defaults(session: zenoh.Session):
+    def callback(query: Query):
+        if "plain" in query.parameters:
+            query.reply(query.key_expr, "text", encoding=zenoh.Encoding.TEXT_PLAIN)
+        else:
+            query.reply(query.key_expr, '{"a": 1}')
+
+    queryable = session.declare_queryable(
+        "defaults/**",
+        callback,
+        reply_encoding=zenoh.Encoding.APPLICATION_JSON,
+        reply_express=True,
+    )
+    assert queryable.reply_encoding == zenoh.Encoding.APPLICATION_JSON
+    assert queryable.reply_express is True
+    [reply] = session.get("defaults/a", timeout=1)
+    assert reply.ok.encoding == zenoh.Encoding.APPLICATION_JSON
+    assert reply.ok.express
+    # overridden per call
+    [reply] = session.get("defaults/a?plain", timeout=1)
+    assert reply.ok.encoding == zenoh.Encoding.TEXT_PLAIN
+    queryable.undeclare()
+
+    queryable = session.declare_queryable("defaults/**")
+    assert queryable.reply_encoding is None
+    assert queryable.reply_express is None
+    handle = session.get("defaults/a", timeout=1)
+    query = queryable.recv()
+    query.reply(query.key_expr, "value")
+    query.drop()
+    [reply] = handle
+    assert reply.ok.encoding == zenoh.Encoding.ZENOH_BYTES
+    queryable.undeclare()
+
+
+def test_reply_errors(session: zenoh.Session):
+    queryable = session.declare_queryable("errors/**")
+    handle = session.get("errors/a", timeout=0.1)
+    query = queryable.recv()
+    with pytest.raises(ZError):
+        query.reply("errors/b", "value")
+    # the querier has timed out, the reply is sent anyway
+    assert list(handle) == []
+    query.reply(query.key_expr, "late")
+    query.drop()
+    with pytest.raises(ZError):
+        query.reply(query.key_expr, "dropped")
+    queryable.undeclare()
+
+
+def test_reply_variants(session: zenoh.Session):
+    def callback(query: Query):
+        query.reply("variants/ok", "value")
+        query.reply_err("failed")
+
+    queryable = session.declare_queryable("variants/**", callback)
+    none = zenoh.ConsolidationMode.NONE
+    replies = list(session.get("variants/**", consolidation=none, timeout=1))
+    assert sorted(reply.is_ok for reply in replies) == [False, True]
+    for reply in replies:
+        assert (reply.ok is not None) == reply.is_ok
+        assert (reply.err is not None) != reply.is_ok
+        assert reply.replier_id.zid == session.zid()
+    queryable.undeclare()
+
+
+def test_deferred_replies(session: zenoh.Session):
+    queries = []
+    queryables = [
+        session.declare_queryable("deferred/**", queries.append) for _ in range(2)
+    ]
+
+    def reply_later():
+        time.sleep(0.1)
+        first, second = queries
+        for i in range(2):
+            first.reply(f"deferred/{i}", str(i))
+        assert first.finish() == 2
+        assert first.is_finished
+        second.reply("deferred/2", "2", final=True)
+        assert second.is_finished
+        assert second.reply_count == 1
+
+    thread = threading.Thread(target=reply_later)
+    thread.start()
+    start = time.monotonic()
+    replies = list(session.get("deferred/**", timeout=10))
+    # the get terminates on finish and final replies, not on timeout
+    assert time.monotonic() - start < 5
+    payloads = sorted(reply.ok.payload.to_string() for reply in replies)
+    assert payloads == ["0", "1", "2"]
+    thread.join()
+    with pytest.raises(ZError):
+        queries[0].reply("deferred/3", "3")
+    for queryable in queryables:
+        queryable.undeclare()
+
+
+def test_query_audit(session: zenoh.Session):
+    audited = []
+    selector = "audit/a?id=1;tags=x%20y;_time=[now(-1h)..];flag"
+
+    def reply(query: Query):
+        query.reply(query.key_expr, "1")
+        query.reply(query.key_expr, "2")
+        if "fail" in query.parameters:
+            raise RuntimeError("callback failure")
+
+    def audit(query: Query, reply_count: int, duration: float):
+        audited.append((query.raw_selector, reply_count, duration))
+
+    queryable = session.declare_queryable("audit/**", reply, audit=audit)
+    publisher = session.declare_publisher("audit/source")
+    source_info = zenoh.SourceInfo(publisher.id, 1)
+    assert len(list(session.get(selector, source_info=source_info))) == 2
+    assert len(list(session.get("audit/b?fail"))) == 2
+    [(raw_selector, reply_count, duration), failed] = audited
+    assert raw_selector == selector
+    assert reply_count == 2
+    assert duration >= 0
+    # the audit is called when the callback raises too
+    assert failed[:2] == ("audit/b?fail", 2)
+
+    origins = []
+    other = session.declare_queryable(
+        "origin/**", lambda query: origins.append(query.origin_zid)
+    )
+    list(session.get("origin/a", source_info=source_info))
+    list(session.get("origin/a"))
+    assert origins == [session.zid(), None]
+    with pytest.raises(ValueError):
+        session.declare_queryable("audit/**", audit=audit)
+    other.undeclare()
+    publisher.undeclare()
+    queryable.undeclare()
+
+
+def test_queryable_once_callback(session: zenoh.Session):
+    calls = []
+
+    def callback(query: Query):
+        calls.append(query.selector)
+        query.reply(query.key_expr, "once")
+
+    queryable = session.queryable_once("once/a", callback)
+    # both queries are sent before the first one is answered
+    first = session.get("once/a", timeout=1)
+    second = session.get("once/a", timeout=1)
+    replies = [r.ok.payload.to_string() for r in [*first, *second]]
+    assert replies == ["once"]
+    assert len(calls) == 1
+    assert queryable.closed
+    with pytest.raises(ValueError):
+        queryable.wait()
+
+
+def test_queryable_once_wait(session: zenoh.Session):
+    queryable = session.queryable_once("once/b")
+    first = session.get("once/b", timeout=1)
+    second = session.get("once/b", timeout=1)
+    query = queryable.wait(1)
+    assert queryable.closed
+    query.reply("once/b", "waited")
+    query.drop()
+    replies = [r.ok.payload.to_string() for r in [*first, *second]]
+    assert replies == ["waited"]
+    with pytest.raises(ZError):
+        queryable.wait(0.1)
+
+
+def test_queryable_once_timeout(session: zenoh.Session):
+    queryable = session.queryable_once("once/c", timeout=0.1)
+    with pytest.raises(ZError) as exc_info:
+        queryable.wait()
+    assert exc_info.value.code == zenoh.ErrorCode.TIMEOUT
+    assert queryable.closed
+    assert list(session.get("once/c", timeout=0.5)) == []
+
+
+def test_bytes_parameters(session: zenoh.Session):
+    tokens = [bytes(range(256)), b"\0", b"", os.urandom(16), os.urandom(17)]
+    for token in tokens:
+        parameters = zenoh.Parameters({"cursor": token})
+        assert str(parameters).startswith("cursor=b64:")
+        assert parameters.get("cursor", as_bytes=True) == token
+    # string values are kept as is
+    for value in ["b64:AAAA", "str:x", "plain"]:
+        parameters = zenoh.Parameters({"v": value})
+        assert str(parameters) == f"v={value}"
+        assert parameters["v"] == value
+        assert parameters.values("v") == [value]
+    assert zenoh.Parameters({"v": "plain"}).get("v", as_bytes=True) == b"plain"
+    received = []
+
+    def callback(query: Query):
+        cursor = query.parameter("cursor", as_bytes=True)
+        received.append((cursor, query.parameter("s")))
+        query.reply(query.key_expr, "ok")
+
+    queryable = session.declare_queryable("params/bytes", callback)
+    for token in tokens:
+        parameters = {"cursor": token, "s": "b64:not bytes"}
+        list(session.get("params/bytes", parameters=parameters, timeout=1))
+    assert received == [(token, "b64:not bytes") for token in tokens]
+    queryable.undeclare()
+
+
+def test_queryable_filter(session: zenoh.Session):
+    queries: list[dict[str, str]] = []
+
+    def callback(query: Query):
+        queries.append(query.properties)
+        query.reply(query.key_expr, "celsius")
+
+    queryable = session.declare_queryable(
+        "filter/temp", callback, filter={"unit": "celsius", "raw": b"\x00"}
+    )
+    assert get_values(session, "filter/temp") == []
+    assert get_values(session, "filter/temp?unit=kelvin") == []
+    parameters = {"unit": "celsius", "raw": b"\x00", "extra": "1"}
+    replies = session.get("filter/temp", parameters=parameters, timeout=1)
+    assert [r.ok.payload.to_string() for r in replies] == ["celsius"]
+    # only the matching query reached the callback
+    assert len(queries) == 1
+    assert queries[0]["unit"] == "celsius"
+    assert queries[0]["extra"] == "1"
+    queryable.undeclare()
+
+
+@pytest.mark.parametrize(
+    "returned, expected",
+    [
+        (None, []),
+        ("value", ["auto/a:value"]),
+        (("auto/a", b"tuple"), ["auto/a:tuple"]),
+        (zenoh.Sample("auto/a", "sample"), ["auto/a:sample"]),
+        (["first", ("auto/a", "second")], ["auto/a:first", "auto/a:second"]),
+        # not a reply: the error is logged, and the query finalized
+        (42, []),
+        (("auto/a", "b", "c"), []),
+    ],
+)
+def test_auto_reply(session: zenoh.Session, returned, expected):
+    def callback(query: Query):
+        return returned
+
+    queryable = session.declare_queryable("auto/a", callback, auto_reply=True)
+    replies = session.get("auto/a", timeout=1)
+    received = [f"{r.ok.key_expr}:{r.ok.payload.to_string()}" for r in replies]
+    assert received == expected
+    queryable.undeclare()
+    with pytest.raises(ValueError):
+        session.declare_queryable("auto/a", auto_reply=True)
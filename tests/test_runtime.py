@@ -0,0 +1,86 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import os
+import subprocess
+import sys
+
+import pytest
+
+import zenoh
+from zenoh import ErrorCode, ZError
+
+# the runtime is configured by the environment zenoh is imported with, so the minimal
+# one runs in a new process
+MINIMAL_ROUND_TRIP = """
+import zenoh
+
+conf = zenoh.Config()
+conf.insert_json5("scouting/multicast/enabled", "false")
+with zenoh.open(conf, runtime="minimal", runtime_stack_kb=512) as session:
+    subscriber = session.declare_subscriber("runtime/minimal")
+    session.put("runtime/minimal", "value")
+    assert subscriber.recv().payload.to_string() == "value"
+info = zenoh.runtime_info()
+print(info["flavor"], info["stack_kb"])
+"""
+
+
+def test_runtime_info(session: zenoh.Session):
+    info = zenoh.runtime_info()
+    assert "default" in info["available"]
+    assert info["flavor"] in info["available"] + ["custom"]
+    assert info["flavor_env"]["default"] is None
+
+
+def test_invalid_runtime(open_session):
+    with pytest.raises(ValueError):
+        open_session(runtime="tiny")
+    with pytest.raises(ValueError):
+        open_session(runtime_stack_kb=0)
+    if "minimal" not in zenoh.runtime_info()["available"]:
+        with pytest.raises(ZError) as excinfo:
+            open_session(runtime="minimal")
+        assert excinfo.value.code == ErrorCode.FEATURE_UNAVAILABLE
+
+
+def test_mismatched_runtime(open_session):
+    info = zenoh.runtime_info()
+    if info["flavor"] == "default":
+        open_session(runtime="default")
+    if info["stack_kb"] != 1024:
+        with pytest.raises(ZError):
+            open_session(runtime_stack_kb=1024)
+
+
+def test_minimal_runtime(open_session):
+    info = zenoh.runtime_info()
+    if "minimal" not in info["available"]:
+        pytest.skip("built without the minimal-runtime feature")
+    if info["flavor"] != "minimal":
+        with pytest.raises(ZError):
+            open_session(runtime="minimal")
+    env = dict(
+        os.environ,
+        ZENOH_RUNTIME=info["flavor_env"]["minimal"],
+        RUST_MIN_STACK=str(512 * 1024),
+    )
+    result = subprocess.run(
+        [sys.executable, "-c", MINIMAL_ROUND_TRIP],
+        capture_output=True,
+        text=True,
+        timeout=30,
+        env=env,
+    )
+    assert result.returncode == 0, result.stderr
+    assert result.stdout.split() == ["minimal", "512"]
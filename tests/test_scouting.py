@@ -0,0 +1,100 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import time
+
+import pytest
+
+import zenoh
+from zenoh import Hello, WhatAmI, ZError
+
+# a dedicated multicast group, so that other tests nodes are not scouted
+MULTICAST_ADDRESS = "224.0.0.224:17462"
+
+
+def scouting_config() -> zenoh.Config:
+    conf = zenoh.Config()
+    conf.insert_json5("scouting/multicast/address", f'"{MULTICAST_ADDRESS}"')
+    conf.insert_json5("listen/endpoints", "[]")
+    return conf
+
+
+def test_open_auto_router():
+    conf = scouting_config()
+    conf.insert_json5("mode", '"router"')
+    conf.insert_json5("listen/endpoints", '["tcp/127.0.0.1:17461"]')
+    conf.insert_json5("scouting/multicast/enabled", "true")
+    with zenoh.open(conf) as router:
+        session, report = zenoh.open_auto(scouting_config(), scout_timeout=5)
+        with session:
+            assert report.mode == WhatAmI.CLIENT
+            assert report.router_zid == router.info.zid()
+            assert report.router_locator == "tcp/127.0.0.1:17461"
+            assert report.scouting_duration < 5
+            assert session.info.routers_zid() == [router.info.zid()]
+
+
+def test_open_auto_fallback():
+    session, report = zenoh.open_auto(scouting_config(), scout_timeout=0.5)
+    with session:
+        assert report.mode == WhatAmI.PEER
+        assert report.router_zid is None and report.router_locator is None
+        assert report.scouting_duration >= 0.5
+        assert "mode=Peer" in repr(report)
+
+    session, report = zenoh.open_auto(prefer="peer")
+    with session:
+        assert report.mode == WhatAmI.PEER
+        assert report.scouting_duration == 0
+
+    with pytest.raises(ValueError):
+        zenoh.open_auto(prefer="router")
+
+
+def test_open_auto_fallback_error():
+    # the peer session can't listen on an invalid endpoint
+    conf = scouting_config()
+    conf.insert_json5("listen/endpoints", '["tcp/256.0.0.1:17463"]')
+    with pytest.raises(zenoh.ZError, match="no router found after scouting"):
+        zenoh.open_auto(conf, scout_timeout=0.2)
+
+
+def test_scout_timeout():
+    conf = scouting_config()
+    conf.insert_json5("mode", '"router"')
+    conf.insert_json5("listen/endpoints", '["tcp/127.0.0.1:17464"]')
+    conf.insert_json5("scouting/multicast/enabled", "true")
+    with zenoh.open(conf) as router:
+        config = {"scouting": {"multicast": {"address": MULTICAST_ADDRESS}}}
+        what = WhatAmI.PEER | WhatAmI.ROUTER
+        hellos = zenoh.scout(what=what, config=config, timeout=2)
+        assert all(isinstance(hello, Hello) for hello in hellos)
+        # each node is reported once
+        assert [hello.zid for hello in hellos] == [router.info.zid()]
+        assert hellos[0].whatami == WhatAmI.ROUTER
+
+        start = time.monotonic()
+        assert isinstance(zenoh.scout(what="router", config=config, timeout=0), list)
+        assert time.monotonic() - start < 0.5
+
+    with pytest.raises(ValueError):
+        zenoh.scout(lambda hello: None, timeout=1)
+
+
+def test_scout_multicast_disabled():
+    conf = scouting_config()
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    with pytest.raises(ZError, match="multicast scouting is disabled"):
+        zenoh.scout(config=conf, timeout=1)
+    with pytest.raises(ZError, match="multicast scouting is disabled"):
+        zenoh.scout(config={"scouting": {"multicast": {"enabled": False}}})
@@ -11,6 +11,7 @@
 # Contributors:
 #   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 #
+import random
 import sys
 from dataclasses import dataclass
 
@@ -62,3 +63,27 @@ default_serializer_tests = [
 def test_default_serializer(tp, value):
     zbytes = z_serialize(value)
     assert z_deserialize(tp, zbytes) == value
+
+
+def test_int_range():
+    random.seed(0)
+    # exponents crossing the int32 and int64 boundaries
+    values = [
+        sign * random.randrange(1 << (exp - 1), 1 << exp)
+        for exp in range(1, 128)
+        for sign in (1, -1)
+        for _ in range(4)
+    ]
+    boundaries = (0, 1 << 31, -(1 << 31), 1 << 63, -(1 << 63))
+    values += [b + d for b in boundaries for d in (-1, 0, 1)]
+    for value in values:
+        if -(1 << 31) <= value < 1 << 31:
+            assert z_deserialize(int, z_serialize(value)) == value
+        else:
+            with pytest.raises(OverflowError, match="out of int32 range"):
+                z_serialize(value)
+        if -(1 << 63) <= value < 1 << 63:
+            assert z_deserialize(Int64, z_serialize(Int64(value))) == value
+        if 0 <= value < 1 << 64:
+            assert z_deserialize(UInt64, z_serialize(UInt64(value))) == value
+        assert z_deserialize(Int128, z_serialize(Int128(value))) == value
@@ -12,11 +12,25 @@
 #   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 #
 import json
+import os
+import resource
+import tempfile
+import threading
 import time
 from typing import List, Tuple
 
+import pytest
+
 import zenoh
-from zenoh import CongestionControl, Priority, Query, Sample, Session
+from zenoh import (
+    CongestionControl,
+    ErrorCode,
+    Priority,
+    Query,
+    Sample,
+    Session,
+    ZError,
+)
 
 SLEEP = 1
 MSG_COUNT = 1_000
@@ -193,3 +207,395 @@ def test_session():
     run_session_pubsub(peer01, peer02)
     run_session_qrrrep(peer01, peer02)
     close_session(peer01, peer02)
+
+
+def test_transport_info():
+    peer01, peer02 = open_session(["tcp/127.0.0.1:17454"])
+    time.sleep(SLEEP)
+
+    infos = peer02.transport_info()
+    assert len(infos) == 1
+    info = infos[0]
+    assert info.transport.zid == peer01.info.zid()
+    assert info.link.zid == peer01.info.zid()
+    assert info.batch_size is not None and info.batch_size > 0
+    assert info.batch_size <= info.link.mtu
+    assert info.fragmentation is None
+    assert info.compression is None
+    assert info.sn_resolution is None
+    assert "batch_size" in repr(info)
+
+    close_session(peer01, peer02)
+
+
+def test_info_canonical():
+    peer01, peer02 = open_session(["tcp/127.0.0.1:17457"])
+    time.sleep(SLEEP)
+
+    info = peer01.info_canonical()
+    assert list(info) == ["zid", "routers", "peers", "mode", "locators"]
+    assert list(info.keys()) == ["zid", "routers", "peers", "mode", "locators"]
+    assert len(info) == 5
+    assert "zid" in info and "stats" not in info
+    assert info["zid"] == str(peer01.info.zid())
+    assert info["routers"] == []
+    assert info["peers"] == [str(peer02.info.zid())]
+    assert info["mode"] == "peer"
+    assert "tcp/127.0.0.1:17457" in info["locators"]
+    with pytest.raises(KeyError):
+        info["unknown"]
+    assert json.loads(json.dumps(dict(info))) == dict(info)
+
+    close_session(peer01, peer02)
+
+
+def test_info_ids():
+    peer01, peer02 = open_session(["tcp/127.0.0.1:17468"])
+    time.sleep(SLEEP)
+
+    zid = peer01.info.zid()
+    [peer] = peer01.info.peers_zid()
+    assert peer == peer02.info.zid()
+    assert peer01.info.routers_zid() == []
+    assert {zid: "self", peer: "peer"}[peer02.info.peers_zid()[0]] == "self"
+    assert bytes(zid) == zid.to_bytes()[1:]
+    assert zenoh.ZenohId.from_bytes(zid.to_bytes()) == zid
+    info = peer01.info_dict()
+    assert info["zid"] == str(zid)
+    assert info["peers"] == str(peer)
+    assert info["routers"] == ""
+    assert info["mode"] == "peer"
+
+    close_session(peer01, peer02)
+
+
+def test_get_require_connectivity():
+    port = 17450
+    # the peer keeps trying to connect in background, unlike a client
+    conf = zenoh.Config()
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    conf.insert_json5("connect/endpoints", f'["tcp/127.0.0.1:{port}"]')
+    conf.insert_json5("connect/retry", "{period_init_ms: 100, period_max_ms: 100}")
+    session = zenoh.open(conf)
+
+    with pytest.raises(ZError) as exc_info:
+        session.get("test/connectivity", timeout=0.2, require_connectivity=True)
+    assert exc_info.value.code == ErrorCode.TIMEOUT
+
+    listener = []
+
+    def start_listener():
+        conf = zenoh.Config()
+        conf.insert_json5("scouting/multicast/enabled", "false")
+        conf.insert_json5("listen/endpoints", f'["tcp/127.0.0.1:{port}"]')
+        session = zenoh.open(conf)
+        session.declare_queryable(
+            "test/connectivity", lambda query: query.reply(query.key_expr, "value")
+        )
+        listener.append(session)
+
+    timer = threading.Timer(0.5, start_listener)
+    timer.start()
+    replies = list(
+        session.get("test/connectivity", timeout=5, require_connectivity=True)
+    )
+    assert [r.ok.payload.to_string() for r in replies] == ["value"]
+    timer.join()
+    # connected, but without matching queryable
+    handle = session.get("test/unmatched", timeout=1, require_connectivity=True)
+    assert list(handle) == []
+    session.close()
+    listener[0].close()
+
+
+def test_publisher_retain():
+    peer01, peer02 = open_session(["tcp/127.0.0.1:17458"])
+    time.sleep(SLEEP)
+
+    publishers = [
+        peer01.declare_publisher(f"retain/{key}", retain=True) for key in "abc"
+    ]
+    for i, publisher in enumerate(publishers):
+        publisher.put(f"old {i}")
+        publisher.put(f"value {i}")
+    assert [s.payload.to_string() for s in publishers[0].retained()] == ["value 0"]
+    time.sleep(SLEEP)
+
+    replies = peer02.get("retain/**")
+    values = sorted((str(r.ok.key_expr), r.ok.payload.to_string()) for r in replies)
+    assert values == [
+        ("retain/a", "value 0"),
+        ("retain/b", "value 1"),
+        ("retain/c", "value 2"),
+    ]
+
+    # deleting the key removes its retained sample
+    publishers[1].delete()
+    assert publishers[1].retained() == []
+    for publisher in publishers:
+        publisher.clear_retained()
+        assert publisher.retained() == []
+    assert list(peer02.get("retain/**")) == []
+
+    for publisher in publishers:
+        publisher.undeclare()
+    close_session(peer01, peer02)
+
+
+def test_reply_sample_metadata():
+    peer01, peer02 = open_session(["tcp/127.0.0.1:17459"])
+    time.sleep(SLEEP)
+
+    stored: List[Sample] = []
+    subscriber = peer02.declare_subscriber("store/data", stored.append)
+
+    def reply_stored(query: Query):
+        for sample in stored:
+            query.reply_sample(sample)
+            query.reply_sample(sample.with_key_expr("store/copy"))
+
+    queryable = peer02.declare_queryable("store/**", reply_stored)
+    publisher = peer01.declare_publisher(
+        "store/data",
+        priority=Priority.DATA_HIGH,
+        congestion_control=CongestionControl.BLOCK,
+        express=True,
+        encoding="text/plain",
+    )
+    time.sleep(SLEEP)
+
+    source_info = zenoh.SourceInfo(publisher.id, 42)
+    timestamp = peer01.new_timestamp()
+    publisher.put(
+        "value", attachment="meta", timestamp=timestamp, source_info=source_info
+    )
+    time.sleep(SLEEP)
+    assert len(stored) == 1
+
+    # routers send replies with the QoS of the query
+    replies = peer01.get(
+        "store/**",
+        priority=Priority.DATA_HIGH,
+        congestion_control=CongestionControl.BLOCK,
+        express=True,
+    )
+    replies = sorted((r.ok for r in replies), key=lambda s: str(s.key_expr))
+    assert [str(s.key_expr) for s in replies] == ["store/copy", "store/data"]
+    for sample in replies:
+        assert sample.kind == stored[0].kind == zenoh.SampleKind.PUT
+        assert sample.payload.to_string() == "value"
+        assert str(sample.encoding) == str(stored[0].encoding) == "text/plain"
+        assert sample.timestamp == stored[0].timestamp == timestamp
+        assert sample.source_info.source_id.eid == publisher.id.eid
+        assert sample.source_info.source_id.zid == peer01.info.zid()
+        assert sample.source_info.source_sn == 42
+        assert sample.priority == stored[0].priority == Priority.DATA_HIGH
+        assert sample.congestion_control == CongestionControl.BLOCK
+        assert sample.express == stored[0].express
+        assert sample.raw_attachment.to_string() == "meta"
+
+    publisher.undeclare()
+    queryable.undeclare()
+    subscriber.undeclare()
+    close_session(peer01, peer02)
+
+
+def test_watch_entities():
+    conf = zenoh.Config()
+    conf.insert_json5("listen/endpoints", '["tcp/127.0.0.1:17464"]')
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    conf.insert_json5("adminspace/enabled", "true")
+    peer01 = zenoh.open(conf)
+    conf = zenoh.Config()
+    conf.insert_json5("connect/endpoints", '["tcp/127.0.0.1:17464"]')
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    peer02 = zenoh.open(conf)
+    time.sleep(SLEEP)
+
+    events = []
+    watched = lambda event: event.key_expr == "watch/data"
+    with peer01.watch_entities(
+        "subscribers", lambda e: watched(e) and events.append(e), interval=0.1
+    ):
+        subscriber = peer02.declare_subscriber("watch/data")
+        time.sleep(SLEEP)
+        entities = peer01.list_entities("subscribers")
+        assert [(str(e.key_expr), e.zid) for e in entities if watched(e)] == [
+            ("watch/data", peer02.info.zid())
+        ]
+        subscriber.undeclare()
+        time.sleep(SLEEP)
+
+    assert [(e.declared, e.zid) for e in events] == [
+        (True, peer02.info.zid()),
+        (False, peer02.info.zid()),
+    ]
+    assert not any(watched(e) for e in peer01.list_entities("subscribers"))
+    with pytest.raises(ValueError):
+        peer01.list_entities("tokens")
+    close_session(peer01, peer02)
+
+
+def test_put_get_file():
+    peer01, peer02 = open_session(["tcp/127.0.0.1:17465"])
+    time.sleep(SLEEP)
+
+    stored: List[Sample] = []
+    subscriber = peer02.declare_subscriber("files/**", stored.append)
+    queryable = peer02.declare_queryable(
+        "files/**", lambda query: query.reply_sample(stored[-1])
+    )
+    time.sleep(SLEEP)
+
+    # the larger file exceeds the batch size, so it is fragmented by the transport
+    files = [("data.bin", os.urandom(1024)), ("image.PNG", os.urandom(4 * 1024 * 1024))]
+    encodings = ["application/octet-stream", "image/png"]
+    with tempfile.TemporaryDirectory() as tmp:
+        for (name, content), encoding in zip(files, encodings):
+            path = os.path.join(tmp, name)
+            with open(path, "wb") as f:
+                f.write(content)
+            peer01.put_file(f"files/{name}", path)
+            time.sleep(SLEEP)
+            assert str(stored[-1].encoding) == encoding
+
+            dest_path = os.path.join(tmp, f"copy-{name}")
+            sample = peer01.get_file(f"files/{name}", dest_path)
+            assert str(sample.encoding) == encoding
+            with open(dest_path, "rb") as f:
+                assert f.read() == content
+
+        peer01.put_file("files/config", os.path.join(tmp, "data.bin"), "text/json")
+        time.sleep(SLEEP)
+        assert str(stored[-1].encoding) == "text/json"
+
+        with pytest.raises(zenoh.ZError) as err:
+            peer01.put_file("files/missing", os.path.join(tmp, "missing.json"))
+        assert err.value.code == zenoh.ErrorCode.IO
+        missing_dir = os.path.join(tmp, "missing", "copy.bin")
+        with pytest.raises(zenoh.ZError) as err:
+            peer01.get_file("files/**", missing_dir)
+        assert err.value.code == zenoh.ErrorCode.IO
+        queryable.undeclare()
+        time.sleep(SLEEP)
+        with pytest.raises(zenoh.ZError) as err:
+            peer01.get_file("files/**", os.path.join(tmp, "copy.bin"), timeout=1)
+        assert err.value.code != zenoh.ErrorCode.IO
+
+    subscriber.undeclare()
+    close_session(peer01, peer02)
+
+
+def test_sample_payload_aliasing():
+    conf = zenoh.Config()
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    session = zenoh.open(conf)
+    subscriber = session.declare_subscriber("alias/data")
+    payload = os.urandom(1024 * 1024)
+    session.put("alias/data", payload)
+    sample = subscriber.recv()
+
+    # peak resident memory, in KiB on Linux
+    max_rss = resource.getrusage(resource.RUSAGE_SELF).ru_maxrss
+    aliases = [sample.with_key_expr(f"alias/{i}") for i in range(10_000)]
+    growth = resource.getrusage(resource.RUSAGE_SELF).ru_maxrss - max_rss
+    # copying the payloads would take 10 GiB
+    assert growth < 1024 * 1024
+
+    def reply_alias(query: Query):
+        index = int(str(query.key_expr).split("/")[1])
+        query.reply_sample(aliases[index])
+
+    queryable = session.declare_queryable("alias/**", reply_alias)
+    for i in [0, 1234, 9999]:
+        [reply] = session.get(f"alias/{i}")
+        assert str(reply.ok.key_expr) == f"alias/{i}"
+        assert reply.ok.payload == sample.payload
+        assert reply.ok.payload.to_bytes() == payload
+
+    queryable.undeclare()
+    subscriber.undeclare()
+    session.close()
+
+
+def test_autoflush_interval():
+    endpoints = '["tcp/127.0.0.1:17467"]'
+    conf = zenoh.Config()
+    conf.insert_json5("listen/endpoints", endpoints)
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    peer01 = zenoh.open(conf, autoflush_interval_ms=50)
+    conf = zenoh.Config()
+    conf.insert_json5("connect/endpoints", endpoints)
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    peer02 = zenoh.open(conf)
+    time.sleep(SLEEP)
+    received = threading.Event()
+    subscriber = peer02.declare_subscriber("flush/data", lambda _: received.set())
+    time.sleep(SLEEP)
+
+    peer01.put("flush/data", "small")
+    # the batch is pushed after the time budget, the sample is delivered promptly
+    assert received.wait(1)
+
+    subscriber.undeclare()
+    close_session(peer01, peer02)
+
+
+def test_context_managers():
+    conf = zenoh.Config()
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    with zenoh.open(conf) as session:
+        with session.declare_subscriber("context/data") as subscriber:
+            with session.declare_publisher("context/data") as publisher:
+                publisher.put("value")
+                assert subscriber.recv().payload.to_string() == "value"
+                # undeclaring within the block doesn't make the exit raise
+                publisher.undeclare()
+            subscriber.undeclare()
+        with session.declare_queryable("context/query") as queryable:
+            queryable.undeclare()
+        with pytest.raises(ZError):
+            queryable.undeclare()
+    assert session.is_closed()
+    with pytest.raises(ZError) as excinfo:
+        session.put("context/data", "value")
+    assert excinfo.value.code == ErrorCode.SESSION_CLOSED
+
+    # closing within the block doesn't make the exit raise either
+    with zenoh.open(conf) as session:
+        session.close()
+    assert session.is_closed()
+
+
+def test_wait_for_matching():
+    conf = zenoh.Config()
+    conf.insert_json5("listen/endpoints", '["tcp/127.0.0.1:17472"]')
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    conf.insert_json5("adminspace/enabled", "true")
+    peer01 = zenoh.open(conf)
+    conf = zenoh.Config()
+    conf.insert_json5("connect/endpoints", '["tcp/127.0.0.1:17472"]')
+    conf.insert_json5("scouting/multicast/enabled", "false")
+    peer02 = zenoh.open(conf)
+    time.sleep(SLEEP)
+
+    subscribers = []
+    declare = lambda: subscribers.append(peer02.declare_subscriber("matching/a"))
+    threading.Timer(0.5, declare).start()
+    start = time.monotonic()
+    assert peer01.wait_for_matching("subscribers", "matching/**", timeout=5) == 1
+    assert 0.5 <= time.monotonic() - start < 2
+    # larger counts are polled through the admin space
+    subscribers.append(peer02.declare_subscriber("matching/b"))
+    assert peer01.wait_for_matching("subscribers", "matching/*", 2, timeout=5) >= 2
+
+    with pytest.raises(ZError, match="0 observed") as excinfo:
+        peer01.wait_for_matching("queryables", "matching/**", timeout=0.2)
+    assert excinfo.value.code == ErrorCode.TIMEOUT
+    with pytest.raises(ValueError):
+        peer01.wait_for_matching("tokens", "matching/**")
+    with pytest.raises(ValueError):
+        peer01.wait_for_matching("subscribers", "matching/**", count=0)
+    for subscriber in subscribers:
+        subscriber.undeclare()
+    close_session(peer01, peer02)
@@ -0,0 +1,74 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import random
+import string
+import time
+
+import pytest
+
+import zenoh
+from zenoh import KeyExpr
+
+SHARDS = 4
+
+
+def keys() -> list[str]:
+    rng = random.Random(42)
+    alphabet = string.ascii_letters + string.digits + "-_.~é"
+    ids = {"".join(rng.choices(alphabet, k=rng.randint(1, 8))) for _ in range(500)}
+    return [f"jobs/{id}/data" for id in sorted(ids)] + ["jobs/élan/data"]
+
+
+def test_partition():
+    population = keys()
+    covered = {key: [] for key in population}
+    for shard in range(SHARDS):
+        key_exprs = zenoh.shard_key_expr("jobs/*/data", shard, SHARDS)
+        for key in population:
+            if any(k.intersects(key) for k in key_exprs) and zenoh.shard_matches(
+                key, shard, SHARDS
+            ):
+                covered[key].append(shard)
+    assert all(len(shards) == 1 for shards in covered.values())
+    assert covered["jobs/élan/data"] == [0]
+    # non-zero shards only subscribe to their keys
+    assert zenoh.shard_key_expr("jobs/**", 1, SHARDS)[0] == KeyExpr("jobs/1$*/**")
+
+
+def test_invalid():
+    with pytest.raises(ValueError):
+        zenoh.shard_key_expr("jobs/a/data", 0, SHARDS)
+    with pytest.raises(ValueError):
+        zenoh.shard_key_expr("jobs/*/data", SHARDS, SHARDS)
+    with pytest.raises(ValueError):
+        zenoh.shard_key_expr("jobs/*/data", 0, SHARDS, by="hash")
+
+
+def test_sharded_subscribers(session: zenoh.Session):
+    population = keys()
+    received = {shard: [] for shard in range(SHARDS)}
+    subscribers = [
+        session.subscriber_builder(key_expr)
+        .shard((shard, SHARDS))
+        .declare(lambda s, shard=shard: received[shard].append(str(s.key_expr)))
+        for shard in range(SHARDS)
+        for key_expr in zenoh.shard_key_expr("jobs/*/data", shard, SHARDS)
+    ]
+    for key in population:
+        session.put(key, b"")
+    time.sleep(1)
+    all_received = [key for keys in received.values() for key in keys]
+    assert sorted(all_received) == sorted(population)
+    for subscriber in subscribers:
+        subscriber.undeclare()
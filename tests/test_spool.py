@@ -0,0 +1,85 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import gc
+import os
+
+import pytest
+
+import zenoh
+from zenoh import Query
+
+PAYLOAD_SIZE = 64 * 1024
+REPLY_COUNT = 16
+
+
+def payload(i: int) -> bytes:
+    return bytes([i]) * PAYLOAD_SIZE
+
+
+def reply_large(query: Query):
+    for i in range(REPLY_COUNT):
+        query.reply(f"spool/{i}", payload(i))
+
+
+def test_spooled_iteration(session: zenoh.Session):
+    queryable = session.declare_queryable("spool/**", reply_large, complete=True)
+    # the first two payloads stay in memory
+    replies = session.get_spooled("spool/**", 2 * PAYLOAD_SIZE, timeout=5)
+    assert len(replies) == REPLY_COUNT
+    assert replies.spooled_count == REPLY_COUNT - 2
+    path = replies.spool_path
+    assert path is not None and os.path.exists(path)
+    assert os.path.getsize(path) == (REPLY_COUNT - 2) * PAYLOAD_SIZE
+    received = [reply.ok for reply in replies]
+    assert [str(sample.key_expr) for sample in received] == [
+        f"spool/{i}" for i in range(REPLY_COUNT)
+    ]
+    for i, sample in enumerate(received):
+        assert sample.payload.to_bytes() == payload(i)
+    # consuming the replies removes the spool file
+    assert not os.path.exists(path)
+    assert replies.spool_path is None
+    queryable.undeclare()
+
+
+def test_spool_cleanup(session: zenoh.Session):
+    queryable = session.declare_queryable("spool/**", reply_large, complete=True)
+    with session.get_spooled("spool/**", 0, timeout=5) as replies:
+        path = replies.spool_path
+        assert os.path.exists(path)
+        assert next(replies).ok.payload.to_bytes() == payload(0)
+    assert not os.path.exists(path)
+    assert replies.spool_path is None
+    assert list(replies) == []
+
+    replies = session.get_spooled("spool/**", 0, timeout=5)
+    path = replies.spool_path
+    assert os.path.exists(path)
+    del replies
+    gc.collect()
+    assert not os.path.exists(path)
+    queryable.undeclare()
+
+
+def test_unspooled(session: zenoh.Session):
+    queryable = session.declare_queryable("spool/**", reply_large, complete=True)
+    budget = REPLY_COUNT * PAYLOAD_SIZE
+    replies = session.get_spooled("spool/**", budget, timeout=5)
+    assert replies.spool_path is None
+    assert replies.spooled_count == 0
+    assert len(list(replies)) == REPLY_COUNT
+    # spooled gets return buffered replies, not handlers
+    with pytest.raises(TypeError):
+        session.get_spooled("spool/**", 0, lambda _: None)
+    queryable.undeclare()
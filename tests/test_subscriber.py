@@ -0,0 +1,446 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import threading
+import time
+
+import pytest
+
+import zenoh
+from zenoh import ErrorCode, Sample, Session, ZError
+
+KEYEXPR = "test/subscriber"
+
+
+def put_range(session: Session, start: int, stop: int):
+    for i in range(start, stop):
+        session.put(KEYEXPR, str(i))
+
+
+def test_pause_drop(session: Session):
+    sub = session.declare_subscriber(KEYEXPR)
+    put_range(session, 0, 3)
+    sub.pause()
+    assert sub.paused
+    put_range(session, 3, 6)
+    sub.resume()
+    assert not sub.paused
+    put_range(session, 6, 9)
+    received = [sub.recv().payload.to_string() for _ in range(6)]
+    assert received == ["0", "1", "2", "6", "7", "8"]
+    assert sub.try_recv() is None
+    assert sub.dropped_while_paused == 3
+
+
+def test_pause_buffer(session: Session):
+    received = []
+
+    def callback(sample: Sample):
+        received.append(sample.payload.to_string())
+
+    sub = session.declare_subscriber(KEYEXPR, callback)
+    put_range(session, 0, 3)
+    sub.pause(pause_buffer=2)
+    put_range(session, 3, 6)
+    time.sleep(0.5)
+    assert received == ["0", "1", "2"]
+    sub.resume()
+    put_range(session, 6, 9)
+    time.sleep(0.5)
+    assert received == ["0", "1", "2", "3", "4", "6", "7", "8"]
+    assert sub.dropped_while_paused == 1
+
+
+def test_pause_from_callback(session: Session):
+    received = []
+
+    def callback(sample: Sample):
+        received.append(sample.payload.to_string())
+        # pausing doesn't deadlock while the buffered samples are delivered
+        if sample.payload.to_string() == "3":
+            sub.pause(pause_buffer=10)
+
+    sub = session.declare_subscriber(KEYEXPR, callback)
+    sub.pause(pause_buffer=10)
+    put_range(session, 0, 5)
+    time.sleep(0.5)
+    sub.resume()
+    put_range(session, 5, 7)
+    time.sleep(0.5)
+    assert received == ["0", "1", "2", "3", "4"]
+    assert sub.paused
+    sub.resume()
+    time.sleep(0.5)
+    assert received == ["0", "1", "2", "3", "4", "5", "6"]
+
+
+def test_max_samples(session: Session):
+    completions = []
+    sub = (
+        session.subscriber_builder(KEYEXPR)
+        .limits(max_samples=3, on_complete=lambda *args: completions.append(args))
+        .declare()
+    )
+    put_range(session, 0, 5)
+    # the channel is closed once the limit is reached
+    assert [s.payload.to_string() for s in sub.handler] == ["0", "1", "2"]
+    time.sleep(0.5)
+    assert completions == [(3, "count")]
+    assert sub.closed
+    sub.undeclare()
+    sub.undeclare()
+
+
+def test_max_duration(session: Session):
+    received = []
+    completions = []
+    sub = (
+        session.subscriber_builder(KEYEXPR)
+        .limits(
+            max_duration=0.5, on_complete=lambda *args: completions.append(args)
+        )
+        .declare(lambda s: received.append(s.payload.to_string()))
+    )
+    put_range(session, 0, 2)
+    assert not sub.closed
+    time.sleep(1)
+    put_range(session, 2, 4)
+    time.sleep(0.5)
+    assert received == ["0", "1"]
+    assert completions == [(2, "duration")]
+    assert sub.closed
+    sub.undeclare()
+
+
+def test_limits_race(session: Session):
+    for _ in range(20):
+        completions = []
+        sub = (
+            session.subscriber_builder(KEYEXPR)
+            .limits(
+                max_duration=0.1,
+                max_samples=50,
+                on_complete=lambda *args: completions.append(args),
+            )
+            .declare(lambda s: None)
+        )
+        start = time.monotonic()
+        i = 0
+        while time.monotonic() - start < 0.2:
+            session.put(KEYEXPR, str(i))
+            i += 1
+            time.sleep(0.002)
+        time.sleep(0.2)
+        assert len(completions) == 1
+        count, reason = completions[0]
+        assert (reason, count) == ("count", 50) or (
+            reason == "duration" and count <= 50
+        )
+        assert sub.closed
+
+
+def test_undeclare_before_limits(session: Session):
+    completions = []
+    sub = (
+        session.subscriber_builder(KEYEXPR)
+        .limits(
+            max_duration=0.2,
+            max_samples=10,
+            on_complete=lambda *args: completions.append(args),
+        )
+        .declare()
+    )
+    sub.undeclare()
+    assert sub.closed
+    time.sleep(0.5)
+    assert completions == []
+
+
+def test_limits_invalid(session: Session):
+    builder = session.subscriber_builder(KEYEXPR)
+    with pytest.raises(ValueError):
+        builder.limits(max_samples=0)
+    with pytest.raises(ValueError):
+        builder.limits()
+
+
+def check_set_callback(session: Session, executor=None):
+    old, new = [], []
+    builder = session.subscriber_builder(KEYEXPR)
+    if executor is not None:
+        builder.executor(executor)
+    sub = builder.declare(lambda s: old.append(s))
+    put_range(session, 0, 500)
+    sub.set_callback(lambda s: new.append(s))
+    put_range(session, 500, 1000)
+    time.sleep(1)
+    assert len(old) + len(new) == 1000
+    assert len(new) > 0
+    received = [int(s.payload.to_string()) for s in old + new]
+    assert sorted(received) == list(range(1000))
+    sub.undeclare()
+
+
+def test_set_callback(session: Session):
+    check_set_callback(session)
+    check_set_callback(session, zenoh.Executor(2))
+
+
+def test_set_callback_invalid(session: Session):
+    sub = session.declare_subscriber(KEYEXPR, lambda s: None)
+    with pytest.raises(ValueError):
+        sub.set_callback(None)
+    channel = session.declare_subscriber(KEYEXPR)
+    with pytest.raises(ValueError):
+        channel.set_callback(print)
+
+
+def test_gap_report(session: Session):
+    sub = session.declare_subscriber(KEYEXPR, lambda s: None)
+    pub = session.declare_publisher(KEYEXPR)
+    assert sub.gap_report() == {}
+    older = session.new_timestamp()
+    time.sleep(0.01)
+    for sn in [0, 1, 2, 5, 6, 10, 3]:
+        source_info = zenoh.SourceInfo(pub.id, sn)
+        timestamp = session.new_timestamp()
+        session.put(KEYEXPR, str(sn), source_info=source_info, timestamp=timestamp)
+    # a timestamp older than the previous sample is out of order
+    source_info = zenoh.SourceInfo(pub.id, 11)
+    session.put(KEYEXPR, "11", source_info=source_info, timestamp=older)
+    # samples without source info are ignored
+    session.put(KEYEXPR, "none")
+    time.sleep(0.5)
+    report = sub.gap_report()
+    assert list(report) == [session.zid()]
+    stats = report[session.zid()]
+    assert stats.gaps == 2
+    assert stats.largest_gap == 3
+    assert stats.out_of_order == 1
+    assert stats.last_sn == 11
+    sub.reset_gap_report()
+    assert sub.gap_report() == {}
+
+
+def test_wait_for(open_session):
+    zenoh.debug.track_handles(True)
+    try:
+        session = open_session()
+        publisher = session.declare_publisher(KEYEXPR)
+        timer = threading.Timer(0.2, put_range, (session, 0, 5))
+        timer.start()
+        sample = session.sample_once(KEYEXPR)
+        timer.join()
+        assert sample.payload.to_string() == "0"
+
+        timer = threading.Timer(0.2, put_range, (session, 0, 5))
+        timer.start()
+        is_three = lambda s: s.payload.to_string() == "3"
+        sample = session.wait_for(KEYEXPR, timeout=5, predicate=is_three)
+        timer.join()
+        assert sample.payload.to_string() == "3"
+
+        with pytest.raises(ZError) as exc_info:
+            session.wait_for(KEYEXPR, timeout=0.2)
+        assert exc_info.value.code == ErrorCode.TIMEOUT
+
+        def failing(sample: Sample) -> bool:
+            raise RuntimeError("predicate failed")
+
+        timer = threading.Timer(0.2, put_range, (session, 0, 1))
+        timer.start()
+        with pytest.raises(RuntimeError):
+            session.wait_for(KEYEXPR, timeout=5, predicate=failing)
+        timer.join()
+
+        # the temporary subscribers are undeclared in every case
+        assert not publisher.matching_status.matching
+        handles = zenoh.debug.open_handles()
+        assert [h.entity for h in handles] == [session, publisher]
+        publisher.undeclare()
+    finally:
+        zenoh.debug.track_handles(False)
+
+
+def test_put_and_confirm(session: Session):
+    publisher = session.declare_publisher(KEYEXPR)
+    querier = session.declare_querier(KEYEXPR)
+    for via in ("local_sub", "queryable"):
+        sample = session.put_and_confirm(
+            KEYEXPR, "ok", encoding=zenoh.Encoding.TEXT_PLAIN, via=via
+        )
+        assert sample.payload.to_string() == "ok"
+        assert sample.encoding == zenoh.Encoding.TEXT_PLAIN
+
+    with pytest.raises(ZError) as exc_info:
+        session.put_and_confirm(KEYEXPR, "late", via="queryable", timeout=0)
+    assert exc_info.value.code == ErrorCode.TIMEOUT
+    with pytest.raises(ValueError):
+        session.put_and_confirm(KEYEXPR, "ok", via="carrier_pigeon")
+
+    # the temporary entities are undeclared in every case
+    assert not publisher.matching_status.matching
+    assert not querier.matching_status.matching
+    publisher.undeclare()
+    querier.undeclare()
+
+
+def delayed_storage(session: Session, delay: float):
+    """A storage-like subscriber and queryable, applying the puts after ``delay``."""
+    stored = {}
+
+    def apply(sample: Sample):
+        # local subscribers are called during the put, so the write is deferred
+        timer = threading.Timer(delay, stored.__setitem__, ("sample", sample))
+        timer.start()
+
+    def reply(query: zenoh.Query):
+        if "sample" in stored:
+            sample = stored["sample"]
+            query.reply(sample.key_expr, sample.payload, encoding=sample.encoding)
+
+    subscriber = session.declare_subscriber(KEYEXPR, apply)
+    queryable = session.declare_queryable(KEYEXPR, reply)
+    return subscriber, queryable
+
+
+def test_put_sync(session: Session):
+    subscriber, queryable = delayed_storage(session, 0.3)
+    start = time.monotonic()
+    polls = session.put_sync(KEYEXPR, "v1", encoding=zenoh.Encoding.TEXT_PLAIN)
+    assert time.monotonic() - start >= 0.3
+    assert polls > 1
+    [reply] = session.get(KEYEXPR)
+    assert reply.ok.payload.to_string() == "v1"
+
+    # the stored value has another encoding, then the update is too late
+    with pytest.raises(ZError, match="last observed 'v1'") as exc_info:
+        session.put_sync(KEYEXPR, "v1", verify_selector=KEYEXPR, timeout=0.1)
+    assert exc_info.value.code == ErrorCode.TIMEOUT
+    with pytest.raises(ZError, match="last observed 'v1'"):
+        session.put_sync(KEYEXPR, "v2", encoding="text/plain", timeout=0.1)
+    queryable.undeclare()
+    subscriber.undeclare()
+
+
+def test_publisher_write(session: Session):
+    sub = session.declare_subscriber(KEYEXPR)
+    publisher = session.declare_publisher(KEYEXPR)
+    publisher.write(zenoh.SampleKind.PUT, "value", attachment="meta")
+    publisher.write(zenoh.SampleKind.DELETE)
+    put, delete = sub.recv(), sub.recv()
+    assert put.kind == zenoh.SampleKind.PUT
+    assert put.payload.to_string() == "value"
+    assert put.raw_attachment.to_string() == "meta"
+    assert delete.kind == zenoh.SampleKind.DELETE
+    with pytest.raises(ValueError):
+        publisher.write(zenoh.SampleKind.PUT)
+    with pytest.raises(ValueError):
+        publisher.write(zenoh.SampleKind.DELETE, "value")
+    publisher.undeclare()
+
+
+def test_recv_timeout(session: Session):
+    subscriber = session.declare_subscriber(KEYEXPR)
+    start = time.monotonic()
+    assert subscriber.recv(timeout=0.3) is None
+    assert time.monotonic() - start >= 0.3
+    assert subscriber.try_recv() is None
+    put_range(session, 0, 2)
+    assert subscriber.recv(timeout=1).payload.to_string() == "0"
+    assert subscriber.handler.recv(1).payload.to_string() == "1"
+    subscriber.undeclare()
+
+
+def test_undeclare_unblocks_recv(session: Session):
+    subscriber = session.declare_subscriber(KEYEXPR)
+    errors = []
+    iterated = []
+
+    def recv():
+        try:
+            subscriber.recv()
+        except ZError as err:
+            errors.append(err)
+
+    def iterate():
+        iterated.extend(sample.payload.to_string() for sample in subscriber)
+
+    threads = [threading.Thread(target=recv), threading.Thread(target=iterate)]
+    for thread in threads:
+        thread.start()
+    time.sleep(0.3)
+    subscriber.undeclare()
+    for thread in threads:
+        thread.join(timeout=2)
+        assert not thread.is_alive()
+    assert len(errors) == 1
+    assert iterated == []
+
+
+
+def test_callback_stats(session: Session):
+    # a key expression of its own, to find the subscriber in the metrics snapshot
+    key_expr = "test/subscriber/stats"
+
+    def callback(sample: Sample):
+        time.sleep(0.05)
+
+    sub = session.subscriber_builder(key_expr).time_callbacks().declare(callback)
+    untimed = session.declare_subscriber(key_expr, callback)
+    channel = session.declare_subscriber(key_expr)
+    for i in range(5):
+        session.put(key_expr, str(i))
+    time.sleep(1)
+    stats = sub.callback_stats()
+    assert stats["count"] == 5
+    assert 0.05 <= stats["min"] <= stats["mean"] <= stats["max"]
+    assert stats["mean"] == pytest.approx(0.05, abs=0.04)
+    assert stats["min"] <= stats["p95"] <= stats["max"]
+    assert untimed.callback_stats() is None
+    assert channel.callback_stats() is None
+    subscribers = zenoh.metrics_snapshot()["subscribers"]
+    [snapshot] = [s for s in subscribers if s["key_expr"] == key_expr]
+    assert snapshot["callback_count"] == 5
+    assert snapshot["callback_mean"] == stats["mean"]
+    sub.undeclare()
+    untimed.undeclare()
+    channel.undeclare()
+
+
+def test_ignore_self(open_session):
+    key_expr = "test/subscriber/self"
+    session, other = open_session(), open_session()
+    own = session.declare_publisher(key_expr)
+    foreign = other.declare_publisher(key_expr)
+    sub = session.subscriber_builder(key_expr).ignore_self().declare()
+    all_samples = session.declare_subscriber(key_expr)
+    session.put(key_expr, "own", source_info=zenoh.SourceInfo(own.id, 0))
+    # the source info is all that matters, not the publishing session
+    session.put(key_expr, "foreign", source_info=zenoh.SourceInfo(foreign.id, 0))
+    session.put(key_expr, "unknown")
+    time.sleep(0.5)
+    assert [sub.try_recv().payload.to_string() for _ in range(2)] == [
+        "foreign",
+        "unknown",
+    ]
+    assert sub.try_recv() is None
+    assert sub.ignored_self_count == 1
+    assert sub.unknown_origin_count == 1
+    assert all(all_samples.try_recv() is not None for _ in range(3))
+    assert all_samples.ignored_self_count == 0
+    assert all_samples.unknown_origin_count == 0
+    sub.undeclare()
+    all_samples.undeclare()
+    own.undeclare()
+    foreign.undeclare()
@@ -0,0 +1,121 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import random
+
+import pytest
+
+import zenoh
+from zenoh import NTP64, Timestamp, TimestampId, ZenohId
+
+
+def random_id(rng: random.Random) -> bytes:
+    # ids are stored without trailing zeros
+    size = rng.randint(1, 16)
+    return bytes(rng.randrange(256) for _ in range(size - 1)) + bytes(
+        [rng.randint(1, 255)]
+    )
+
+
+def test_timestamp_bytes_round_trip():
+    rng = random.Random(0)
+    for _ in range(1_000):
+        ntp64 = rng.randrange(2**64)
+        seconds, fraction = divmod(ntp64, 2**32)
+        timestamp = Timestamp(
+            NTP64(seconds, fraction * 10**9 >> 32), TimestampId(random_id(rng))
+        )
+        decoded = Timestamp.from_bytes(timestamp.to_bytes())
+        assert decoded == timestamp
+        assert decoded.ntp64 == timestamp.ntp64
+        assert bytes(decoded.get_id()) == bytes(timestamp.get_id())
+
+
+def test_zenoh_id_bytes_round_trip():
+    rng = random.Random(0)
+    for _ in range(1_000):
+        data = b"\x01" + random_id(rng)
+        zid = ZenohId.from_bytes(data)
+        assert zid.to_bytes() == data
+        assert ZenohId.from_bytes(zid.to_bytes()) == zid
+
+
+def test_bytes_golden(session: zenoh.Session):
+    # the format is stable: these bytes must be decoded the same way by all releases
+    golden = bytes.fromhex("01" "0123456789abcdef" "a1b2")
+    timestamp = Timestamp.from_bytes(golden)
+    assert timestamp.ntp64 == 0x0123456789ABCDEF
+    assert timestamp.get_time_as_ntp64().as_secs() == 0x01234567
+    assert bytes(timestamp.get_id()) == bytes.fromhex("a1b2")
+    assert str(timestamp) == "81985529216486895/b2a1"
+    assert timestamp.to_bytes() == golden
+
+    golden = bytes.fromhex("01" "a1b2c3")
+    zid = ZenohId.from_bytes(golden)
+    assert str(zid) == "c3b2a1"
+    assert zid.to_bytes() == golden
+
+    zid = session.info.zid()
+    assert ZenohId.from_bytes(zid.to_bytes()) == zid
+    timestamp = session.new_timestamp()
+    assert Timestamp.from_bytes(timestamp.to_bytes()) == timestamp
+
+
+@pytest.mark.parametrize(
+    "data", [b"", b"\x02\x01", b"\x01", b"\x01\x00", b"\x01" + bytes(17)]
+)
+def test_zenoh_id_invalid_bytes(data: bytes):
+    with pytest.raises(ValueError):
+        ZenohId.from_bytes(data)
+
+
+@pytest.mark.parametrize(
+    "data", [b"", b"\x02" + bytes(9), b"\x01" + bytes(7), b"\x01" + bytes(8)]
+)
+def test_timestamp_invalid_bytes(data: bytes):
+    with pytest.raises(ValueError):
+        Timestamp.from_bytes(data)
+
+
+def test_timestamp_construction(session: zenoh.Session):
+    subscriber = session.declare_subscriber("timestamp/**")
+    timestamp = Timestamp(1_700_000_000.25, b"\x2a")
+    assert timestamp.get_time_as_ntp64().as_secs() == 1_700_000_000
+    session.put("timestamp/a", "value", timestamp=timestamp)
+    received = subscriber.recv().timestamp
+    assert received == timestamp
+    assert received.ntp64 == timestamp.ntp64
+    assert hash(received) == hash(timestamp)
+    now = Timestamp.now(session)
+    assert sorted([now, timestamp, session.new_timestamp()])[:2] == [
+        timestamp,
+        now,
+    ]
+    subscriber.undeclare()
+    for time in [-1.0, float("nan")]:
+        with pytest.raises(ValueError):
+            Timestamp(time, b"\x2a")
+
+
+def test_replayed_timestamps(session: zenoh.Session):
+    subscriber = session.declare_subscriber("replay/**")
+    recorded = [Timestamp(1_600_000_000.0 + i, b"\x07") for i in range(2)]
+    session.put("replay/a", "value", timestamp=recorded[0])
+    session.delete("replay/a", timestamp=recorded[1])
+    put, delete = subscriber.recv(), subscriber.recv()
+    assert put.kind == zenoh.SampleKind.PUT
+    assert delete.kind == zenoh.SampleKind.DELETE
+    # the original timestamps are kept, not replaced by fresh ones
+    assert [put.timestamp, delete.timestamp] == recorded
+    assert bytes(delete.timestamp.get_id()) == b"\x07"
+    subscriber.undeclare()
@@ -0,0 +1,96 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+import pytest
+
+import zenoh
+from zenoh import Encoding, Query
+
+# a lone surrogate, as produced by bad upstream decoding
+SURROGATE = "ok \ud800".encode("utf-8", "surrogatepass")
+
+
+def test_publish_validation(session: zenoh.Session):
+    zenoh.set_publish_validation(True)
+    try:
+        subscriber = session.declare_subscriber("validation/**")
+        with pytest.raises(ValueError, match="UTF-8 .* byte offset 3"):
+            session.put("validation/text", SURROGATE, encoding=Encoding.TEXT_PLAIN)
+        with pytest.raises(ValueError, match="JSON .* column 7"):
+            session.put(
+                "validation/json", '{"a": }', encoding=Encoding.APPLICATION_JSON
+            )
+        # the publisher encoding is validated
+        publisher = session.declare_publisher(
+            "validation/json", encoding=Encoding.TEXT_JSON
+        )
+        with pytest.raises(ValueError, match="JSON"):
+            publisher.put("[1, 2")
+        # other encodings are not
+        session.put("validation/bytes", SURROGATE)
+        session.put("validation/json", '{"a": 1}', encoding=Encoding.TEXT_JSON)
+        assert subscriber.recv().payload.to_bytes() == SURROGATE
+        assert subscriber.recv().payload.to_string() == '{"a": 1}'
+        assert subscriber.try_recv() is None
+        publisher.undeclare()
+        subscriber.undeclare()
+    finally:
+        zenoh.set_publish_validation(False)
+
+
+def test_publish_validation_override(session: zenoh.Session):
+    subscriber = session.declare_subscriber("validation/**")
+    session.put("validation/text", SURROGATE, encoding=Encoding.TEXT_PLAIN)
+    with pytest.raises(ValueError):
+        session.put(
+            "validation/text",
+            SURROGATE,
+            encoding=Encoding.TEXT_PLAIN,
+            validate=True,
+        )
+    zenoh.set_publish_validation(True)
+    try:
+        session.put(
+            "validation/text",
+            SURROGATE,
+            encoding=Encoding.TEXT_PLAIN,
+            validate=False,
+        )
+    finally:
+        zenoh.set_publish_validation(False)
+    assert [subscriber.recv().payload.to_bytes() for _ in range(2)] == [
+        SURROGATE,
+        SURROGATE,
+    ]
+    assert subscriber.try_recv() is None
+
+    errors = []
+
+    def reply(query: Query):
+        try:
+            query.reply(
+                query.key_expr,
+                "{",
+                encoding=Encoding.APPLICATION_JSON,
+                validate=True,
+            )
+        except ValueError as err:
+            errors.append(err)
+        query.reply(query.key_expr, "{", encoding=Encoding.APPLICATION_JSON)
+
+    queryable = session.declare_queryable("validation/**", reply)
+    [reply] = session.get("validation/query")
+    assert reply.ok.payload.to_string() == "{"
+    assert len(errors) == 1
+    queryable.undeclare()
+    subscriber.undeclare()
@@ -11,12 +11,13 @@
 # Contributors:
 #   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 #
-from collections.abc import Callable
+from collections.abc import Callable, Iterable, Iterator
 from datetime import datetime, timedelta
 from enum import Enum, auto
 from pathlib import Path
-from typing import Any, Generic, Self, TypeVar, final, overload
+from typing import Any, Generic, Literal, Self, TypeVar, final, overload
 
+from . import debug as debug
 from . import ext as ext
 from . import handlers as handlers
 from . import shm as shm
@@ -51,9 +52,28 @@ class ZError(Exception):
             print(f"Error: {e}")  # Get error message
 
     The error message can be accessed via str(e) or by printing the exception directly.
+
+    When raised by a :class:`Session` operation, the message is prefixed with the operation
+    name and the key expression (or selector) it was applied to; both are also available as
+    attributes:
+
+    .. code-block:: python
+
+        try:
+            session.put("invalid//key", "value")
+        except ZError as e:
+            print(e.operation, e.key_expr)  # put invalid//key
     """
 
-    ...
+    operation: str | None
+    """The name of the :class:`Session` operation which raised the error, if any."""
+    key_expr: str | None
+    """The key expression or selector the failed operation was applied to, if any."""
+    code: ErrorCode
+    """The category of the error, see :class:`ErrorCode`.
+
+    ZError instances can be pickled, e.g. to be re-raised by a ``multiprocessing`` pool, and keep
+    their message, code and context attributes."""
 
 @_unstable
 @final
@@ -72,6 +92,27 @@ class CancellationToken:
     def is_cancelled(self) -> bool:
         """Return true if token was cancelled, false otherwise."""
 
+@final
+class CanonicalInfo:
+    """Read-only mapping returned by :meth:`Session.info_canonical`.
+
+    Its keys are always, in this order:
+
+    - ``"zid"``: the :class:`ZenohId` of the session, as a string
+    - ``"routers"``: the ids of the connected routers, as a list of strings
+    - ``"peers"``: the ids of the connected peers, as a list of strings
+    - ``"mode"``: the session mode, ``"peer"``, ``"client"`` or ``"router"``, ``None`` if unknown
+    - ``"locators"``: the locators the session listens on, as a list of strings
+
+    This set of keys is frozen, so it can be relied on, e.g. with ``json.dumps(dict(info))``.
+    """
+
+    def keys(self) -> list[str]: ...
+    def __getitem__(self, key: str) -> Any: ...
+    def __contains__(self, key: str) -> bool: ...
+    def __iter__(self) -> Iterator[str]: ...
+    def __len__(self) -> int: ...
+
 @final
 class Config:
     """The main configuration structure for Zenoh.
@@ -100,14 +141,102 @@ class Config:
     def from_json5(cls, json: str) -> Self:
         """Load configuration from the JSON5 string json."""
 
+    @staticmethod
+    def validate_key(key: str) -> str:
+        """Normalizes a dotted or slashed key, e.g. ``"timestamping.enabled"``, to its slashed form.
+
+        Raises :exc:`ValueError` if the key is not part of the configuration schema, suggesting the closest keys.
+        Keys under ``plugins/`` are not checked, as their schema is defined by the plugins themselves.
+        """
+
+    @staticmethod
+    def keys() -> list[str]:
+        """Returns all the keys of the configuration schema, plugin ones excepted."""
+
     def get_json(self, key: str) -> Any:
         """Returns a JSON string containing the configuration at key."""
 
     def insert_json5(self, key: str, value: Any):
         """Inserts configuration value value at key."""
 
+    def set_credentials(self, user: str, password: str):
+        """Set the user and password of the ``transport/auth/usrpwd`` authentication.
+
+        Raises:
+            ValueError: If ``user`` is empty.
+        """
+
+    def set_tls(
+        self,
+        *,
+        root_ca: str | Path | None = None,
+        cert: str | Path | None = None,
+        key: str | Path | None = None,
+        verify_name: bool = True,
+    ):
+        """Set the certificate files of the TLS links, under ``transport/link/tls``.
+
+        ``cert`` and ``key`` are used both to listen and to connect; the latter requires
+        ``transport/link/tls/enable_mtls`` to be set. ``verify_name`` checks the name of the
+        connected nodes against their certificate.
+
+        The files are checked to be readable when called, and the configuration is left
+        unchanged if one isn't.
+
+        Raises:
+            ZError: With the ``IO`` code and the file path, if a file can't be read.
+            ValueError: If only one of ``cert`` and ``key`` is given.
+        """
+
+    def to_json(self) -> str:
+        """Returns the configuration as JSON, with the values of the keys containing ``password``,
+        ``secret`` or ``private_key`` redacted."""
+
     def __str__(self) -> str:
-        """Returns a string representation of the configuration."""
+        """Returns a string representation of the configuration, with secrets redacted, see :meth:`to_json`."""
+
+@final
+class ConfigNotifier:
+    """The configuration of an opened :class:`Session`, returned by :meth:`Session.config`.
+
+    Unlike a :class:`Config`, it reflects the changes made after opening the session, e.g. by
+    :meth:`insert_json5` or through the admin space, and notifies them with :meth:`on_change`.
+    """
+
+    def get_json(self, key: str) -> str:
+        """Returns the current value of the key as JSON."""
+
+    def insert_json5(self, key: str, value: str):
+        """Changes the value of the key, notifying the :meth:`on_change` callbacks.
+
+        Raises:
+            ZError: If the key is unknown or the value invalid, with the zenoh message.
+        """
+
+    def to_json(self) -> str:
+        """Returns the current configuration as JSON, with secrets redacted, see
+        :meth:`Config.to_json`."""
+
+    def on_change(
+        self, prefix: str, callback: Callable[[str, Any], Any]
+    ) -> ConfigWatcher:
+        """Calls ``callback`` with the key and its new value, decoded from JSON, whenever a
+        configuration entry under ``prefix``, e.g. ``"connect"``, changes.
+
+        The callback is called in a dedicated thread, its errors being logged, like the
+        subscriber callbacks. A change of a parent of ``prefix`` is notified with the parent
+        key, and ``""`` matches all the keys.
+        """
+
+@final
+class ConfigWatcher:
+    """The registration of a :meth:`ConfigNotifier.on_change` callback."""
+
+    def unsubscribe(self):
+        """Stops calling the callback, except if it is already running."""
+
+    @property
+    def unsubscribed(self) -> bool: ...
 
 @final
 class CongestionControl(Enum):
@@ -174,6 +303,17 @@ class Encoding:
     """
 
     def __new__(cls, encoding: str | None = None) -> Self: ...
+    @classmethod
+    def from_prefix_id(cls, prefix_id: int, schema: str | None = None) -> Self:
+        """Build an encoding from its raw numeric prefix, e.g. one produced by another binding and unknown to this one.
+
+        Unlike parsing its string form, the prefix is preserved as is, so republishing it emits the
+        same encoding on the wire."""
+
+    @property
+    def prefix_id(self) -> int:
+        """The raw numeric prefix of the encoding, as sent on the wire."""
+
     def with_schema(self, schema: str) -> Self:
         """Set a schema to this encoding. Zenoh does not define what a schema is and its semantics are left to the implementer. E.g. a common schema for text/plain encoding is utf-8."""
 
@@ -193,6 +333,10 @@ class Encoding:
     """Zenoh serialized data.
 
     Constant alias for string: "zenoh/serialized"."""
+    ZENOH_DATETIME: Self
+    """An RFC3339 datetime, as produced by ``ZBytes(datetime)``, see :meth:`ZBytes.to_datetime`.
+
+    Constant alias for string: "zenoh/string;rfc3339"."""
     APPLICATION_OCTET_STREAM: Self
     """An application-specific stream of bytes.
 
@@ -413,6 +557,275 @@ class EntityGlobalId:
     def eid(self) -> EntityId:
         """Returns the `EntityId` used to identify the entity in a Zenoh session."""
 
+@final
+class EntityGroup:
+    """A group of entities declared on a :class:`Session`, to be closed together.
+
+    The ``declare_*`` methods take the same arguments as their :class:`Session` (or
+    :class:`Liveliness`) counterpart, and record the created entity in the group. Entities can
+    still be undeclared individually, and :meth:`close_all` skips them.
+
+    The session refuses to be closed, unless forced, while its groups hold open entities.
+
+    .. code-block:: python
+
+        with zenoh.EntityGroup(session) as group:
+            group.declare_subscriber("component/**", on_sample)
+            publisher = group.declare_publisher("component/status")
+            ...
+        # entities are undeclared in reverse creation order
+    """
+
+    def __new__(cls, session: Session) -> Self: ...
+    def __enter__(self) -> Self: ...
+    def __exit__(self, *_args, **_kwargs): ...
+    @property
+    def session(self) -> Session:
+        """The session entities are declared on."""
+
+    @property
+    def names(self) -> list[str]:
+        """The names of the named entities, in insertion order."""
+
+    def add(self, entity: Any, *, name: str | None = None):
+        """Record an entity created outside the group, e.g. an advanced publisher, to be closed with it.
+
+        The entity must have an ``undeclare`` method. If ``name`` is given, the entity can be
+        retrieved with ``group[name]``; names must be unique within the group."""
+
+    def declare_subscriber(self, *args, **kwargs) -> Subscriber:
+        """Same as :meth:`Session.declare_subscriber`, recording the subscriber in the group."""
+
+    def declare_queryable(self, *args, **kwargs) -> Queryable:
+        """Same as :meth:`Session.declare_queryable`, recording the queryable in the group."""
+
+    def declare_publisher(self, *args, **kwargs) -> Publisher:
+        """Same as :meth:`Session.declare_publisher`, recording the publisher in the group."""
+
+    def declare_querier(self, *args, **kwargs) -> Querier:
+        """Same as :meth:`Session.declare_querier`, recording the querier in the group."""
+
+    def declare_liveliness_token(self, *args, **kwargs) -> LivelinessToken:
+        """Same as :meth:`Liveliness.declare_token`, recording the token in the group."""
+
+    def close_all(self):
+        """Undeclare the entities of the group still open, in reverse creation order.
+
+        Every entity is tried, and failures are reported together in a single :class:`ZError`."""
+
+    def __getitem__(self, name: str) -> Any:
+        """The entity recorded with the given name, raising KeyError if there is none."""
+
+    def __contains__(self, name: str) -> bool: ...
+    def __len__(self) -> int:
+        """The number of open entities in the group."""
+
+@final
+class EntityEvent:
+    """An entity declared or undeclared on the network, see :meth:`Session.watch_entities`."""
+
+    @property
+    def declared(self) -> bool:
+        """Whether the entity was declared, or else undeclared."""
+
+    @property
+    def key_expr(self) -> KeyExpr:
+        """The key expression of the entity."""
+
+    @property
+    def zid(self) -> ZenohId:
+        """The id of the Zenoh node which declared the entity."""
+
+@final
+class EntityInfo:
+    """An entity active on the network, see :meth:`Session.list_entities`."""
+
+    @property
+    def key_expr(self) -> KeyExpr:
+        """The key expression of the entity."""
+
+    @property
+    def zid(self) -> ZenohId:
+        """The id of the Zenoh node which declared the entity."""
+
+@final
+class EntityWatcher:
+    """A watcher of the entities declared on the network, returned by :meth:`Session.watch_entities`.
+
+    The watcher stops when it is stopped or dropped, or when its session is closed.
+    """
+
+    def __enter__(self) -> Self: ...
+    def __exit__(self, *_args, **_kwargs): ...
+    def stop(self):
+        """Stops the watcher, waiting for the callback in progress, unless called from the callback."""
+
+@final
+class ErrorCode(Enum):
+    """The category of a :class:`ZError`, available as its ``code`` attribute.
+
+    The category is set from the type of the zenoh error, for closed sessions and I/O errors, or
+    by the operations knowing the cause of the error, e.g. parsing a key expression or waiting
+    with a timeout; most zenoh errors are not typed, and have the code :attr:`OTHER`. In
+    particular, the linked zenoh version doesn't report :attr:`CONGESTION` errors distinctly.
+    """
+
+    SESSION_CLOSED = auto()
+    INVALID_KEYEXPR = auto()
+    TIMEOUT = auto()
+    IO = auto()
+    CONGESTION = auto()
+    ACCESS_DENIED = auto()
+    FEATURE_UNAVAILABLE = auto()
+    OTHER = auto()
+
+ErrorCode.SESSION_CLOSED.__doc__ = """The session was closed."""
+ErrorCode.INVALID_KEYEXPR.__doc__ = """A key expression is invalid."""
+ErrorCode.TIMEOUT.__doc__ = """An operation timed out."""
+ErrorCode.IO.__doc__ = """A network or file I/O operation failed."""
+ErrorCode.CONGESTION.__doc__ = """A message was dropped because of congestion."""
+ErrorCode.ACCESS_DENIED.__doc__ = """An operation was denied by access control."""
+ErrorCode.FEATURE_UNAVAILABLE.__doc__ = """A feature is not enabled or supported."""
+ErrorCode.OTHER.__doc__ = """Any other error."""
+
+@final
+class Executor:
+    """A pool of Python worker threads running the callbacks of the subscribers declared with
+    :meth:`SubscriberBuilder.executor`, and of the queryables declared with ``executor=``.
+
+    Zenoh threads only enqueue the callback invocations, and never take the GIL, so blocking
+    or GIL-heavy callbacks cannot stall zenoh, and closing a session cannot deadlock with them.
+    The executor can be shared between several entities and sessions; with more than one
+    thread, invocations may run concurrently and out of order, except for the replies of a
+    :meth:`Session.get_handle`, which are all handled in order by the same worker.
+
+    Once shut down, invocations are dropped and counted in :attr:`dropped`, as well as
+    the ones exceeding ``max_queue``.
+
+    .. code-block:: python
+
+        with zenoh.Executor(threads=4) as executor:
+            session.subscriber_builder("key/**").executor(executor).declare(callback)
+            ...
+        # the queued invocations have been processed, and the workers joined
+    """
+
+    def __new__(cls, threads: int = 1, *, max_queue: int | None = None) -> Self: ...
+    def __enter__(self) -> Self: ...
+    def __exit__(self, *_args, **_kwargs): ...
+    @property
+    def threads(self) -> int:
+        """The number of worker threads."""
+
+    @property
+    def queue_depth(self) -> int:
+        """The number of invocations waiting for a worker."""
+
+    @property
+    def busy_workers(self) -> int:
+        """The number of workers currently running a callback."""
+
+    @property
+    def dropped(self) -> int:
+        """The number of invocations dropped because the executor was shut down or its queue was full."""
+
+    @property
+    def is_shutdown(self) -> bool:
+        """Whether :meth:`shutdown` has been called."""
+
+    def shutdown(self, *, wait: bool = True, timeout: float | int | None = None) -> bool:
+        """Stop accepting invocations; the workers terminate after processing the queued ones.
+
+        If ``wait`` is true, wait for the workers to terminate, at most ``timeout`` seconds.
+        Returns whether all workers have terminated.
+        """
+
+@final
+class GapStats:
+    """The reception diagnostics of a source, see :meth:`Subscriber.gap_report`."""
+
+    @property
+    def gaps(self) -> int:
+        """The number of jumps in the sequence numbers."""
+
+    @property
+    def largest_gap(self) -> int:
+        """The largest number of sequence numbers skipped by a jump."""
+
+    @property
+    def out_of_order(self) -> int:
+        """The number of samples whose timestamp is older than the one of the previous sample."""
+
+    @property
+    def last_sn(self) -> SourceSn | None:
+        """The highest sequence number seen."""
+
+@final
+class GetHandle(Generic[_H]):
+    """Handle of an ongoing query, returned by :meth:`Session.get_handle`.
+
+    It can be iterated like the channel handler, and allows cancelling the query: once
+    :meth:`cancel` returns, the reply callback is no longer invoked, and iteration stops.
+    """
+
+    @property
+    def handler(self) -> _H:
+        """The handler associated with this query.
+
+        See :ref:`channels-and-callbacks` for more information on handlers."""
+
+    @property
+    def replies_received(self) -> int:
+        """The number of replies received for this query."""
+
+    @property
+    def unprojected_count(self) -> int:
+        """The number of replies passed through unmodified by ``project``, as their payload
+        is not JSON, see :meth:`Session.get_handle`."""
+
+    def is_done(self) -> bool:
+        """Returns True if the query is finished, i.e. all the replies have been received, or it
+        has been cancelled."""
+
+    def wait(self, timeout: float | int | None = None) -> bool:
+        """Block until the query is finished, see :meth:`is_done`, releasing the GIL.
+
+        With a callback handler, all the reply callbacks have then returned; exceptions raised by
+        the callback are logged, and don't interrupt the query. Returns False if ``timeout``
+        (in seconds) elapsed first. It must not be called from the reply callback itself."""
+
+    def cancel(self):
+        """Cancel the query, dropping its reply callback/channel.
+
+        It is safe to call it from the reply callback itself; the query state is then released
+        asynchronously, after the callback returns."""
+
+    def try_recv(self: GetHandle[Handler[Reply]]) -> Reply | None:
+        """Try to receive a :class:`Reply` without blocking."""
+
+    def recv(self: GetHandle[Handler[Reply]]) -> Reply:
+        """Receive a :class:`Reply`, blocking until one is available."""
+
+    def ok(self: GetHandle[Handler[Reply]]) -> list[Sample]:
+        """Receive the remaining replies until the query is finished, returning the successful
+        samples."""
+
+    def errors(self: GetHandle[Handler[Reply]]) -> list[ReplyError]:
+        """Receive the remaining replies until the query is finished, returning the errors."""
+
+    def first(self: GetHandle[Handler[Reply]]) -> Sample | None:
+        """Receive replies until a successful one, returning its sample, or None if the query
+        finishes first. The following replies can still be received."""
+
+    def __length_hint__(self) -> int:
+        """The number of replies received but not consumed yet; more may follow until the query
+        is finished."""
+
+    def __iter__(self: GetHandle[Handler[Reply]]) -> Self:
+        """Iterate over received :class:`Reply` instances."""
+
+    def __next__(self: GetHandle[Handler[Reply]]) -> Reply: ...
+
 @final
 class Hello:
     """A zenoh Hello message.
@@ -433,6 +846,11 @@ class Hello:
     def locators(self) -> list[str]:
         """Get the locators (network addresses) of the Zenoh node."""
 
+    @property
+    def best_locator(self) -> str | None:
+        """Get the preferred locator to connect to the Zenoh node, by protocol: ``tcp``, ``tls``,
+        ``quic``, ``ws``, ``udp``, then the others, in advertised order among equals."""
+
     def __str__(self) -> str:
         """Returns a string representation of the Hello message."""
 
@@ -479,6 +897,18 @@ class KeyExpr:
         You should probably prefer :meth:`join` as Zenoh may then take advantage of the hierachical separation it inserts.
         """
 
+    def __truediv__(self, other: _IntoKeyExpr) -> KeyExpr:
+        """Joins both sides like :meth:`join`, e.g. ``KeyExpr("demo") / "room1" / "temp"`` is ``demo/room1/temp``.
+        Raises :exc:`ZError` if ``other`` is not a valid key expression.
+        """
+
+    def __eq__(self, other: object) -> bool:
+        """Compares the canonical string forms."""
+
+    def __hash__(self) -> int:
+        """Hashes the canonical string form, consistently with :meth:`__eq__`, so that key expressions
+        can be used in sets and as dict keys."""
+
     def __str__(self) -> str: ...
 
 _IntoKeyExpr = KeyExpr | str
@@ -626,20 +1056,39 @@ class Parameters:
     When combined with a key expression, they form a :class:`Selector` for query operations.
 
     See also: :ref:`query-parameters`
+
+    Bytes values are encoded in URL-safe base64 without padding, prefixed with ``b64:``, and
+    decoded by ``get(key, as_bytes=True)`` and :meth:`Query.parameter`. String values are kept
+    as is, so a string starting with ``b64:`` is decoded as base64 too when read as bytes.
     """
 
-    def __new__(cls, parameters: dict[str, str] | str | None = None): ...
+    def __new__(cls, parameters: dict[str, str | bytes | datetime] | str | None = None):
+        """Datetime values are converted to RFC3339, see :meth:`ZBytes.to_datetime`."""
     def is_empty(self) -> bool:
         """Returns true if properties does not contain anything."""
 
+    @overload
     def get(self, key: str, default: str | None = None) -> str | None:
         """Returns the value corresponding to the key."""
 
+    @overload
+    def get(
+        self, key: str, default: bytes | None = None, *, as_bytes: Literal[True]
+    ) -> bytes | None:
+        """Returns the value corresponding to the key, bytes values being decoded, and other
+        values encoded in UTF-8.
+
+        Raises:
+            ValueError: If a bytes value is not valid base64.
+        """
+
     def values(self, key: str) -> list[str]:
         """Returns the list of values corresponding to the key."""
 
-    def insert(self, key: str, value: str):
-        """Inserts a key-value pair into the map. If the map did not have this key present, None` is returned. If the map did have this key present, the value is updated, and the old value is returned."""
+    def insert(self, key: str, value: str | bytes | datetime):
+        """Inserts a key-value pair into the map. If the map did not have this key present, None` is returned. If the map did have this key present, the value is updated, and the old value is returned.
+
+        Datetime values are converted to RFC3339, and bytes values to base64."""
 
     def remove(self, key: str):
         """Removes a key from the map, returning the value at the key if the key was previously in the properties."""
@@ -656,7 +1105,91 @@ class Parameters:
     def __iter__(self) -> list[tuple[str, str]]: ...
     def __str__(self) -> str: ...
 
-_IntoParameters = Parameters | dict[str, str] | str
+_IntoParameters = Parameters | dict[str, str | bytes | datetime] | str
+
+@final
+class PagedGet:
+    """Iterator over the replies of a paged get, see :meth:`Session.get_paged`."""
+
+    @property
+    def page_size(self) -> int:
+        """The maximum number of replies per page."""
+
+    @property
+    def pages(self) -> int:
+        """The number of pages fetched so far."""
+
+    def __iter__(self) -> Self: ...
+    def __next__(self) -> Reply: ...
+
+@final
+class PayloadRing:
+    """A ring of preallocated slots, filled with received payloads by :meth:`Session.subscribe_into`.
+
+    This is an expert API for high-rate feeds: no Python object is created per sample. Each
+    slot starts with a header of :attr:`HEADER_SIZE` bytes, followed by the payload:
+
+    - the sequence number of the sample among the ones received by the ring (u64)
+    - the sample timestamp as a NTP64 value, 0 if the sample has no timestamp (u64)
+    - the payload length (u32)
+    - the key id, to be resolved with :meth:`key_expr` (u32)
+
+    all little-endian, i.e. ``struct.unpack_from("<QQII", slot)``.
+
+    Samples received while the ring is full are dropped, and payloads larger than ``slot_size``
+    are truncated, or skipped if ``skip_oversized`` is set; dropped and skipped samples leave
+    gaps in sequence numbers.
+
+    .. warning::
+       The ring supports a single consumer: :meth:`read_available` must not be called
+       concurrently, and slots returned by it must not be accessed after the next call to
+       :meth:`read_available` or :meth:`release`, as they can be overwritten.
+
+    .. code-block:: python
+
+        ring = zenoh.PayloadRing(4096, 256)
+        subscriber = session.subscribe_into("market/**", ring)
+        while True:
+            for slot in ring.read_available():
+                seq, timestamp, length, key_id = struct.unpack_from("<QQII", slot)
+                payload = slot[zenoh.PayloadRing.HEADER_SIZE:][:length]
+    """
+
+    HEADER_SIZE: int
+    def __new__(cls, capacity: int, slot_size: int, *, skip_oversized: bool = False) -> Self: ...
+    @property
+    def capacity(self) -> int:
+        """The number of slots."""
+
+    @property
+    def slot_size(self) -> int:
+        """The maximum payload size of a slot, not including the header."""
+
+    @property
+    def received(self) -> int:
+        """The number of samples received, including the dropped and skipped ones."""
+
+    @property
+    def truncated(self) -> int:
+        """The number of payloads truncated because they were larger than :attr:`slot_size`."""
+
+    @property
+    def skipped(self) -> int:
+        """The number of samples skipped because their payload was larger than :attr:`slot_size`."""
+
+    @property
+    def dropped(self) -> int:
+        """The number of samples dropped because the ring was full."""
+
+    def key_expr(self, key_id: int) -> str:
+        """Resolve the key id of a slot header to its key expression."""
+
+    def read_available(self) -> list[memoryview]:
+        """Release the slots returned by the previous call, and return read-only views over the
+        slots filled since, in reception order."""
+
+    def release(self):
+        """Release the slots returned by the last call to :meth:`read_available`."""
 
 @final
 class Priority(Enum):
@@ -743,11 +1276,23 @@ class Publisher:
         timestamp: Timestamp | None = None,
         timestamp_instrumentation: TimestampInstrumentation | None = None,
         source_info: SourceInfo | None = None,
+        compression: Literal["zstd", "lz4"] | None = None,
+        integrity: Literal["crc32c", "xxh3"] | None = None,
+        validate: bool | None = None,
     ):
         """Publish data to :class:`Subscriber` instances matching this publisher's key expression.
 
         Subscribers will receive the data as a :class:`zenoh.Sample` with
         :attr:`zenoh.SampleKind.PUT` kind.
+
+        If ``compression`` is set, the payload is compressed and the algorithm is appended as a suffix
+        to the encoding, e.g. ``application/json;zstd``, see :meth:`Sample.decode`.
+
+        If ``integrity`` is set, a digest of the (compressed) payload is appended to the attachment
+        under a reserved key, to be verified by subscribers, see :meth:`SubscriberBuilder.verify_integrity`.
+
+        ``validate`` overrides :func:`set_publish_validation` for this call, the payload is validated
+        against the given encoding, or the publisher one.
         """
 
     def delete(
@@ -764,6 +1309,29 @@ class Publisher:
         indicating that the data is no longer associated with the key expression.
         """
 
+    def write(
+        self,
+        kind: SampleKind,
+        payload: _IntoZBytes | None = None,
+        *,
+        encoding: _IntoEncoding | None = None,
+        attachment: _IntoZBytes | None = None,
+        timestamp: Timestamp | None = None,
+    ):
+        """Publish a :meth:`put` or a :meth:`delete` depending on ``kind``, e.g. to forward
+        received samples.
+
+        Raises:
+            ValueError: If a put has no payload, or a delete has a payload or an encoding.
+        """
+
+    def retained(self) -> list[Sample]:
+        """The sample retained by a publisher declared with ``retain=True``, if any; the list is
+        empty for other publishers."""
+
+    def clear_retained(self):
+        """Remove the retained samples, see :meth:`Session.declare_publisher`."""
+
     def undeclare(self):
         """Undeclare the publisher, informing the network that it needn't optimize publications for its key expression anymore."""
 
@@ -812,6 +1380,10 @@ class Query:
         Alternatively, the query may contain special parameter ``_anyke`` which also enables disjoint replies.
         See the :class:`Selector` documentation for more information about this parameter.
 
+    A query can be kept after the callback returns, e.g. to reply later from another thread
+    or an asyncio task, as many times as needed; the querier waits for the replies until
+    a reply is sent with ``final=True``, :meth:`finish` is called, or its timeout expires.
+
     See :ref:`query-reply` for more information on the query/reply paradigm.
     """
 
@@ -829,6 +1401,23 @@ class Query:
     def parameters(self) -> Parameters:
         """The selector parameters of this query."""
 
+    @overload
+    def parameter(self, key: str, default: str | None = None) -> str | None:
+        """The value of a selector parameter, see :meth:`Parameters.get`."""
+
+    @overload
+    def parameter(
+        self, key: str, default: bytes | None = None, *, as_bytes: Literal[True]
+    ) -> bytes | None:
+        """The value of a selector parameter, bytes values passed to :meth:`Session.get`
+        being decoded, see :meth:`Parameters.get`."""
+
+    @property
+    def properties(self) -> dict[str, str]:
+        """The selector parameters of this query as a dict, decoded like :meth:`parameter`.
+
+        They are decoded once per query; the returned dict is a copy."""
+
     @property
     def payload(self) -> ZBytes | None:
         """The payload of this query, if any."""
@@ -838,24 +1427,64 @@ class Query:
         """The encoding of this query's payload, if any."""
 
     @property
-    def attachment(self) -> ZBytes | None:
-        """The attachment of this query, if any."""
+    def attachment(self) -> dict[str | bytes, str | bytes] | None:
+        """The attachment of this query, if any, as the dict given as ``attachment``, see
+        :meth:`ZBytes.to_dict`.
+
+        Raises:
+            ValueError: If the attachment is not a serialized dict, see :attr:`raw_attachment`.
+        """
+
+    @property
+    def raw_attachment(self) -> ZBytes | None:
+        """The attachment of this query as is, if any."""
 
     def accepts_replies(self) -> ReplyKeyExpr:
         """Returns the :class:`ReplyKeyExpr` setting of this query, indicating whether replies
         must match the query's key expression or can use any key expression."""
 
-    def reply(
-        self,
-        key_expr: _IntoKeyExpr,
-        payload: _IntoZBytes,
-        *,
-        encoding: _IntoEncoding | None = None,
+    @property
+    def target(self) -> QueryTarget:
+        """The target of this query, always :attr:`QueryTarget.BEST_MATCHING`.
+
+        The target requested by the querier is not part of the queries received by
+        queryables, so the default is returned."""
+
+    @property
+    def consolidation(self) -> ConsolidationMode:
+        """The consolidation mode of this query, always :attr:`ConsolidationMode.AUTO`.
+
+        Same as :attr:`target`, the mode requested by the querier is not received."""
+
+    @property
+    def projection(self) -> list[str] | None:
+        """The field paths requested with ``Session.get_handle(..., project=...)``, carried by the
+        ``_project`` selector parameter, or ``None``.
+
+        Queryables can reply with the projected objects instead of the whole documents; the
+        querier projects the replies anyway, which doesn't change projected payloads."""
+
+    def paging(self) -> tuple[int, int] | None:
+        """Returns the ``(offset, limit)`` paging parameters set by :meth:`Session.get_paged`,
+        or ``None`` if the query is not paged.
+
+        Raises:
+            ValueError: If the paging parameters are invalid or incomplete.
+        """
+
+    def reply(
+        self,
+        key_expr: _IntoKeyExpr,
+        payload: _IntoZBytes,
+        *,
+        encoding: _IntoEncoding | None = None,
         congestion_control: CongestionControl | None = None,
         priority: Priority | None = None,
         express: bool | None = None,
         attachment: _IntoZBytes | None = None,
         timestamp: Timestamp | None = None,
+        validate: bool | None = None,
+        final: bool = False,
     ):
         """Sends a :class:`Sample` of kind :attr:`SampleKind.PUT` as a reply to this query.
 
@@ -865,10 +1494,58 @@ class Query:
         .. deprecated::
            The ``congestion_control`` and ``priority`` parameters are deprecated and will be ignored.
            Response QoS now automatically matches the original query's QoS to avoid priority inversion.
+
+        ``validate`` overrides :func:`set_publish_validation` for this call.
+        If ``final`` is true, the query is finished once the reply is sent, see :meth:`finish`.
+
+        Raises:
+            ZError: If ``key_expr`` doesn't intersect the query key expression, unless the query
+                accepts any reply key expression, or if the query has been dropped. A querier
+                having timed out can't be detected, the reply is then sent and dropped on arrival.
         """
 
-    def reply_err(self, payload: _IntoZBytes, *, encoding: _IntoEncoding | None = None):
-        """Sends a :class:`ReplyError` as a reply to this query."""
+    def reply_sample(self, sample: Sample, *, final: bool = False):
+        """Sends a :class:`Sample` as a reply to this query, e.g. a sample received by a subscriber.
+
+        Unlike :meth:`reply` and :meth:`reply_del`, the reply keeps all the metadata of the sample:
+        its kind, encoding, timestamp, source info, QoS and attachment.
+        Use :meth:`Sample.with_key_expr` and the other ``with_*`` methods to modify it.
+
+        Routers forward replies with the QoS of the query, so a remote querier receives the sample
+        QoS only if it matches the one of the query.
+
+        If ``final`` is true, the query is finished once the reply is sent, see :meth:`finish`.
+
+        .. note::
+           See the class documentation for important details about which key expression to use for replies.
+        """
+
+    def reply_negotiated(self, sample: Sample, *, strict: bool = False):
+        """Sends a :class:`Sample` as a reply like :meth:`reply_sample`, transcoded to an encoding
+        accepted by the querier if needed.
+
+        Accepted encodings are listed, by order of preference, in the ``_accept`` parameter of the
+        query, separated by ``|``, e.g. ``"data?_accept=application/properties|text/plain"``.
+        If the sample encoding isn't accepted, its payload is transcoded to the first accepted one
+        it can be: JSON objects of scalars to ``application/properties`` ``key=value`` pairs
+        separated by ``;`` and back, text to a JSON string, and JSON or properties to text.
+        The transcoded reply keeps the key expression, timestamp and attachment of the sample.
+
+        If the payload can't be transcoded, the sample is sent as is, unless ``strict`` is true,
+        in which case a ValueError is raised.
+        """
+
+    def reply_err(
+        self,
+        payload: _IntoZBytes,
+        *,
+        encoding: _IntoEncoding | None = None,
+        final: bool = False,
+    ):
+        """Sends a :class:`ReplyError` as a reply to this query.
+
+        If ``final`` is true, the query is finished once the reply is sent, see :meth:`finish`.
+        """
 
     def reply_del(
         self,
@@ -879,11 +1556,14 @@ class Query:
         express: bool | None = None,
         attachment: _IntoZBytes | None = None,
         timestamp: Timestamp | None = None,
+        final: bool = False,
     ):
         """Sends a :class:`Sample` of kind :attr:`SampleKind.DELETE` as a reply to this query.
 
         By default, queries only accept replies whose key expression intersects with the query's. Unless the query has enabled disjoint replies (you can check this through :meth:`accepts_replies`), replying on a disjoint key expression will result in an error when resolving the reply.
 
+        If ``final`` is true, the query is finished once the reply is sent, see :meth:`finish`.
+
         .. note::
            See the class documentation for important details about which key expression to use for replies.
 
@@ -892,6 +1572,36 @@ class Query:
            Response QoS now automatically matches the original query's QoS to avoid priority inversion.
         """
 
+    def forward(
+        self,
+        session: Session,
+        selector: _IntoSelector,
+        *,
+        key_remap: tuple[str, str] | None = None,
+        target: QueryTarget | None = None,
+        consolidation: _IntoQueryConsolidation | None = None,
+        timeout: float | int | None = None,
+    ) -> int:
+        """Forward this query to another selector, relaying every reply back to this query.
+
+        A get is issued on ``selector`` through ``session``, and each of its replies, ok or error, is
+        sent back as a reply to this query, without going through Python objects. Blocks until the
+        get is complete, and returns the number of relayed replies.
+
+        ``key_remap`` is a ``(prefix, replacement)`` pair: reply key expressions starting with the
+        ``prefix`` chunks have them replaced by ``replacement``, others are relayed unchanged.
+
+        .. note::
+           Queries are delivered to a queryable one at a time, so ``selector`` must not match the
+           forwarding queryable itself, or the forwarded get only completes with its timeout.
+
+        .. code-block:: python
+
+            def aggregator(query: zenoh.Query):
+                # "api/sensors/**" is served by "backend/sensors/**"
+                query.forward(session, "backend/sensors/**", key_remap=("backend", "api"))
+        """
+
     @_unstable
     @property
     def source_info(self) -> SourceInfo | None:
@@ -906,6 +1616,26 @@ class Query:
         collected along the message's path through the network.
         """
 
+    @_unstable
+    @property
+    def origin_zid(self) -> ZenohId | None:
+        """Gets the zid of the querier, as declared in the ``source_info`` of the query, or
+        ``None`` if it has none.
+
+        Zenoh doesn't expose the session a query was received from, so the querier has to set
+        ``source_info`` for the zid to be known, and it is not authenticated.
+        """
+
+    @property
+    def raw_selector(self) -> str:
+        """Gets the selector of this query as received, parameters included, without any
+        parsing or percent-decoding."""
+
+    @property
+    def reply_count(self) -> int:
+        """Gets the number of replies sent successfully on this query, error and delete
+        replies included."""
+
     def drop(self):
         """Drop the instance of a query.
         The query will only be finalized when all query instances (one per queryable
@@ -919,6 +1649,20 @@ class Query:
         methods will raise an exception.
         """
 
+    def finish(self) -> int:
+        """Signals that all the replies have been sent, and returns the number of replies
+        sent successfully, see :attr:`reply_count`.
+
+        The querier receives the replies sent so far, and its get terminates once all the
+        matched queryables have finished; further replies raise a :class:`ZError`.
+        Passing ``final=True`` to the last reply finishes the query the same way.
+        """
+
+    @property
+    def is_finished(self) -> bool:
+        """Whether the query has been finished, by a final reply, :meth:`finish` or
+        :meth:`drop`."""
+
     def __str__(self) -> str:
         """Returns a string representation of this query."""
 
@@ -941,7 +1685,26 @@ class Queryable(Generic[_H]):
 
     @property
     def key_expr(self) -> KeyExpr:
-        """Returns the :class:`KeyExpr` this queryable responds to."""
+        """Returns the :class:`KeyExpr` this queryable responds to.
+
+        For a queryable declared on several key expressions, this is the first one.
+        """
+
+    @property
+    def key_exprs(self) -> list[KeyExpr]:
+        """Returns all the key expressions this queryable responds to."""
+
+    @property
+    def rejected_count(self) -> int:
+        """The number of queries rejected as too broad, see :meth:`Session.declare_queryable`."""
+
+    @property
+    def reply_encoding(self) -> Encoding | None:
+        """The default encoding of the replies, see :meth:`Session.declare_queryable`."""
+
+    @property
+    def reply_express(self) -> bool | None:
+        """The default express flag of the replies, see :meth:`Session.declare_queryable`."""
 
     @property
     def handler(self) -> _H:
@@ -958,9 +1721,47 @@ class Queryable(Generic[_H]):
     def recv(self: Queryable[handlers.Handler[Query]]) -> Query:
         """Receive a :class:`Query` from the handler, blocking if necessary."""
 
+    def set_callback(self: Queryable[None], callback: _PythonCallback[Query]):
+        """Replace the callback of this queryable without redeclaring it.
+
+        Invocations in progress complete with the previous callback, and the following ones,
+        including queries already queued for an indirect callback or an executor, use the new one.
+        The drop callback of the original handler is kept.
+
+        Raises:
+            ValueError: If ``callback`` is None, or if the handler is not a callback.
+        """
+
     def __iter__(self: Queryable[Handler[Query]]) -> Handler[Query]:
         """Iterate over :class:`Query` received by the handler."""
 
+@final
+class OnceQueryable:
+    """A queryable receiving a single query, returned by :meth:`Session.queryable_once`.
+
+    It is closed, i.e. undeclared, as soon as the first matching query is received. The
+    following queries never reach Python: they are finalized without reply, even if they
+    arrive before the queryable is undeclared.
+    """
+
+    def wait(self, timeout: float | int | None = None) -> Query:
+        """Wait for the query, with the GIL released, and close the queryable.
+
+        ``timeout`` defaults to the one passed to :meth:`Session.queryable_once`.
+
+        Raises:
+            ZError: With :attr:`ErrorCode.TIMEOUT` code if no query is received within
+                ``timeout`` seconds, the queryable being closed; or if it is already closed.
+            ValueError: If the queryable has a callback.
+        """
+
+    def close(self) -> None:
+        """Close the queryable if it is still waiting for a query."""
+
+    @property
+    def closed(self) -> bool:
+        """Whether the queryable is closed."""
+
 @final
 class Querier:
     """A querier that allows sending queries to a :class:`Queryable`.
@@ -1202,11 +2003,39 @@ class Reply:
     def err(self) -> ReplyError | None:
         """Returns the error if this reply failed, `None` otherwise."""
 
+    @property
+    def is_ok(self) -> bool:
+        """Whether this reply is successful, i.e. :attr:`ok` is a :class:`Sample`, and
+        :attr:`err` is `None`."""
+
     @property
     @_unstable
     def replier_id(self) -> EntityGlobalId | None:
         """Returns the ID of the zenoh instance that answered this reply."""
 
+@final
+class Replies:
+    """The replies of a get fully received, returned by :meth:`Session.get_cached` and by
+    :meth:`Session.get_many`.
+
+    It behaves like a list of :class:`Reply`: it can be iterated several times, indexed, and
+    converted with ``list(replies)``. :meth:`ok`, :meth:`errors` and :meth:`first` are also
+    available on :class:`GetHandle` and :class:`SpooledReplies`, where they consume the replies.
+    """
+
+    def ok(self) -> list[Sample]:
+        """The samples of the successful replies, in order."""
+
+    def errors(self) -> list[ReplyError]:
+        """The errors of the failed replies, in order."""
+
+    def first(self) -> Sample | None:
+        """The sample of the first successful reply, or None if there is none."""
+
+    def __len__(self) -> int: ...
+    def __getitem__(self, index: int) -> Reply: ...
+    def __iter__(self) -> Iterator[Reply]: ...
+
 @final
 class ReplyError:
     """An error reply received from a :class:`Queryable` and available in the :class:`Reply` structure."""
@@ -1228,6 +2057,9 @@ class ReplyError:
         collected along the message's path through the network.
         """
 
+    def decode(self, *, decompress: bool = True) -> ZBytes:
+        """Gets the payload of this `ReplyError`, decompressed if needed, see :meth:`Sample.decode`."""
+
 @final
 class SampleKind(Enum):
     """The kind of a :class:`Sample`, indicating whether it contains data or indicates deletion."""
@@ -1245,6 +2077,28 @@ class Sample:
     It contains the payload and all metadata associated with the data.
     """
 
+    def __new__(
+        cls,
+        key_expr: _IntoKeyExpr,
+        payload: _IntoZBytes | None = None,
+        *,
+        kind: SampleKind = SampleKind.PUT,
+        encoding: _IntoEncoding | None = None,
+        timestamp: Timestamp | None = None,
+        attachment: _IntoZBytes | None = None,
+        source_info: SourceInfo | None = None,
+        congestion_control: CongestionControl | None = None,
+        priority: Priority | None = None,
+        express: bool | None = None,
+    ) -> Self:
+        """Build a sample, e.g. to reply to a query with :meth:`Query.reply_sample`, which keeps its kind
+        and its QoS, i.e. ``congestion_control``, ``priority`` and ``express``. They default to those
+        of :meth:`Session.put`.
+
+        Raises:
+            ValueError: If ``payload`` or ``encoding`` is given for a :attr:`SampleKind.DELETE` sample.
+        """
+
     @property
     def key_expr(self) -> KeyExpr:
         """Gets the key expression on which this Sample was published."""
@@ -1281,8 +2135,17 @@ class Sample:
         """
 
     @property
-    def attachment(self) -> ZBytes | None:
-        """Gets the sample attachment: a map of key-value pairs."""
+    def attachment(self) -> dict[str | bytes, str | bytes] | None:
+        """Gets the sample attachment: a map of key-value pairs, as the dict given as
+        ``attachment``, see :meth:`ZBytes.to_dict`.
+
+        Raises:
+            ValueError: If the attachment is not a serialized dict, see :attr:`raw_attachment`.
+        """
+
+    @property
+    def raw_attachment(self) -> ZBytes | None:
+        """Gets the sample attachment as is, e.g. an attachment given as bytes or str."""
 
     @_unstable
     @property
@@ -1298,6 +2161,42 @@ class Sample:
         collected along the message's path through the network.
         """
 
+    def with_key_expr(self, key_expr: _IntoKeyExpr) -> Sample:
+        """Returns a copy of this Sample with the given key expression, and all its other fields.
+
+        The payload is not copied: both samples share the same reference-counted buffers, so a
+        storage can reply a sample under many keys with :meth:`Query.reply_sample` without its
+        memory growing with the number of copies. Samples are immutable, which is why sharing is
+        safe; any mutating operation added in the future must copy the buffers it modifies.
+        """
+
+    def with_payload(self, payload: _IntoZBytes) -> Sample:
+        """Returns a copy of this Sample with the given payload, and all its other fields.
+
+        :raises ZError: if the sample kind is :attr:`SampleKind.DELETE`.
+        """
+
+    def with_encoding(self, encoding: _IntoEncoding) -> Sample:
+        """Returns a copy of this Sample with the given encoding, and all its other fields.
+
+        :raises ZError: if the sample kind is :attr:`SampleKind.DELETE`.
+        """
+
+    def with_attachment(self, attachment: _IntoZBytes | None) -> Sample:
+        """Returns a copy of this Sample with the given attachment, and all its other fields."""
+
+    def decode(self, *, decompress: bool = True) -> Any:
+        """Gets the payload of this Sample, decompressed if its encoding ends with a compression suffix,
+        i.e. ``;zstd`` or ``;lz4`` as set by ``put(..., compression=...)``.
+
+        The payload is returned as is if ``decompress`` is false, or if the encoding has no compression suffix.
+
+        If a decoder registered with :func:`register_type` matches the sample key expression, the result
+        of the decoder called with the payload bytes is returned instead of the payload.
+
+        :raises ZError: if the compression suffix is not supported, e.g. ``;gzip``, or if decompression fails.
+        """
+
 @final
 class Scout(Generic[_H]):
     """A Scout object that yields :class:`zenoh.Hello` messages for discovered Zenoh nodes on the network.
@@ -1400,6 +2299,19 @@ class Selector:
 
     @parameters.setter
     def parameters(self, parameters: _IntoParameters): ...
+    def with_key_expr(self, key_expr: _IntoKeyExpr) -> Selector:
+        """Return a new selector with the given key expression and the parameters of this one, carried verbatim.
+
+        This is typically used to forward a query to another key expression:
+
+        .. code-block:: python
+
+            session.get(query.selector.with_key_expr("backend/data"))
+        """
+
+    def without_parameters(self) -> Selector:
+        """Return a new selector with the key expression of this one and no parameters."""
+
     def __str__(self) -> str: ...
 
 _IntoSelector = Selector | _IntoKeyExpr
@@ -1411,23 +2323,51 @@ class Session:
     The session allows declaring other zenoh entities like :class:`Publisher`, :class:`Subscriber`, :class:`Querier`, :class:`Queryable`, and obtaining :class:`Liveliness` instances, and keeps them functioning. Closing the session will undeclare all objects declared by it.
 
     A Zenoh session is instantiated using :func:`open` with parameters specified in the :class:`Config` object.
+
+    Used as a context manager, the session is closed when exiting the ``with`` block, unless it was
+    already closed; the same goes for the undeclaration of its entities.
     """
 
     def __enter__(self) -> Self: ...
     def __exit__(self, *_args, **_kwargs): ...
+    def config(self) -> ConfigNotifier:
+        """Get the session configuration, whose changes can be watched, see :class:`ConfigNotifier`."""
+
     @property
     def info(self) -> SessionInfo:
         """Get information about the session: the session id, the connected nodes."""
 
+    def info_canonical(self) -> CanonicalInfo:
+        """Get a snapshot of the session information, with a stable set of keys, see :class:`CanonicalInfo`."""
+
+    def info_dict(self) -> dict[str, str]:
+        """Get the session information as a dict of strings, for compatibility.
+
+        The keys are the ones of :meth:`info_canonical`, the lists being joined with commas,
+        and a missing mode being an empty string. :attr:`info` returns typed objects instead,
+        e.g. :class:`ZenohId`, which can be compared and used as dict keys.
+        """
+
     @_unstable
     @property
     def id(self) -> EntityGlobalId:
         """Returns the global identifier of the session object."""
 
+    @property
+    def namespace(self) -> str | None:
+        """The namespace of the session, see :func:`open`, or None if it has none."""
+
     def zid(self) -> ZenohId:
         """Returns the identifier of the current session."""
 
-    def close(self):
+    def close(
+        self,
+        *,
+        force: bool = False,
+        parallelism: int = 8,
+        entity_timeout: float | int | None = None,
+        on_progress: Callable[[int, int, str], Any] | None = None,
+    ) -> list[tuple[str, str]]:
         """Close the zenoh Session.
 
         Every :class:`Subscriber` and :class:`Queryable` declared will stop receiving data, and further
@@ -1437,6 +2377,18 @@ class Session:
 
         Sessions are automatically closed when all their instances are dropped. But it can
         be useful to close the session explicitly.
+
+        With ``force``, the open entities of the session's :class:`EntityGroup` instances are
+        undeclared before closing, up to ``parallelism`` at a time. ``on_progress(done, total, key)``
+        is called each time one of them is undeclared. An entity not undeclared within
+        ``entity_timeout`` seconds, 10 by default, is left behind so it doesn't block the closing.
+
+        Returns:
+            The ``(key, error)`` pairs of the entities which failed to be undeclared or timed out.
+
+        Raises:
+            ZError: If an :class:`EntityGroup` of the session holds open entities, unless ``force``
+                is set.
         """
 
     def is_closed(self) -> bool:
@@ -1473,10 +2425,26 @@ class Session:
         timestamp_instrumentation: TimestampInstrumentation | None = None,
         allowed_destination: Locality | None = None,
         source_info: SourceInfo | None = None,
+        compression: Literal["zstd", "lz4"] | None = None,
+        integrity: Literal["crc32c", "xxh3"] | None = None,
+        validate: bool | None = None,
     ):
         """Publish data directly from the session.
 
         This is a shortcut for declaring a :class:`Publisher` and calling put on it.
+
+        If ``compression`` is set, the payload is compressed and the algorithm is appended as a suffix
+        to the encoding, e.g. ``application/json;zstd``, see :meth:`Sample.decode`.
+
+        If ``integrity`` is set, a digest of the (compressed) payload is appended to the attachment
+        under a reserved key, to be verified by subscribers, see :meth:`SubscriberBuilder.verify_integrity`.
+
+        ``validate`` overrides :func:`set_publish_validation` for this call.
+
+        ``timestamp`` is sent as is instead of the one routers may add, e.g. to replay recorded
+        samples with their original timestamps, see :class:`Timestamp`. zenoh never rejects it,
+        but routers configured with ``timestamping/drop_future_timestamp`` drop the samples whose
+        timestamp is ahead of their clock, which the publisher isn't notified of.
         """
 
     def delete(
@@ -1495,6 +2463,8 @@ class Session:
         """Publish a delete sample directly from the session.
 
         This is a shortcut for declaring a :class:`Publisher` and calling delete on it.
+
+        See :meth:`put` for ``timestamp``.
         """
 
     @overload
@@ -1503,6 +2473,7 @@ class Session:
         selector: _IntoSelector,
         handler: _RustHandler[Reply] | None = None,
         *,
+        parameters: _IntoParameters | None = None,
         target: QueryTarget | None = None,
         consolidation: _IntoQueryConsolidation | None = None,
         accept_replies: ReplyKeyExpr | None = None,
@@ -1517,10 +2488,23 @@ class Session:
         source_info: SourceInfo | None = None,
         cancellation_token: CancellationToken | None = None,
         timestamp_instrumentation: TimestampInstrumentation | None = None,
+        require_connectivity: bool = False,
     ) -> Handler[Reply]:
         """Query data from the matching queryables in the system.
 
         This is a shortcut for declaring a :class:`Querier` and calling get on it.
+
+        If ``require_connectivity`` is true, the query is issued only once a router or peer is
+        connected, waiting up to ``timeout``, or the ``queries_default_timeout`` configuration. A
+        :class:`ZError` with :attr:`ErrorCode.TIMEOUT` code is raised if none is connected by
+        then, instead of returning no replies; once connected, a query without matching
+        queryables returns no replies.
+
+        ``parameters`` are added to the ones of the selector, e.g. bytes values, see
+        :class:`Parameters`.
+
+        See :meth:`get_handle` to cancel the query or wait for its completion,
+        :meth:`get_cached` and :meth:`get_spooled` to cache the replies or spill them to disk.
         """
 
     @overload
@@ -1529,6 +2513,7 @@ class Session:
         selector: _IntoSelector,
         handler: _PythonHandler[Reply, _H],
         *,
+        parameters: _IntoParameters | None = None,
         target: QueryTarget | None = None,
         consolidation: _IntoQueryConsolidation | None = None,
         accept_replies: ReplyKeyExpr | None = None,
@@ -1543,6 +2528,7 @@ class Session:
         source_info: SourceInfo | None = None,
         cancellation_token: CancellationToken | None = None,
         timestamp_instrumentation: TimestampInstrumentation | None = None,
+        require_connectivity: bool = False,
     ) -> _H:
         """Query data from the matching queryables in the system.
 
@@ -1555,6 +2541,7 @@ class Session:
         selector: _IntoSelector,
         handler: _PythonCallback[Reply],
         *,
+        parameters: _IntoParameters | None = None,
         target: QueryTarget | None = None,
         consolidation: _IntoQueryConsolidation | None = None,
         accept_replies: ReplyKeyExpr | None = None,
@@ -1569,21 +2556,201 @@ class Session:
         source_info: SourceInfo | None = None,
         cancellation_token: CancellationToken | None = None,
         timestamp_instrumentation: TimestampInstrumentation | None = None,
+        require_connectivity: bool = False,
     ) -> None:
         """Query data from the matching queryables in the system.
 
         This is a shortcut for declaring a :class:`Querier` and calling get on it.
         """
 
+    @overload
+    def get_handle(
+        self,
+        selector: _IntoSelector,
+        handler: _RustHandler[Reply] | None = None,
+        *,
+        target: QueryTarget | None = None,
+        consolidation: _IntoQueryConsolidation | None = None,
+        timeout: float | int | None = None,
+        raise_on_timeout: bool = False,
+        project: Iterable[str] | None = None,
+    ) -> GetHandle[Handler[Reply]]:
+        """Query data like :meth:`get`, returning a :class:`GetHandle` to follow the query.
+
+        The returned :class:`GetHandle` yields the replies as they arrive, without waiting for the
+        slowest queryables, and releases the GIL while waiting; iteration stops when the query is
+        finished, or early after :meth:`GetHandle.cancel`, which drops the remaining replies.
+
+        ``timeout`` (in seconds) defaults to the ``queries_default_timeout`` configuration. Once
+        it elapses, zenoh finalizes the query, which closes the channel, so iterating over the
+        returned handle stops with the replies received so far, even if some queryables never
+        complete; ``timeout=0`` returns without waiting for any reply. If ``raise_on_timeout`` is
+        true, the iteration raises a :class:`ZError` with :attr:`ErrorCode.TIMEOUT` code instead,
+        when the query is finalized by its timeout rather than by the completion of the
+        queryables.
+
+        If ``project`` is set to field paths, e.g. ``["pose.x", "pose.y"]``, the JSON payloads
+        of the replies are replaced by objects containing only these fields, before the replies
+        reach Python, which saves the decoding of wide documents. Path components index the
+        objects by key and the arrays by position; missing fields are set to ``null``, e.g.
+        ``{"pose": {"x": 1.0, "y": null}}``. Payloads encoded otherwise than with
+        :attr:`Encoding.APPLICATION_JSON` or :attr:`Encoding.TEXT_JSON`, or that aren't valid
+        JSON, are passed through unmodified, and counted in :attr:`GetHandle.unprojected_count`.
+        The paths are also sent in the ``_project`` selector parameter, so that queryables can
+        project at the source, see :attr:`Query.projection`; projecting a projected payload
+        doesn't change it.
+        """
+
+    @overload
+    def get_handle(
+        self,
+        selector: _IntoSelector,
+        handler: _PythonHandler[Reply, _H],
+        *,
+        target: QueryTarget | None = None,
+        consolidation: _IntoQueryConsolidation | None = None,
+        timeout: float | int | None = None,
+        raise_on_timeout: bool = False,
+        project: Iterable[str] | None = None,
+    ) -> GetHandle[_H]:
+        """Query data like :meth:`get`, returning a :class:`GetHandle` to follow the query."""
+
+    @overload
+    def get_handle(
+        self,
+        selector: _IntoSelector,
+        handler: _PythonCallback[Reply],
+        *,
+        target: QueryTarget | None = None,
+        consolidation: _IntoQueryConsolidation | None = None,
+        timeout: float | int | None = None,
+        raise_on_timeout: bool = False,
+        executor: Executor | None = None,
+        project: Iterable[str] | None = None,
+    ) -> GetHandle[None]:
+        """Query data like :meth:`get`, returning a :class:`GetHandle` to follow the query.
+
+        If ``executor`` is set, the callback is called by a single :class:`Executor` worker, in
+        the order of arrival of the replies, and the drop callback of a
+        :class:`handlers.Callback` is called after the last invocation returns. The workers are
+        assigned to the gets in turn.
+        """
+
+    def get_cached(
+        self,
+        selector: _IntoSelector,
+        *,
+        refresh: bool = False,
+        target: QueryTarget | None = None,
+        consolidation: _IntoQueryConsolidation | None = None,
+        timeout: float | int | None = None,
+    ) -> Replies:
+        """Query data through the cache enabled by :func:`enable_get_cache`.
+
+        The replies cached for the selector, whose parameters order doesn't matter, are returned
+        if they are younger than the cache ``ttl``; otherwise the query is performed and its
+        replies are cached, unless one of them is an error. With ``refresh=True``, the query is
+        always performed, and its replies cached. Cached replies are shared, not copied.
+
+        Raises:
+            ValueError: If the cache is not enabled.
+        """
+
+    def get_spooled(
+        self,
+        selector: _IntoSelector,
+        max_memory_bytes: int,
+        *,
+        target: QueryTarget | None = None,
+        consolidation: _IntoQueryConsolidation | None = None,
+        timeout: float | int | None = None,
+    ) -> SpooledReplies:
+        """Query data, collecting all the replies before returning.
+
+        Once the received payloads exceed ``max_memory_bytes``, the payloads of the following
+        replies are spilled to a temporary file, see :class:`SpooledReplies`; replies order is
+        preserved.
+        """
+
+    def get_paged(
+        self,
+        selector: _IntoSelector,
+        *,
+        page_size: int = 1000,
+        target: QueryTarget | None = None,
+        consolidation: _IntoQueryConsolidation | None = None,
+        timeout: float | int | None = None,
+    ) -> PagedGet:
+        """Query data page by page, following the paging convention: each get adds
+        ``_offset`` and ``_limit`` parameters to the selector, and the iteration stops after a page
+        with less than ``page_size`` replies.
+
+        Pages are fetched lazily while iterating; queryables can honor the paging with :meth:`Query.paging`.
+        """
+
+    def get_many(
+        self,
+        selectors: Iterable[_IntoSelector],
+        *,
+        max_concurrency: int = 16,
+        target: QueryTarget | None = None,
+        consolidation: _IntoQueryConsolidation | None = None,
+        timeout: float | int | None = None,
+    ) -> dict[str, Replies | Exception]:
+        """Query several selectors concurrently, and gather their replies.
+
+        At most ``max_concurrency`` gets are in flight at once, and the GIL is released until
+        all of them are finished. The other arguments apply to each get, as for :meth:`get`.
+
+        Returns a dict mapping each selector, as a string, to its replies, or to the exception
+        raised for selectors that failed, e.g. invalid ones.
+        """
+
+    def get_to(
+        self,
+        selector: _IntoSelector,
+        writer: Any,
+        *,
+        format: Literal["ndjson", "raw"] = "ndjson",
+        chunk_size: int = 65536,
+        target: QueryTarget | None = None,
+        consolidation: _IntoQueryConsolidation | None = None,
+        timeout: float | int | None = None,
+    ) -> int:
+        """Query ``selector`` and stream the serialized replies to ``writer``, any object with
+        a ``write(bytes)`` method, e.g. a file opened in binary mode or ``socket.makefile("wb")``.
+
+        The replies are received and serialized without the GIL, and written by chunks of at
+        least ``chunk_size`` bytes, the last one excepted, so that ``writer.write`` is called
+        once per chunk.
+
+        - ``"ndjson"`` writes one JSON object per line, with ``key_expr``, ``payload``,
+          ``encoding``, ``kind``, ``timestamp`` and ``attachment`` fields; error replies have
+          ``error`` and ``encoding`` fields instead. Payloads that aren't valid UTF-8 are
+          written as ``{"base64": ...}`` objects.
+        - ``"raw"`` writes the payload of each ok reply, prefixed by its length as a
+          little-endian u32; error replies are skipped.
+
+        Returns the count of replies written. An exception raised by ``writer.write`` aborts
+        the transfer and is propagated.
+        """
+
     @overload
     def declare_subscriber(
         self,
         key_expr: _IntoKeyExpr,
-        handler: _RustHandler[Sample] | None = None,
+        handler: _RustHandler[Sample] | handlers.PriorityChannel | None = None,
         *,
         allowed_origin: Locality | None = None,
     ) -> Subscriber[Handler[Sample]]:
-        """Create a :class:`Subscriber` for the given key expression."""
+        """Create a :class:`Subscriber` for the given key expression.
+
+        With a :class:`handlers.PriorityChannel` handler, the samples are queued by priority,
+        the higher priority ones being received first.
+
+        The opt-in features, e.g. limits, integrity verification or sharding, are set with
+        :meth:`subscriber_builder`.
+        """
 
     @overload
     def declare_subscriber(
@@ -1605,38 +2772,287 @@ class Session:
     ) -> Subscriber[None]:
         """Create a :class:`Subscriber` for the given key expression."""
 
+    def subscriber_builder(self, key_expr: _IntoKeyExpr) -> SubscriberBuilder:
+        """Returns a :class:`SubscriberBuilder`, to declare a :class:`Subscriber` for the given
+        key expression with opt-in features.
+
+        .. code-block:: python
+
+            subscriber = (
+                session.subscriber_builder("key/**")
+                .limits(max_samples=10)
+                .verify_integrity()
+                .declare(callback)
+            )
+        """
+
+    def wait_for(
+        self,
+        key_expr: _IntoKeyExpr,
+        timeout: float | int | None = None,
+        predicate: Callable[[Sample], bool] | None = None,
+    ) -> Sample:
+        """Waits for the next sample published on ``key_expr`` and returns it.
+
+        A temporary subscriber is declared for the call, and undeclared before returning, including
+        when the call times out or ``predicate`` raises. If ``predicate`` is given, it is called with
+        each received sample, and samples for which it returns false are discarded.
+
+        The GIL is released while waiting.
+
+        :raises ZError: with code :attr:`ErrorCode.TIMEOUT` if no matching sample is received
+            within ``timeout`` seconds.
+        """
+
+    def sample_once(self, key_expr: _IntoKeyExpr) -> Sample:
+        """Waits without timeout for the next sample published on ``key_expr``,
+        see :meth:`wait_for`."""
+
+    def put_and_confirm(
+        self,
+        key_expr: _IntoKeyExpr,
+        payload: _IntoZBytes,
+        *,
+        encoding: _IntoEncoding | None = None,
+        timeout: float | int | None = None,
+        via: Literal["local_sub", "queryable"] = "local_sub",
+    ) -> Sample:
+        """Publish a value and wait until it is received back, e.g. for end-to-end smoke tests.
+
+        With ``via="local_sub"``, a temporary subscriber is declared on ``key_expr`` before the put.
+        With ``via="queryable"``, a temporary storage is declared instead, i.e. a subscriber keeping
+        the sample and a queryable replying with it, and queried until it returns the sample.
+        The temporary entities are undeclared whatever the outcome.
+
+        Returns:
+            The received sample, whose payload and encoding are checked to be the put ones.
+
+        Raises:
+            ZError: With :attr:`ErrorCode.TIMEOUT` code if the sample isn't received within
+                ``timeout`` seconds, 2 by default, or if it differs from the put one.
+        """
+
+    def put_sync(
+        self,
+        key_expr: _IntoKeyExpr,
+        payload: _IntoZBytes,
+        *,
+        encoding: _IntoEncoding | None = None,
+        verify_selector: _IntoSelector | None = None,
+        timeout: float | int | None = None,
+    ) -> int:
+        """Publish a value and wait until it can be read back, e.g. from a storage, for
+        read-your-writes consistency.
+
+        After the put, ``verify_selector``, ``key_expr`` by default, is queried with an
+        exponential backoff until a reply has the put payload and encoding.
+
+        Returns:
+            The number of queries sent.
+
+        Raises:
+            ZError: With :attr:`ErrorCode.TIMEOUT` code if no reply has the put value within
+                ``timeout`` seconds, 2 by default, the message giving the last observed value.
+        """
+
+    def put_file(
+        self,
+        key_expr: _IntoKeyExpr,
+        path: str | Path,
+        encoding: _IntoEncoding | None = None,
+    ) -> None:
+        """Puts the content of the file at ``path`` on the given key expression.
+
+        The file is read without holding the GIL. If ``encoding`` is not given, it is inferred from
+        the file extension: ``.json``, ``.yaml``/``.yml``, ``.png``, ``.jpeg``/``.jpg`` and
+        ``.txt`` files have their matching encoding, other files are published as
+        ``application/octet-stream``.
+
+        :raises ZError: with code :attr:`ErrorCode.IO` if the file can't be read.
+        """
+
+    def get_file(
+        self,
+        selector: _IntoSelector,
+        dest_path: str | Path,
+        *,
+        timeout: float | int | None = None,
+    ) -> Sample:
+        """Queries ``selector`` and writes the payload of the first ok reply to ``dest_path``,
+        returning the reply sample.
+
+        :raises ZError: with code :attr:`ErrorCode.IO` if the file can't be written, or with
+            another code if the query fails or no ok reply is received.
+        """
+
+    def watch_entities(
+        self,
+        kind: Literal["subscribers", "publishers", "queryables"],
+        callback: Callable[[EntityEvent], Any],
+        *,
+        interval: float | int | None = None,
+    ) -> EntityWatcher:
+        """Watches the entities of the given kind active on the network, calling ``callback`` with
+        an :class:`EntityEvent` each time one is declared or undeclared.
+
+        The entities are polled every ``interval`` seconds (1 by default) through the admin space,
+        see :meth:`list_entities`. The entities active when the watcher starts are reported as
+        declared by the first poll. The callback is called in a thread of the watcher.
+        """
+
+    def list_entities(
+        self,
+        kind: Literal["subscribers", "publishers", "queryables"],
+        *,
+        timeout: float | int | None = None,
+    ) -> list[EntityInfo]:
+        """Returns the entities of the given kind active on the network, sorted by key expression.
+
+        Entities are listed through the admin space, so only the ones known to the nodes enabling it
+        (``adminspace/enabled`` configuration) are reported, e.g. the ones whose declarations were
+        routed to this session if it enables the admin space. Nodes running zenoh 1.0 or later are
+        supported. Publishers are only reported by the nodes their declarations are propagated to,
+        which depends on the routing configuration.
+        """
+
+    def wait_for_matching(
+        self,
+        kind: Literal["subscribers", "publishers", "queryables"],
+        key_expr: _IntoKeyExpr,
+        count: int = 1,
+        timeout: float | int | None = None,
+    ) -> int:
+        """Waits until at least ``count`` entities of the given kind match ``key_expr``, i.e.
+        their key expressions intersect it, and returns the observed count, e.g. to wait for
+        the subscribers of a benchmark instead of sleeping.
+
+        A single subscriber or queryable is detected with the matching status of a temporary
+        publisher or querier, which sees the local entities and the remote ones whose
+        declarations reach this session. Larger counts, and publishers, are polled through the
+        admin space like :meth:`list_entities`, counting distinct (key expression, session)
+        pairs: the admin space must be enabled on this session or on a router it is connected
+        to, and in peer-only meshes, only the entities whose declarations are routed to the
+        nodes enabling it are seen. Polls are spaced by an exponential backoff, from 10ms to
+        500ms, releasing the GIL.
+
+        Raises:
+            ZError: With :attr:`ErrorCode.TIMEOUT` code, and the last observed count in its
+                message, if ``timeout`` (10 seconds by default) elapses first.
+        """
+
+    def subscribe_into(
+        self,
+        key_expr: _IntoKeyExpr,
+        ring: PayloadRing,
+        *,
+        allowed_origin: Locality | None = None,
+    ) -> Subscriber[PayloadRing]:
+        """Create a :class:`Subscriber` copying received payloads into ``ring``, without involving
+        Python for each sample, see :class:`PayloadRing`.
+
+        Several subscribers can write into the same ring. The subscriber must be kept alive,
+        the subscription ends when it is undeclared or dropped.
+        """
+
     @overload
     def declare_queryable(
         self,
-        key_expr: _IntoKeyExpr,
+        key_expr: _IntoKeyExpr | list[_IntoKeyExpr | tuple[_IntoKeyExpr, bool]],
         handler: _RustHandler[Query] | None = None,
         *,
         complete: bool | None = None,
         allowed_origin: Locality | None = None,
+        executor: Executor | None = None,
+        max_breadth: int | Callable[[Selector], bool] | None = None,
+        reply_encoding: _IntoEncoding | None = None,
+        reply_express: bool | None = None,
+        compatibility: Literal["pico"] | None = None,
+        filter: dict[str, str | bytes | datetime] | None = None,
     ) -> Queryable[Handler[Query]]:
-        """Create a :class:`Queryable` for the given key expression."""
+        """Create a :class:`Queryable` for the given key expression.
+
+        ``key_expr`` can also be a list of key expressions, or of ``(key_expr, complete)`` pairs to set
+        completeness per key expression. One queryable is declared per key expression, all sharing
+        the same handler, and they are undeclared together by :meth:`Queryable.undeclare`.
+        If one declaration fails, the already declared ones are undeclared before raising.
+
+        If ``executor`` is set, the handler must be a callback, which is called by the
+        :class:`Executor` workers.
+
+        ``max_breadth`` protects the queryable against queries too broad, e.g. on ``**``: either a
+        maximum number of key expression chunks containing wildcards, or a predicate taking the
+        query :class:`Selector`. Rejected queries are answered with a "query too broad" error
+        reply without calling the handler, and counted by :attr:`Queryable.rejected_count`.
+        By default, all queries are accepted.
+
+        ``reply_encoding`` and ``reply_express`` are the defaults of the ``encoding`` and ``express``
+        parameters of :meth:`Query.reply`, and of ``express`` for :meth:`Query.reply_del`, for all
+        the queries of the queryable. The priority and congestion control of the replies follow
+        those of the query.
+
+        If ``filter`` is set, only the queries whose selector has all its parameters, with the
+        same values, are passed to the handler, e.g. ``filter={"unit": "celsius"}``; values are
+        encoded like the ``parameters`` of :meth:`get`. The other queries are finalized without
+        reply, without acquiring the GIL.
+
+        With ``compatibility="pico"``, ``allowed_origin``, outside of the zenoh-pico subset,
+        raises a ``ValueError``.
+        """
 
     @overload
     def declare_queryable(
         self,
-        key_expr: _IntoKeyExpr,
+        key_expr: _IntoKeyExpr | list[_IntoKeyExpr | tuple[_IntoKeyExpr, bool]],
         handler: _PythonHandler[Query, _H],
         *,
         complete: bool | None = None,
         allowed_origin: Locality | None = None,
+        executor: Executor | None = None,
+        max_breadth: int | Callable[[Selector], bool] | None = None,
+        reply_encoding: _IntoEncoding | None = None,
+        reply_express: bool | None = None,
+        compatibility: Literal["pico"] | None = None,
+        filter: dict[str, str | bytes | datetime] | None = None,
     ) -> Queryable[_H]:
         """Create a :class:`Queryable` for the given key expression."""
 
     @overload
     def declare_queryable(
         self,
-        key_expr: _IntoKeyExpr,
+        key_expr: _IntoKeyExpr | list[_IntoKeyExpr | tuple[_IntoKeyExpr, bool]],
         handler: _PythonCallback[Query],
         *,
         complete: bool | None = None,
         allowed_origin: Locality | None = None,
+        executor: Executor | None = None,
+        max_breadth: int | Callable[[Selector], bool] | None = None,
+        reply_encoding: _IntoEncoding | None = None,
+        reply_express: bool | None = None,
+        compatibility: Literal["pico"] | None = None,
+        filter: dict[str, str | bytes | datetime] | None = None,
+        audit: Callable[[Query, int, float], Any] | None = None,
+        auto_reply: bool = False,
     ) -> Queryable[None]:
-        """Create a :class:`Queryable` for the given key expression."""
+        """Create a :class:`Queryable` for the given key expression.
+
+        If ``audit`` is set, it is called once the callback has returned or raised, with the
+        query, its :attr:`Query.reply_count` and the callback duration in seconds, e.g. to log
+        the served queries along with :attr:`Query.raw_selector` and :attr:`Query.origin_zid`.
+        Errors raised by ``audit`` are logged. It requires a callback handler, a ``ValueError``
+        is raised otherwise.
+
+        If ``auto_reply`` is true, the value returned by the callback is replied with:
+
+        - ``None`` means the callback replied itself, if at all;
+        - a :class:`Sample` is replied with :meth:`Query.reply_sample`;
+        - a ``(key_expr, payload)`` tuple is replied with :meth:`Query.reply`;
+        - another payload, e.g. ``str`` or ``bytes``, is replied on the query key expression;
+        - a list of the above is replied item by item.
+
+        The query is then finalized. Any other value raises a ``TypeError`` describing these
+        rules, logged like the other callback errors. Like ``audit``, it requires a callback
+        handler.
+        """
 
     def declare_publisher(
         self,
@@ -1648,8 +3064,32 @@ class Session:
         express: bool | None = None,
         reliability: Reliability | None = None,
         allowed_destination: Locality | None = None,
+        retain: bool = False,
+        compatibility: Literal["pico"] | None = None,
     ) -> Publisher:
-        """Create a :class:`Publisher` for the given key expression."""
+        """Create a :class:`Publisher` for the given key expression.
+
+        If ``retain`` is true, the publisher keeps the last sample successfully put, and declares
+        a queryable on its key expression answering gets with it, like MQTT retained messages;
+        deleting the key removes the sample. See :meth:`Publisher.retained`.
+
+        With ``compatibility="pico"``, the options outside of the zenoh-pico subset raise a
+        ``ValueError``: ``allowed_destination``, ``reliability`` and ``retain``.
+        """
+
+    def queryable_once(
+        self,
+        key_expr: _IntoKeyExpr,
+        callback: Callable[[Query], Any] | None = None,
+        *,
+        timeout: float | int | None = None,
+    ) -> OnceQueryable:
+        """Declare a queryable answering a single query, e.g. for a reply channel.
+
+        With a ``callback``, it is called with the first matching query in a dedicated thread,
+        and the queryable is closed silently if no query is received within ``timeout``
+        seconds. Without it, the query is returned by :meth:`OnceQueryable.wait`.
+        """
 
     def declare_querier(
         self,
@@ -1666,6 +3106,21 @@ class Session:
     ) -> Querier:
         """Create a :class:`Querier` for the given key expression."""
 
+    def transport_info(self) -> list[TransportInfo]:
+        """Return the wire-level parameters of every link of the established transports."""
+
+    def dump_state(self, *, format: Literal["text", "json"] = "text") -> str:
+        """Return a description of the session local state, meant to be attached to bug reports.
+
+        It includes the binding and zenoh versions, the session mode and zid, its configuration,
+        the established transports and links, the open entities of its :class:`EntityGroup`
+        instances, and some counters. The values of configuration keys containing ``password``,
+        ``secret`` or ``private_key`` are redacted.
+
+        The ``"text"`` format is a list of ``[section]`` followed by ``key = value`` lines, the
+        ``"json"`` one a JSON object with the same sections; both have a ``format_version``.
+        """
+
     def liveliness(self) -> Liveliness:
         """Obtain a :class:`Liveliness` instance tied to this Zenoh session."""
 
@@ -1818,6 +3273,43 @@ class Link:
     def __eq__(self, other: Link) -> bool: ...
     def __repr__(self) -> str: ...
 
+@final
+class TransportInfo:
+    """Wire-level parameters of a link, returned by :meth:`Session.transport_info`.
+
+    Parameters not exposed by the underlying zenoh version are ``None``.
+    """
+
+    @property
+    def transport(self) -> Transport:
+        """The :class:`Transport` this link belongs to."""
+
+    @property
+    def link(self) -> Link:
+        """The :class:`Link` these parameters apply to."""
+
+    @property
+    def batch_size(self) -> int | None:
+        """The upper bound of the batch size, i.e. the configured batch size capped by the link MTU.
+
+        This is not the negotiated value, which zenoh doesn't expose: the remote node may have
+        negotiated a smaller one.
+        """
+
+    @property
+    def fragmentation(self) -> bool | None:
+        """Whether fragmentation is enabled on this link, always ``None`` for now."""
+
+    @property
+    def compression(self) -> bool | None:
+        """Whether compression is enabled on this link, always ``None`` for now."""
+
+    @property
+    def sn_resolution(self) -> int | None:
+        """The resolution, in bits, of the sequence numbers, always ``None`` for now."""
+
+    def __repr__(self) -> str: ...
+
 @final
 class TransportEvent:
     """An event indicating a transport connection was opened or closed.
@@ -2006,6 +3498,42 @@ class SourceInfo:
 
 SourceSn = int
 
+@final
+class SpooledReplies:
+    """The replies of a get with a memory budget, see :meth:`Session.get_spooled`.
+
+    Iterating yields the replies in order, reading back the payloads spilled to the spool file;
+    the file is removed once the replies are consumed, when :meth:`close` is called, or when the
+    object is garbage collected.
+    """
+
+    @property
+    def spool_path(self) -> Path | None:
+        """The path of the spool file, or None if no payload was spilled or it was removed."""
+
+    @property
+    def spooled_count(self) -> int:
+        """The number of remaining replies whose payload is spilled."""
+
+    def ok(self) -> list[Sample]:
+        """Consume the remaining replies, returning the successful samples."""
+
+    def errors(self) -> list[ReplyError]:
+        """Consume the remaining replies, returning the errors."""
+
+    def first(self) -> Sample | None:
+        """Consume the replies up to the first successful one, returning its sample, or None if
+        there is none."""
+
+    def close(self):
+        """Drops the remaining replies, and removes the spool file."""
+
+    def __enter__(self) -> Self: ...
+    def __exit__(self, *_args, **_kwargs): ...
+    def __len__(self) -> int: ...
+    def __iter__(self) -> Self: ...
+    def __next__(self) -> Reply: ...
+
 @final
 class Subscriber(Generic[_H]):
     """A subscriber that receives data from :class:`Publisher` instances matching its key expression.
@@ -2035,9 +3563,75 @@ class Subscriber(Generic[_H]):
 
         See :ref:`channels-and-callbacks` for more information on handlers."""
 
+    @property
+    def allowed_origin(self) -> Locality:
+        """The effective origin restriction of this subscriber, either passed to
+        :meth:`Session.declare_subscriber` or resolved from :func:`set_subscriber_policy`."""
+
+    @property
+    def paused(self) -> bool:
+        """Whether the subscriber is paused, see :meth:`pause`."""
+
+    @property
+    def dropped_while_paused(self) -> int:
+        """The number of samples dropped because the subscriber was paused."""
+
+    @property
+    def corrupt_count(self) -> int:
+        """The number of samples whose integrity verification failed, see :meth:`SubscriberBuilder.verify_integrity`."""
+
+    @property
+    def unverified_count(self) -> int:
+        """The number of samples delivered without integrity verification, as they had no digest."""
+
+    @property
+    def ignored_self_count(self) -> int:
+        """The number of samples of this session dropped, see :meth:`SubscriberBuilder.ignore_self`."""
+
+    @property
+    def unknown_origin_count(self) -> int:
+        """The number of samples delivered with :meth:`SubscriberBuilder.ignore_self` though their
+        origin is unknown, as they had no source info."""
+
+    def gap_report(self) -> dict[ZenohId, GapStats]:
+        """Returns the reception diagnostics of the samples received since the subscriber declaration,
+        or the last :meth:`reset_gap_report`, per source zid.
+
+        Gaps are detected using the sequence numbers of :attr:`Sample.source_info`, and out-of-order
+        arrivals using the sample timestamps; samples without source info are not tracked."""
+
+    def reset_gap_report(self):
+        """Clears the gap report, see :meth:`gap_report`."""
+
+    def callback_stats(self) -> dict[str, float] | None:
+        """Returns the ``count`` of the invocations of the callback handler, and their ``min``,
+        ``mean``, ``max`` and ``p95`` durations in seconds, the percentile being estimated from
+        a uniform sample of 1024 invocations.
+
+        Returns ``None`` for channel handlers, or unless :meth:`SubscriberBuilder.time_callbacks`
+        was enabled. The durations include the GIL-held call only, not the wait for the GIL."""
+
+    @property
+    def closed(self) -> bool:
+        """Whether the subscriber is undeclared, either explicitly or because its limits were reached,
+        see :meth:`SubscriberBuilder.limits`."""
+
+    def pause(self, *, pause_buffer: int = 0):
+        """Stop delivering samples to the handler, without undeclaring the subscriber.
+
+        While paused, received samples are buffered up to `pause_buffer` samples, and the
+        following ones are dropped; by default, all samples are dropped.
+        """
+
+    def resume(self):
+        """Resume the delivery of samples, after the samples buffered while paused
+        have been delivered in order."""
+
     def undeclare(self):
         """Close a Subscriber.
         Subscribers are automatically closed when dropped, but you may want to use this function to handle errors or close the Subscriber asynchronously.
+
+        Once the subscriber limits are reached, this is a no-op.
         """
 
     def try_recv(self: Subscriber[Handler[Sample]]) -> Sample | None:
@@ -2046,11 +3640,156 @@ class Subscriber(Generic[_H]):
         Returns the sample if available, or None if no sample is ready.
         """
 
+    @overload
     def recv(self: Subscriber[Handler[Sample]]) -> Sample:
         """Receive a :class:`Sample`, blocking until one is available."""
 
+    @overload
+    def recv(
+        self: Subscriber[Handler[Sample]], timeout: float | int | None
+    ) -> Sample | None:
+        """Receive a :class:`Sample`, blocking until one is available, or ``timeout`` seconds
+        have elapsed, in which case None is returned.
+
+        The GIL is released while blocking. Undeclaring the subscriber from another thread
+        unblocks the call, which then raises a :class:`ZError`."""
+
+    def set_callback(self: Subscriber[None], callback: _PythonCallback[Sample]):
+        """Replace the callback of this subscriber without redeclaring it.
+
+        Invocations in progress complete with the previous callback, and the following ones,
+        including samples already queued for an indirect callback or an executor, use the new one.
+        The drop callback of the original handler is kept.
+
+        Raises:
+            ValueError: If ``callback`` is None, or if the handler is not a callback.
+        """
+
     def __iter__(self: Subscriber[Handler[Sample]]) -> Handler[Sample]:
-        """Iterate over received :class:`Sample` instances."""
+        """Iterate over received :class:`Sample` instances, until the subscriber is undeclared."""
+
+@final
+class SubscriberBuilder:
+    """The opt-in features of a :class:`Subscriber`, returned by
+    :meth:`Session.subscriber_builder`.
+
+    The methods set a feature and return the builder, which can then declare any number of
+    subscribers with :meth:`declare`.
+    """
+
+    def allowed_origin(self, allowed_origin: Locality) -> Self:
+        """Restricts the origin of the received samples, as the ``allowed_origin`` argument of
+        :meth:`Session.declare_subscriber`."""
+
+    def limits(
+        self,
+        *,
+        max_duration: float | int | None = None,
+        max_samples: int | None = None,
+        on_complete: Callable[[int, Literal["duration", "count"]], Any] | None = None,
+    ) -> Self:
+        """Undeclares the subscriber automatically when the first of the ``max_duration``
+        (in seconds) or ``max_samples`` limits is reached: samples are no longer delivered,
+        and the channel is closed, or the drop callback called.
+
+        ``on_complete`` is then called exactly once, with the number of delivered samples and
+        the reason, ``"duration"`` or ``"count"``; it is not called if the subscriber is
+        undeclared before. See :attr:`Subscriber.closed`.
+        """
+
+    def executor(self, executor: Executor) -> Self:
+        """Runs the handler, which must be a callback, on the :class:`Executor` workers."""
+
+    def verify_integrity(self, on_corrupt: Callable[[Sample], Any] | None = None) -> Self:
+        """Verifies the digest attached by ``put(..., integrity=...)`` before delivery, and
+        removes it from the sample attachment.
+
+        Corrupted samples are passed to ``on_corrupt`` instead of the handler; samples without
+        digest are delivered unverified. See :attr:`Subscriber.corrupt_count` and
+        :attr:`Subscriber.unverified_count`.
+        """
+
+    def auto_decode(self) -> Self:
+        """Calls the handler, which must be a callback, with the sample and the result of
+        :meth:`Sample.decode` as second argument, see :func:`register_type`."""
+
+    def shard(self, shard: tuple[int, int] | tuple[int, int, int] | None) -> Self:
+        """Delivers only the samples for which :func:`shard_matches` is true, with ``shard``
+        being ``(shard_index, shard_count)`` or ``(shard_index, shard_count, chunk_index)``,
+        see :func:`shard_key_expr`."""
+
+    def compatibility(self, compatibility: Literal["pico"] | None) -> Self:
+        """With ``"pico"``, the options outside of the zenoh-pico subset raise a ``ValueError``
+        on :meth:`declare`: ``allowed_origin``, :meth:`verify_integrity` and
+        :meth:`auto_decode`; the default locality of :func:`set_subscriber_policy` is then
+        ignored."""
+
+    def time_callbacks(self) -> Self:
+        """Times the invocations of callback handlers, see :meth:`Subscriber.callback_stats`;
+        it is disabled by default, as it costs two clock reads and a lock per sample."""
+
+    def ignore_self(self) -> Self:
+        """Drops the samples published by this session, which is decided from their source
+        info: samples without one, e.g. not published with ``source_info=``, are delivered,
+        as their origin is unknown. Both are counted, see
+        :attr:`Subscriber.ignored_self_count` and :attr:`Subscriber.unknown_origin_count`.
+
+        Local routing is controlled by these mechanisms:
+
+        - ``allowed_destination`` on ``put``/``delete``/``get`` and publishers, per operation
+          or publisher, for whether the local entities are reached at all;
+        - ``allowed_origin`` on subscribers and queryables, per entity, for whether local
+          (:attr:`Locality.SESSION_LOCAL`) or remote publications are received, defaulting to
+          :func:`set_subscriber_policy`;
+        - :meth:`ignore_self` on subscribers, per entity, for the publications of this session
+          only.
+        """
+
+    def project(self, project: Iterable[str]) -> Self:
+        """Projects the JSON payloads of the samples to the given field paths before delivery,
+        like the replies of :meth:`Session.get_handle`, after the integrity verification;
+        see :attr:`Subscriber.unprojected_count`."""
+
+    @overload
+    def declare(
+        self, handler: _RustHandler[Sample] | handlers.PriorityChannel | None = None
+    ) -> Subscriber[Handler[Sample]]:
+        """Declares a :class:`Subscriber` with the features of this builder."""
+
+    @overload
+    def declare(self, handler: _PythonHandler[Sample, _H]) -> Subscriber[_H]:
+        """Declares a :class:`Subscriber` with the features of this builder."""
+
+    @overload
+    def declare(
+        self, handler: _PythonCallback[Sample] | Callable[[Sample, Any], Any]
+    ) -> Subscriber[None]:
+        """Declares a :class:`Subscriber` with the features of this builder."""
+
+@final
+class SubscriberPolicy:
+    """A rule of the subscriber policy table, see :func:`set_subscriber_policy`.
+
+    Subscribers declared on a key expression included in the rule's ``key_expr`` use the rule's
+    settings when the corresponding argument of :meth:`Session.declare_subscriber` is not given.
+    """
+
+    def __new__(
+        cls, key_expr: _IntoKeyExpr, *, allowed_origin: Locality | None = None
+    ) -> Self: ...
+    @property
+    def key_expr(self) -> KeyExpr: ...
+    @property
+    def allowed_origin(self) -> Locality | None: ...
+
+@final
+class TypeRegistration:
+    """A decoder registered with :func:`register_type`."""
+
+    @property
+    def key_expr(self) -> KeyExpr: ...
+    def unregister(self) -> bool:
+        """Removes the decoder, returning false if it was already removed."""
 
 @final
 class Timestamp:
@@ -2078,7 +3817,22 @@ class Timestamp:
     For detailed information about Timestamp, see: https://docs.rs/zenoh/latest/zenoh/time/struct.Timestamp.html
     """
 
-    def __new__(cls, time: datetime | NTP64, id: _IntoTimestampId) -> Self: ...
+    def __new__(
+        cls, time: datetime | NTP64 | float, id: _IntoTimestampId
+    ) -> Self:
+        """Creates a timestamp from its time, either a datetime, a :class:`NTP64`, or seconds
+        since the Unix epoch like :func:`time.time`, and its id, e.g. to put a sample with
+        ``timestamp=``.
+
+        Raises:
+            ValueError: If the time in seconds is negative, or not finite.
+        """
+
+    @classmethod
+    def now(cls, session: Session) -> Self:
+        """Returns a new timestamp from the clock of ``session``, same as
+        :meth:`Session.new_timestamp`."""
+
     def get_time(self) -> datetime:
         """Returns the time component of the timestamp as a datetime object."""
 
@@ -2088,6 +3842,27 @@ class Timestamp:
     def get_id(self) -> TimestampId:
         """Returns the unique identifier component of the timestamp as a :class:`TimestampId`."""
 
+    @property
+    def ntp64(self) -> int:
+        """The time component of the timestamp as a raw NTP64 integer, without the precision
+        loss of :meth:`get_time`."""
+
+    def to_bytes(self) -> bytes:
+        """Serializes the timestamp into a compact binary format, e.g. to embed it into a payload.
+
+        The format is a stable part of the binding API, versioned by its first byte, currently 1,
+        followed by the NTP64 time as a big-endian 64-bit integer, then by the id bytes in
+        little-endian order, without trailing zeros (1 to 16 bytes).
+        """
+
+    @classmethod
+    def from_bytes(cls, bytes: bytes) -> Self:
+        """Deserializes a timestamp serialized by :meth:`to_bytes`.
+
+        Raises:
+            ValueError: If the format version is not supported, or the bytes are invalid.
+        """
+
     def get_diff_duration(self, other: Timestamp) -> timedelta:
         """Returns the duration difference between this timestamp and another.
 
@@ -2363,8 +4138,30 @@ class ZBytes:
     any serialization approach that fits their needs."""
 
     def __new__(
-        cls, bytes: bytearray | bytes | str | shm.ZShm | shm.ZShmMut | None = None
-    ) -> Self: ...
+        cls,
+        bytes: bytearray | bytes | memoryview | str | bool | dict | list | datetime | shm.ZShm | shm.ZShmMut | None = None,
+    ) -> Self:
+        """Datetimes are stored as RFC3339 text, to be published with :attr:`Encoding.ZENOH_DATETIME`.
+
+        Bools are stored as the ``true`` or ``false`` JSON literals, see :meth:`to_bool`, and
+        published with :attr:`Encoding.APPLICATION_JSON` if no encoding is given. ``None`` gives
+        empty bytes.
+
+        Any other object supporting the buffer protocol, e.g. a memoryview or a numpy array, is
+        copied in C order. It is published with :attr:`Encoding.APPLICATION_OCTET_STREAM` if no
+        encoding is given.
+
+        Dataclass instances are serialized as JSON, following :func:`dataclasses.asdict` semantics;
+        fields whose metadata has a truthy ``zenoh_exclude`` key are skipped. Dicts with string keys
+        and lists are serialized as JSON too, nested values included, see :meth:`to_json_value`.
+        They are published with :attr:`Encoding.APPLICATION_JSON` if no encoding is given.
+
+        Raises:
+            ValueError: If the datetime is naive, unless :func:`set_naive_datetime_policy` was set to ``"utc"``.
+                Or if a dict key is not a string, or a float is not finite.
+            TypeError: If a dataclass field or a nested value has a type which can't be serialized
+                as JSON, the error message giving the field path.
+        """
     def to_bytes(self) -> bytes:
         """Return the underlying data as bytes.
 
@@ -2382,6 +4179,55 @@ class ZBytes:
             ValueError: If the byte data cannot be decoded as valid UTF-8.
         """
 
+    def to_datetime(self) -> datetime:
+        """Return the underlying RFC3339 text as an aware datetime.
+
+        Microseconds and UTC offset are preserved.
+
+        Raises:
+            ValueError: If the byte data is not a valid RFC3339 datetime.
+        """
+
+    def to_bool(self) -> bool:
+        """Return the ``true`` or ``false`` JSON literal of the underlying data as a bool, the
+        inverse of ``ZBytes(bool)``.
+
+        Raises:
+            ValueError: If the byte data is neither ``true`` nor ``false``.
+        """
+
+    def to_json_value(self) -> Any:
+        """Return the underlying JSON data as the corresponding dicts, lists, strings, numbers,
+        bools and ``None``, the inverse of ``ZBytes(dict)`` and ``ZBytes(list)``.
+
+        Raises:
+            ValueError: If the byte data is not valid JSON.
+        """
+
+    @staticmethod
+    def from_dict(dict: dict[str | bytes, str | bytes]) -> ZBytes:
+        """Serialize a dict of bytes/str like a ``dict[bytes, bytes]`` by
+        :func:`zenoh.ext.z_serialize`, so that other bindings can deserialize it as a map.
+        This is how the dicts given as ``attachment`` are serialized, e.g.
+        ``attachment={"trace-id": "42"}``.
+
+        Unlike ``ZBytes(dict)``, which serializes any dict as JSON, keys and values are
+        serialized as raw bytes.
+
+        Raises:
+            TypeError: If a key or a value is neither bytes nor str.
+        """
+
+    def to_dict(self) -> dict[str | bytes, str | bytes]:
+        """Return the dict serialized by :meth:`from_dict`.
+
+        Keys and values are returned as ``str`` when they are valid UTF-8, and as ``bytes``
+        otherwise.
+
+        Raises:
+            ValueError: If the byte data is not a serialized dict.
+        """
+
     @_unstable
     def as_shm(self) -> shm.ZShm | None: ...
     def __bool__(self) -> bool: ...
@@ -2397,8 +4243,61 @@ _IntoZBytes = Any
 class ZenohId:
     """The global unique id of a zenoh peer."""
 
+    def to_bytes(self) -> bytes:
+        """Serializes the id into a compact binary format, e.g. to embed it into a payload.
+
+        The format is a stable part of the binding API, versioned by its first byte, currently 1,
+        followed by the id bytes in little-endian order, without trailing zeros (1 to 16 bytes).
+        """
+
+    @classmethod
+    def from_bytes(cls, bytes: bytes) -> Self:
+        """Deserializes an id serialized by :meth:`to_bytes`.
+
+        Raises:
+            ValueError: If the format version is not supported, or the bytes are invalid.
+        """
+
+    def __bytes__(self) -> bytes:
+        """The id bytes in little-endian order, without trailing zeros, e.g. to use it as a
+        key; see :meth:`to_bytes` for a versioned format."""
+
+    def __eq__(self, other: Any) -> bool: ...
+    def __hash__(self) -> int: ...
     def __str__(self) -> str: ...
 
+def apply_manifest(
+    session: Session, manifest: dict[str, Any], namespace: dict[str, Any] | None = None
+) -> EntityGroup:
+    """Declare the entities described by a manifest, e.g. loaded from a JSON or YAML file.
+
+    The manifest maps ``"publishers"``, ``"subscribers"`` and ``"queryables"`` to lists of entries.
+    Each entry has a ``"key"`` and an optional ``"name"``, defaulting to the key, under which the
+    entity is available in the returned :class:`EntityGroup`. Other fields depend on the kind:
+
+    - publishers: ``"priority"``, ``"congestion_control"``, ``"reliability"`` (lowercase variant
+      names, e.g. ``"real_time"``), ``"express"`` and ``"encoding"``
+    - subscribers: ``"handler"``, and ``"capacity"`` for ``"fifo"`` and ``"ring"`` handlers; any
+      other handler is looked up in ``namespace``
+    - queryables: same as subscribers, plus ``"complete"``
+
+    The whole manifest is validated before declaring anything, and a ValueError lists every
+    error with its JSON pointer, e.g. ``/subscribers/1/capacity``. If a declaration fails, the
+    entities already declared are undeclared before the error is raised.
+
+    .. code-block:: python
+
+        manifest = {
+            "publishers": [{"key": "robot/status", "priority": "real_time"}],
+            "subscribers": [{"key": "robot/cmd", "name": "cmd", "handler": "on_cmd"}],
+        }
+        group = zenoh.apply_manifest(session, manifest, {"on_cmd": on_cmd})
+        group["robot/status"].put("ready")
+    """
+
+def bug_report(session: Session, path: str | Path):
+    """Write the :meth:`Session.dump_state` text description of ``session`` to ``path``."""
+
 def try_init_log_from_env():
     """Redirect zenoh logs to stdout, according to the `RUST_LOG` environment variable.
 
@@ -2412,20 +4311,100 @@ def init_log_from_env_or(level: str):
     If `RUST_LOG` is not set, then logging is set to the provided level."""
 
 def open(
-    config: Config,
+    config: Config | dict[str, Any],
     *,
     timestamp_callback: Callable[[TimestampContext], bytes] | None = None,
+    autoflush_interval_ms: int | None = None,
+    credentials: tuple[str, str] | None = None,
+    tls: dict[str, Any] | None = None,
+    namespace: str | None = None,
+    runtime: Literal["default", "minimal"] | None = None,
+    runtime_stack_kb: int | None = None,
 ) -> Session:
     """Open a zenoh :class:`zenoh.Session`.
 
     For more information about sessions and configuration, see :ref:`session-and-config`.
 
     Args:
-        config: The configuration for the session.
+        config: The configuration for the session, or a dict converted to JSON, e.g.
+        ``{"mode": "client", "connect": {"endpoints": ["tcp/localhost:7447"]}}``.
 
         timestamp_callback: An optional callback invoked at each interception point
         (Send, Route, Receive) when timestamp stack instrumentation is enabled.
         The callback receives a :class:`TimestampContext` and must return ``bytes``.
+
+        autoflush_interval_ms: The time budget of the transport batches, after which small
+        messages are pushed even if the batch is not full, overriding the
+        ``transport/link/tx/batching/time_limit`` configuration. Lower values reduce the latency
+        jitter of small messages, at the cost of throughput. Messages sent with ``express=True``
+        are never batched, and push the pending batch of their priority along with them.
+
+        credentials: The ``(user, password)`` pair passed to :meth:`Config.set_credentials`.
+
+        tls: The keyword arguments passed to :meth:`Config.set_tls`.
+
+        Both take precedence over the values of ``config``, which is left unchanged.
+
+        namespace: A key prefix, e.g. ``"tenants/acme"``, overriding the ``namespace``
+        configuration. Zenoh prepends it to the key expressions sent by the session, i.e. of the
+        puts, deletes, gets, and subscriber, queryable and publisher declarations, and strips it
+        from the received samples, queries and replies, so the application never sees it.
+
+        runtime: The flavor of the zenoh runtime the session expects: ``"minimal"`` runs zenoh
+        on a single worker thread, for constrained devices, if built with the
+        ``minimal-runtime`` cargo feature. The runtime is shared by all the sessions and read
+        from the ``ZENOH_RUNTIME`` environment variable when zenoh is imported, so it must be
+        set beforehand, to the value given by :func:`runtime_info` in ``flavor_env``.
+
+        runtime_stack_kb: The stack size of the runtime threads the session expects, in KiB;
+        like ``runtime``, it must be set beforehand, with the ``RUST_MIN_STACK`` environment
+        variable, in bytes.
+
+    Raises:
+        ZError: With the ``FEATURE_UNAVAILABLE`` code if ``autoflush_interval_ms`` or
+        ``namespace`` is given but the linked zenoh version doesn't support it, or if
+        ``runtime="minimal"`` but zenoh is built without it. Or if ``namespace`` is not a
+        valid key expression, or if ``runtime`` or ``runtime_stack_kb`` differ from the
+        environment zenoh was imported with.
+        ValueError: If ``namespace`` contains wildcards.
+    """
+
+@final
+class AutoOpenReport:
+    """The decision made by :func:`open_auto`."""
+
+    @property
+    def mode(self) -> WhatAmI:
+        """The mode of the opened session, :attr:`WhatAmI.CLIENT` or :attr:`WhatAmI.PEER`."""
+
+    @property
+    def router_zid(self) -> ZenohId | None:
+        """The id of the router the client session is connected to, or ``None`` in peer mode."""
+
+    @property
+    def router_locator(self) -> str | None:
+        """The locator of the router the client session is connected to, or ``None`` in peer mode."""
+
+    @property
+    def scouting_duration(self) -> float:
+        """The time spent scouting for routers, in seconds."""
+
+def open_auto(
+    config: Config | None = None,
+    *,
+    prefer: Literal["client", "peer"] = "client",
+    scout_timeout: float | int | None = None,
+) -> tuple[Session, AutoOpenReport]:
+    """Open a client session connected to a reachable router, or else a peer session.
+
+    With ``prefer="client"``, routers are scouted for ``scout_timeout`` seconds (2 by default).
+    If one is found, a client session is opened, connected to its :attr:`Hello.best_locator`.
+    Otherwise, or if the connection fails, a peer session is opened with multicast scouting enabled.
+    With ``prefer="peer"``, the peer session is opened without scouting.
+
+    The other settings, including the scouting ones, are taken from ``config``.
+
+    :raises ZError: if the peer session can't be opened, the message includes what was attempted first.
     """
 
 # Common docstring for all scout function overloads
@@ -2441,16 +4420,40 @@ Args:
     what: Optional :class:`zenoh.WhatAmIMatcher` or string specifying which node types to scout for
     (e.g., "peer|router"). If None, scouts for all node types.
 
-    config: Optional :class:`zenoh.Config` for the scouting session.
+    config: Optional :class:`zenoh.Config`, or dict of configuration, for the scouting session.
+
+    timeout: If set, scouting is stopped after ``timeout`` seconds, and the list of the
+    :class:`zenoh.Hello` received in the meantime is returned, one per node, instead of a
+    :class:`zenoh.Scout`; a timeout of 0 only returns the ones already received. It cannot be
+    used with a handler.
+
+Raises:
+    ZError: If multicast scouting is disabled in the configuration, as no reply would be received.
 
 For more information about scouting, see :ref:`scouting`.
 """
 
+def replies_to_columns(
+    replies: Iterable[Reply],
+    payload_as: Literal["float", "int", "bytes", "json"] = "bytes",
+) -> dict[str, list[Any]]:
+    """Extract the replies to parallel lists, e.g. to build a data frame, in a single pass.
+
+    The returned dict has the ``"key"`` (str), ``"time"`` (the timestamp as datetime, or None) and
+    ``"payload"`` lists of the ok replies, and the :class:`ReplyError` of the error replies under
+    ``"errors"``. Payloads are decompressed like by :meth:`Sample.decode`, then converted according
+    to ``payload_as``: ``"float"`` and ``"int"`` parse the payload text, ``"json"`` loads it with
+    ``json.loads``, and ``"bytes"`` returns it as is.
+
+    Raises:
+        ValueError: On the first payload that can't be converted, with the index of its reply.
+    """
+
 @overload
 def scout(
     handler: _RustHandler[Hello] | None = None,
     what: _IntoWhatAmIMatcher | None = None,
-    config: Config | None = None,
+    config: Config | dict[str, Any] | None = None,
 ) -> Scout[Handler[Hello]]: ...
 
 scout.__doc__ = _SCOUT_DOC
@@ -2459,11 +4462,126 @@ scout.__doc__ = _SCOUT_DOC
 def scout(
     handler: _PythonHandler[Hello, _H],
     what: _IntoWhatAmIMatcher | None = None,
-    config: Config | None = None,
+    config: Config | dict[str, Any] | None = None,
 ) -> Scout[_H]: ...
 @overload
 def scout(
     handler: _PythonCallback[Hello],
     what: _IntoWhatAmIMatcher | None = None,
-    config: Config | None = None,
+    config: Config | dict[str, Any] | None = None,
 ) -> Scout[None]: ...
+@overload
+def scout(
+    handler: None = None,
+    what: _IntoWhatAmIMatcher | None = None,
+    config: Config | dict[str, Any] | None = None,
+    *,
+    timeout: float | int,
+) -> list[Hello]: ...
+
+def register_type(key_expr: _IntoKeyExpr, decoder: Callable[[bytes], Any]) -> TypeRegistration:
+    """Register a decoder for the samples whose key expression intersects ``key_expr``.
+
+    :meth:`Sample.decode` calls the first registered decoder matching the sample, in registration
+    order, with the payload bytes; exceptions raised by the decoder are propagated.
+
+    .. code-block:: python
+
+        registration = zenoh.register_type("fleet/*/pose", lambda payload: Pose.parse(payload))
+        ...
+        registration.unregister()
+    """
+
+def set_naive_datetime_policy(policy: Literal["error", "utc"]):
+    """Set how naive datetimes, i.e. without timezone, are serialized in payloads and parameters.
+
+    With ``"error"``, the default, a ValueError is raised; with ``"utc"``, they are assumed to be UTC.
+    """
+
+def enable_get_cache(max_entries: int = 1024, ttl: float | int | None = None):
+    """Enable the client-side cache of :meth:`Session.get_cached`, or reset it, clearing its
+    entries and statistics.
+
+    At most ``max_entries`` selectors are cached, the least recently used being evicted first,
+    and their replies are cached for ``ttl`` seconds, 30 by default.
+    """
+
+def get_cache_stats() -> dict[str, Any]:
+    """Return the ``enabled`` status, the number of ``entries``, and the ``hits``, ``misses`` and
+    ``evictions`` counters of the cache, see :func:`enable_get_cache`."""
+
+def metrics_snapshot() -> dict[str, Any]:
+    """Return the metrics of the live entities: ``subscribers`` is the list of the timed
+    subscribers, as dicts with their ``key_expr``, and the ``callback_count``,
+    ``callback_min``, ``callback_mean``, ``callback_max`` and ``callback_p95`` stats of
+    :meth:`Subscriber.callback_stats`."""
+
+def runtime_info() -> dict[str, Any]:
+    """Return the configuration of the zenoh runtime, read from the environment when zenoh is
+    imported: its ``flavor``, ``"default"``, ``"minimal"``, or ``"custom"`` if set to another
+    value with the ``ZENOH_RUNTIME`` environment variable, the flavors ``available`` in this
+    build, the ``ZENOH_RUNTIME`` value selecting each of them in ``flavor_env``, None for the
+    default one, and the ``stack_kb`` set with ``RUST_MIN_STACK``, or None if not set, see
+    :func:`open`."""
+
+def set_publish_validation(enabled: bool):
+    """Enable or disable the validation of published payloads, by :meth:`Session.put`,
+    :meth:`Publisher.put` and :meth:`Query.reply`.
+
+    When enabled, payloads with ``text/plain``, ``text/json`` or ``application/json`` encoding
+    must be valid UTF-8, and valid JSON for the JSON encodings; a ValueError with the byte offset
+    or the JSON error position is raised instead of sending them. Each of these methods takes a
+    ``validate`` parameter overriding this setting for the call.
+
+    Validation is disabled by default, as it reads the whole payload.
+    """
+
+def set_subscriber_policy(rules: list[SubscriberPolicy]):
+    """Set the process-wide subscriber policy table, replacing the previous one.
+
+    When declaring a subscriber, the rules are tried in order, and the first one whose key expression
+    includes the subscriber's key expression provides the default settings; explicit arguments of
+    :meth:`Session.declare_subscriber` always take precedence. Passing an empty list clears the table.
+
+    .. code-block:: python
+
+        zenoh.set_subscriber_policy([
+            zenoh.SubscriberPolicy("local/**", allowed_origin=zenoh.Locality.SESSION_LOCAL),
+            zenoh.SubscriberPolicy("**", allowed_origin=zenoh.Locality.REMOTE),
+        ])
+
+    Reliability is not part of the policy, as it is chosen by publishers
+    (see :meth:`Session.declare_publisher`).
+    """
+
+def shard_key_expr(
+    base_expr: _IntoKeyExpr,
+    shard_index: int,
+    shard_count: int,
+    *,
+    by: Literal["chunk"] = "chunk",
+    chunk_index: int = 1,
+) -> list[KeyExpr]:
+    """Return the key expressions a subscriber of the given shard must be declared on.
+
+    Keys are partitioned by the first character of their chunk at ``chunk_index``, which must be
+    a ``*`` or ``**`` wildcard in ``base_expr``, without ``**`` before it. A chunk starting with
+    the n-th character of the alphabet
+    ``0-9``, ``A-Z``, ``a-z``, then ``-_.~@+=:,;!%&'()[]<>^`|``, belongs to shard
+    ``n % shard_count``, and any other chunk, e.g. starting with a non-ASCII character, to shard 0.
+
+    As the latter cannot be expressed as key expressions, shard 0 is given ``base_expr`` itself.
+    The key expressions are thus a superset of the shard keys, and subscribers must be declared
+    with ``shard((shard_index, shard_count, chunk_index))`` to filter them exactly, see
+    :func:`shard_matches`; each key is then processed by exactly one shard.
+
+    .. code-block:: python
+
+        for key_expr in zenoh.shard_key_expr("jobs/*/**", i, n):
+            session.subscriber_builder(key_expr).shard((i, n)).declare(on_job)
+    """
+
+def shard_matches(
+    key: _IntoKeyExpr, shard_index: int, shard_count: int, *, chunk_index: int = 1
+) -> bool:
+    """Whether ``key`` belongs to the given shard, see :func:`shard_key_expr`."""
@@ -0,0 +1,112 @@
+#
+# Copyright (c) 2025 ZettaScale Technology
+#
+# This program and the accompanying materials are made available under the
+# terms of the Eclipse Public License 2.0 which is available at
+# http://www.eclipse.org/legal/epl-2.0, or the Apache License, Version 2.0
+# which is available at https://www.apache.org/licenses/LICENSE-2.0.
+#
+# SPDX-License-Identifier: EPL-2.0 OR Apache-2.0
+#
+# Contributors:
+#   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
+#
+from collections.abc import Callable
+from typing import Any, Protocol, final
+
+@final
+class OpenHandle:
+    """A tracked session or entity still open, see :func:`open_handles`."""
+
+    @property
+    def entity(self) -> Any:
+        """The open :class:`zenoh.Session`, :class:`zenoh.Subscriber`, :class:`zenoh.Queryable`
+        or :class:`zenoh.Publisher`."""
+
+    @property
+    def location(self) -> str:
+        """The ``filename:lineno`` where the entity was created."""
+
+    @property
+    def traceback(self) -> str:
+        """The Python stack at creation, formatted like :func:`traceback.format_stack`."""
+
+def track_handles(enabled: bool):
+    """Enable or disable the tracking of sessions, subscribers, queryables and publishers.
+
+    While enabled, the Python stack is captured each time one of them is created; disabling
+    the tracking forgets the entities already recorded. The first time tracking is enabled, an
+    :mod:`atexit` hook is registered, which prints the handles still open at interpreter exit to
+    stderr, as they are a common cause of programs hanging at exit.
+
+    Tracking is disabled by default, and costs nothing in that case."""
+
+def open_handles() -> list[OpenHandle]:
+    """The tracked entities not yet closed or undeclared, in creation order.
+
+    Garbage collected entities are not reported, as dropping an entity undeclares it, except
+    for callback subscribers and queryables running in the background."""
+
+@final
+class PendingOperation:
+    """A blocking call in progress, see :func:`pending_operations`."""
+
+    @property
+    def operation(self) -> str:
+        """The operation, e.g. ``"put"``, ``"get"``, ``"recv"`` or ``"close"``."""
+
+    @property
+    def key_expr(self) -> str | None:
+        """The key expression or selector of the operation, if any."""
+
+    @property
+    def thread_id(self) -> int:
+        """The :func:`threading.get_ident` of the blocked thread."""
+
+    @property
+    def age(self) -> float:
+        """The time elapsed since the call started, in seconds, when it was listed."""
+
+def pending_operations() -> list[PendingOperation]:
+    """The blocking calls in progress, oldest first, e.g. to find which thread is stuck in zenoh.
+
+    Session operations, publisher puts and deletes, handler receptions, iterating over the replies
+    of :meth:`zenoh.Session.get_handle` and closing a session are recorded, for the duration of the call.
+    Only the outermost call of each thread is listed. Recording is enabled by :func:`track_pending`
+    or :func:`enable_watchdog`, the list being empty otherwise."""
+
+def track_pending(enabled: bool):
+    """Enable or disable the recording of the blocking calls listed by :func:`pending_operations`.
+
+    Recording is disabled by default, and costs nothing in that case; once enabled, it costs a few
+    atomic operations per call. The calls in progress when it is disabled are still listed until
+    they return."""
+
+def enable_watchdog(
+    threshold: float | int = 10.0, *, callback: Callable[[PendingOperation], Any]
+):
+    """Start a background thread calling ``callback`` once with each pending operation lasting
+    more than ``threshold`` seconds, see :func:`pending_operations`.
+
+    The operations are checked every tenth of the threshold, between 10ms and 1s. Enabling the
+    watchdog enables the recording of the pending operations, see :func:`track_pending`, and
+    enabling it again replaces the previous one."""
+
+def disable_watchdog():
+    """Stop the watchdog started by :func:`enable_watchdog`, if any."""
+
+class _ClockController(Protocol):
+    def now(self) -> float: ...
+
+def set_mock_clock(controller: _ClockController | None):
+    """Replace the monotonic clock of the time-based features, i.e. the :func:`zenoh.enable_get_cache`
+    TTL and the age of the :func:`pending_operations` checked by the watchdog, by
+    ``controller.now()``, in seconds; ``None`` restores the system clock.
+
+    The controller is polled instead of the system clock, so advancing its time deterministically
+    expires the cache entries and, at its next check, triggers the watchdog.
+
+    Raises:
+        zenoh.ZError: With the ``FEATURE_UNAVAILABLE`` code if the binding is not built with the
+        ``mock-clock`` cargo feature, so that production builds have no clock overhead.
+    """
@@ -99,6 +99,10 @@ def z_serialize(obj: Any) -> ZBytes:
     * Str, Bytes, ByteArray;
 
     * List, Dict, Set, FrozenSet and Tuple of supported types.
+
+    Raises:
+        OverflowError: If an int is out of the int32 range; integer wrappers like Int64 or UInt128
+        must be used to serialize bigger integers.
     """
     pass
 
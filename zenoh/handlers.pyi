@@ -12,7 +12,9 @@
 #   ZettaScale Zenoh Team, <zenoh@zettascale.tech>
 #
 from collections.abc import Callable
-from typing import Any, Generic, Protocol, Self, TypeVar, final
+from typing import Any, Generic, Protocol, Self, TypeVar, final, overload
+
+from zenoh import Priority
 
 _T = TypeVar("_T")
 
@@ -50,16 +52,31 @@ class Handler(Generic[_T]):
             The next item if available, None otherwise.
         """
 
+    @overload
     def recv(self) -> _T:
+        """Receive an item, blocking until one is available."""
+
+    @overload
+    def recv(self, timeout: float | int | None) -> _T | None:
         """Receive an item, blocking if necessary.
 
         Waits until an item is available and returns it. This method will block
-        the calling thread until data arrives.
+        the calling thread, with the GIL released, until data arrives, or ``timeout``
+        seconds have elapsed.
 
         Returns:
-            The next available item.
+            The next available item, or None if ``timeout`` elapsed first.
+
+        Raises:
+            ZError: If the channel is closed, e.g. when its subscriber is undeclared.
         """
 
+    @property
+    def priority_counters(self) -> dict[Priority, dict[str, int]] | None:
+        """*Unstable* The counters of each priority of a :class:`PriorityChannel`, as dicts with
+        the ``enqueued``, ``delivered`` and ``pending`` sample counts; None for the other
+        channels."""
+
     def __iter__(self) -> Self: ...
     def __next__(self) -> _T: ...
 
@@ -126,6 +143,28 @@ class RingChannel(Generic[_T]):
 
     def __new__(cls, capacity: int) -> Self: ...
 
+@final
+class PriorityChannel:
+    """*Unstable* A subscriber handler queuing the samples by priority.
+
+    `PriorityChannel` keeps one FIFO queue per :class:`zenoh.Priority`, the sample priority
+    being read when it is enqueued, and receiving always returns the oldest sample of the
+    highest pending priority: a burst of ``Background`` samples doesn't delay the
+    ``RealTime`` ones. To prevent starvation, once ``starvation_ratio`` samples have been
+    received in a row ahead of a lower priority one, the oldest sample of the next pending
+    priority is received; ``starvation_ratio=None`` disables it.
+
+    Like :class:`FifoChannel`, pushing additional samples blocks when ``capacity`` samples
+    are pending, whatever their priority. It can only be passed to
+    :meth:`zenoh.Session.declare_subscriber`, see :attr:`Handler.priority_counters`.
+
+    Args:
+        capacity: The maximum number of samples the channel can hold.
+        starvation_ratio: The number of samples received ahead of a lower priority one.
+    """
+
+    def __new__(cls, capacity: int, *, starvation_ratio: int | None = 8) -> Self: ...
+
 @final
 class Callback(Generic[_T]):
     """A callback handler that invokes a user-defined function for each received item.